@@ -0,0 +1,408 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Capabilities of operators
+//!
+//! Every operator implements `CostFunction`, which defines the types used by the solvers. All
+//! further capabilities (computing gradients, Hessians, Jacobians or modifying parameter vectors)
+//! are expressed by separate traits. Solvers state exactly which of these capabilities they need
+//! in their trait bounds. Pairing a solver with an operator which lacks a required capability
+//! therefore fails at compile time instead of returning an `ArgminError::NotImplemented` at
+//! runtime.
+//!
+//! ```rust
+//! use argmin::core::capabilities::{CostFunction, Gradient};
+//! use argmin::prelude::*;
+//!
+//! struct Paraboloid {}
+//!
+//! impl CostFunction for Paraboloid {
+//!     type Param = Vec<f64>;
+//!     type Output = f64;
+//!     type Hessian = ();
+//!     type Jacobian = ();
+//!     type Float = f64;
+//!
+//!     fn apply(&self, p: &Vec<f64>) -> Result<f64, Error> {
+//!         Ok(p.iter().map(|x| x.powi(2)).sum())
+//!     }
+//! }
+//!
+//! impl Gradient for Paraboloid {
+//!     fn gradient(&self, p: &Vec<f64>) -> Result<Vec<f64>, Error> {
+//!         Ok(p.iter().map(|x| 2.0 * x).collect())
+//!     }
+//! }
+//! ```
+//!
+//! # Transition from `ArgminOp`
+//!
+//! All types implementing `ArgminOp` automatically implement all of the traits in this module by
+//! forwarding to the respective methods of `ArgminOp`. Such operators therefore still report
+//! missing methods at runtime. These blanket implementations will be removed together with
+//! `ArgminOp` in a future release.
+//!
+//! The traits in this module are deliberately not part of the prelude: For types implementing
+//! `ArgminOp`, calling for instance `op.gradient(&param)` would otherwise be ambiguous.
+
+use crate::core::{
+    ArgminDot, ArgminError, ArgminFloat, ArgminMul, ArgminNorm, ArgminOp, ArgminScaledAdd,
+    ArgminSub, Error, OpWrapper,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Base trait of all operators. It defines the types of parameter vectors, outputs, Hessians,
+/// Jacobians and floats and computes the cost function value (or the output of the operator).
+pub trait CostFunction {
+    /// Type of the parameter vector
+    type Param: Clone + Serialize + DeserializeOwned;
+    /// Output of the operator
+    type Output: Clone + Serialize + DeserializeOwned;
+    /// Type of Hessian
+    type Hessian: Clone + Serialize + DeserializeOwned;
+    /// Type of Jacobian
+    type Jacobian: Clone + Serialize + DeserializeOwned;
+    /// Precision of floats
+    type Float: ArgminFloat;
+
+    /// Applies the operator/cost function to parameters
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error>;
+}
+
+/// Operators which can compute the gradient of the cost function.
+pub trait Gradient: CostFunction {
+    /// Computes the gradient at the given parameters
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error>;
+
+    /// Number of gradient evaluations performed by one call of `hessian_vec`. Solvers add them to
+    /// the gradient evaluation count. Operators which override `hessian_vec` without evaluating
+    /// gradients should set this to 0.
+    const HESSIAN_VEC_GRAD_EVALS: u64 = 1;
+
+    /// Computes the product of the Hessian at `param` with the vector `vec`. `grad` is the
+    /// gradient at `param`, which the solvers already know.
+    ///
+    /// The default implementation approximates the product with a forward difference of
+    /// gradients, `(g(param + h * vec) - grad) / h`, which costs one gradient evaluation. The
+    /// Hessian is never formed, which makes this suitable for problems with a large number of
+    /// parameters. Operators which can compute the product exactly should override this method.
+    ///
+    /// `NewtonCG` and `Steihaug` only use this method if `HessianFree` is selected via
+    /// `with_hessian_product`; by default they compute the products with the Hessian.
+    fn hessian_vec(
+        &self,
+        param: &Self::Param,
+        grad: &Self::Param,
+        vec: &Self::Param,
+    ) -> Result<Self::Param, Error>
+    where
+        Self::Param: ArgminScaledAdd<Self::Param, Self::Float, Self::Param>
+            + ArgminSub<Self::Param, Self::Param>
+            + ArgminMul<Self::Float, Self::Param>
+            + ArgminNorm<Self::Float>,
+    {
+        hessian_vec_fd(|p| self.gradient(p), param, grad, vec)
+    }
+}
+
+/// Operators which can compute the Hessian of the cost function.
+pub trait Hessian: CostFunction {
+    /// Computes the Hessian at the given parameters
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error>;
+}
+
+/// Operators which can compute the Jacobian of the operator.
+pub trait Jacobian: CostFunction {
+    /// Computes the Jacobian at the given parameters
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error>;
+}
+
+/// Operators which can modify a parameter vector, as needed for instance by Simulated Annealing.
+pub trait Anneal: CostFunction {
+    /// Modifies a parameter vector. Comes with a variable that indicates the "degree" of the
+    /// modification.
+    fn modify(&self, param: &Self::Param, extent: Self::Float) -> Result<Self::Param, Error>;
+}
+
+/// Operators whose cost function is the mean of a number of terms,
+/// `f(x) = 1/n * sum_i f_i(x)`, as is the case for most data fitting problems. Stochastic solvers
+/// evaluate cost function and gradient only on a subset (mini-batch) of the terms, which is
+/// given by their indices in `0..num_terms()`.
+pub trait StochasticOp: CostFunction {
+    /// Returns the number of terms `n`
+    fn num_terms(&self) -> usize;
+
+    /// Computes the mean of the terms given by `indices` at the given parameters
+    fn apply_batch(&self, param: &Self::Param, indices: &[usize]) -> Result<Self::Output, Error>;
+
+    /// Computes the mean of the gradients of the terms given by `indices` at the given parameters
+    fn gradient_batch(&self, param: &Self::Param, indices: &[usize]) -> Result<Self::Param, Error>;
+}
+
+/// Approximates the product of the Hessian at `param` with `vec` by a forward difference of the
+/// gradients computed by `gradient`. `grad` is the gradient at `param`.
+pub(crate) fn hessian_vec_fd<P, F, G>(gradient: G, param: &P, grad: &P, vec: &P) -> Result<P, Error>
+where
+    P: Clone + ArgminScaledAdd<P, F, P> + ArgminSub<P, P> + ArgminMul<F, P> + ArgminNorm<F>,
+    F: ArgminFloat,
+    G: Fn(&P) -> Result<P, Error>,
+{
+    let vec_norm = vec.norm();
+    if vec_norm <= F::from_f64(0.0).unwrap() {
+        return Ok(vec.clone());
+    }
+    let h = F::epsilon().sqrt() * F::from_f64(1.0).unwrap().max(param.norm()) / vec_norm;
+    let grad_h = gradient(&param.scaled_add(&h, vec))?;
+    Ok(grad_h.sub(grad).mul(&(F::from_f64(1.0).unwrap() / h)))
+}
+
+/// Determines how solvers which only need products of the Hessian with vectors (`NewtonCG` and
+/// `Steihaug`) compute these products.
+///
+/// `ExactHessian` computes the Hessian once per point via `Hessian::hessian` and multiplies it with
+/// the vectors. `HessianFree` never forms the Hessian and calls `Gradient::hessian_vec` for every
+/// product instead, which makes problems with a large number of parameters tractable.
+pub trait HessianProduct<O: CostFunction> {
+    /// Computes the Hessian at `param` if the products are computed from it and returns `None`
+    /// otherwise.
+    fn hessian(&self, op: &mut OpWrapper<O>, param: &O::Param)
+        -> Result<Option<O::Hessian>, Error>;
+
+    /// Computes the product of the Hessian at `param` with `vec`. `grad` is the gradient and
+    /// `hessian` the Hessian at `param` if it is known. Products computed without a Hessian are
+    /// Hessian-vector products, which the caller adds to `hessian_vec_func_count` (and their
+    /// `Gradient::HESSIAN_VEC_GRAD_EVALS` to `grad_func_count`).
+    fn product(
+        &self,
+        op: &O,
+        param: &O::Param,
+        grad: &O::Param,
+        hessian: Option<&O::Hessian>,
+        vec: &O::Param,
+    ) -> Result<O::Param, Error>;
+}
+
+/// Computes products with the Hessian given by `Hessian::hessian`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExactHessian;
+
+impl<O> HessianProduct<O> for ExactHessian
+where
+    O: Hessian,
+    O::Hessian: ArgminDot<O::Param, O::Param>,
+{
+    fn hessian(
+        &self,
+        op: &mut OpWrapper<O>,
+        param: &O::Param,
+    ) -> Result<Option<O::Hessian>, Error> {
+        Ok(Some(op.hessian(param)?))
+    }
+
+    fn product(
+        &self,
+        _op: &O,
+        _param: &O::Param,
+        _grad: &O::Param,
+        hessian: Option<&O::Hessian>,
+        vec: &O::Param,
+    ) -> Result<O::Param, Error> {
+        match hessian {
+            Some(hessian) => Ok(hessian.dot(vec)),
+            None => Err(ArgminError::NotInitialized {
+                text: "ExactHessian: Hessian is not available.".to_string(),
+            }
+            .into()),
+        }
+    }
+}
+
+/// Computes products with the Hessian via `Gradient::hessian_vec` without forming the Hessian
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct HessianFree;
+
+impl<O> HessianProduct<O> for HessianFree
+where
+    O: Gradient,
+    O::Param: ArgminScaledAdd<O::Param, O::Float, O::Param>
+        + ArgminSub<O::Param, O::Param>
+        + ArgminMul<O::Float, O::Param>
+        + ArgminNorm<O::Float>,
+{
+    fn hessian(
+        &self,
+        _op: &mut OpWrapper<O>,
+        _param: &O::Param,
+    ) -> Result<Option<O::Hessian>, Error> {
+        Ok(None)
+    }
+
+    fn product(
+        &self,
+        op: &O,
+        param: &O::Param,
+        grad: &O::Param,
+        _hessian: Option<&O::Hessian>,
+        vec: &O::Param,
+    ) -> Result<O::Param, Error> {
+        op.hessian_vec(param, grad, vec)
+    }
+}
+
+impl<O: ArgminOp> CostFunction for O {
+    type Param = O::Param;
+    type Output = O::Output;
+    type Hessian = O::Hessian;
+    type Jacobian = O::Jacobian;
+    type Float = O::Float;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        ArgminOp::apply(self, param)
+    }
+}
+
+impl<O: ArgminOp> Gradient for O {
+    const HESSIAN_VEC_GRAD_EVALS: u64 = <O as ArgminOp>::HESSIAN_VEC_GRAD_EVALS;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+        ArgminOp::gradient(self, param)
+    }
+
+    fn hessian_vec(
+        &self,
+        param: &Self::Param,
+        grad: &Self::Param,
+        vec: &Self::Param,
+    ) -> Result<Self::Param, Error>
+    where
+        Self::Param: ArgminScaledAdd<Self::Param, Self::Float, Self::Param>
+            + ArgminSub<Self::Param, Self::Param>
+            + ArgminMul<Self::Float, Self::Param>
+            + ArgminNorm<Self::Float>,
+    {
+        ArgminOp::hessian_vec(self, param, grad, vec)
+    }
+}
+
+impl<O: ArgminOp> Hessian for O {
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        ArgminOp::hessian(self, param)
+    }
+}
+
+impl<O: ArgminOp> Jacobian for O {
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+        ArgminOp::jacobian(self, param)
+    }
+}
+
+impl<O: ArgminOp> Anneal for O {
+    fn modify(&self, param: &Self::Param, extent: Self::Float) -> Result<Self::Param, Error> {
+        ArgminOp::modify(self, param, extent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::MinimalNoOperator;
+
+    struct Paraboloid {}
+
+    impl CostFunction for Paraboloid {
+        type Param = Vec<f64>;
+        type Output = f64;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, p: &Vec<f64>) -> Result<f64, Error> {
+            Ok(p.iter().map(|x| x.powi(2)).sum())
+        }
+    }
+
+    impl Gradient for Paraboloid {
+        fn gradient(&self, p: &Vec<f64>) -> Result<Vec<f64>, Error> {
+            Ok(p.iter().map(|x| 2.0 * x).collect())
+        }
+    }
+
+    #[test]
+    fn test_hessian_vec_default() {
+        let op = Paraboloid {};
+        let hv = op
+            .hessian_vec(
+                &vec![1.0, -2.0, 3.0],
+                &vec![2.0, -4.0, 6.0],
+                &vec![1.0, 0.5, -1.0],
+            )
+            .unwrap();
+        for (a, b) in hv.iter().zip([2.0, 1.0, -2.0].iter()) {
+            assert!((a - b).abs() < 1e-6);
+        }
+
+        // the gradient at `param` is given, only the one at the shifted point is computed
+        let mut op = OpWrapper::new(Paraboloid {});
+        OpWrapper::hessian_vec(&mut op, &vec![1.0, 2.0], &vec![2.0, 4.0], &vec![1.0, -1.0])
+            .unwrap();
+        assert_eq!(op.hessian_vec_func_count, 1);
+        assert_eq!(op.grad_func_count, 1);
+    }
+
+    #[test]
+    fn test_hessian_product() {
+        let op = MinimalNoOperator::new();
+        let hessian = vec![vec![2.0, 1.0], vec![1.0, 2.0]];
+        let hv = ExactHessian
+            .product(
+                &op,
+                &vec![0.0, 0.0],
+                &vec![0.0, 0.0],
+                Some(&hessian),
+                &vec![1.0, -1.0],
+            )
+            .unwrap();
+        for (a, b) in hv.iter().zip([1.0, -1.0].iter()) {
+            assert!((a - b).abs() < 1e-15);
+        }
+        assert!(ExactHessian
+            .product(
+                &op,
+                &vec![0.0, 0.0],
+                &vec![0.0, 0.0],
+                None,
+                &vec![1.0, -1.0]
+            )
+            .is_err());
+
+        let mut op = OpWrapper::new(Paraboloid {});
+        assert!(HessianFree
+            .hessian(&mut op, &vec![1.0, 2.0])
+            .unwrap()
+            .is_none());
+        let hv = HessianFree
+            .product(
+                &Paraboloid {},
+                &vec![1.0, 2.0],
+                &vec![2.0, 4.0],
+                None,
+                &vec![1.0, -1.0],
+            )
+            .unwrap();
+        for (a, b) in hv.iter().zip([2.0, -2.0].iter()) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_argminop_blanket() {
+        let op = MinimalNoOperator::new();
+        fn assert_capabilities<T: Gradient + Hessian + Jacobian + Anneal>(_: &T) {}
+        assert_capabilities(&op);
+    }
+}
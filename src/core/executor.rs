@@ -7,10 +7,11 @@
 
 // TODO: Logging of "initial info"
 
+use crate::core::capabilities::CostFunction;
 use crate::core::serialization::*;
 use crate::core::{
-    ArgminCheckpoint, ArgminIterData, ArgminKV, ArgminOp, ArgminResult, Error, IterState, Observe,
-    Observer, ObserverMode, OpWrapper, Solver, TerminationReason,
+    ArgminCheckpoint, ArgminIterData, ArgminKV, ArgminResult, Error, IterState, Observe, Observer,
+    ObserverMode, OpWrapper, Solver, TerminationReason,
 };
 use instant;
 use serde::de::DeserializeOwned;
@@ -21,17 +22,17 @@ use std::sync::Arc;
 
 /// Executes a solver
 #[derive(Clone, Serialize, Deserialize)]
-pub struct Executor<O: ArgminOp, S> {
+pub struct Executor<O: CostFunction, S> {
     /// solver
     solver: S,
     /// operator
-    #[serde(skip)]
+    #[serde(skip, default = "OpWrapper::default")]
     pub op: OpWrapper<O>,
     /// State
     #[serde(bound = "IterState<O>: Serialize")]
     state: IterState<O>,
     /// Storage for observers
-    #[serde(skip)]
+    #[serde(skip, default = "Observer::new")]
     observers: Observer<O>,
     /// Checkpoint
     checkpoint: ArgminCheckpoint,
@@ -43,7 +44,7 @@ pub struct Executor<O: ArgminOp, S> {
 
 impl<O, S> Executor<O, S>
 where
-    O: ArgminOp,
+    O: CostFunction,
     S: Solver<O>,
 {
     /// Create a new executor with a `solver` and an initial parameter `init_param`
@@ -99,6 +100,10 @@ where
             self.state.population(population.clone());
         }
 
+        if let Some(epoch) = data.get_epoch() {
+            self.state.epoch(epoch);
+        }
+
         if let Some(termination_reason) = data.get_termination_reason() {
             self.state.termination_reason(termination_reason);
         }
@@ -245,6 +250,13 @@ where
         self
     }
 
+    /// Set maximum number of epochs. Only solvers for `StochasticOp`s, which report the number of
+    /// completed passes over all terms, are affected.
+    pub fn max_epochs(mut self, epochs: u64) -> Self {
+        self.state.max_epochs(epochs);
+        self
+    }
+
     /// Set target cost value
     pub fn target_cost(mut self, cost: O::Float) -> Self {
         self.state.target_cost(cost);
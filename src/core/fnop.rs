@@ -0,0 +1,398 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::capabilities::{Anneal, CostFunction, Gradient, Hessian, Jacobian};
+use crate::core::{ArgminFloat, Error};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Shared closure mapping a parameter vector to a value of type `T`
+pub type FnOpFunc<P, T> = Arc<dyn Fn(&P) -> Result<T, Error> + Send + Sync>;
+
+/// Shared closure modifying a parameter vector by a given extent
+pub type FnOpModifyFunc<P, F> = Arc<dyn Fn(&P, F) -> Result<P, Error> + Send + Sync>;
+
+/// Slot of an optional closure of a `FnOp`: `()` if the closure was not provided, the closure
+/// itself otherwise.
+pub trait FnOpSlot {
+    /// Whether the closure was provided
+    const PROVIDED: bool;
+    /// Type of the values computed by the closure, `()` if it was not provided
+    type Output: Clone + Serialize + DeserializeOwned;
+}
+
+impl FnOpSlot for () {
+    const PROVIDED: bool = false;
+    type Output = ();
+}
+
+impl<P, T> FnOpSlot for FnOpFunc<P, T>
+where
+    T: Clone + Serialize + DeserializeOwned,
+{
+    const PROVIDED: bool = true;
+    type Output = T;
+}
+
+impl<P, F> FnOpSlot for FnOpModifyFunc<P, F>
+where
+    P: Clone + Serialize + DeserializeOwned,
+{
+    const PROVIDED: bool = true;
+    type Output = P;
+}
+
+/// Operator which is defined by closures.
+///
+/// The cost function is mandatory, gradient, Hessian, Jacobian and the `modify` function are
+/// added with the corresponding builder methods. Each of them adds the matching capability
+/// (`Gradient`, `Hessian`, `Jacobian` or `Anneal`), therefore passing a `FnOp` without a required
+/// closure to a solver fails to compile. The Hessian and Jacobian types default to `()` and change
+/// when the respective closure is provided.
+///
+/// ```rust
+/// use argmin::prelude::*;
+/// use argmin::solver::gradientdescent::SteepestDescent;
+/// use argmin::solver::linesearch::MoreThuenteLineSearch;
+///
+/// # fn main() -> Result<(), Error> {
+/// let op = FnOp::new(|x: &Vec<f64>| Ok(x[0].powi(2) + 10.0 * x[1].powi(2)))
+///     .gradient(|x: &Vec<f64>| Ok(vec![2.0 * x[0], 20.0 * x[1]]));
+///
+/// let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+/// let res = Executor::new(op, solver, vec![1.0, -2.0])
+///     .max_iters(5)
+///     .run()?;
+/// # assert!(res.state.get_best_cost() < 41.0);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Without the gradient, the same solver is rejected by the compiler:
+///
+/// ```compile_fail
+/// use argmin::prelude::*;
+/// use argmin::solver::gradientdescent::SteepestDescent;
+/// use argmin::solver::linesearch::MoreThuenteLineSearch;
+///
+/// let op = FnOp::new(|x: &Vec<f64>| Ok(x[0].powi(2) + 10.0 * x[1].powi(2)));
+/// let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+/// let res = Executor::new(op, solver, vec![1.0, -2.0]).run();
+/// ```
+///
+/// Closures cannot be serialized, therefore a `FnOp` needs to be passed again to
+/// `Executor::from_checkpoint` when resuming from a checkpoint.
+pub struct FnOp<P, U, F = U, G = (), H = (), J = (), M = ()> {
+    /// Cost function
+    cost: FnOpFunc<P, U>,
+    /// Gradient
+    gradient: G,
+    /// Hessian
+    hessian: H,
+    /// Jacobian
+    jacobian: J,
+    /// Modify function
+    modify: M,
+    /// Precision of floats
+    float: PhantomData<F>,
+}
+
+impl<P, F: ArgminFloat> FnOp<P, F, F> {
+    /// Constructor for a scalar cost function
+    pub fn new<C>(cost: C) -> Self
+    where
+        C: Fn(&P) -> Result<F, Error> + Send + Sync + 'static,
+    {
+        FnOp::from_operator(cost)
+    }
+}
+
+impl<P, U, F> FnOp<P, U, F> {
+    /// Constructor for operators with a non-scalar output, for instance the residuals needed by
+    /// Gauss-Newton methods. Usually the type of the floats needs to be stated explicitly.
+    pub fn from_operator<C>(apply: C) -> Self
+    where
+        C: Fn(&P) -> Result<U, Error> + Send + Sync + 'static,
+    {
+        FnOp {
+            cost: Arc::new(apply),
+            gradient: (),
+            hessian: (),
+            jacobian: (),
+            modify: (),
+            float: PhantomData,
+        }
+    }
+}
+
+impl<P, U, F, H, J, M> FnOp<P, U, F, (), H, J, M> {
+    /// Set gradient
+    pub fn gradient<C>(self, gradient: C) -> FnOp<P, U, F, FnOpFunc<P, P>, H, J, M>
+    where
+        C: Fn(&P) -> Result<P, Error> + Send + Sync + 'static,
+    {
+        FnOp {
+            cost: self.cost,
+            gradient: Arc::new(gradient),
+            hessian: self.hessian,
+            jacobian: self.jacobian,
+            modify: self.modify,
+            float: PhantomData,
+        }
+    }
+}
+
+impl<P, U, F, G, J, M> FnOp<P, U, F, G, (), J, M> {
+    /// Set Hessian
+    pub fn hessian<T, C>(self, hessian: C) -> FnOp<P, U, F, G, FnOpFunc<P, T>, J, M>
+    where
+        C: Fn(&P) -> Result<T, Error> + Send + Sync + 'static,
+    {
+        FnOp {
+            cost: self.cost,
+            gradient: self.gradient,
+            hessian: Arc::new(hessian),
+            jacobian: self.jacobian,
+            modify: self.modify,
+            float: PhantomData,
+        }
+    }
+}
+
+impl<P, U, F, G, H, M> FnOp<P, U, F, G, H, (), M> {
+    /// Set Jacobian
+    pub fn jacobian<T, C>(self, jacobian: C) -> FnOp<P, U, F, G, H, FnOpFunc<P, T>, M>
+    where
+        C: Fn(&P) -> Result<T, Error> + Send + Sync + 'static,
+    {
+        FnOp {
+            cost: self.cost,
+            gradient: self.gradient,
+            hessian: self.hessian,
+            jacobian: Arc::new(jacobian),
+            modify: self.modify,
+            float: PhantomData,
+        }
+    }
+}
+
+impl<P, U, F, G, H, J> FnOp<P, U, F, G, H, J, ()> {
+    /// Set modify function
+    pub fn modify<C>(self, modify: C) -> FnOp<P, U, F, G, H, J, FnOpModifyFunc<P, F>>
+    where
+        C: Fn(&P, F) -> Result<P, Error> + Send + Sync + 'static,
+    {
+        FnOp {
+            cost: self.cost,
+            gradient: self.gradient,
+            hessian: self.hessian,
+            jacobian: self.jacobian,
+            modify: Arc::new(modify),
+            float: PhantomData,
+        }
+    }
+}
+
+impl<P, U, F, G, H, J, M> Clone for FnOp<P, U, F, G, H, J, M>
+where
+    G: Clone,
+    H: Clone,
+    J: Clone,
+    M: Clone,
+{
+    fn clone(&self) -> Self {
+        FnOp {
+            cost: self.cost.clone(),
+            gradient: self.gradient.clone(),
+            hessian: self.hessian.clone(),
+            jacobian: self.jacobian.clone(),
+            modify: self.modify.clone(),
+            float: PhantomData,
+        }
+    }
+}
+
+impl<P, U, F, G, H, J, M> std::fmt::Debug for FnOp<P, U, F, G, H, J, M>
+where
+    G: FnOpSlot,
+    H: FnOpSlot,
+    J: FnOpSlot,
+    M: FnOpSlot,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("FnOp")
+            .field("gradient", &G::PROVIDED)
+            .field("hessian", &H::PROVIDED)
+            .field("jacobian", &J::PROVIDED)
+            .field("modify", &M::PROVIDED)
+            .finish()
+    }
+}
+
+impl<P, U, F, G, H, J, M> CostFunction for FnOp<P, U, F, G, H, J, M>
+where
+    P: Clone + Serialize + DeserializeOwned,
+    U: Clone + Serialize + DeserializeOwned,
+    F: ArgminFloat,
+    H: FnOpSlot,
+    J: FnOpSlot,
+{
+    type Param = P;
+    type Output = U;
+    type Hessian = H::Output;
+    type Jacobian = J::Output;
+    type Float = F;
+
+    fn apply(&self, param: &P) -> Result<U, Error> {
+        (self.cost)(param)
+    }
+}
+
+impl<P, U, F, H, J, M> Gradient for FnOp<P, U, F, FnOpFunc<P, P>, H, J, M>
+where
+    P: Clone + Serialize + DeserializeOwned,
+    U: Clone + Serialize + DeserializeOwned,
+    F: ArgminFloat,
+    H: FnOpSlot,
+    J: FnOpSlot,
+{
+    fn gradient(&self, param: &P) -> Result<P, Error> {
+        (self.gradient)(param)
+    }
+}
+
+impl<P, U, F, G, T, J, M> Hessian for FnOp<P, U, F, G, FnOpFunc<P, T>, J, M>
+where
+    P: Clone + Serialize + DeserializeOwned,
+    U: Clone + Serialize + DeserializeOwned,
+    F: ArgminFloat,
+    T: Clone + Serialize + DeserializeOwned,
+    J: FnOpSlot,
+{
+    fn hessian(&self, param: &P) -> Result<T, Error> {
+        (self.hessian)(param)
+    }
+}
+
+impl<P, U, F, G, H, T, M> Jacobian for FnOp<P, U, F, G, H, FnOpFunc<P, T>, M>
+where
+    P: Clone + Serialize + DeserializeOwned,
+    U: Clone + Serialize + DeserializeOwned,
+    F: ArgminFloat,
+    H: FnOpSlot,
+    T: Clone + Serialize + DeserializeOwned,
+{
+    fn jacobian(&self, param: &P) -> Result<T, Error> {
+        (self.jacobian)(param)
+    }
+}
+
+impl<P, U, F, G, H, J> Anneal for FnOp<P, U, F, G, H, J, FnOpModifyFunc<P, F>>
+where
+    P: Clone + Serialize + DeserializeOwned,
+    U: Clone + Serialize + DeserializeOwned,
+    F: ArgminFloat,
+    H: FnOpSlot,
+    J: FnOpSlot,
+{
+    fn modify(&self, param: &P, extent: F) -> Result<P, Error> {
+        (self.modify)(param, extent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminCheckpoint, ArgminError, CheckpointMode, Executor};
+    use crate::solver::gradientdescent::SteepestDescent;
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(fnop, FnOp<Vec<f64>, f64>);
+    test_trait_impl!(
+        fnop_gradient,
+        FnOp<Vec<f64>, f64, f64, FnOpFunc<Vec<f64>, Vec<f64>>>
+    );
+
+    #[test]
+    fn test_fnop_methods() {
+        let op = FnOp::new(|x: &Vec<f64>| Ok(x[0].powi(2) + x[1].powi(2)))
+            .gradient(|x: &Vec<f64>| Ok(vec![2.0 * x[0], 2.0 * x[1]]))
+            .hessian(|_: &Vec<f64>| Ok(vec![vec![2.0, 0.0], vec![0.0, 2.0]]))
+            .modify(|x: &Vec<f64>, t: f64| Ok(vec![x[0] + t, x[1]]));
+        let x = vec![1.0, 2.0];
+        assert!((op.apply(&x).unwrap() - 5.0).abs() < std::f64::EPSILON);
+        assert_eq!(op.gradient(&x).unwrap(), vec![2.0, 4.0]);
+        assert_eq!(
+            op.hessian(&x).unwrap(),
+            vec![vec![2.0, 0.0], vec![0.0, 2.0]]
+        );
+        assert_eq!(op.modify(&x, 1.0).unwrap(), vec![2.0, 2.0]);
+        assert_eq!(
+            format!("{:?}", op),
+            "FnOp { gradient: true, hessian: true, jacobian: false, modify: true }"
+        );
+    }
+
+    #[test]
+    fn test_fnop_errors() {
+        let op = FnOp::new(|x: &Vec<f64>| {
+            if x[0] < 0.0 {
+                Err(ArgminError::InvalidParameter {
+                    text: "negative".to_string(),
+                }
+                .into())
+            } else {
+                Ok(x[0])
+            }
+        })
+        .jacobian(|_: &Vec<f64>| -> Result<Vec<f64>, Error> {
+            Err(ArgminError::NotImplemented {
+                text: "Jacobian".to_string(),
+            }
+            .into())
+        });
+        assert!(op.apply(&vec![-1.0]).is_err());
+        assert!(op.apply(&vec![1.0]).is_ok());
+        assert!(op.jacobian(&vec![1.0]).is_err());
+    }
+
+    #[test]
+    fn test_fnop_executor() {
+        let op = FnOp::new(|x: &Vec<f64>| Ok((x[0] - 1.0).powi(2) + 10.0 * (x[1] + 2.0).powi(2)))
+            .gradient(|x: &Vec<f64>| Ok(vec![2.0 * (x[0] - 1.0), 20.0 * (x[1] + 2.0)]));
+        let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+        let res = Executor::new(op, solver, vec![0.0, 0.0])
+            .max_iters(10)
+            .run()
+            .unwrap();
+        let x = res.state.get_best_param();
+        assert!((x[0] - 1.0).abs() < 1e-6);
+        assert!((x[1] + 2.0).abs() < 1e-6);
+        assert!(res.state.get_grad_func_count() > 0);
+    }
+
+    #[test]
+    fn test_fnop_checkpoint() {
+        let op = || {
+            FnOp::new(|x: &Vec<f64>| Ok(x[0].powi(2) + x[1].powi(2)))
+                .gradient(|x: &Vec<f64>| Ok(vec![2.0 * x[0], 2.0 * x[1]]))
+        };
+        let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+        let exec = Executor::new(op(), solver, vec![1.0, 2.0]);
+        let dir = std::env::temp_dir().join("argmin_fnop_checkpoint");
+        let check = ArgminCheckpoint::new(dir.to_str().unwrap(), CheckpointMode::Always).unwrap();
+        check.store(&exec, "fnop.arg").unwrap();
+
+        let mut loaded: Executor<_, SteepestDescent<MoreThuenteLineSearch<Vec<f64>, f64>>> =
+            Executor::from_checkpoint(dir.join("fnop.arg"), op()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!((loaded.op.apply(&vec![1.0, 2.0]).unwrap() - 5.0).abs() < std::f64::EPSILON);
+    }
+}
@@ -5,7 +5,8 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::core::{ArgminOp, OpWrapper, TerminationReason};
+use crate::core::capabilities::CostFunction;
+use crate::core::{OpWrapper, TerminationReason};
 use instant;
 use num::traits::float::Float;
 use paste::item;
@@ -13,7 +14,7 @@ use serde::{Deserialize, Serialize};
 
 /// Maintains the state from iteration to iteration of a solver
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct IterState<O: ArgminOp> {
+pub struct IterState<O: CostFunction> {
     /// Current parameter vector
     pub param: O::Param,
     /// Previous parameter vector
@@ -52,12 +53,18 @@ pub struct IterState<O: ArgminOp> {
     pub last_best_iter: u64,
     /// Maximum number of iterations
     pub max_iters: u64,
+    /// Number of completed epochs (passes over all terms of a `StochasticOp`)
+    pub epoch: u64,
+    /// Maximum number of epochs
+    pub max_epochs: u64,
     /// Number of cost function evaluations so far
     pub cost_func_count: u64,
     /// Number of gradient evaluations so far
     pub grad_func_count: u64,
     /// Number of Hessian evaluations so far
     pub hessian_func_count: u64,
+    /// Number of Hessian-vector product evaluations so far
+    pub hessian_vec_func_count: u64,
     /// Number of Jacobian evaluations so far
     pub jacobian_func_count: u64,
     /// Number of modify evaluations so far
@@ -100,7 +107,7 @@ macro_rules! getter {
     };
 }
 
-impl<O: ArgminOp> std::default::Default for IterState<O>
+impl<O: CostFunction> std::default::Default for IterState<O>
 where
     O::Param: Default,
 {
@@ -109,7 +116,7 @@ where
     }
 }
 
-impl<O: ArgminOp> IterState<O> {
+impl<O: CostFunction> IterState<O> {
     /// Create new IterState from `param`
     pub fn new(param: O::Param) -> Self {
         IterState {
@@ -132,9 +139,12 @@ impl<O: ArgminOp> IterState<O> {
             iter: 0,
             last_best_iter: 0,
             max_iters: std::u64::MAX,
+            epoch: 0,
+            max_epochs: std::u64::MAX,
             cost_func_count: 0,
             grad_func_count: 0,
             hessian_func_count: 0,
+            hessian_vec_func_count: 0,
             jacobian_func_count: 0,
             modify_func_count: 0,
             time: Some(instant::Duration::new(0, 0)),
@@ -203,6 +213,8 @@ impl<O: ArgminOp> IterState<O> {
 
     setter!(target_cost, O::Float, "Set target cost value");
     setter!(max_iters, u64, "Set maximum number of iterations");
+    setter!(epoch, u64, "Set number of completed epochs");
+    setter!(max_epochs, u64, "Set maximum number of epochs");
     setter!(
         last_best_iter,
         u64,
@@ -250,6 +262,11 @@ impl<O: ArgminOp> IterState<O> {
         u64,
         "Returns current Hessian function evaluation count"
     );
+    getter!(
+        hessian_vec_func_count,
+        u64,
+        "Returns current Hessian-vector product evaluation count"
+    );
     getter!(
         jacobian_func_count,
         u64,
@@ -279,6 +296,8 @@ impl<O: ArgminOp> IterState<O> {
     getter_option!(prev_jacobian, O::Jacobian, "Returns previous Jacobian");
     getter!(iter, u64, "Returns current number of iterations");
     getter!(max_iters, u64, "Returns maximum number of iterations");
+    getter!(epoch, u64, "Returns number of completed epochs");
+    getter!(max_epochs, u64, "Returns maximum number of epochs");
 
     /// Returns population
     pub fn get_population(&self) -> Option<&Vec<(O::Param, O::Float)>> {
@@ -296,6 +315,7 @@ impl<O: ArgminOp> IterState<O> {
         self.cost_func_count += op.cost_func_count;
         self.grad_func_count += op.grad_func_count;
         self.hessian_func_count += op.hessian_func_count;
+        self.hessian_vec_func_count += op.hessian_vec_func_count;
         self.jacobian_func_count += op.jacobian_func_count;
         self.modify_func_count += op.modify_func_count;
     }
@@ -306,6 +326,7 @@ impl<O: ArgminOp> IterState<O> {
         self.cost_func_count = op.cost_func_count;
         self.grad_func_count = op.grad_func_count;
         self.hessian_func_count = op.hessian_func_count;
+        self.hessian_vec_func_count = op.hessian_vec_func_count;
         self.jacobian_func_count = op.jacobian_func_count;
         self.modify_func_count = op.modify_func_count;
     }
@@ -325,6 +346,11 @@ impl<O: ArgminOp> IterState<O> {
         self.hessian_func_count += num;
     }
 
+    /// Increment the Hessian-vector product evaluation count by `num`
+    pub fn increment_hessian_vec_func_count(&mut self, num: u64) {
+        self.hessian_vec_func_count += num;
+    }
+
     /// Increment Jacobian function evaluation count by `num`
     pub fn increment_jacobian_func_count(&mut self, num: u64) {
         self.jacobian_func_count += num;
@@ -382,9 +408,12 @@ mod tests {
         assert_eq!(state.get_iter(), 0);
         assert_eq!(state.is_best(), true);
         assert_eq!(state.get_max_iters(), std::u64::MAX);
+        assert_eq!(state.get_epoch(), 0);
+        assert_eq!(state.get_max_epochs(), std::u64::MAX);
         assert_eq!(state.get_cost_func_count(), 0);
         assert_eq!(state.get_grad_func_count(), 0);
         assert_eq!(state.get_hessian_func_count(), 0);
+        assert_eq!(state.get_hessian_vec_func_count(), 0);
         assert_eq!(state.get_jacobian_func_count(), 0);
         assert_eq!(state.get_modify_func_count(), 0);
 
@@ -392,6 +421,11 @@ mod tests {
 
         assert_eq!(state.get_max_iters(), 42);
 
+        state.epoch(3).max_epochs(5);
+
+        assert_eq!(state.get_epoch(), 3);
+        assert_eq!(state.get_max_epochs(), 5);
+
         state.cost(cost);
 
         assert_eq!(state.get_cost(), cost);
@@ -487,6 +521,8 @@ mod tests {
         assert_eq!(state.get_grad_func_count(), 43);
         state.increment_hessian_func_count(44);
         assert_eq!(state.get_hessian_func_count(), 44);
+        state.increment_hessian_vec_func_count(47);
+        assert_eq!(state.get_hessian_vec_func_count(), 47);
         state.increment_jacobian_func_count(46);
         assert_eq!(state.get_jacobian_func_count(), 46);
         state.increment_modify_func_count(45);
@@ -516,6 +552,7 @@ mod tests {
         assert_eq!(state.get_cost_func_count(), 42);
         assert_eq!(state.get_grad_func_count(), 43);
         assert_eq!(state.get_hessian_func_count(), 44);
+        assert_eq!(state.get_hessian_vec_func_count(), 47);
         assert_eq!(state.get_jacobian_func_count(), 46);
         assert_eq!(state.get_modify_func_count(), 45);
     }
@@ -0,0 +1,62 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::ArgminAdd;
+use sprs::CsMat;
+
+macro_rules! make_add {
+    ($t:ty) => {
+        impl ArgminAdd<CsMat<$t>, CsMat<$t>> for CsMat<$t> {
+            #[inline]
+            fn add(&self, other: &CsMat<$t>) -> CsMat<$t> {
+                self + other
+            }
+        }
+    };
+}
+
+make_add!(f32);
+make_add!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_add_sparse_ $t>]() {
+                    let a = CsMat::new((2, 2), vec![0, 1, 2], vec![0, 1], vec![1 as $t, 4 as $t]);
+                    let b = CsMat::new((2, 2), vec![0, 1, 2], vec![1, 1], vec![2 as $t, 3 as $t]);
+                    let res = a.add(&b);
+                    let target = [[1 as $t, 2 as $t], [0 as $t, 7 as $t]];
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            let v = res.get(i, j).copied().unwrap_or(0 as $t);
+                            assert!((v - target[i][j]).abs() < std::$t::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_add_sparse_panic_ $t>]() {
+                    let a = CsMat::<$t>::eye(2);
+                    let b = CsMat::<$t>::eye(3);
+                    a.add(&b);
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,121 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::decomposition::{check_rhs_len, check_square_nalgebra};
+use crate::core::math::{ArgminCholesky, ArgminDecompositionSolve, Cholesky};
+use crate::core::{ArgminError, Error};
+
+use nalgebra::{
+    base::{allocator::Allocator, dimension::Dim, storage::Storage},
+    DefaultAllocator, OMatrix, OVector, RealField, SquareMatrix,
+};
+
+impl<N, D, S> ArgminCholesky<Cholesky<OMatrix<N, D, D>>> for SquareMatrix<N, D, S>
+where
+    N: RealField,
+    D: Dim,
+    S: Storage<N, D, D>,
+    DefaultAllocator: Allocator<N, D, D> + Allocator<N, D>,
+{
+    fn cholesky(&self) -> Result<Cholesky<OMatrix<N, D, D>>, Error> {
+        check_square_nalgebra(self, "ArgminCholesky")?;
+        let l = nalgebra::linalg::Cholesky::new(self.clone_owned())
+            .map(|chol| chol.unpack())
+            .filter(|l| l.diagonal().iter().all(|d| *d > N::zero()));
+        match l {
+            Some(l) => Ok(Cholesky { l }),
+            None => Err(ArgminError::ConditionViolated {
+                text: "ArgminCholesky: matrix is not positive definite.".to_string(),
+            }
+            .into()),
+        }
+    }
+}
+
+impl<N, D> ArgminDecompositionSolve<OVector<N, D>> for Cholesky<OMatrix<N, D, D>>
+where
+    N: RealField,
+    D: Dim,
+    DefaultAllocator: Allocator<N, D, D> + Allocator<N, D>,
+{
+    fn solve(&self, b: &OVector<N, D>) -> Result<OVector<N, D>, Error> {
+        check_rhs_len(b.len(), self.l.nrows(), "ArgminCholesky")?;
+        // L y = b, then L^T x = y
+        self.l
+            .solve_lower_triangular(b)
+            .and_then(|y| self.l.tr_solve_lower_triangular(&y))
+            .ok_or_else(|| {
+                ArgminError::ConditionViolated {
+                    text: "ArgminCholesky: singular factor.".to_string(),
+                }
+                .into()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{DMatrix, DVector, Matrix2, Matrix3, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_cholesky_ $t>]() {
+                    let a: Matrix3<$t> = Matrix3::new(4.0, 2.0, 0.4, 2.0, 5.0, 1.0, 0.4, 1.0, 3.0);
+                    let chol = <Matrix3<$t> as ArgminCholesky<Cholesky<Matrix3<$t>>>>::cholesky(&a).unwrap();
+                    let llt = chol.l * chol.l.transpose();
+                    for i in 0..3 {
+                        for j in 0..3 {
+                            assert!((llt[(i, j)] - a[(i, j)]).abs() < 1e-5);
+                        }
+                    }
+                    let x: Vector3<$t> = Vector3::new(1.0, -2.0, 3.0);
+                    let res = chol.solve(&(a * x)).unwrap();
+                    for i in 0..3 {
+                        assert!((res[i] - x[i]).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_dynamic_ $t>]() {
+                    let a: DMatrix<$t> = DMatrix::from_row_slice(2, 2, &[2.0, 1.0, 1.0, 3.0]);
+                    let x: DVector<$t> = DVector::from_vec(vec![1.0, -1.0]);
+                    let res = <DMatrix<$t> as ArgminCholesky<Cholesky<DMatrix<$t>>>>::cholesky(&a)
+                        .unwrap()
+                        .solve(&(&a * &x)).unwrap();
+                    for i in 0..2 {
+                        assert!((res[i] - x[i]).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_indefinite_ $t>]() {
+                    let a: Matrix2<$t> = Matrix2::new(1.0, 2.0, 2.0, 1.0);
+                    assert!(<Matrix2<$t> as ArgminCholesky<Cholesky<Matrix2<$t>>>>::cholesky(&a).is_err());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_not_square_ $t>]() {
+                    let a: DMatrix<$t> = DMatrix::from_row_slice(2, 1, &[1.0, 2.0]);
+                    assert!(<DMatrix<$t> as ArgminCholesky<Cholesky<DMatrix<$t>>>>::cholesky(&a).is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,85 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::decomposition::{check_rhs_len, check_square_ndarray};
+use crate::core::math::{ArgminCholesky, ArgminDecompositionSolve, Cholesky};
+use crate::core::{ArgminError, Error};
+use ndarray::{Array1, Array2};
+use ndarray_linalg::{Diag, SolveTriangular, UPLO};
+
+macro_rules! make_cholesky {
+    ($t:ty) => {
+        impl ArgminCholesky<Cholesky<Array2<$t>>> for Array2<$t> {
+            fn cholesky(&self) -> Result<Cholesky<Array2<$t>>, Error> {
+                check_square_ndarray(self, "ArgminCholesky")?;
+                match ndarray_linalg::Cholesky::cholesky(self, UPLO::Lower) {
+                    Ok(l) => Ok(Cholesky { l }),
+                    Err(_) => Err(ArgminError::ConditionViolated {
+                        text: "ArgminCholesky: matrix is not positive definite.".to_string(),
+                    }
+                    .into()),
+                }
+            }
+        }
+
+        impl ArgminDecompositionSolve<Array1<$t>> for Cholesky<Array2<$t>> {
+            fn solve(&self, b: &Array1<$t>) -> Result<Array1<$t>, Error> {
+                check_rhs_len(b.len(), self.l.nrows(), "ArgminCholesky")?;
+                // L y = b, then L^T x = y
+                let y = self.l.solve_triangular(UPLO::Lower, Diag::NonUnit, b)?;
+                Ok(self
+                    .l
+                    .t()
+                    .solve_triangular(UPLO::Upper, Diag::NonUnit, &y)?)
+            }
+        }
+    };
+}
+
+make_cholesky!(f32);
+make_cholesky!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_cholesky_ $t>]() {
+                    let a: Array2<$t> = array![[4.0, 2.0, 0.4], [2.0, 5.0, 1.0], [0.4, 1.0, 3.0]];
+                    let chol = a.cholesky().unwrap();
+                    let llt = chol.l.dot(&chol.l.t());
+                    for i in 0..3 {
+                        for j in 0..3 {
+                            assert!((llt[(i, j)] - a[(i, j)]).abs() < 1e-5);
+                        }
+                    }
+                    let x: Array1<$t> = array![1.0, -2.0, 3.0];
+                    let res = chol.solve(&a.dot(&x)).unwrap();
+                    for i in 0..3 {
+                        assert!((res[i] - x[i]).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_indefinite_ $t>]() {
+                    let a: Array2<$t> = array![[1.0, 2.0], [2.0, 1.0]];
+                    assert!(a.cholesky().is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,168 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+// Left-looking sparse Cholesky decomposition. The factor `L` is computed column by column; column
+// `j` only depends on the columns `k` of `L` with `L[j][k] != 0`. No fill-reducing ordering is
+// applied.
+
+use crate::core::math::decomposition::{check_rhs_vec, check_square_sprs};
+use crate::core::math::{ArgminCholesky, ArgminDecompositionSolve, Cholesky};
+use crate::core::{ArgminError, Error};
+use sprs::CsMat;
+
+macro_rules! make_cholesky {
+    ($t:ty) => {
+        impl ArgminCholesky<Cholesky<CsMat<$t>>> for CsMat<$t> {
+            fn cholesky(&self) -> Result<Cholesky<CsMat<$t>>, Error> {
+                let n = check_square_sprs(self, "ArgminCholesky")?;
+                let a = self.to_csc();
+                // Nonzero entries (column, value) of each row of L computed so far
+                let mut rows: Vec<Vec<(usize, $t)>> = vec![vec![]; n];
+                let mut indptr: Vec<usize> = vec![0];
+                let mut indices: Vec<usize> = vec![];
+                let mut data: Vec<$t> = vec![];
+                // Dense work vector and its (structural) nonzero pattern
+                let mut x = vec![0 as $t; n];
+                let mut marked = vec![false; n];
+                let mut pattern = vec![];
+                for j in 0..n {
+                    for (i, &v) in a.outer_view(j).unwrap().iter().filter(|(i, _)| *i >= j) {
+                        x[i] = v;
+                        marked[i] = true;
+                        pattern.push(i);
+                    }
+                    for &(k, ljk) in rows[j].iter() {
+                        let col = indptr[k]..indptr[k + 1];
+                        for (&i, &lik) in indices[col.clone()].iter().zip(data[col].iter()) {
+                            if i < j {
+                                continue;
+                            }
+                            if !marked[i] {
+                                marked[i] = true;
+                                pattern.push(i);
+                            }
+                            x[i] -= lik * ljk;
+                        }
+                    }
+                    let d = x[j];
+                    if d.is_nan() || d <= 0 as $t {
+                        return Err(ArgminError::ConditionViolated {
+                            text: "ArgminCholesky: matrix is not positive definite.".to_string(),
+                        }
+                        .into());
+                    }
+                    let d = d.sqrt();
+                    pattern.sort_unstable();
+                    for &i in pattern.iter() {
+                        let lij = if i == j { d } else { x[i] / d };
+                        if i != j {
+                            rows[i].push((j, lij));
+                        }
+                        indices.push(i);
+                        data.push(lij);
+                        x[i] = 0 as $t;
+                        marked[i] = false;
+                    }
+                    pattern.clear();
+                    indptr.push(indices.len());
+                }
+                Ok(Cholesky {
+                    l: CsMat::new_csc((n, n), indptr, indices, data),
+                })
+            }
+        }
+
+        impl ArgminDecompositionSolve<Vec<$t>> for Cholesky<CsMat<$t>> {
+            fn solve(&self, b: &Vec<$t>) -> Result<Vec<$t>, Error> {
+                let n = self.l.rows();
+                check_rhs_vec(b, n, "ArgminCholesky")?;
+                let l = self.l.to_csc();
+                // The diagonal is the first entry of each column
+                // L y = b
+                let mut x = b.clone();
+                for j in 0..n {
+                    let col = l.outer_view(j).unwrap();
+                    let mut entries = col.iter();
+                    let (_, &ljj) = entries.next().unwrap();
+                    x[j] /= ljj;
+                    for (i, &lij) in entries {
+                        x[i] -= lij * x[j];
+                    }
+                }
+                // L^T x = y
+                for j in (0..n).rev() {
+                    let col = l.outer_view(j).unwrap();
+                    let mut entries = col.iter();
+                    let (_, &ljj) = entries.next().unwrap();
+                    for (i, &lij) in entries {
+                        x[j] -= lij * x[i];
+                    }
+                    x[j] /= ljj;
+                }
+                Ok(x)
+            }
+        }
+    };
+}
+
+make_cholesky!(f32);
+make_cholesky!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+    use sprs::TriMat;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_cholesky_sparse_ $t>]() {
+                    // arrow matrix: the first row and column cause fill-in
+                    let mut tri = TriMat::new((4, 4));
+                    for &(i, j, v) in &[(0, 0, 4.0), (1, 1, 3.0), (2, 2, 2.0), (3, 3, 5.0)] {
+                        tri.add_triplet(i, j, v as $t);
+                    }
+                    for i in 1..4 {
+                        tri.add_triplet(i, 0, 1 as $t);
+                        tri.add_triplet(0, i, 1 as $t);
+                    }
+                    let a: CsMat<$t> = tri.to_csr();
+                    let chol = a.cholesky().unwrap();
+                    let l = chol.l.to_dense();
+                    for i in 0..4 {
+                        for j in 0..4 {
+                            let llt: $t = (0..4).map(|k| l[[i, k]] * l[[j, k]]).sum();
+                            let aij = a.get(i, j).copied().unwrap_or(0 as $t);
+                            assert!((llt - aij).abs() < 1e-5);
+                        }
+                    }
+                    let x = chol.solve(&vec![7.0, 4.0, 3.0, 6.0]).unwrap();
+                    for xi in x.iter() {
+                        assert!((xi - 1.0).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_sparse_errors_ $t>]() {
+                    let a: CsMat<$t> = CsMat::new((2, 2), vec![0, 1, 2], vec![0, 1], vec![-1.0, 4.0]);
+                    assert!(a.cholesky().is_err());
+                    let a: CsMat<$t> = CsMat::new((1, 2), vec![0, 1], vec![0], vec![1.0]);
+                    assert!(a.cholesky().is_err());
+                    let a: CsMat<$t> = CsMat::eye(2);
+                    assert!(a.cholesky().unwrap().solve(&vec![1.0]).is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
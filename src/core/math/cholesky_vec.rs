@@ -0,0 +1,116 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::decomposition::{check_rhs_vec, check_square_vec};
+use crate::core::math::{ArgminCholesky, ArgminDecompositionSolve, Cholesky};
+use crate::core::{ArgminError, Error};
+
+macro_rules! make_cholesky {
+    ($t:ty) => {
+        impl ArgminCholesky<Cholesky<Vec<Vec<$t>>>> for Vec<Vec<$t>> {
+            #[allow(clippy::needless_range_loop)]
+            fn cholesky(&self) -> Result<Cholesky<Vec<Vec<$t>>>, Error> {
+                let n = check_square_vec(self, "ArgminCholesky")?;
+                let mut l = vec![vec![0 as $t; n]; n];
+                for j in 0..n {
+                    let mut d = self[j][j];
+                    for k in 0..j {
+                        d -= l[j][k] * l[j][k];
+                    }
+                    if d.is_nan() || d <= 0 as $t {
+                        return Err(ArgminError::ConditionViolated {
+                            text: "ArgminCholesky: matrix is not positive definite.".to_string(),
+                        }
+                        .into());
+                    }
+                    let d = d.sqrt();
+                    l[j][j] = d;
+                    for i in (j + 1)..n {
+                        let mut s = self[i][j];
+                        for k in 0..j {
+                            s -= l[i][k] * l[j][k];
+                        }
+                        l[i][j] = s / d;
+                    }
+                }
+                Ok(Cholesky { l })
+            }
+        }
+
+        impl ArgminDecompositionSolve<Vec<$t>> for Cholesky<Vec<Vec<$t>>> {
+            #[allow(clippy::needless_range_loop)]
+            fn solve(&self, b: &Vec<$t>) -> Result<Vec<$t>, Error> {
+                let n = self.l.len();
+                check_rhs_vec(b, n, "ArgminCholesky")?;
+                // L y = b
+                let mut x = b.clone();
+                for i in 0..n {
+                    for k in 0..i {
+                        x[i] -= self.l[i][k] * x[k];
+                    }
+                    x[i] /= self.l[i][i];
+                }
+                // L^T x = y
+                for i in (0..n).rev() {
+                    for k in (i + 1)..n {
+                        x[i] -= self.l[k][i] * x[k];
+                    }
+                    x[i] /= self.l[i][i];
+                }
+                Ok(x)
+            }
+        }
+    };
+}
+
+make_cholesky!(f32);
+make_cholesky!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_cholesky_ $t>]() {
+                    let a: Vec<Vec<$t>> = vec![
+                        vec![4.0, 2.0, 0.4],
+                        vec![2.0, 5.0, 1.0],
+                        vec![0.4, 1.0, 3.0],
+                    ];
+                    let chol = a.cholesky().unwrap();
+                    for i in 0..3 {
+                        for j in 0..3 {
+                            let lij: $t = (0..3).map(|k| chol.l[i][k] * chol.l[j][k]).sum();
+                            assert!((lij - a[i][j]).abs() < 1e-5);
+                        }
+                    }
+                    let x = vec![1.0, -2.0, 3.0];
+                    let b: Vec<$t> = a.iter().map(|r| r.iter().zip(x.iter()).map(|(a, x)| a * x).sum()).collect();
+                    let res = chol.solve(&b).unwrap();
+                    for i in 0..3 {
+                        assert!((res[i] - x[i]).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_indefinite_ $t>]() {
+                    let a: Vec<Vec<$t>> = vec![vec![1.0, 2.0], vec![2.0, 1.0]];
+                    assert!(a.cholesky().is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
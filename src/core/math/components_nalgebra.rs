@@ -0,0 +1,80 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+use crate::core::math::ArgminComponents;
+
+use nalgebra::{
+    base::{allocator::Allocator, dimension::Dim},
+    DefaultAllocator, OVector, Scalar,
+};
+
+impl<N, D> ArgminComponents<N> for OVector<N, D>
+where
+    N: Scalar,
+    D: Dim,
+    DefaultAllocator: Allocator<N, D>,
+{
+    #[inline]
+    fn num_components(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn component(&self, i: usize) -> N {
+        self[i].clone()
+    }
+
+    #[inline]
+    fn set_component(&mut self, i: usize, value: N) {
+        self[i] = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{DVector, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_components_vec_ $t>]() {
+                    let mut a = Vector3::new(1 as $t, 4 as $t, 8 as $t);
+                    assert_eq!(a.num_components(), 3);
+                    assert_eq!(a.component(1) as i64, 4);
+                    a.set_component(2, 5 as $t);
+                    assert_eq!(a, Vector3::new(1 as $t, 4 as $t, 5 as $t));
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_components_dvec_ $t>]() {
+                    let mut a = DVector::from_vec(vec![1 as $t, 4 as $t, 8 as $t]);
+                    assert_eq!(a.num_components(), 3);
+                    assert_eq!(a.component(0) as i64, 1);
+                    a.set_component(0, 5 as $t);
+                    assert_eq!(a, DVector::from_vec(vec![5 as $t, 4 as $t, 8 as $t]));
+                }
+            }
+        };
+    }
+
+    make_test!(isize);
+    make_test!(usize);
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
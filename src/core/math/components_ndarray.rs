@@ -0,0 +1,72 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+use crate::core::math::ArgminComponents;
+use ndarray::Array1;
+
+impl<T> ArgminComponents<T> for Array1<T>
+where
+    T: Clone,
+{
+    #[inline]
+    fn num_components(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn component(&self, i: usize) -> T {
+        self[i].clone()
+    }
+
+    #[inline]
+    fn set_component(&mut self, i: usize, value: T) {
+        self[i] = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_components_vec_ $t>]() {
+                    let mut a = array![1 as $t, 4 as $t, 8 as $t];
+                    assert_eq!(a.num_components(), 3);
+                    assert_eq!(a.component(1) as i64, 4);
+                    a.set_component(2, 5 as $t);
+                    assert_eq!(a, array![1 as $t, 4 as $t, 5 as $t]);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_components_vec_panic_ $t>]() {
+                    let a = array![1 as $t, 4 as $t];
+                    a.component(2);
+                }
+            }
+        };
+    }
+
+    make_test!(isize);
+    make_test!(usize);
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,110 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminError, Error};
+use serde::{Deserialize, Serialize};
+
+/// Cholesky decomposition `A = L L^T` of a symmetric positive definite matrix
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Cholesky<M> {
+    /// Lower triangular factor `L`
+    pub l: M,
+}
+
+/// LU decomposition with partial pivoting `P A = L U`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LU<M> {
+    /// `U` is stored in the upper triangle (including the diagonal), `L` below the diagonal. The
+    /// diagonal of `L` consists of ones and is not stored.
+    pub lu: M,
+    /// Row permutation: Row `i` of `P A` is row `perm[i]` of `A`
+    pub perm: Vec<usize>,
+}
+
+/// QR decomposition `A = Q R` of an `m x n` matrix with `m >= n`, computed with Householder
+/// reflections
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QR<M> {
+    /// `m x n` matrix with orthonormal columns
+    pub q: M,
+    /// `n x n` upper triangular matrix
+    pub r: M,
+}
+
+/// Checks that `m` is a non-empty square matrix and returns its dimension
+pub(crate) fn check_square_vec<T>(m: &[Vec<T>], name: &str) -> Result<usize, Error> {
+    let n = m.len();
+    if n == 0 || m.iter().any(|row| row.len() != n) {
+        return Err(ArgminError::InvalidParameter {
+            text: format!("{}: matrix must be square and non-empty.", name),
+        }
+        .into());
+    }
+    Ok(n)
+}
+
+/// Checks that `m` is a non-empty square sparse matrix and returns its dimension
+#[cfg(feature = "sprsl")]
+pub(crate) fn check_square_sprs<T>(m: &sprs::CsMat<T>, name: &str) -> Result<usize, Error> {
+    if m.rows() == 0 || m.rows() != m.cols() {
+        return Err(ArgminError::InvalidParameter {
+            text: format!("{}: matrix must be square and non-empty.", name),
+        }
+        .into());
+    }
+    Ok(m.rows())
+}
+
+/// Checks that `m` is a non-empty square matrix and returns its dimension
+#[cfg(feature = "nalgebral")]
+pub(crate) fn check_square_nalgebra<N, R, C, S>(
+    m: &nalgebra::Matrix<N, R, C, S>,
+    name: &str,
+) -> Result<usize, Error>
+where
+    N: nalgebra::Scalar,
+    R: nalgebra::Dim,
+    C: nalgebra::Dim,
+    S: nalgebra::base::storage::Storage<N, R, C>,
+{
+    if m.nrows() == 0 || m.nrows() != m.ncols() {
+        return Err(ArgminError::InvalidParameter {
+            text: format!("{}: matrix must be square and non-empty.", name),
+        }
+        .into());
+    }
+    Ok(m.nrows())
+}
+
+/// Checks that `m` is a non-empty square matrix and returns its dimension
+#[cfg(feature = "ndarrayl")]
+pub(crate) fn check_square_ndarray<T>(m: &ndarray::Array2<T>, name: &str) -> Result<usize, Error> {
+    let (rows, cols) = m.dim();
+    if rows == 0 || rows != cols {
+        return Err(ArgminError::InvalidParameter {
+            text: format!("{}: matrix must be square and non-empty.", name),
+        }
+        .into());
+    }
+    Ok(rows)
+}
+
+/// Checks that the right hand side `b` has `n` elements
+pub(crate) fn check_rhs_vec<T>(b: &[T], n: usize, name: &str) -> Result<(), Error> {
+    check_rhs_len(b.len(), n, name)
+}
+
+/// Checks that the right hand side has `n` elements
+pub(crate) fn check_rhs_len(len: usize, n: usize, name: &str) -> Result<(), Error> {
+    if len != n {
+        return Err(ArgminError::InvalidParameter {
+            text: format!("{}: dimensions of matrix and vector do not match.", name),
+        }
+        .into());
+    }
+    Ok(())
+}
@@ -0,0 +1,115 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::ArgminDot;
+use sprs::CsMat;
+
+macro_rules! make_dot {
+    ($t:ty) => {
+        impl ArgminDot<Vec<$t>, Vec<$t>> for CsMat<$t> {
+            #[inline]
+            fn dot(&self, other: &Vec<$t>) -> Vec<$t> {
+                assert_eq!(self.cols(), other.len());
+                let mut out = vec![0 as $t; self.rows()];
+                for (v, (i, j)) in self.iter() {
+                    out[i] += v * other[j];
+                }
+                out
+            }
+        }
+
+        impl ArgminDot<CsMat<$t>, CsMat<$t>> for CsMat<$t> {
+            #[inline]
+            fn dot(&self, other: &CsMat<$t>) -> CsMat<$t> {
+                assert_eq!(self.cols(), other.rows());
+                self * other
+            }
+        }
+    };
+}
+
+make_dot!(f32);
+make_dot!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_dot_sparse_vec_ $t>]() {
+                    let a = CsMat::new((2, 3), vec![0, 2, 3], vec![0, 2, 1], vec![1 as $t, 4 as $t, 8 as $t]);
+                    let b = vec![1 as $t, 2 as $t, 3 as $t];
+                    let res = <CsMat<$t> as ArgminDot<Vec<$t>, Vec<$t>>>::dot(&a, &b);
+                    let target = vec![13 as $t, 16 as $t];
+                    assert_eq!(res.len(), 2);
+                    for i in 0..2 {
+                        assert!((res[i] - target[i]).abs() < std::$t::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_dot_sparse_vec_csc_ $t>]() {
+                    let a = CsMat::new((2, 3), vec![0, 2, 3], vec![0, 2, 1], vec![1 as $t, 4 as $t, 8 as $t]).to_csc();
+                    let b = vec![1 as $t, 2 as $t, 3 as $t];
+                    let res = <CsMat<$t> as ArgminDot<Vec<$t>, Vec<$t>>>::dot(&a, &b);
+                    let target = vec![13 as $t, 16 as $t];
+                    for i in 0..2 {
+                        assert!((res[i] - target[i]).abs() < std::$t::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_dot_sparse_vec_panic_ $t>]() {
+                    let a = CsMat::<$t>::eye(3);
+                    let b = vec![1 as $t, 2 as $t];
+                    <CsMat<$t> as ArgminDot<Vec<$t>, Vec<$t>>>::dot(&a, &b);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_dot_sparse_sparse_ $t>]() {
+                    // a = [[1, 0, 4], [0, 8, 0]]
+                    let a = CsMat::new((2, 3), vec![0, 2, 3], vec![0, 2, 1], vec![1 as $t, 4 as $t, 8 as $t]);
+                    // b = [[1, 2], [0, 1], [3, 0]]
+                    let b = CsMat::new((3, 2), vec![0, 2, 3, 4], vec![0, 1, 1, 0], vec![1 as $t, 2 as $t, 1 as $t, 3 as $t]);
+                    let res = <CsMat<$t> as ArgminDot<CsMat<$t>, CsMat<$t>>>::dot(&a, &b);
+                    let target = [[13 as $t, 2 as $t], [0 as $t, 8 as $t]];
+                    assert_eq!(res.shape(), (2, 2));
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            let v = res.get(i, j).copied().unwrap_or(0 as $t);
+                            assert!((v - target[i][j]).abs() < std::$t::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_dot_sparse_sparse_panic_ $t>]() {
+                    let a = CsMat::<$t>::eye(3);
+                    let b = CsMat::<$t>::eye(2);
+                    <CsMat<$t> as ArgminDot<CsMat<$t>, CsMat<$t>>>::dot(&a, &b);
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
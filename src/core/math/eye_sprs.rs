@@ -0,0 +1,78 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::ArgminEye;
+use sprs::CsMat;
+
+macro_rules! make_eye {
+    ($t:ty) => {
+        impl ArgminEye for CsMat<$t> {
+            #[inline]
+            fn eye_like(&self) -> CsMat<$t> {
+                assert_eq!(self.rows(), self.cols());
+                CsMat::eye(self.rows())
+            }
+
+            #[inline]
+            fn eye(n: usize) -> CsMat<$t> {
+                CsMat::eye(n)
+            }
+        }
+    };
+}
+
+make_eye!(f32);
+make_eye!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_eye_sparse_ $t>]() {
+                    let e: CsMat<$t> = <CsMat<$t> as ArgminEye>::eye(3);
+                    assert_eq!(e.nnz(), 3);
+                    for i in 0..3 {
+                        for j in 0..3 {
+                            let target = if i == j { 1 as $t } else { 0 as $t };
+                            let v = e.get(i, j).copied().unwrap_or(0 as $t);
+                            assert!((v - target).abs() < std::$t::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_eye_like_sparse_ $t>]() {
+                    let a = CsMat::new((2, 2), vec![0, 1, 2], vec![1, 0], vec![3 as $t, 4 as $t]);
+                    let e = a.eye_like();
+                    assert_eq!(e.shape(), (2, 2));
+                    assert_eq!(e.nnz(), 2);
+                    assert!((e.get(0, 0).unwrap() - 1 as $t).abs() < std::$t::EPSILON);
+                    assert!((e.get(1, 1).unwrap() - 1 as $t).abs() < std::$t::EPSILON);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_eye_like_sparse_panic_ $t>]() {
+                    let a: CsMat<$t> = CsMat::zero((2, 3));
+                    a.eye_like();
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,80 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::{ArgminDecompositionSolve, ArgminInv, ArgminLU};
+use crate::core::Error;
+
+macro_rules! make_inv {
+    ($t:ty) => {
+        impl ArgminInv<Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            /// Computes the inverse via an LU decomposition with partial pivoting.
+            #[allow(clippy::needless_range_loop)]
+            fn inv(&self) -> Result<Vec<Vec<$t>>, Error> {
+                let lu = self.lu()?;
+                let n = self.len();
+                let mut out = vec![vec![0 as $t; n]; n];
+                let mut e = vec![0 as $t; n];
+                for j in 0..n {
+                    e[j] = 1 as $t;
+                    let col = lu.solve(&e)?;
+                    e[j] = 0 as $t;
+                    for i in 0..n {
+                        out[i][j] = col[i];
+                    }
+                }
+                Ok(out)
+            }
+        }
+    };
+}
+
+make_inv!(f32);
+make_inv!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_inv_ $t>]() {
+                    let a = vec![
+                        vec![2 as $t, 5 as $t],
+                        vec![1 as $t, 3 as $t],
+                    ];
+                    let target = vec![
+                        vec![3 as $t, -5 as $t],
+                        vec![-1 as $t, 2 as $t],
+                    ];
+                    let res = <Vec<Vec<$t>> as ArgminInv<Vec<Vec<$t>>>>::inv(&a).unwrap();
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert!((((res[i][j] - target[i][j]) as f64).abs()) < 0.00001);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_inv_singular_ $t>]() {
+                    let a = vec![
+                        vec![1 as $t, 2 as $t],
+                        vec![2 as $t, 4 as $t],
+                    ];
+                    assert!(<Vec<Vec<$t>> as ArgminInv<Vec<Vec<$t>>>>::inv(&a).is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
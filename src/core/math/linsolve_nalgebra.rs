@@ -0,0 +1,137 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    errors::ArgminError,
+    math::{ArgminLinSolve, ArgminMaxAbsDiagonal},
+    Error,
+};
+use approx::AbsDiffEq;
+use num::Zero;
+
+use nalgebra::{
+    base::{
+        allocator::Allocator,
+        dimension::{Dim, DimMin},
+        storage::Storage,
+    },
+    ComplexField, DefaultAllocator, OVector, SquareMatrix,
+};
+
+impl<N, D, S> ArgminLinSolve<OVector<N, D>> for SquareMatrix<N, D, S>
+where
+    N: ComplexField,
+    D: Dim + DimMin<D, Output = D>,
+    S: Storage<N, D, D>,
+    DefaultAllocator: Allocator<N, D, D> + Allocator<N, D> + Allocator<(usize, usize), D>,
+{
+    fn solve_spd(&self, b: &OVector<N, D>) -> Result<OVector<N, D>, Error> {
+        match self.clone_owned().cholesky() {
+            Some(chol) => Ok(chol.solve(b)),
+            None => Err(ArgminError::ConditionViolated {
+                text: "ArgminLinSolve: matrix is not positive definite.".to_string(),
+            }
+            .into()),
+        }
+    }
+
+    fn lin_solve(&self, b: &OVector<N, D>) -> Result<OVector<N, D>, Error> {
+        let n = self.nrows();
+        let scale = self.iter().fold(N::RealField::zero(), |acc, x| {
+            let m = x.clone().modulus();
+            if m > acc {
+                m
+            } else {
+                acc
+            }
+        });
+        let tol = N::RealField::default_epsilon() * scale * nalgebra::convert(n as f64);
+        let hermitian = (0..n).all(|i| {
+            (0..i)
+                .all(|j| (self[(i, j)].clone() - self[(j, i)].clone().conjugate()).modulus() <= tol)
+        });
+        if hermitian {
+            if let Ok(x) = self.solve_spd(b) {
+                return Ok(x);
+            }
+        }
+        match self.clone_owned().lu().solve(b) {
+            Some(x) => Ok(x),
+            None => Err(ArgminError::ConditionViolated {
+                text: "ArgminLinSolve: matrix is singular.".to_string(),
+            }
+            .into()),
+        }
+    }
+}
+
+impl<N, D, S> ArgminMaxAbsDiagonal<N::RealField> for SquareMatrix<N, D, S>
+where
+    N: ComplexField,
+    D: Dim,
+    S: Storage<N, D, D>,
+{
+    fn max_abs_diagonal(&self) -> N::RealField {
+        (0..self.nrows()).fold(N::RealField::zero(), |acc, i| {
+            let m = self[(i, i)].clone().modulus();
+            if m > acc {
+                m
+            } else {
+                acc
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{Matrix3, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_lin_solve_ $t>]() {
+                    let x: Vector3<$t> = Vector3::new(1.0, -2.0, 3.0);
+                    let matrices: Vec<Matrix3<$t>> = vec![
+                        Matrix3::new(4.0, 1.0, 0.0, 1.0, 3.0, 1.0, 0.0, 1.0, 2.0),
+                        Matrix3::new(1.0, 2.0, 0.0, 2.0, 1.0, 0.0, 0.0, 0.0, 1.0),
+                        Matrix3::new(0.0, 2.0, 1.0, 1.0, 1.0, 0.0, 3.0, 0.0, 1.0),
+                    ];
+                    for a in matrices.iter() {
+                        let b = a * x;
+                        let res = a.lin_solve(&b).unwrap();
+                        for i in 0..3 {
+                            assert!((res[i] - x[i]).abs() < 1e-4);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_lin_solve_singular_ $t>]() {
+                    let a: Matrix3<$t> = Matrix3::new(1.0, 2.0, 0.0, 2.0, 4.0, 0.0, 0.0, 0.0, 0.0);
+                    assert!(a.lin_solve(&Vector3::new(1.0, 2.0, 0.0)).is_err());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_max_abs_diagonal_ $t>]() {
+                    let a: Matrix3<$t> = Matrix3::new(1.0, 9.0, 0.0, 9.0, -3.0, 0.0, 0.0, 0.0, 2.0);
+                    assert!((a.max_abs_diagonal() - 3.0).abs() < std::$t::EPSILON);
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,81 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::{ArgminLinSolve, ArgminMaxAbsDiagonal};
+use crate::core::Error;
+use ndarray::{Array1, Array2};
+use ndarray_linalg::{LeastSquaresSvd, Solve, SolveC};
+
+macro_rules! make_linsolve {
+    ($t:ty) => {
+        impl ArgminLinSolve<Array1<$t>> for Array2<$t> {
+            #[inline]
+            fn solve_spd(&self, b: &Array1<$t>) -> Result<Array1<$t>, Error> {
+                Ok(SolveC::solvec(self, b)?)
+            }
+
+            fn lin_solve(&self, b: &Array1<$t>) -> Result<Array1<$t>, Error> {
+                let (m, n) = self.dim();
+                if m != n {
+                    return Ok(LeastSquaresSvd::least_squares(self, b)?.solution);
+                }
+                let scale = self.iter().fold(0 as $t, |acc, x| acc.max(x.abs()));
+                let tol = <$t>::EPSILON * scale * n as $t;
+                let symmetric =
+                    (0..n).all(|i| (0..i).all(|j| (self[(i, j)] - self[(j, i)]).abs() <= tol));
+                if symmetric {
+                    if let Ok(x) = self.solve_spd(b) {
+                        return Ok(x);
+                    }
+                }
+                Ok(Solve::solve(self, b)?)
+            }
+        }
+
+        impl ArgminMaxAbsDiagonal<$t> for Array2<$t> {
+            fn max_abs_diagonal(&self) -> $t {
+                self.diag().iter().fold(0 as $t, |acc, x| acc.max(x.abs()))
+            }
+        }
+    };
+}
+
+make_linsolve!(f32);
+make_linsolve!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_lin_solve_ $t>]() {
+                    let x: Array1<$t> = array![1.0, -2.0, 3.0];
+                    let matrices: Vec<Array2<$t>> = vec![
+                        array![[4.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]],
+                        array![[1.0, 2.0, 0.0], [2.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+                        array![[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [3.0, 0.0, 1.0]],
+                    ];
+                    for a in matrices.iter() {
+                        let b = a.dot(&x);
+                        let res = a.lin_solve(&b).unwrap();
+                        for i in 0..3 {
+                            assert!((res[i] - x[i]).abs() < 1e-4);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
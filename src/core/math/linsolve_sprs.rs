@@ -0,0 +1,190 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+// Symmetric positive definite systems are solved with a sparse Cholesky decomposition, all other
+// square systems with a sparse LU decomposition with partial pivoting. Non-square systems are
+// solved in the least squares (or minimum norm) sense via the augmented system
+//
+// [ I    A ] [ r ]   [ b ]               [ I    A^T ] [ x ]   [ 0 ]
+// [ A^T  0 ] [ x ] = [ 0 ]    (m > n),   [ A    0   ] [ z ] = [ b ]    (m < n),
+//
+// which, unlike the normal equations, does not square the condition number of `A`.
+
+use crate::core::math::decomposition::check_rhs_vec;
+use crate::core::math::{
+    ArgminCholesky, ArgminDecompositionSolve, ArgminLU, ArgminLinSolve, ArgminMaxAbsDiagonal,
+};
+use crate::core::Error;
+use sprs::{CsMat, TriMat};
+
+macro_rules! make_linsolve {
+    ($t:ty) => {
+        impl ArgminLinSolve<Vec<$t>> for CsMat<$t> {
+            fn solve_spd(&self, b: &Vec<$t>) -> Result<Vec<$t>, Error> {
+                self.cholesky()?.solve(b)
+            }
+
+            fn lin_solve(&self, b: &Vec<$t>) -> Result<Vec<$t>, Error> {
+                let (m, n) = self.shape();
+                check_rhs_vec(b, m, "ArgminLinSolve")?;
+                if m == n {
+                    let at = self.transpose_view().to_csr();
+                    let scale = self.data().iter().fold(0 as $t, |acc, x| acc.max(x.abs()));
+                    let tol = <$t>::EPSILON * scale * n as $t;
+                    let symmetric = (&self.to_csr() - &at).data().iter().all(|x| x.abs() <= tol);
+                    if symmetric {
+                        if let Ok(x) = self.solve_spd(b) {
+                            return Ok(x);
+                        }
+                    }
+                    return self.lu()?.solve(b);
+                }
+                // Augmented system: the identity block has dimension k = max(m, n)
+                let k = m.max(n);
+                let mut aug = TriMat::new((m + n, m + n));
+                for i in 0..k {
+                    aug.add_triplet(i, i, 1 as $t);
+                }
+                for (&v, (i, j)) in self.iter() {
+                    let (i, j) = if m > n { (i, j) } else { (j, i) };
+                    aug.add_triplet(i, k + j, v);
+                    aug.add_triplet(k + j, i, v);
+                }
+                let mut rhs = vec![0 as $t; m + n];
+                if m > n {
+                    rhs[..m].copy_from_slice(b);
+                } else {
+                    rhs[n..].copy_from_slice(b);
+                }
+                let sol = aug.to_csc::<usize>().lu()?.solve(&rhs)?;
+                Ok(if m > n {
+                    sol[m..].to_vec()
+                } else {
+                    sol[..n].to_vec()
+                })
+            }
+        }
+
+        impl ArgminMaxAbsDiagonal<$t> for CsMat<$t> {
+            fn max_abs_diagonal(&self) -> $t {
+                self.iter()
+                    .filter(|(_, (i, j))| i == j)
+                    .fold(0 as $t, |acc, (x, _)| acc.max(x.abs()))
+            }
+        }
+    };
+}
+
+make_linsolve!(f32);
+make_linsolve!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::math::ArgminDot;
+    use paste::item;
+
+    fn sparse<T: Copy + num::Num>(
+        rows: usize,
+        cols: usize,
+        entries: &[(usize, usize, T)],
+    ) -> CsMat<T> {
+        let mut tri = TriMat::new((rows, cols));
+        for &(i, j, v) in entries {
+            tri.add_triplet(i, j, v);
+        }
+        tri.to_csr()
+    }
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_lin_solve_sparse_ $t>]() {
+                    let x: Vec<$t> = vec![1.0, -2.0, 3.0];
+                    let matrices: Vec<CsMat<$t>> = vec![
+                        // symmetric positive definite
+                        sparse(3, 3, &[
+                            (0, 0, 4.0), (0, 1, 1.0), (1, 0, 1.0),
+                            (1, 1, 3.0), (1, 2, 1.0), (2, 1, 1.0), (2, 2, 2.0),
+                        ]),
+                        // symmetric indefinite
+                        sparse(3, 3, &[(0, 0, 1.0), (0, 1, 2.0), (1, 0, 2.0), (1, 1, 1.0), (2, 2, 1.0)]),
+                        // not symmetric
+                        sparse(3, 3, &[
+                            (0, 0, 2.0), (0, 1, 2.0), (0, 2, 1.0),
+                            (1, 0, 1.0), (1, 1, 1.0), (2, 0, 3.0), (2, 2, 1.0),
+                        ]),
+                        // overdetermined
+                        sparse(4, 3, &[(0, 0, 1.0), (1, 1, 2.0), (2, 2, 3.0), (3, 0, 1.0), (3, 2, 1.0)]),
+                    ];
+                    for a in matrices.iter() {
+                        let b: Vec<$t> = a.dot(&x);
+                        let res = a.lin_solve(&b).unwrap();
+                        for i in 0..3 {
+                            assert!((res[i] - x[i]).abs() < 1e-4);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_lin_solve_sparse_underdetermined_ $t>]() {
+                    // minimum norm solution of x_0 + x_1 + x_2 = 3, x_1 - x_2 = 0
+                    let a: CsMat<$t> = sparse(2, 3, &[(0, 0, 1.0), (0, 1, 1.0), (0, 2, 1.0), (1, 1, 1.0), (1, 2, -1.0)]);
+                    let res = a.lin_solve(&vec![3.0, 0.0]).unwrap();
+                    for i in 0..3 {
+                        assert!((res[i] - 1.0).abs() < 1e-4);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_lin_solve_sparse_errors_ $t>]() {
+                    let a: CsMat<$t> = sparse(2, 2, &[(0, 0, 1.0), (0, 1, 2.0), (1, 0, 2.0), (1, 1, 4.0)]);
+                    assert!(a.lin_solve(&vec![1.0, 2.0]).is_err());
+                    assert!(a.lin_solve(&vec![1.0, 2.0, 3.0]).is_err());
+                    assert!(a.solve_spd(&vec![1.0]).is_err());
+                    let a: CsMat<$t> = sparse(3, 2, &[(0, 0, 1.0), (1, 1, 1.0), (2, 0, 1.0)]);
+                    assert!(a.lin_solve(&vec![1.0, 2.0]).is_err());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_spd_sparse_not_pd_ $t>]() {
+                    let a: CsMat<$t> = sparse(2, 2, &[(0, 0, -1.0), (1, 1, 4.0)]);
+                    assert!(a.solve_spd(&vec![1.0, 1.0]).is_err());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_regularized_sparse_ $t>]() {
+                    let b: Vec<$t> = vec![1.0, 1.0];
+                    // positive definite: no regularization
+                    let a: CsMat<$t> = sparse(2, 2, &[(0, 0, 2.0), (1, 1, 4.0)]);
+                    let (x, tau) = a.solve_regularized(&b, 1e-3 as $t).unwrap();
+                    assert!(tau.abs() < std::$t::EPSILON);
+                    assert!((x[0] - 0.5).abs() < 1e-6);
+                    assert!((x[1] - 0.25).abs() < 1e-6);
+                    // indefinite: smallest eigenvalue is -1
+                    let a: CsMat<$t> = sparse(2, 2, &[(0, 0, -1.0), (1, 1, 4.0)]);
+                    let (x, tau) = a.solve_regularized(&b, 1e-3 as $t).unwrap();
+                    assert!(tau > 1.0);
+                    assert!(x[0] > 0.0 && x[1] > 0.0);
+                    assert!((a.max_abs_diagonal() - 4.0).abs() < std::$t::EPSILON);
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
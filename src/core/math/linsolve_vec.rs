@@ -0,0 +1,134 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::{
+    ArgminCholesky, ArgminDecompositionSolve, ArgminLU, ArgminLinSolve, ArgminMaxAbsDiagonal,
+    ArgminQR,
+};
+use crate::core::Error;
+
+macro_rules! make_linsolve {
+    ($t:ty) => {
+        impl ArgminLinSolve<Vec<$t>> for Vec<Vec<$t>> {
+            fn solve_spd(&self, b: &Vec<$t>) -> Result<Vec<$t>, Error> {
+                self.cholesky()?.solve(b)
+            }
+
+            fn lin_solve(&self, b: &Vec<$t>) -> Result<Vec<$t>, Error> {
+                let n = self.len();
+                if self.iter().any(|row| row.len() != n) {
+                    return self.qr()?.solve(b);
+                }
+                let scale = self
+                    .iter()
+                    .flat_map(|row| row.iter())
+                    .fold(0 as $t, |acc, x| acc.max(x.abs()));
+                let tol = <$t>::EPSILON * scale * n as $t;
+                let symmetric =
+                    (0..n).all(|i| (0..i).all(|j| (self[i][j] - self[j][i]).abs() <= tol));
+                if symmetric {
+                    if let Ok(x) = self.solve_spd(b) {
+                        return Ok(x);
+                    }
+                }
+                self.lu()?.solve(b)
+            }
+        }
+
+        impl ArgminMaxAbsDiagonal<$t> for Vec<Vec<$t>> {
+            fn max_abs_diagonal(&self) -> $t {
+                self.iter()
+                    .enumerate()
+                    .filter_map(|(i, row)| row.get(i))
+                    .fold(0 as $t, |acc, x| acc.max(x.abs()))
+            }
+        }
+    };
+}
+
+make_linsolve!(f32);
+make_linsolve!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_lin_solve_ $t>]() {
+                    let x: Vec<$t> = vec![1.0, -2.0, 3.0];
+                    let matrices: Vec<Vec<Vec<$t>>> = vec![
+                        // symmetric positive definite
+                        vec![vec![4.0, 1.0, 0.0], vec![1.0, 3.0, 1.0], vec![0.0, 1.0, 2.0]],
+                        // symmetric indefinite
+                        vec![vec![1.0, 2.0, 0.0], vec![2.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]],
+                        // not symmetric
+                        vec![vec![0.0, 2.0, 1.0], vec![1.0, 1.0, 0.0], vec![3.0, 0.0, 1.0]],
+                    ];
+                    for a in matrices.iter() {
+                        let b: Vec<$t> = a.iter().map(|r| r.iter().zip(x.iter()).map(|(a, x)| a * x).sum()).collect();
+                        let res = a.lin_solve(&b).unwrap();
+                        for i in 0..3 {
+                            assert!((res[i] - x[i]).abs() < 1e-4);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_lin_solve_singular_ $t>]() {
+                    let a: Vec<Vec<$t>> = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+                    let err = a.lin_solve(&vec![1.0, 2.0]).unwrap_err();
+                    assert!(matches!(
+                        err.downcast_ref::<ArgminError>(),
+                        Some(ArgminError::ConditionViolated { .. })
+                    ));
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_regularized_ $t>]() {
+                    let b: Vec<$t> = vec![1.0, 1.0];
+                    // positive definite: no regularization
+                    let a: Vec<Vec<$t>> = vec![vec![2.0, 0.0], vec![0.0, 4.0]];
+                    let (x, tau) = a.solve_regularized(&b, 1e-3 as $t).unwrap();
+                    assert!(tau.abs() < std::$t::EPSILON);
+                    assert!((x[0] - 0.5).abs() < 1e-6);
+                    assert!((x[1] - 0.25).abs() < 1e-6);
+                    // indefinite: smallest eigenvalue is -1
+                    let a: Vec<Vec<$t>> = vec![vec![-1.0, 0.0], vec![0.0, 4.0]];
+                    let (x, tau) = a.solve_regularized(&b, 1e-3 as $t).unwrap();
+                    assert!(tau > 1.0);
+                    assert!(x[0] > 0.0 && x[1] > 0.0);
+                    // the shift is relative to the largest absolute diagonal entry
+                    let a: Vec<Vec<$t>> = vec![vec![-1.0e6, 0.0], vec![0.0, 4.0e6]];
+                    let (_, tau_scaled) = a.solve_regularized(&b, 1e-3 as $t).unwrap();
+                    assert!((tau_scaled / tau - 1.0e6).abs() < 1.0);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_max_abs_diagonal_ $t>]() {
+                    let a: Vec<Vec<$t>> = vec![vec![1.0, 9.0], vec![9.0, -3.0]];
+                    assert!((a.max_abs_diagonal() - 3.0).abs() < std::$t::EPSILON);
+                    let a: Vec<Vec<$t>> = vec![vec![0.0, 1.0], vec![1.0, 0.0]];
+                    assert!(a.max_abs_diagonal().abs() < std::$t::EPSILON);
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
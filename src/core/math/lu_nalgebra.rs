@@ -0,0 +1,128 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::decomposition::{check_rhs_len, check_square_nalgebra};
+use crate::core::math::{ArgminDecompositionSolve, ArgminLU, LU};
+use crate::core::{ArgminError, Error};
+use std::cmp::Ordering;
+
+use nalgebra::{
+    base::{
+        allocator::Allocator,
+        dimension::{Dim, DimMin},
+        storage::Storage,
+    },
+    DVector, DefaultAllocator, OMatrix, OVector, RealField, SquareMatrix,
+};
+
+impl<N, D, S> ArgminLU<LU<OMatrix<N, D, D>>> for SquareMatrix<N, D, S>
+where
+    N: RealField,
+    D: Dim + DimMin<D, Output = D>,
+    S: Storage<N, D, D>,
+    DefaultAllocator: Allocator<N, D, D> + Allocator<N, D> + Allocator<(usize, usize), D>,
+{
+    fn lu(&self) -> Result<LU<OMatrix<N, D, D>>, Error> {
+        let n = check_square_nalgebra(self, "ArgminLU")?;
+        let scale = self
+            .iter()
+            .fold(N::zero(), |acc, x| acc.max(x.clone().abs()));
+        let tol = N::default_epsilon() * scale * nalgebra::convert(n as f64);
+        let decomp = nalgebra::linalg::LU::new(self.clone_owned());
+        let lu = decomp.lu_internal().clone();
+        // also catches NaN
+        if lu
+            .diagonal()
+            .iter()
+            .any(|d| d.clone().abs().partial_cmp(&tol) != Some(Ordering::Greater))
+        {
+            return Err(ArgminError::ConditionViolated {
+                text: "ArgminLU: matrix is singular.".to_string(),
+            }
+            .into());
+        }
+        let mut perm = DVector::from_iterator(n, 0..n);
+        decomp.p().permute_rows(&mut perm);
+        Ok(LU {
+            lu,
+            perm: perm.iter().copied().collect(),
+        })
+    }
+}
+
+impl<N, D> ArgminDecompositionSolve<OVector<N, D>> for LU<OMatrix<N, D, D>>
+where
+    N: RealField,
+    D: Dim,
+    DefaultAllocator: Allocator<N, D, D> + Allocator<N, D>,
+{
+    fn solve(&self, b: &OVector<N, D>) -> Result<OVector<N, D>, Error> {
+        check_rhs_len(b.len(), self.lu.nrows(), "ArgminLU")?;
+        let mut x = b.clone();
+        for (i, &p) in self.perm.iter().enumerate() {
+            x[i] = b[p].clone();
+        }
+        // L y = P b, then U x = y
+        if self
+            .lu
+            .solve_lower_triangular_with_diag_mut(&mut x, N::one())
+            && self.lu.solve_upper_triangular_mut(&mut x)
+        {
+            Ok(x)
+        } else {
+            Err(ArgminError::ConditionViolated {
+                text: "ArgminLU: matrix is singular.".to_string(),
+            }
+            .into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{DMatrix, Matrix2, Matrix3, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_lu_ $t>]() {
+                    // requires pivoting
+                    let a: Matrix3<$t> = Matrix3::new(0.0, 2.0, 1.0, 1.0, 1.0, 0.0, 3.0, 0.0, 1.0);
+                    let x: Vector3<$t> = Vector3::new(1.0, -2.0, 3.0);
+                    let lu = <Matrix3<$t> as ArgminLU<LU<Matrix3<$t>>>>::lu(&a).unwrap();
+                    assert_eq!(lu.perm[0], 2);
+                    let res = lu.solve(&(a * x)).unwrap();
+                    for i in 0..3 {
+                        assert!((res[i] - x[i]).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_lu_singular_ $t>]() {
+                    let a: Matrix2<$t> = Matrix2::new(1.0, 2.0, 2.0, 4.0);
+                    assert!(<Matrix2<$t> as ArgminLU<LU<Matrix2<$t>>>>::lu(&a).is_err());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_lu_not_square_ $t>]() {
+                    let a: DMatrix<$t> = DMatrix::from_row_slice(1, 2, &[1.0, 2.0]);
+                    assert!(<DMatrix<$t> as ArgminLU<LU<DMatrix<$t>>>>::lu(&a).is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
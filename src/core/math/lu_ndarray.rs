@@ -0,0 +1,116 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+// ndarray-linalg's `LUFactorized` does not expose its factors, therefore the decomposition is
+// computed here directly.
+
+use crate::core::math::decomposition::{check_rhs_len, check_square_ndarray};
+use crate::core::math::{ArgminDecompositionSolve, ArgminLU, LU};
+use crate::core::{ArgminError, Error};
+use ndarray::{Array1, Array2};
+
+macro_rules! make_lu {
+    ($t:ty) => {
+        impl ArgminLU<LU<Array2<$t>>> for Array2<$t> {
+            fn lu(&self) -> Result<LU<Array2<$t>>, Error> {
+                let n = check_square_ndarray(self, "ArgminLU")?;
+                let scale = self.fold(0 as $t, |acc, x| acc.max(x.abs()));
+                let tol = <$t>::EPSILON * scale * n as $t;
+                let mut lu = self.clone();
+                let mut perm: Vec<usize> = (0..n).collect();
+                for k in 0..n {
+                    let mut p = k;
+                    for i in (k + 1)..n {
+                        if lu[(i, k)].abs() > lu[(p, k)].abs() {
+                            p = i;
+                        }
+                    }
+                    if lu[(p, k)].is_nan() || lu[(p, k)].abs() <= tol {
+                        return Err(ArgminError::ConditionViolated {
+                            text: "ArgminLU: matrix is singular.".to_string(),
+                        }
+                        .into());
+                    }
+                    for j in 0..n {
+                        lu.swap((k, j), (p, j));
+                    }
+                    perm.swap(k, p);
+                    for i in (k + 1)..n {
+                        let f = lu[(i, k)] / lu[(k, k)];
+                        lu[(i, k)] = f;
+                        for j in (k + 1)..n {
+                            lu[(i, j)] -= f * lu[(k, j)];
+                        }
+                    }
+                }
+                Ok(LU { lu, perm })
+            }
+        }
+
+        impl ArgminDecompositionSolve<Array1<$t>> for LU<Array2<$t>> {
+            fn solve(&self, b: &Array1<$t>) -> Result<Array1<$t>, Error> {
+                let n = self.lu.nrows();
+                check_rhs_len(b.len(), n, "ArgminLU")?;
+                // L y = P b
+                let mut x: Array1<$t> = self.perm.iter().map(|&i| b[i]).collect();
+                for i in 0..n {
+                    for k in 0..i {
+                        x[i] -= self.lu[(i, k)] * x[k];
+                    }
+                }
+                // U x = y
+                for i in (0..n).rev() {
+                    for k in (i + 1)..n {
+                        x[i] -= self.lu[(i, k)] * x[k];
+                    }
+                    x[i] /= self.lu[(i, i)];
+                }
+                Ok(x)
+            }
+        }
+    };
+}
+
+make_lu!(f32);
+make_lu!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_lu_ $t>]() {
+                    // requires pivoting
+                    let a: Array2<$t> = array![[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [3.0, 0.0, 1.0]];
+                    let x: Array1<$t> = array![1.0, -2.0, 3.0];
+                    let lu = a.lu().unwrap();
+                    assert_eq!(lu.perm[0], 2);
+                    let res = lu.solve(&a.dot(&x)).unwrap();
+                    for i in 0..3 {
+                        assert!((res[i] - x[i]).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_lu_singular_ $t>]() {
+                    let a: Array2<$t> = array![[1.0, 2.0], [2.0, 4.0]];
+                    assert!(a.lu().is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,197 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+// Left-looking sparse LU decomposition with partial pivoting. Column `j` of `L` and `U` is
+// obtained by applying the previous columns of `L` to column `j` of `A`, the pivot is the entry
+// of largest magnitude among the rows which have not been pivoted yet. No fill-reducing ordering
+// is applied.
+
+use crate::core::math::decomposition::{check_rhs_vec, check_square_sprs};
+use crate::core::math::{ArgminDecompositionSolve, ArgminLU, LU};
+use crate::core::{ArgminError, Error};
+use sprs::CsMat;
+
+macro_rules! make_lu {
+    ($t:ty) => {
+        impl ArgminLU<LU<CsMat<$t>>> for CsMat<$t> {
+            fn lu(&self) -> Result<LU<CsMat<$t>>, Error> {
+                let n = check_square_sprs(self, "ArgminLU")?;
+                let a = self.to_csc();
+                let scale = a.data().iter().fold(0 as $t, |acc, x| acc.max(x.abs()));
+                let tol = <$t>::EPSILON * scale * n as $t;
+                // Columns of L (row of A, value) without the unit diagonal and of U (step, value)
+                let mut l_cols: Vec<Vec<(usize, $t)>> = Vec::with_capacity(n);
+                let mut u_cols: Vec<Vec<(usize, $t)>> = Vec::with_capacity(n);
+                // Row of A chosen as pivot in each step and step in which a row was pivoted
+                let mut perm: Vec<usize> = Vec::with_capacity(n);
+                let mut step: Vec<Option<usize>> = vec![None; n];
+                // Dense work vector and its (structural) nonzero pattern
+                let mut x = vec![0 as $t; n];
+                let mut marked = vec![false; n];
+                let mut pattern = vec![];
+                for j in 0..n {
+                    for (i, &v) in a.outer_view(j).unwrap().iter() {
+                        x[i] = v;
+                        marked[i] = true;
+                        pattern.push(i);
+                    }
+                    for k in 0..j {
+                        if !marked[perm[k]] {
+                            continue;
+                        }
+                        let xk = x[perm[k]];
+                        for &(i, lik) in l_cols[k].iter() {
+                            if !marked[i] {
+                                marked[i] = true;
+                                pattern.push(i);
+                            }
+                            x[i] -= lik * xk;
+                        }
+                    }
+                    let mut p = None;
+                    for &i in pattern.iter().filter(|&&i| step[i].is_none()) {
+                        if p.map_or(true, |p: usize| x[i].abs() > x[p].abs()) {
+                            p = Some(i);
+                        }
+                    }
+                    let p = match p {
+                        // also catches NaN
+                        Some(p) if x[p].abs() > tol => p,
+                        _ => {
+                            return Err(ArgminError::ConditionViolated {
+                                text: "ArgminLU: matrix is singular.".to_string(),
+                            }
+                            .into())
+                        }
+                    };
+                    let pivot = x[p];
+                    let mut l_col = vec![];
+                    let mut u_col = vec![];
+                    for &i in pattern.iter() {
+                        match step[i] {
+                            Some(k) => u_col.push((k, x[i])),
+                            None if i != p => l_col.push((i, x[i] / pivot)),
+                            None => {}
+                        }
+                        x[i] = 0 as $t;
+                        marked[i] = false;
+                    }
+                    pattern.clear();
+                    u_col.push((j, pivot));
+                    perm.push(p);
+                    step[p] = Some(j);
+                    l_cols.push(l_col);
+                    u_cols.push(u_col);
+                }
+                // Store L and U in one matrix, the rows of L are permuted accordingly
+                let mut indptr = vec![0];
+                let mut indices = vec![];
+                let mut data = vec![];
+                for (u_col, l_col) in u_cols.into_iter().zip(l_cols.into_iter()) {
+                    let mut col: Vec<(usize, $t)> = u_col
+                        .into_iter()
+                        .chain(l_col.into_iter().map(|(i, v)| (step[i].unwrap(), v)))
+                        .collect();
+                    col.sort_unstable_by_key(|&(i, _)| i);
+                    for (i, v) in col {
+                        indices.push(i);
+                        data.push(v);
+                    }
+                    indptr.push(indices.len());
+                }
+                Ok(LU {
+                    lu: CsMat::new_csc((n, n), indptr, indices, data),
+                    perm,
+                })
+            }
+        }
+
+        impl ArgminDecompositionSolve<Vec<$t>> for LU<CsMat<$t>> {
+            fn solve(&self, b: &Vec<$t>) -> Result<Vec<$t>, Error> {
+                let n = self.lu.rows();
+                check_rhs_vec(b, n, "ArgminLU")?;
+                let lu = self.lu.to_csc();
+                // L y = P b
+                let mut x: Vec<$t> = self.perm.iter().map(|&i| b[i]).collect();
+                for j in 0..n {
+                    for (i, &lij) in lu.outer_view(j).unwrap().iter().filter(|(i, _)| *i > j) {
+                        x[i] -= lij * x[j];
+                    }
+                }
+                // U x = y
+                for j in (0..n).rev() {
+                    let col = lu.outer_view(j).unwrap();
+                    let ujj = *col.get(j).unwrap();
+                    x[j] /= ujj;
+                    for (i, &uij) in col.iter().filter(|(i, _)| *i < j) {
+                        x[i] -= uij * x[j];
+                    }
+                }
+                Ok(x)
+            }
+        }
+    };
+}
+
+make_lu!(f32);
+make_lu!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+    use sprs::TriMat;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_lu_sparse_ $t>]() {
+                    // zero diagonal, requires pivoting
+                    let mut tri = TriMat::new((4, 4));
+                    for &(i, j, v) in &[
+                        (0, 1, 2.0), (0, 3, 1.0), (1, 0, 3.0), (1, 2, 1.0),
+                        (2, 0, 1.0), (2, 3, 4.0), (3, 1, 1.0), (3, 2, 2.0),
+                    ] {
+                        tri.add_triplet(i, j, v as $t);
+                    }
+                    let a: CsMat<$t> = tri.to_csr();
+                    let lu = a.lu().unwrap();
+                    let m = lu.lu.to_dense();
+                    for i in 0..4 {
+                        for j in 0..4 {
+                            let lu_ij: $t = (0..=i.min(j))
+                                .map(|k| if k == i { m[[k, j]] } else { m[[i, k]] * m[[k, j]] })
+                                .sum();
+                            let aij = a.get(lu.perm[i], j).copied().unwrap_or(0 as $t);
+                            assert!((lu_ij - aij).abs() < 1e-5);
+                        }
+                    }
+                    let x = lu.solve(&vec![3.0, 4.0, 5.0, 3.0]).unwrap();
+                    for xi in x.iter() {
+                        assert!((xi - 1.0).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_lu_sparse_errors_ $t>]() {
+                    let a: CsMat<$t> = CsMat::new((2, 2), vec![0, 2, 4], vec![0, 1, 0, 1], vec![1.0, 2.0, 2.0, 4.0]);
+                    assert!(a.lu().is_err());
+                    let a: CsMat<$t> = CsMat::new((1, 2), vec![0, 1], vec![0], vec![1.0]);
+                    assert!(a.lu().is_err());
+                    let a: CsMat<$t> = CsMat::eye(2);
+                    assert!(a.lu().unwrap().solve(&vec![1.0]).is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,121 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::decomposition::{check_rhs_vec, check_square_vec};
+use crate::core::math::{ArgminDecompositionSolve, ArgminLU, LU};
+use crate::core::{ArgminError, Error};
+
+macro_rules! make_lu {
+    ($t:ty) => {
+        impl ArgminLU<LU<Vec<Vec<$t>>>> for Vec<Vec<$t>> {
+            #[allow(clippy::needless_range_loop)]
+            fn lu(&self) -> Result<LU<Vec<Vec<$t>>>, Error> {
+                let n = check_square_vec(self, "ArgminLU")?;
+                let scale = self
+                    .iter()
+                    .flat_map(|row| row.iter())
+                    .fold(0 as $t, |acc, x| acc.max(x.abs()));
+                let tol = <$t>::EPSILON * scale * n as $t;
+                let mut lu = self.clone();
+                let mut perm: Vec<usize> = (0..n).collect();
+                for k in 0..n {
+                    let mut p = k;
+                    for i in (k + 1)..n {
+                        if lu[i][k].abs() > lu[p][k].abs() {
+                            p = i;
+                        }
+                    }
+                    if lu[p][k].is_nan() || lu[p][k].abs() <= tol {
+                        return Err(ArgminError::ConditionViolated {
+                            text: "ArgminLU: matrix is singular.".to_string(),
+                        }
+                        .into());
+                    }
+                    lu.swap(k, p);
+                    perm.swap(k, p);
+                    for i in (k + 1)..n {
+                        let f = lu[i][k] / lu[k][k];
+                        lu[i][k] = f;
+                        for j in (k + 1)..n {
+                            lu[i][j] -= f * lu[k][j];
+                        }
+                    }
+                }
+                Ok(LU { lu, perm })
+            }
+        }
+
+        impl ArgminDecompositionSolve<Vec<$t>> for LU<Vec<Vec<$t>>> {
+            #[allow(clippy::needless_range_loop)]
+            fn solve(&self, b: &Vec<$t>) -> Result<Vec<$t>, Error> {
+                let n = self.lu.len();
+                check_rhs_vec(b, n, "ArgminLU")?;
+                // L y = P b
+                let mut x: Vec<$t> = self.perm.iter().map(|&i| b[i]).collect();
+                for i in 0..n {
+                    for k in 0..i {
+                        x[i] -= self.lu[i][k] * x[k];
+                    }
+                }
+                // U x = y
+                for i in (0..n).rev() {
+                    for k in (i + 1)..n {
+                        x[i] -= self.lu[i][k] * x[k];
+                    }
+                    x[i] /= self.lu[i][i];
+                }
+                Ok(x)
+            }
+        }
+    };
+}
+
+make_lu!(f32);
+make_lu!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_lu_ $t>]() {
+                    // requires pivoting
+                    let a: Vec<Vec<$t>> = vec![
+                        vec![0.0, 2.0, 1.0],
+                        vec![1.0, 1.0, 0.0],
+                        vec![3.0, 0.0, 1.0],
+                    ];
+                    let x = vec![1.0, -2.0, 3.0];
+                    let b: Vec<$t> = a.iter().map(|r| r.iter().zip(x.iter()).map(|(a, x)| a * x).sum()).collect();
+                    let res = a.lu().unwrap().solve(&b).unwrap();
+                    for i in 0..3 {
+                        assert!((res[i] - x[i]).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_lu_singular_ $t>]() {
+                    let a: Vec<Vec<$t>> = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+                    let err = a.lu().unwrap_err();
+                    assert!(matches!(
+                        err.downcast_ref::<ArgminError>(),
+                        Some(ArgminError::ConditionViolated { .. })
+                    ));
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
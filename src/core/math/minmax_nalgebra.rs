@@ -0,0 +1,131 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::ArgminMinMax;
+
+use nalgebra::{
+    base::{allocator::Allocator, dimension::Dim},
+    DefaultAllocator, OMatrix, Scalar,
+};
+
+impl<N, R, C> ArgminMinMax for OMatrix<N, R, C>
+where
+    N: Scalar + PartialOrd,
+    R: Dim,
+    C: Dim,
+    DefaultAllocator: Allocator<N, R, C>,
+{
+    fn min(x: &Self, y: &Self) -> OMatrix<N, R, C> {
+        assert!(!x.is_empty());
+        assert_eq!(x.shape(), y.shape());
+
+        x.zip_map(y, |a, b| if a < b { a } else { b })
+    }
+
+    fn max(x: &Self, y: &Self) -> OMatrix<N, R, C> {
+        assert!(!x.is_empty());
+        assert_eq!(x.shape(), y.shape());
+
+        x.zip_map(y, |a, b| if a > b { a } else { b })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{DMatrix, DVector, Matrix2x3, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_minmax_vec_ $t>]() {
+                    let a = Vector3::new(1 as $t, 4 as $t, 8 as $t);
+                    let b = Vector3::new(2 as $t, 3 as $t, 4 as $t);
+                    let target_max = Vector3::new(2 as $t, 4 as $t, 8 as $t);
+                    let target_min = Vector3::new(1 as $t, 3 as $t, 4 as $t);
+                    assert_eq!(<Vector3<$t> as ArgminMinMax>::max(&a, &b), target_max);
+                    assert_eq!(<Vector3<$t> as ArgminMinMax>::min(&a, &b), target_min);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_minmax_dvec_ $t>]() {
+                    let a = DVector::from_vec(vec![1 as $t, 4 as $t, 8 as $t]);
+                    let b = DVector::from_vec(vec![2 as $t, 3 as $t, 4 as $t]);
+                    let target_max = DVector::from_vec(vec![2 as $t, 4 as $t, 8 as $t]);
+                    let target_min = DVector::from_vec(vec![1 as $t, 3 as $t, 4 as $t]);
+                    assert_eq!(<DVector<$t> as ArgminMinMax>::max(&a, &b), target_max);
+                    assert_eq!(<DVector<$t> as ArgminMinMax>::min(&a, &b), target_min);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_minmax_mat_ $t>]() {
+                    let a = Matrix2x3::new(
+                        1 as $t, 4 as $t, 8 as $t,
+                        2 as $t, 5 as $t, 9 as $t,
+                    );
+                    let b = Matrix2x3::new(
+                        2 as $t, 3 as $t, 4 as $t,
+                        3 as $t, 4 as $t, 3 as $t,
+                    );
+                    let target_max = Matrix2x3::new(
+                        2 as $t, 4 as $t, 8 as $t,
+                        3 as $t, 5 as $t, 9 as $t,
+                    );
+                    let target_min = Matrix2x3::new(
+                        1 as $t, 3 as $t, 4 as $t,
+                        2 as $t, 4 as $t, 3 as $t,
+                    );
+                    assert_eq!(<Matrix2x3<$t> as ArgminMinMax>::max(&a, &b), target_max);
+                    assert_eq!(<Matrix2x3<$t> as ArgminMinMax>::min(&a, &b), target_min);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_minmax_dmat_ $t>]() {
+                    let a = DMatrix::from_row_slice(2, 2, &[1 as $t, 4 as $t, 2 as $t, 5 as $t]);
+                    let b = DMatrix::from_row_slice(2, 2, &[2 as $t, 3 as $t, 3 as $t, 4 as $t]);
+                    let target_max =
+                        DMatrix::from_row_slice(2, 2, &[2 as $t, 4 as $t, 3 as $t, 5 as $t]);
+                    let target_min =
+                        DMatrix::from_row_slice(2, 2, &[1 as $t, 3 as $t, 2 as $t, 4 as $t]);
+                    assert_eq!(<DMatrix<$t> as ArgminMinMax>::max(&a, &b), target_max);
+                    assert_eq!(<DMatrix<$t> as ArgminMinMax>::min(&a, &b), target_min);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_minmax_dvec_panic_ $t>]() {
+                    let a = DVector::from_vec(vec![1 as $t, 4 as $t]);
+                    let b = DVector::from_vec(vec![2 as $t, 3 as $t, 4 as $t]);
+                    <DVector<$t> as ArgminMinMax>::max(&a, &b);
+                }
+            }
+        };
+    }
+
+    make_test!(isize);
+    make_test!(usize);
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
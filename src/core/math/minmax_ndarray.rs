@@ -0,0 +1,113 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::ArgminMinMax;
+use ndarray::{Array, Dimension, Zip};
+
+impl<T, D> ArgminMinMax for Array<T, D>
+where
+    T: PartialOrd + Clone,
+    D: Dimension,
+{
+    fn min(x: &Self, y: &Self) -> Array<T, D> {
+        assert!(!x.is_empty());
+        assert_eq!(x.shape(), y.shape());
+
+        Zip::from(x)
+            .and(y)
+            .map_collect(|a, b| if a < b { a.clone() } else { b.clone() })
+    }
+
+    fn max(x: &Self, y: &Self) -> Array<T, D> {
+        assert!(!x.is_empty());
+        assert_eq!(x.shape(), y.shape());
+
+        Zip::from(x)
+            .and(y)
+            .map_collect(|a, b| if a > b { a.clone() } else { b.clone() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{array, Array1, Array2};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_minmax_vec_ $t>]() {
+                    let a = array![1 as $t, 4 as $t, 8 as $t];
+                    let b = array![2 as $t, 3 as $t, 4 as $t];
+                    let target_max = array![2 as $t, 4 as $t, 8 as $t];
+                    let target_min = array![1 as $t, 3 as $t, 4 as $t];
+                    assert_eq!(<Array1<$t> as ArgminMinMax>::max(&a, &b), target_max);
+                    assert_eq!(<Array1<$t> as ArgminMinMax>::min(&a, &b), target_min);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_minmax_mat_ $t>]() {
+                    let a = array![
+                        [1 as $t, 4 as $t, 8 as $t],
+                        [2 as $t, 5 as $t, 9 as $t]
+                    ];
+                    let b = array![
+                        [2 as $t, 3 as $t, 4 as $t],
+                        [3 as $t, 4 as $t, 3 as $t]
+                    ];
+                    let target_max = array![
+                        [2 as $t, 4 as $t, 8 as $t],
+                        [3 as $t, 5 as $t, 9 as $t]
+                    ];
+                    let target_min = array![
+                        [1 as $t, 3 as $t, 4 as $t],
+                        [2 as $t, 4 as $t, 3 as $t]
+                    ];
+                    assert_eq!(<Array2<$t> as ArgminMinMax>::max(&a, &b), target_max);
+                    assert_eq!(<Array2<$t> as ArgminMinMax>::min(&a, &b), target_min);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_minmax_vec_panic_ $t>]() {
+                    let a = array![1 as $t, 4 as $t];
+                    let b = array![2 as $t, 3 as $t, 4 as $t];
+                    <Array1<$t> as ArgminMinMax>::max(&a, &b);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_minmax_mat_panic_ $t>]() {
+                    let a = array![[1 as $t, 4 as $t], [2 as $t, 5 as $t]];
+                    let b = array![[2 as $t, 3 as $t]];
+                    <Array2<$t> as ArgminMinMax>::min(&a, &b);
+                }
+            }
+        };
+    }
+
+    make_test!(isize);
+    make_test!(usize);
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
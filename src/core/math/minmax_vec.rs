@@ -31,3 +31,48 @@ where
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_minmax_vec_ $t>]() {
+                    let a = vec![1 as $t, 4 as $t, 8 as $t];
+                    let b = vec![2 as $t, 3 as $t, 4 as $t];
+                    let target_max = vec![2 as $t, 4 as $t, 8 as $t];
+                    let target_min = vec![1 as $t, 3 as $t, 4 as $t];
+                    assert_eq!(<Vec<$t> as ArgminMinMax>::max(&a, &b), target_max);
+                    assert_eq!(<Vec<$t> as ArgminMinMax>::min(&a, &b), target_min);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_minmax_vec_panic_ $t>]() {
+                    let a = vec![1 as $t, 4 as $t];
+                    let b = vec![2 as $t, 3 as $t, 4 as $t];
+                    <Vec<$t> as ArgminMinMax>::max(&a, &b);
+                }
+            }
+        };
+    }
+
+    make_test!(isize);
+    make_test!(usize);
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -11,9 +11,12 @@
 //! different types of parameter vectors. The idea is, that it does not matter whether you would
 //! like to use simple `Vec`s, `ndarray`, `nalgebra` or custom defined types: As long as the traits
 //! required by the solver are implemented, you should be fine. In this module several of these
-//! traits are defined and implemented. These will be extended as needed. They are also already
-//! implemented for basic `Vec`s, and will in the future also be implemented for types defined by
-//! `ndarray` and `nalgebra`.
+//! traits are defined and implemented. These will be extended as needed. They are implemented for
+//! basic `Vec`s, for `ndarray`'s `Array1` and `Array2` (feature `ndarrayl`) and for `nalgebra`'s
+//! statically and dynamically sized vectors and matrices (feature `nalgebral`). Sparse Hessians
+//! and Jacobians can be represented as `sprs` CSR matrices (feature `sprsl`) together with `Vec`
+//! parameter vectors. Traits ending in `Assign` modify `self` in place and should be preferred in
+//! hot loops to avoid allocations.
 //!
 //! # TODO
 //!
@@ -24,13 +27,33 @@ mod add;
 mod add_nalgebra;
 #[cfg(feature = "ndarrayl")]
 mod add_ndarray;
+#[cfg(feature = "sprsl")]
+mod add_sprs;
 mod add_vec;
+#[cfg(feature = "nalgebral")]
+mod addassign_nalgebra;
+#[cfg(feature = "ndarrayl")]
+mod addassign_ndarray;
+mod addassign_vec;
+#[cfg(feature = "nalgebral")]
+mod cholesky_nalgebra;
+#[cfg(feature = "ndarrayl")]
+mod cholesky_ndarray;
+#[cfg(feature = "sprsl")]
+mod cholesky_sprs;
+mod cholesky_vec;
+#[cfg(feature = "nalgebral")]
+mod components_nalgebra;
+#[cfg(feature = "ndarrayl")]
+mod components_ndarray;
+mod components_vec;
 mod conj;
 #[cfg(feature = "nalgebral")]
 mod conj_nalgebra;
 #[cfg(feature = "ndarrayl")]
 mod conj_ndarray;
 mod conj_vec;
+mod decomposition;
 mod div;
 #[cfg(feature = "nalgebral")]
 mod div_nalgebra;
@@ -42,51 +65,109 @@ mod dot;
 mod dot_nalgebra;
 #[cfg(feature = "ndarrayl")]
 mod dot_ndarray;
+#[cfg(feature = "sprsl")]
+mod dot_sprs;
 mod dot_vec;
 #[cfg(feature = "nalgebral")]
 mod eye_nalgebra;
 #[cfg(feature = "ndarrayl")]
 mod eye_ndarray;
+#[cfg(feature = "sprsl")]
+mod eye_sprs;
 mod eye_vec;
 #[cfg(feature = "nalgebral")]
 mod inv_nalgebra;
 #[cfg(feature = "ndarrayl")]
 mod inv_ndarray;
+mod inv_vec;
+#[cfg(feature = "nalgebral")]
+mod linsolve_nalgebra;
+#[cfg(feature = "ndarrayl")]
+mod linsolve_ndarray;
+#[cfg(feature = "sprsl")]
+mod linsolve_sprs;
+mod linsolve_vec;
+#[cfg(feature = "nalgebral")]
+mod lu_nalgebra;
+#[cfg(feature = "ndarrayl")]
+mod lu_ndarray;
+#[cfg(feature = "sprsl")]
+mod lu_sprs;
+mod lu_vec;
+#[cfg(feature = "nalgebral")]
+mod minmax_nalgebra;
+#[cfg(feature = "ndarrayl")]
+mod minmax_ndarray;
+mod minmax_vec;
 mod mul;
 #[cfg(feature = "nalgebral")]
 mod mul_nalgebra;
 #[cfg(feature = "ndarrayl")]
 mod mul_ndarray;
+#[cfg(feature = "sprsl")]
+mod mul_sprs;
 mod mul_vec;
+#[cfg(feature = "nalgebral")]
+mod mulassign_nalgebra;
+#[cfg(feature = "ndarrayl")]
+mod mulassign_ndarray;
+mod mulassign_vec;
 mod norm;
 #[cfg(feature = "nalgebral")]
 mod norm_nalgebra;
 #[cfg(feature = "ndarrayl")]
 mod norm_ndarray;
 mod norm_vec;
+#[cfg(feature = "nalgebral")]
+mod qr_nalgebra;
+#[cfg(feature = "ndarrayl")]
+mod qr_ndarray;
+mod qr_vec;
+#[cfg(feature = "nalgebral")]
+mod random_nalgebra;
+#[cfg(feature = "ndarrayl")]
+mod random_ndarray;
+mod random_vec;
 mod scaledadd;
 #[cfg(feature = "nalgebral")]
 mod scaledadd_nalgebra;
 #[cfg(feature = "ndarrayl")]
 mod scaledadd_ndarray;
 mod scaledadd_vec;
+#[cfg(feature = "nalgebral")]
+mod scaledaddassign_nalgebra;
+#[cfg(feature = "ndarrayl")]
+mod scaledaddassign_ndarray;
+mod scaledaddassign_vec;
 mod scaledsub;
 #[cfg(feature = "nalgebral")]
 mod scaledsub_nalgebra;
 #[cfg(feature = "ndarrayl")]
 mod scaledsub_ndarray;
 mod scaledsub_vec;
+#[cfg(feature = "nalgebral")]
+mod scaledsubassign_nalgebra;
+#[cfg(feature = "ndarrayl")]
+mod scaledsubassign_ndarray;
+mod scaledsubassign_vec;
 mod sub;
 #[cfg(feature = "nalgebral")]
 mod sub_nalgebra;
 #[cfg(feature = "ndarrayl")]
 mod sub_ndarray;
 mod sub_vec;
+#[cfg(feature = "nalgebral")]
+mod subassign_nalgebra;
+#[cfg(feature = "ndarrayl")]
+mod subassign_ndarray;
+mod subassign_vec;
 mod transpose;
 #[cfg(feature = "nalgebral")]
 mod transpose_nalgebra;
 #[cfg(feature = "ndarrayl")]
 mod transpose_ndarray;
+#[cfg(feature = "sprsl")]
+mod transpose_sprs;
 mod transpose_vec;
 mod weighteddot;
 mod zero;
@@ -95,68 +176,133 @@ mod zero_nalgebra;
 #[cfg(feature = "ndarrayl")]
 mod zero_ndarray;
 mod zero_vec;
-// #[cfg(feature = "ndarrayl")]
-// mod random_ndarray; // TODO
-mod random_vec;
-// #[cfg(feature = "ndarrayl")]
-// mod minmax_ndarray; // TODO
-mod minmax_vec;
 pub use crate::core::math::add::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::add_nalgebra::*;
 #[cfg(feature = "ndarrayl")]
 pub use crate::core::math::add_ndarray::*;
+#[cfg(feature = "sprsl")]
+pub use crate::core::math::add_sprs::*;
 pub use crate::core::math::add_vec::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::addassign_nalgebra::*;
+#[cfg(feature = "ndarrayl")]
+pub use crate::core::math::addassign_ndarray::*;
+pub use crate::core::math::addassign_vec::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::components_nalgebra::*;
+#[cfg(feature = "ndarrayl")]
+pub use crate::core::math::components_ndarray::*;
+pub use crate::core::math::components_vec::*;
 pub use crate::core::math::conj::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::conj_nalgebra::*;
 #[cfg(feature = "ndarrayl")]
 pub use crate::core::math::conj_ndarray::*;
 pub use crate::core::math::conj_vec::*;
+pub use crate::core::math::decomposition::{Cholesky, LU, QR};
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::div_nalgebra::*;
 #[cfg(feature = "ndarrayl")]
 pub use crate::core::math::div_ndarray::*;
 pub use crate::core::math::dot::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::dot_nalgebra::*;
 #[cfg(feature = "ndarrayl")]
 pub use crate::core::math::dot_ndarray::*;
+#[cfg(feature = "sprsl")]
+pub use crate::core::math::dot_sprs::*;
 pub use crate::core::math::dot_vec::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::eye_nalgebra::*;
 #[cfg(feature = "ndarrayl")]
 pub use crate::core::math::eye_ndarray::*;
+#[cfg(feature = "sprsl")]
+pub use crate::core::math::eye_sprs::*;
 pub use crate::core::math::eye_vec::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::inv_nalgebra::*;
 #[cfg(feature = "ndarrayl")]
 pub use crate::core::math::inv_ndarray::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::minmax_nalgebra::*;
+#[cfg(feature = "ndarrayl")]
+pub use crate::core::math::minmax_ndarray::*;
+pub use crate::core::math::minmax_vec::*;
 pub use crate::core::math::mul::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::mul_nalgebra::*;
 #[cfg(feature = "ndarrayl")]
 pub use crate::core::math::mul_ndarray::*;
+#[cfg(feature = "sprsl")]
+pub use crate::core::math::mul_sprs::*;
 pub use crate::core::math::mul_vec::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::mulassign_nalgebra::*;
+#[cfg(feature = "ndarrayl")]
+pub use crate::core::math::mulassign_ndarray::*;
+pub use crate::core::math::mulassign_vec::*;
 pub use crate::core::math::norm::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::norm_nalgebra::*;
 #[cfg(feature = "ndarrayl")]
 pub use crate::core::math::norm_ndarray::*;
 pub use crate::core::math::norm_vec::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::random_nalgebra::*;
+#[cfg(feature = "ndarrayl")]
+pub use crate::core::math::random_ndarray::*;
+pub use crate::core::math::random_vec::*;
 pub use crate::core::math::scaledadd::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::scaledadd_nalgebra::*;
 #[cfg(feature = "ndarrayl")]
 pub use crate::core::math::scaledadd_ndarray::*;
 pub use crate::core::math::scaledadd_vec::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::scaledaddassign_nalgebra::*;
+#[cfg(feature = "ndarrayl")]
+pub use crate::core::math::scaledaddassign_ndarray::*;
+pub use crate::core::math::scaledaddassign_vec::*;
 pub use crate::core::math::scaledsub::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::scaledsub_nalgebra::*;
 #[cfg(feature = "ndarrayl")]
 pub use crate::core::math::scaledsub_ndarray::*;
 pub use crate::core::math::scaledsub_vec::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::scaledsubassign_nalgebra::*;
+#[cfg(feature = "ndarrayl")]
+pub use crate::core::math::scaledsubassign_ndarray::*;
+pub use crate::core::math::scaledsubassign_vec::*;
 pub use crate::core::math::sub::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::sub_nalgebra::*;
 #[cfg(feature = "ndarrayl")]
 pub use crate::core::math::sub_ndarray::*;
 pub use crate::core::math::sub_vec::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::subassign_nalgebra::*;
+#[cfg(feature = "ndarrayl")]
+pub use crate::core::math::subassign_ndarray::*;
+pub use crate::core::math::subassign_vec::*;
 pub use crate::core::math::transpose::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::transpose_nalgebra::*;
 #[cfg(feature = "ndarrayl")]
 pub use crate::core::math::transpose_ndarray::*;
+#[cfg(feature = "sprsl")]
+pub use crate::core::math::transpose_sprs::*;
 pub use crate::core::math::transpose_vec::*;
 pub use crate::core::math::weighteddot::*;
 pub use crate::core::math::zero::*;
+#[cfg(feature = "nalgebral")]
+pub use crate::core::math::zero_nalgebra::*;
 #[cfg(feature = "ndarrayl")]
 pub use crate::core::math::zero_ndarray::*;
 pub use crate::core::math::zero_vec::*;
-// #[cfg(feature = "ndarrayl")] // TODO
-// pub use crate::core::math::random_ndarray::*;
-pub use crate::core::math::random_vec::*;
-// #[cfg(feature = "ndarrayl")] // TODO
-// pub use crate::core::math::minmax_ndarray::*;
-pub use crate::core::math::minmax_vec::*;
 
-use crate::core::Error;
+use crate::core::{ArgminError, ArgminFloat, Error};
 
 /// Dot/scalar product of `T` and `self`
 pub trait ArgminDot<T, U> {
@@ -232,6 +378,36 @@ pub trait ArgminScaledSub<T, U, V> {
     fn scaled_sub(&self, factor: &U, vec: &T) -> V;
 }
 
+/// Add a `T` to `self` in place
+pub trait ArgminAddAssign<T> {
+    /// Add a `T` to `self` in place
+    fn add_assign(&mut self, other: &T);
+}
+
+/// Subtract a `T` from `self` in place
+pub trait ArgminSubAssign<T> {
+    /// Subtract a `T` from `self` in place
+    fn sub_assign(&mut self, other: &T);
+}
+
+/// (Pointwise) Multiply `self` with a `T` in place
+pub trait ArgminMulAssign<T> {
+    /// (Pointwise) Multiply `self` with a `T` in place
+    fn mul_assign(&mut self, other: &T);
+}
+
+/// Add a `T` scaled by an `U` to `self` in place
+pub trait ArgminScaledAddAssign<T, U> {
+    /// Add a `T` scaled by an `U` to `self` in place
+    fn scaled_add_assign(&mut self, factor: &U, vec: &T);
+}
+
+/// Subtract a `T` scaled by an `U` from `self` in place
+pub trait ArgminScaledSubAssign<T, U> {
+    /// Subtract a `T` scaled by an `U` from `self` in place
+    fn scaled_sub_assign(&mut self, factor: &U, vec: &T);
+}
+
 /// Compute the l2-norm (`U`) of `self`
 pub trait ArgminNorm<U> {
     /// Compute the l2-norm (`U`) of `self`
@@ -251,12 +427,101 @@ pub trait ArgminInv<T> {
     fn inv(&self) -> Result<T, Error>;
 }
 
+/// Cholesky decomposition (`T`) of a symmetric positive definite matrix `self`
+pub trait ArgminCholesky<T> {
+    /// Compute the Cholesky decomposition
+    fn cholesky(&self) -> Result<T, Error>;
+}
+
+/// LU decomposition with partial pivoting (`T`) of a square matrix `self`
+pub trait ArgminLU<T> {
+    /// Compute the LU decomposition
+    fn lu(&self) -> Result<T, Error>;
+}
+
+/// QR decomposition (`T`) of a matrix `self`
+pub trait ArgminQR<T> {
+    /// Compute the QR decomposition
+    fn qr(&self) -> Result<T, Error>;
+}
+
+/// Solve the linear system `A x = b` with a decomposition of `A`
+pub trait ArgminDecompositionSolve<T> {
+    /// Solve `A x = b`
+    fn solve(&self, b: &T) -> Result<T, Error>;
+}
+
+/// Solve the linear system `self x = b`
+pub trait ArgminLinSolve<T> {
+    /// Solve `self x = b` for a symmetric positive definite `self` using a Cholesky
+    /// decomposition. Returns an error if `self` is not positive definite. Only the lower triangle
+    /// of `self` is used.
+    fn solve_spd(&self, b: &T) -> Result<T, Error>;
+
+    /// Solve `self x = b`. Symmetric matrices are decomposed with Cholesky first, falling back to
+    /// LU with partial pivoting. Non-square matrices are solved in the least squares sense with
+    /// QR (or SVD). Returns `ArgminError::ConditionViolated` if `self` is singular.
+    fn lin_solve(&self, b: &T) -> Result<T, Error>;
+
+    /// Solve `(self + tau * I) x = b` with the smallest `tau` in `0, s beta, 2 s beta, ...`
+    /// for which `self + tau * I` is positive definite. `s` is the largest absolute diagonal
+    /// entry of `self` (1 if the diagonal is zero), which makes `beta` independent of the scale of
+    /// `self`. This is useful for Newton-type methods, where singular or indefinite Hessians would
+    /// lead to meaningless steps. Returns `x` and `tau`.
+    fn solve_regularized<F>(&self, b: &T, beta: F) -> Result<(T, F), Error>
+    where
+        Self: Sized
+            + ArgminEye
+            + ArgminAdd<Self, Self>
+            + ArgminMul<F, Self>
+            + ArgminMaxAbsDiagonal<F>,
+        F: ArgminFloat,
+    {
+        if let Ok(x) = self.solve_spd(b) {
+            return Ok((x, F::from_f64(0.0).unwrap()));
+        }
+        let eye = self.eye_like();
+        let scale = self.max_abs_diagonal();
+        let mut tau = if scale > F::from_f64(0.0).unwrap() {
+            beta * scale
+        } else {
+            beta
+        };
+        for _ in 0..100 {
+            if let Ok(x) = self.add(&eye.mul(&tau)).solve_spd(b) {
+                return Ok((x, tau));
+            }
+            tau = tau * F::from_f64(2.0).unwrap();
+        }
+        Err(ArgminError::ConditionViolated {
+            text: "ArgminLinSolve: regularization failed.".to_string(),
+        }
+        .into())
+    }
+}
+
+/// Largest absolute value of the diagonal entries of a matrix
+pub trait ArgminMaxAbsDiagonal<F> {
+    /// Largest absolute value of the diagonal entries
+    fn max_abs_diagonal(&self) -> F;
+}
+
 /// Create a random number
 pub trait ArgminRandom {
     /// Get a random element between min and max,
     fn rand_from_range(min: &Self, max: &Self) -> Self;
 }
 
+/// Access to the individual components of a vector
+pub trait ArgminComponents<T> {
+    /// Number of components
+    fn num_components(&self) -> usize;
+    /// Component `i`
+    fn component(&self, i: usize) -> T;
+    /// Set component `i` to `value`
+    fn set_component(&mut self, i: usize, value: T);
+}
+
 /// Minimum and Maximum of type `T`
 pub trait ArgminMinMax {
     /// Select piecewise minimum
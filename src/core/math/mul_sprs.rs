@@ -0,0 +1,63 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::ArgminMul;
+use sprs::CsMat;
+
+macro_rules! make_mul {
+    ($t:ty) => {
+        impl ArgminMul<$t, CsMat<$t>> for CsMat<$t> {
+            #[inline]
+            fn mul(&self, other: &$t) -> CsMat<$t> {
+                self.map(|x| x * other)
+            }
+        }
+
+        impl ArgminMul<CsMat<$t>, CsMat<$t>> for $t {
+            #[inline]
+            fn mul(&self, other: &CsMat<$t>) -> CsMat<$t> {
+                other.map(|x| x * self)
+            }
+        }
+    };
+}
+
+make_mul!(f32);
+make_mul!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_mul_sparse_scalar_ $t>]() {
+                    let a = CsMat::new((2, 3), vec![0, 2, 3], vec![0, 2, 1], vec![1 as $t, 4 as $t, 8 as $t]);
+                    let b = 2 as $t;
+                    let res = <CsMat<$t> as ArgminMul<$t, CsMat<$t>>>::mul(&a, &b);
+                    let res2 = <$t as ArgminMul<CsMat<$t>, CsMat<$t>>>::mul(&b, &a);
+                    let target = [[2 as $t, 0 as $t, 8 as $t], [0 as $t, 16 as $t, 0 as $t]];
+                    assert_eq!(res.nnz(), 3);
+                    for i in 0..2 {
+                        for j in 0..3 {
+                            let v = res.get(i, j).copied().unwrap_or(0 as $t);
+                            let v2 = res2.get(i, j).copied().unwrap_or(0 as $t);
+                            assert!((v - target[i][j]).abs() < std::$t::EPSILON);
+                            assert!((v2 - target[i][j]).abs() < std::$t::EPSILON);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
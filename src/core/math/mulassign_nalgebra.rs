@@ -0,0 +1,166 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::ArgminMulAssign;
+
+use nalgebra::{
+    base::{
+        constraint::{SameNumberOfColumns, SameNumberOfRows, ShapeConstraint},
+        dimension::Dim,
+        storage::{Storage, StorageMut},
+        Scalar,
+    },
+    ClosedMul, Matrix,
+};
+
+impl<N, R, C, S> ArgminMulAssign<N> for Matrix<N, R, C, S>
+where
+    N: Scalar + ClosedMul + Copy,
+    R: Dim,
+    C: Dim,
+    S: StorageMut<N, R, C>,
+{
+    #[inline]
+    fn mul_assign(&mut self, other: &N) {
+        *self *= *other;
+    }
+}
+
+impl<N, R1, C1, R2, C2, SA, SB> ArgminMulAssign<Matrix<N, R2, C2, SB>> for Matrix<N, R1, C1, SA>
+where
+    N: Scalar + ClosedMul,
+    R1: Dim,
+    C1: Dim,
+    R2: Dim,
+    C2: Dim,
+    SA: StorageMut<N, R1, C1>,
+    SB: Storage<N, R2, C2>,
+    ShapeConstraint: SameNumberOfRows<R1, R2> + SameNumberOfColumns<C1, C2>,
+{
+    #[inline]
+    fn mul_assign(&mut self, other: &Matrix<N, R2, C2, SB>) {
+        self.component_mul_assign(other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{DVector, Matrix2x3, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_mul_assign_vec_scalar_ $t>]() {
+                    let mut a = Vector3::new(1 as $t, 4 as $t, 8 as $t);
+                    let b = 2 as $t;
+                    let target = Vector3::new(2 as $t, 8 as $t, 16 as $t);
+                    <Vector3<$t> as ArgminMulAssign<$t>>::mul_assign(&mut a, &b);
+                    for i in 0..3 {
+                        assert!((((a[i] - target[i]) as f64).abs()) < std::f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_mul_assign_vec_vec_ $t>]() {
+                    let mut a = Vector3::new(1 as $t, 4 as $t, 8 as $t);
+                    let b = Vector3::new(2 as $t, 3 as $t, 4 as $t);
+                    let target = Vector3::new(2 as $t, 12 as $t, 32 as $t);
+                    <Vector3<$t> as ArgminMulAssign<Vector3<$t>>>::mul_assign(&mut a, &b);
+                    for i in 0..3 {
+                        assert!((((a[i] - target[i]) as f64).abs()) < std::f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_mul_assign_dvec_dvec_ $t>]() {
+                    let mut a = DVector::from_vec(vec![1 as $t, 4 as $t, 8 as $t]);
+                    let b = DVector::from_vec(vec![2 as $t, 3 as $t, 4 as $t]);
+                    let target = DVector::from_vec(vec![2 as $t, 12 as $t, 32 as $t]);
+                    <DVector<$t> as ArgminMulAssign<DVector<$t>>>::mul_assign(&mut a, &b);
+                    for i in 0..3 {
+                        assert!((((a[i] - target[i]) as f64).abs()) < std::f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_mul_assign_dvec_dvec_panic_ $t>]() {
+                    let mut a = DVector::from_vec(vec![1 as $t, 4 as $t, 8 as $t]);
+                    let b = DVector::from_vec(vec![2 as $t, 3 as $t]);
+                    <DVector<$t> as ArgminMulAssign<DVector<$t>>>::mul_assign(&mut a, &b);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_mul_assign_mat_scalar_ $t>]() {
+                    let mut a = Matrix2x3::new(
+                        1 as $t, 4 as $t, 8 as $t,
+                        2 as $t, 5 as $t, 9 as $t,
+                    );
+                    let b = 2 as $t;
+                    let target = Matrix2x3::new(
+                        2 as $t, 8 as $t, 16 as $t,
+                        4 as $t, 10 as $t, 18 as $t,
+                    );
+                    <Matrix2x3<$t> as ArgminMulAssign<$t>>::mul_assign(&mut a, &b);
+                    for i in 0..2 {
+                        for j in 0..3 {
+                            assert!((((a[(i, j)] - target[(i, j)]) as f64).abs()) < std::f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_mul_assign_mat_mat_ $t>]() {
+                    let mut a = Matrix2x3::new(
+                        1 as $t, 4 as $t, 8 as $t,
+                        2 as $t, 5 as $t, 9 as $t,
+                    );
+                    let b = Matrix2x3::new(
+                        2 as $t, 3 as $t, 4 as $t,
+                        3 as $t, 4 as $t, 5 as $t,
+                    );
+                    let target = Matrix2x3::new(
+                        2 as $t, 12 as $t, 32 as $t,
+                        6 as $t, 20 as $t, 45 as $t,
+                    );
+                    <Matrix2x3<$t> as ArgminMulAssign<Matrix2x3<$t>>>::mul_assign(&mut a, &b);
+                    for i in 0..2 {
+                        for j in 0..3 {
+                            assert!((((a[(i, j)] - target[(i, j)]) as f64).abs()) < std::f64::EPSILON);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(isize);
+    make_test!(usize);
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,150 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::decomposition::check_rhs_len;
+use crate::core::math::{ArgminDecompositionSolve, ArgminQR, QR};
+use crate::core::{ArgminError, Error};
+use std::cmp::Ordering;
+
+use nalgebra::{
+    base::{
+        allocator::Allocator,
+        dimension::{Dim, DimMin},
+        storage::Storage,
+    },
+    DefaultAllocator, OMatrix, OVector, RealField, SquareMatrix,
+};
+
+impl<N, D, S> ArgminQR<QR<OMatrix<N, D, D>>> for SquareMatrix<N, D, S>
+where
+    N: RealField,
+    D: Dim + DimMin<D, Output = D>,
+    S: Storage<N, D, D>,
+    DefaultAllocator: Allocator<N, D, D> + Allocator<N, D>,
+{
+    /// Computes the thin QR decomposition. Dynamically sized matrices may have more rows than
+    /// columns.
+    fn qr(&self) -> Result<QR<OMatrix<N, D, D>>, Error> {
+        let (m, n) = self.shape();
+        if n == 0 || m < n {
+            return Err(ArgminError::InvalidParameter {
+                text: "ArgminQR: matrix must be non-empty with at least as many rows as columns."
+                    .to_string(),
+            }
+            .into());
+        }
+        let qr = nalgebra::linalg::QR::new(self.clone_owned());
+        Ok(QR {
+            q: qr.q(),
+            r: qr.r(),
+        })
+    }
+}
+
+impl<N, D> ArgminDecompositionSolve<OVector<N, D>> for QR<OMatrix<N, D, D>>
+where
+    N: RealField,
+    D: Dim,
+    DefaultAllocator: Allocator<N, D, D> + Allocator<N, D>,
+{
+    /// Solves `A x = b`. For overdetermined systems this is the least squares solution.
+    fn solve(&self, b: &OVector<N, D>) -> Result<OVector<N, D>, Error> {
+        let m = self.q.nrows();
+        check_rhs_len(b.len(), m, "ArgminQR")?;
+        let scale = self
+            .r
+            .diagonal()
+            .iter()
+            .fold(N::zero(), |acc, x| acc.max(x.clone().abs()));
+        let tol = N::default_epsilon() * scale * nalgebra::convert(m as f64);
+        // also catches NaN
+        if self
+            .r
+            .diagonal()
+            .iter()
+            .any(|d| d.clone().abs().partial_cmp(&tol) != Some(Ordering::Greater))
+        {
+            return Err(ArgminError::ConditionViolated {
+                text: "ArgminQR: matrix is rank deficient.".to_string(),
+            }
+            .into());
+        }
+        // R x = Q^T b
+        let mut x = self.q.tr_mul(b);
+        self.r.solve_upper_triangular_mut(&mut x);
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{DMatrix, DVector, Matrix3, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_qr_ $t>]() {
+                    let a: Matrix3<$t> =
+                        Matrix3::new(12.0, -51.0, 4.0, 6.0, 167.0, -68.0, -4.0, 24.0, -41.0);
+                    let qr = <Matrix3<$t> as ArgminQR<QR<Matrix3<$t>>>>::qr(&a).unwrap();
+                    let prod = qr.q * qr.r;
+                    for i in 0..3 {
+                        for j in 0..3 {
+                            assert!((prod[(i, j)] - a[(i, j)]).abs() < 1e-3);
+                        }
+                    }
+                    let x: Vector3<$t> = Vector3::new(1.0, -2.0, 3.0);
+                    let res = qr.solve(&(a * x)).unwrap();
+                    for i in 0..3 {
+                        assert!((res[i] - x[i]).abs() < 1e-4);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_least_squares_ $t>]() {
+                    // fit y = c0 + c1 * t to (0, 1), (1, 3), (2, 5), (3, 7.5)
+                    let a: DMatrix<$t> =
+                        DMatrix::from_row_slice(4, 2, &[1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0]);
+                    let b: DVector<$t> = DVector::from_vec(vec![1.0, 3.0, 5.0, 7.5]);
+                    let res = <DMatrix<$t> as ArgminQR<QR<DMatrix<$t>>>>::qr(&a)
+                        .unwrap()
+                        .solve(&b)
+                        .unwrap();
+                    assert!((res[0] - 0.9).abs() < 1e-4);
+                    assert!((res[1] - 2.15).abs() < 1e-4);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_rank_deficient_ $t>]() {
+                    let a: DMatrix<$t> =
+                        DMatrix::from_row_slice(3, 2, &[1.0, 2.0, 2.0, 4.0, 3.0, 6.0]);
+                    let b: DVector<$t> = DVector::from_vec(vec![1.0, 2.0, 3.0]);
+                    let qr = <DMatrix<$t> as ArgminQR<QR<DMatrix<$t>>>>::qr(&a).unwrap();
+                    assert!(qr.solve(&b).is_err());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_wide_ $t>]() {
+                    let a: DMatrix<$t> = DMatrix::from_row_slice(1, 2, &[1.0, 2.0]);
+                    assert!(<DMatrix<$t> as ArgminQR<QR<DMatrix<$t>>>>::qr(&a).is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
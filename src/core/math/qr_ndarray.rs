@@ -0,0 +1,110 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::decomposition::check_rhs_len;
+use crate::core::math::{ArgminDecompositionSolve, ArgminQR, QR};
+use crate::core::{ArgminError, Error};
+use ndarray::{Array1, Array2};
+use ndarray_linalg::{Diag, SolveTriangular, UPLO};
+
+macro_rules! make_qr {
+    ($t:ty) => {
+        impl ArgminQR<QR<Array2<$t>>> for Array2<$t> {
+            fn qr(&self) -> Result<QR<Array2<$t>>, Error> {
+                let (m, n) = self.dim();
+                if n == 0 || m < n {
+                    return Err(ArgminError::InvalidParameter {
+                        text: "ArgminQR: matrix must be non-empty with at least as many rows as columns."
+                            .to_string(),
+                    }
+                    .into());
+                }
+                let (q, r) = ndarray_linalg::QR::qr(self)?;
+                Ok(QR { q, r })
+            }
+        }
+
+        impl ArgminDecompositionSolve<Array1<$t>> for QR<Array2<$t>> {
+            /// Solves `A x = b`. For overdetermined systems this is the least squares solution.
+            fn solve(&self, b: &Array1<$t>) -> Result<Array1<$t>, Error> {
+                let m = self.q.nrows();
+                check_rhs_len(b.len(), m, "ArgminQR")?;
+                let scale = self.r.diag().fold(0 as $t, |acc, x| acc.max(x.abs()));
+                let tol = <$t>::EPSILON * scale * m as $t;
+                if self.r.diag().iter().any(|d| d.is_nan() || d.abs() <= tol) {
+                    return Err(ArgminError::ConditionViolated {
+                        text: "ArgminQR: matrix is rank deficient.".to_string(),
+                    }
+                    .into());
+                }
+                // R x = Q^T b
+                let y = self.q.t().dot(b);
+                Ok(self.r.solve_triangular(UPLO::Upper, Diag::NonUnit, &y)?)
+            }
+        }
+    };
+}
+
+make_qr!(f32);
+make_qr!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_qr_ $t>]() {
+                    let a: Array2<$t> = array![
+                        [12.0, -51.0, 4.0],
+                        [6.0, 167.0, -68.0],
+                        [-4.0, 24.0, -41.0]
+                    ];
+                    let qr = a.qr().unwrap();
+                    let prod = qr.q.dot(&qr.r);
+                    for i in 0..3 {
+                        for j in 0..3 {
+                            assert!((prod[(i, j)] - a[(i, j)]).abs() < 1e-3);
+                        }
+                    }
+                    let x: Array1<$t> = array![1.0, -2.0, 3.0];
+                    let res = qr.solve(&a.dot(&x)).unwrap();
+                    for i in 0..3 {
+                        assert!((res[i] - x[i]).abs() < 1e-4);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_least_squares_ $t>]() {
+                    // fit y = c0 + c1 * t to (0, 1), (1, 3), (2, 5), (3, 7.5)
+                    let a: Array2<$t> = array![[1.0, 0.0], [1.0, 1.0], [1.0, 2.0], [1.0, 3.0]];
+                    let b: Array1<$t> = array![1.0, 3.0, 5.0, 7.5];
+                    let res = a.qr().unwrap().solve(&b).unwrap();
+                    assert!((res[0] - 0.9).abs() < 1e-4);
+                    assert!((res[1] - 2.15).abs() < 1e-4);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_rank_deficient_ $t>]() {
+                    let a: Array2<$t> = array![[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]];
+                    assert!(a.qr().unwrap().solve(&array![1.0, 2.0, 3.0]).is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,168 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::decomposition::check_rhs_vec;
+use crate::core::math::{ArgminDecompositionSolve, ArgminQR, QR};
+use crate::core::{ArgminError, Error};
+
+macro_rules! make_qr {
+    ($t:ty) => {
+        impl ArgminQR<QR<Vec<Vec<$t>>>> for Vec<Vec<$t>> {
+            #[allow(clippy::needless_range_loop)]
+            fn qr(&self) -> Result<QR<Vec<Vec<$t>>>, Error> {
+                let m = self.len();
+                let n = if m > 0 { self[0].len() } else { 0 };
+                if n == 0 || m < n || self.iter().any(|row| row.len() != n) {
+                    return Err(ArgminError::InvalidParameter {
+                        text: "ArgminQR: matrix must be non-empty with at least as many rows as columns."
+                            .to_string(),
+                    }
+                    .into());
+                }
+                let mut r = self.clone();
+                // Householder vectors and their squared norms
+                let mut vs: Vec<(Vec<$t>, $t)> = Vec::with_capacity(n);
+                for k in 0..n {
+                    let norm = (k..m).map(|i| r[i][k] * r[i][k]).sum::<$t>().sqrt();
+                    let alpha = if r[k][k] > 0 as $t { -norm } else { norm };
+                    let mut v = vec![0 as $t; m];
+                    v[k] = r[k][k] - alpha;
+                    for i in (k + 1)..m {
+                        v[i] = r[i][k];
+                    }
+                    let vnorm2: $t = v.iter().map(|x| x * x).sum();
+                    if vnorm2 > 0 as $t {
+                        for j in k..n {
+                            let s: $t = (k..m).map(|i| v[i] * r[i][j]).sum();
+                            let f = 2 as $t * s / vnorm2;
+                            for i in k..m {
+                                r[i][j] -= f * v[i];
+                            }
+                        }
+                    }
+                    vs.push((v, vnorm2));
+                }
+                // Thin Q: apply the reflections in reverse order to the first n columns of I
+                let mut q = vec![vec![0 as $t; n]; m];
+                for i in 0..n {
+                    q[i][i] = 1 as $t;
+                }
+                for (k, (v, vnorm2)) in vs.iter().enumerate().rev() {
+                    if *vnorm2 > 0 as $t {
+                        for j in 0..n {
+                            let s: $t = (k..m).map(|i| v[i] * q[i][j]).sum();
+                            let f = 2 as $t * s / vnorm2;
+                            for i in k..m {
+                                q[i][j] -= f * v[i];
+                            }
+                        }
+                    }
+                }
+                r.truncate(n);
+                for i in 0..n {
+                    for j in 0..i {
+                        r[i][j] = 0 as $t;
+                    }
+                }
+                Ok(QR { q, r })
+            }
+        }
+
+        impl ArgminDecompositionSolve<Vec<$t>> for QR<Vec<Vec<$t>>> {
+            /// Solves `A x = b`. For overdetermined systems this is the least squares solution.
+            #[allow(clippy::needless_range_loop)]
+            fn solve(&self, b: &Vec<$t>) -> Result<Vec<$t>, Error> {
+                let m = self.q.len();
+                let n = self.r.len();
+                check_rhs_vec(b, m, "ArgminQR")?;
+                let scale = (0..n).fold(0 as $t, |acc, i| acc.max(self.r[i][i].abs()));
+                let tol = <$t>::EPSILON * scale * m as $t;
+                // Q^T b
+                let mut x: Vec<$t> = (0..n).map(|j| (0..m).map(|i| self.q[i][j] * b[i]).sum()).collect();
+                // R x = Q^T b
+                for i in (0..n).rev() {
+                    if self.r[i][i].is_nan() || self.r[i][i].abs() <= tol {
+                        return Err(ArgminError::ConditionViolated {
+                            text: "ArgminQR: matrix is rank deficient.".to_string(),
+                        }
+                        .into());
+                    }
+                    for k in (i + 1)..n {
+                        x[i] -= self.r[i][k] * x[k];
+                    }
+                    x[i] /= self.r[i][i];
+                }
+                Ok(x)
+            }
+        }
+    };
+}
+
+make_qr!(f32);
+make_qr!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_qr_ $t>]() {
+                    let a: Vec<Vec<$t>> = vec![
+                        vec![12.0, -51.0, 4.0],
+                        vec![6.0, 167.0, -68.0],
+                        vec![-4.0, 24.0, -41.0],
+                    ];
+                    let qr = a.qr().unwrap();
+                    for i in 0..3 {
+                        for j in 0..3 {
+                            let qrij: $t = (0..3).map(|k| qr.q[i][k] * qr.r[k][j]).sum();
+                            assert!((qrij - a[i][j]).abs() < 1e-3);
+                        }
+                    }
+                    let x = vec![1.0, -2.0, 3.0];
+                    let b: Vec<$t> = a.iter().map(|r| r.iter().zip(x.iter()).map(|(a, x)| a * x).sum()).collect();
+                    let res = qr.solve(&b).unwrap();
+                    for i in 0..3 {
+                        assert!((res[i] - x[i]).abs() < 1e-4);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_least_squares_ $t>]() {
+                    // fit y = c0 + c1 * t to (0, 1), (1, 3), (2, 5), (3, 7.5)
+                    let a: Vec<Vec<$t>> = vec![
+                        vec![1.0, 0.0],
+                        vec![1.0, 1.0],
+                        vec![1.0, 2.0],
+                        vec![1.0, 3.0],
+                    ];
+                    let b: Vec<$t> = vec![1.0, 3.0, 5.0, 7.5];
+                    let res = a.qr().unwrap().solve(&b).unwrap();
+                    assert!((res[0] - 0.9).abs() < 1e-4);
+                    assert!((res[1] - 2.15).abs() < 1e-4);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_rank_deficient_ $t>]() {
+                    let a: Vec<Vec<$t>> = vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]];
+                    assert!(a.qr().unwrap().solve(&vec![1.0, 2.0, 3.0]).is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,144 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::ArgminRandom;
+use rand::distributions::uniform::SampleUniform;
+use rand::Rng;
+
+use nalgebra::{
+    base::{allocator::Allocator, dimension::Dim},
+    DefaultAllocator, OMatrix, Scalar,
+};
+
+impl<N, R, C> ArgminRandom for OMatrix<N, R, C>
+where
+    N: Scalar + SampleUniform + PartialOrd,
+    R: Dim,
+    C: Dim,
+    DefaultAllocator: Allocator<N, R, C>,
+{
+    fn rand_from_range(min: &Self, max: &Self) -> OMatrix<N, R, C> {
+        assert!(!min.is_empty());
+        assert_eq!(min.shape(), max.shape());
+
+        let mut rng = rand::thread_rng();
+
+        min.zip_map(max, |a, b| {
+            // Do not require a < b:
+
+            if a == b {
+                a
+            } else if a < b {
+                rng.gen_range(a..b)
+            } else {
+                rng.gen_range(b..a)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{DMatrix, DVector, Matrix2x3, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_random_vec_ $t>]() {
+                    let a = Vector3::new(1 as $t, 4 as $t, 8 as $t);
+                    let b = Vector3::new(2 as $t, 3 as $t, 8 as $t);
+                    let random = Vector3::<$t>::rand_from_range(&a, &b);
+                    assert!(random[0] >= 1 as $t && random[0] < 2 as $t);
+                    assert!(random[1] >= 3 as $t && random[1] < 4 as $t);
+                    assert!(((random[2] - 8 as $t) as f64).abs() < std::f64::EPSILON);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_random_dvec_ $t>]() {
+                    let a = DVector::from_vec(vec![1 as $t, 4 as $t, 8 as $t]);
+                    let b = DVector::from_vec(vec![2 as $t, 3 as $t, 8 as $t]);
+                    let random = DVector::<$t>::rand_from_range(&a, &b);
+                    assert_eq!(random.len(), 3);
+                    assert!(random[0] >= 1 as $t && random[0] < 2 as $t);
+                    assert!(random[1] >= 3 as $t && random[1] < 4 as $t);
+                    assert!(((random[2] - 8 as $t) as f64).abs() < std::f64::EPSILON);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_random_mat_ $t>]() {
+                    let a = Matrix2x3::new(
+                        1 as $t, 4 as $t, 8 as $t,
+                        2 as $t, 6 as $t, 9 as $t,
+                    );
+                    let b = Matrix2x3::new(
+                        2 as $t, 3 as $t, 8 as $t,
+                        3 as $t, 5 as $t, 10 as $t,
+                    );
+                    let random = Matrix2x3::<$t>::rand_from_range(&a, &b);
+                    for i in 0..2 {
+                        for j in 0..3 {
+                            let (lo, hi) = if a[(i, j)] < b[(i, j)] {
+                                (a[(i, j)], b[(i, j)])
+                            } else {
+                                (b[(i, j)], a[(i, j)])
+                            };
+                            assert!(random[(i, j)] >= lo && random[(i, j)] <= hi);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_random_dmat_ $t>]() {
+                    let a = DMatrix::from_row_slice(2, 2, &[1 as $t, 4 as $t, 2 as $t, 6 as $t]);
+                    let b = DMatrix::from_row_slice(2, 2, &[2 as $t, 3 as $t, 3 as $t, 5 as $t]);
+                    let random = DMatrix::<$t>::rand_from_range(&a, &b);
+                    assert_eq!(random.shape(), (2, 2));
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            let (lo, hi) = if a[(i, j)] < b[(i, j)] {
+                                (a[(i, j)], b[(i, j)])
+                            } else {
+                                (b[(i, j)], a[(i, j)])
+                            };
+                            assert!(random[(i, j)] >= lo && random[(i, j)] <= hi);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_random_dvec_panic_ $t>]() {
+                    let a = DVector::from_vec(vec![1 as $t, 4 as $t]);
+                    let b = DVector::from_vec(vec![2 as $t, 3 as $t, 8 as $t]);
+                    DVector::<$t>::rand_from_range(&a, &b);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
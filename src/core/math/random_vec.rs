@@ -35,3 +35,57 @@ where
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_random_vec_ $t>]() {
+                    let a = vec![1 as $t, 4 as $t, 8 as $t];
+                    let b = vec![2 as $t, 3 as $t, 8 as $t];
+                    let random = Vec::<$t>::rand_from_range(&a, &b);
+                    assert_eq!(random.len(), 3);
+                    assert!(random[0] >= 1 as $t && random[0] < 2 as $t);
+                    assert!(random[1] >= 3 as $t && random[1] < 4 as $t);
+                    assert!(((random[2] - 8 as $t) as f64).abs() < std::f64::EPSILON);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_random_vec_panic_ $t>]() {
+                    let a = vec![1 as $t, 4 as $t];
+                    let b = vec![2 as $t, 3 as $t, 8 as $t];
+                    Vec::<$t>::rand_from_range(&a, &b);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_random_vec_empty_panic_ $t>]() {
+                    let a: Vec<$t> = vec![];
+                    let b: Vec<$t> = vec![];
+                    Vec::<$t>::rand_from_range(&a, &b);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
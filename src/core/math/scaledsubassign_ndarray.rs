@@ -0,0 +1,129 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::ArgminScaledSubAssign;
+use ndarray::{Array1, Array2, Zip};
+use num_complex::Complex;
+
+macro_rules! make_scaled_sub_assign {
+    ($t:ty) => {
+        impl ArgminScaledSubAssign<Array1<$t>, $t> for Array1<$t> {
+            #[inline]
+            fn scaled_sub_assign(&mut self, factor: &$t, vec: &Array1<$t>) {
+                Zip::from(self).and(vec).for_each(|a, &b| *a -= *factor * b);
+            }
+        }
+
+        impl ArgminScaledSubAssign<Array2<$t>, $t> for Array2<$t> {
+            #[inline]
+            fn scaled_sub_assign(&mut self, factor: &$t, vec: &Array2<$t>) {
+                Zip::from(self).and(vec).for_each(|a, &b| *a -= *factor * b);
+            }
+        }
+    };
+}
+
+make_scaled_sub_assign!(i8);
+make_scaled_sub_assign!(u8);
+make_scaled_sub_assign!(i16);
+make_scaled_sub_assign!(u16);
+make_scaled_sub_assign!(i32);
+make_scaled_sub_assign!(u32);
+make_scaled_sub_assign!(i64);
+make_scaled_sub_assign!(u64);
+make_scaled_sub_assign!(f32);
+make_scaled_sub_assign!(f64);
+make_scaled_sub_assign!(Complex<f32>);
+make_scaled_sub_assign!(Complex<f64>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_scaledsub_assign_vec_ $t>]() {
+                    let mut a = array![9 as $t, 12 as $t, 15 as $t];
+                    let b = 2 as $t;
+                    let c = array![4 as $t, 5 as $t, 6 as $t];
+                    let target = array![1 as $t, 2 as $t, 3 as $t];
+                    <Array1<$t> as ArgminScaledSubAssign<Array1<$t>, $t>>::scaled_sub_assign(&mut a, &b, &c);
+                    for i in 0..3 {
+                        assert!((((a[i] - target[i]) as f64).abs()) < std::f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_scaledsub_assign_vec_panic_ $t>]() {
+                    let mut a = array![9 as $t, 12 as $t, 15 as $t];
+                    let b = 2 as $t;
+                    let c = array![4 as $t, 5 as $t];
+                    <Array1<$t> as ArgminScaledSubAssign<Array1<$t>, $t>>::scaled_sub_assign(&mut a, &b, &c);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_scaledsub_assign_mat_ $t>]() {
+                    let mut a = array![
+                        [9 as $t, 12 as $t, 15 as $t],
+                        [6 as $t, 9 as $t, 12 as $t]
+                    ];
+                    let b = 2 as $t;
+                    let c = array![
+                        [4 as $t, 5 as $t, 6 as $t],
+                        [1 as $t, 2 as $t, 3 as $t]
+                    ];
+                    let target = array![
+                        [1 as $t, 2 as $t, 3 as $t],
+                        [4 as $t, 5 as $t, 6 as $t]
+                    ];
+                    <Array2<$t> as ArgminScaledSubAssign<Array2<$t>, $t>>::scaled_sub_assign(&mut a, &b, &c);
+                    for i in 0..2 {
+                        for j in 0..3 {
+                            assert!((((a[(i, j)] - target[(i, j)]) as f64).abs()) < std::f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_scaledsub_assign_mat_panic_ $t>]() {
+                    let mut a = array![
+                        [9 as $t, 12 as $t, 15 as $t],
+                        [6 as $t, 9 as $t, 12 as $t]
+                    ];
+                    let b = 2 as $t;
+                    let c = array![
+                        [4 as $t, 5 as $t, 6 as $t]
+                    ];
+                    <Array2<$t> as ArgminScaledSubAssign<Array2<$t>, $t>>::scaled_sub_assign(&mut a, &b, &c);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,168 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::ArgminScaledSubAssign;
+use num_complex::Complex;
+
+macro_rules! make_scaled_sub_assign {
+    ($t:ty) => {
+        impl ArgminScaledSubAssign<Vec<$t>, $t> for Vec<$t> {
+            #[inline]
+            fn scaled_sub_assign(&mut self, factor: &$t, vec: &Vec<$t>) {
+                let n1 = self.len();
+                let n2 = vec.len();
+                assert!(n1 > 0);
+                assert!(n2 > 0);
+                assert_eq!(n1, n2);
+                self.iter_mut()
+                    .zip(vec.iter())
+                    .for_each(|(a, b)| *a -= *factor * *b);
+            }
+        }
+
+        impl ArgminScaledSubAssign<Vec<Vec<$t>>, $t> for Vec<Vec<$t>> {
+            #[inline]
+            fn scaled_sub_assign(&mut self, factor: &$t, vec: &Vec<Vec<$t>>) {
+                let sr = self.len();
+                let or = vec.len();
+                assert!(sr > 0);
+                assert_eq!(sr, or);
+                let sc = self[0].len();
+                self.iter_mut().zip(vec.iter()).for_each(|(a, b)| {
+                    assert_eq!(a.len(), sc);
+                    assert_eq!(b.len(), sc);
+                    <Vec<$t> as ArgminScaledSubAssign<Vec<$t>, $t>>::scaled_sub_assign(a, factor, b)
+                });
+            }
+        }
+    };
+}
+
+make_scaled_sub_assign!(isize);
+make_scaled_sub_assign!(usize);
+make_scaled_sub_assign!(i8);
+make_scaled_sub_assign!(u8);
+make_scaled_sub_assign!(i16);
+make_scaled_sub_assign!(u16);
+make_scaled_sub_assign!(i32);
+make_scaled_sub_assign!(u32);
+make_scaled_sub_assign!(i64);
+make_scaled_sub_assign!(u64);
+make_scaled_sub_assign!(f32);
+make_scaled_sub_assign!(f64);
+make_scaled_sub_assign!(Complex<isize>);
+make_scaled_sub_assign!(Complex<usize>);
+make_scaled_sub_assign!(Complex<i8>);
+make_scaled_sub_assign!(Complex<u8>);
+make_scaled_sub_assign!(Complex<i16>);
+make_scaled_sub_assign!(Complex<u16>);
+make_scaled_sub_assign!(Complex<i32>);
+make_scaled_sub_assign!(Complex<u32>);
+make_scaled_sub_assign!(Complex<i64>);
+make_scaled_sub_assign!(Complex<u64>);
+make_scaled_sub_assign!(Complex<f32>);
+make_scaled_sub_assign!(Complex<f64>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_scaledsub_assign_vec_ $t>]() {
+                    let mut a = vec![9 as $t, 12 as $t, 15 as $t];
+                    let b = 2 as $t;
+                    let c = vec![4 as $t, 5 as $t, 6 as $t];
+                    let target = vec![1 as $t, 2 as $t, 3 as $t];
+                    <Vec<$t> as ArgminScaledSubAssign<Vec<$t>, $t>>::scaled_sub_assign(&mut a, &b, &c);
+                    for i in 0..3 {
+                        assert!((((a[i] - target[i]) as f64).abs()) < std::f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_scaledsub_assign_vec_panic_1_ $t>]() {
+                    let mut a = vec![9 as $t, 12 as $t, 15 as $t];
+                    let b = 2 as $t;
+                    let c = vec![4 as $t, 5 as $t];
+                    <Vec<$t> as ArgminScaledSubAssign<Vec<$t>, $t>>::scaled_sub_assign(&mut a, &b, &c);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_scaledsub_assign_vec_panic_2_ $t>]() {
+                    let mut a = vec![];
+                    let b = 2 as $t;
+                    let c = vec![4 as $t, 5 as $t, 6 as $t];
+                    <Vec<$t> as ArgminScaledSubAssign<Vec<$t>, $t>>::scaled_sub_assign(&mut a, &b, &c);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_scaledsub_assign_mat_ $t>]() {
+                    let mut a = vec![
+                        vec![9 as $t, 12 as $t, 15 as $t],
+                        vec![6 as $t, 9 as $t, 12 as $t],
+                    ];
+                    let b = 2 as $t;
+                    let c = vec![
+                        vec![4 as $t, 5 as $t, 6 as $t],
+                        vec![1 as $t, 2 as $t, 3 as $t],
+                    ];
+                    let target = vec![
+                        vec![1 as $t, 2 as $t, 3 as $t],
+                        vec![4 as $t, 5 as $t, 6 as $t],
+                    ];
+                    <Vec<Vec<$t>> as ArgminScaledSubAssign<Vec<Vec<$t>>, $t>>::scaled_sub_assign(&mut a, &b, &c);
+                    for i in 0..2 {
+                        for j in 0..3 {
+                            assert!((((a[i][j] - target[i][j]) as f64).abs()) < std::f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_scaledsub_assign_mat_panic_ $t>]() {
+                    let mut a = vec![
+                        vec![9 as $t, 12 as $t, 15 as $t],
+                        vec![6 as $t, 9 as $t, 12 as $t],
+                    ];
+                    let b = 2 as $t;
+                    let c = vec![
+                        vec![4 as $t, 5 as $t, 6 as $t],
+                    ];
+                    <Vec<Vec<$t>> as ArgminScaledSubAssign<Vec<Vec<$t>>, $t>>::scaled_sub_assign(&mut a, &b, &c);
+                }
+            }
+        };
+    }
+
+    make_test!(isize);
+    make_test!(usize);
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,53 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::math::ArgminTranspose;
+use sprs::CsMat;
+
+macro_rules! make_transpose {
+    ($t:ty) => {
+        impl ArgminTranspose<CsMat<$t>> for CsMat<$t> {
+            #[inline]
+            fn t(self) -> CsMat<$t> {
+                self.transpose_view().to_csr()
+            }
+        }
+    };
+}
+
+make_transpose!(f32);
+make_transpose!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_transpose_sparse_ $t>]() {
+                    let a = CsMat::new((2, 3), vec![0, 2, 3], vec![0, 2, 1], vec![1 as $t, 4 as $t, 8 as $t]);
+                    let res = <CsMat<$t> as ArgminTranspose<CsMat<$t>>>::t(a);
+                    let target = [[1 as $t, 0 as $t], [0 as $t, 8 as $t], [4 as $t, 0 as $t]];
+                    assert!(res.is_csr());
+                    assert_eq!(res.shape(), (3, 2));
+                    for i in 0..3 {
+                        for j in 0..2 {
+                            let v = res.get(i, j).copied().unwrap_or(0 as $t);
+                            assert!((v - target[i][j]).abs() < std::$t::EPSILON);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
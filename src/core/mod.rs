@@ -17,10 +17,14 @@
 /// Macros
 #[macro_use]
 pub mod macros;
+/// Capabilities of operators
+pub mod capabilities;
 /// Error handling
 mod errors;
 /// Executor
 pub mod executor;
+/// Operators built from closures
+mod fnop;
 /// iteration state
 mod iterstate;
 /// Key value datastructure
@@ -42,8 +46,10 @@ mod serialization;
 mod termination;
 
 pub use anyhow::Error;
+use capabilities::CostFunction;
 pub use errors::*;
 pub use executor::*;
+pub use fnop::{FnOp, FnOpFunc, FnOpModifyFunc, FnOpSlot};
 pub use iterstate::*;
 pub use kv::ArgminKV;
 pub use math::*;
@@ -81,6 +87,9 @@ impl<I> ArgminFloat for I where
 /// implementation which is essentially returning an error which indicates that the method has not
 /// been implemented. Those methods (`gradient` and `modify`) only need to be implemented if the
 /// uses solver requires it.
+///
+/// New code should prefer the traits in `capabilities`, which turn missing methods into compile
+/// time errors. Every `ArgminOp` automatically implements these traits.
 pub trait ArgminOp {
     // TODO: Once associated type defaults are stable, it hopefully will be possible to define
     // default types for `Hessian` and `Jacobian`.
@@ -119,7 +128,39 @@ pub trait ArgminOp {
         .into())
     }
 
-    /// Computes the Hessian at the given parameters
+    /// Number of gradient evaluations performed by one call of `hessian_vec`. Solvers add them to
+    /// the gradient evaluation count. Operators which override `hessian_vec` without evaluating
+    /// gradients should set this to 0.
+    const HESSIAN_VEC_GRAD_EVALS: u64 = 1;
+
+    /// Computes the product of the Hessian at `param` with the vector `vec`. `grad` is the
+    /// gradient at `param`.
+    ///
+    /// The default implementation approximates the product with a forward difference of
+    /// gradients, `(g(param + h * vec) - grad) / h`, and therefore only requires `gradient` to
+    /// be implemented. Each product costs one gradient evaluation and the Hessian is never formed,
+    /// which makes this suitable for problems with a large number of parameters. Operators which
+    /// can compute the product exactly (for instance via `self.hessian(param)?.dot(vec)`) should
+    /// override this method.
+    ///
+    /// `NewtonCG` and `Steihaug` only use this method if `HessianFree` is selected via
+    /// `with_hessian_product`; by default they compute the products with the Hessian.
+    fn hessian_vec(
+        &self,
+        param: &Self::Param,
+        grad: &Self::Param,
+        vec: &Self::Param,
+    ) -> Result<Self::Param, Error>
+    where
+        Self::Param: ArgminScaledAdd<Self::Param, Self::Float, Self::Param>
+            + ArgminSub<Self::Param, Self::Param>
+            + ArgminMul<Self::Float, Self::Param>
+            + ArgminNorm<Self::Float>,
+    {
+        capabilities::hessian_vec_fd(|p| self.gradient(p), param, grad, vec)
+    }
+
+    /// Computes the Jacobian at the given parameters
     fn jacobian(&self, _param: &Self::Param) -> Result<Self::Jacobian, Error> {
         Err(ArgminError::NotImplemented {
             text: "Method `jacobian` of ArgminOp trait not implemented!".to_string(),
@@ -140,7 +181,7 @@ pub trait ArgminOp {
 /// Solver
 ///
 /// Every solver needs to implement this trait.
-pub trait Solver<O: ArgminOp>: Serialize {
+pub trait Solver<O: CostFunction>: Serialize {
     /// Name of the solver
     const NAME: &'static str = "UNDEFINED";
 
@@ -180,6 +221,9 @@ pub trait Solver<O: ArgminOp>: Serialize {
         if state.get_iter() >= state.get_max_iters() {
             return TerminationReason::MaxItersReached;
         }
+        if state.get_epoch() >= state.get_max_epochs() {
+            return TerminationReason::MaxEpochsReached;
+        }
         if state.get_cost() <= state.get_target_cost() {
             return TerminationReason::TargetCostReached;
         }
@@ -196,7 +240,7 @@ pub trait Solver<O: ArgminOp>: Serialize {
 ///
 /// TODO: Rename to IterResult?
 #[derive(Clone, Debug, Default)]
-pub struct ArgminIterData<O: ArgminOp> {
+pub struct ArgminIterData<O: CostFunction> {
     /// Current parameter vector
     param: Option<O::Param>,
     /// Current cost function value
@@ -209,6 +253,8 @@ pub struct ArgminIterData<O: ArgminOp> {
     jacobian: Option<O::Jacobian>,
     /// Current population
     population: Option<Vec<(O::Param, O::Float)>>,
+    /// Number of completed epochs
+    epoch: Option<u64>,
     /// terminationreason
     termination_reason: Option<TerminationReason>,
     /// Key value pairs which are used to provide additional information for the Observers
@@ -217,7 +263,7 @@ pub struct ArgminIterData<O: ArgminOp> {
 
 // TODO: Many clones are necessary in the getters.. maybe a complete "deconstruct" method would be
 // better?
-impl<O: ArgminOp> ArgminIterData<O> {
+impl<O: CostFunction> ArgminIterData<O> {
     /// Constructor
     pub fn new() -> Self {
         ArgminIterData {
@@ -228,6 +274,7 @@ impl<O: ArgminOp> ArgminIterData<O> {
             jacobian: None,
             termination_reason: None,
             population: None,
+            epoch: None,
             kv: make_kv!(),
         }
     }
@@ -268,6 +315,12 @@ impl<O: ArgminOp> ArgminIterData<O> {
         self
     }
 
+    /// Set number of completed epochs
+    pub fn epoch(mut self, epoch: u64) -> Self {
+        self.epoch = Some(epoch);
+        self
+    }
+
     /// Adds an `ArgminKV`
     pub fn kv(mut self, kv: ArgminKV) -> Self {
         self.kv = kv;
@@ -313,6 +366,11 @@ impl<O: ArgminOp> ArgminIterData<O> {
         }
     }
 
+    /// Get number of completed epochs
+    pub fn get_epoch(&self) -> Option<u64> {
+        self.epoch
+    }
+
     /// Get termination reason
     pub fn get_termination_reason(&self) -> Option<TerminationReason> {
         self.termination_reason
@@ -335,9 +393,21 @@ pub trait ArgminLineSearch<P, F>: Serialize {
 
 /// Defines a common interface to methods which calculate approximate steps for trust region
 /// methods.
-pub trait ArgminTrustRegion<F>: Clone + Serialize {
+///
+/// Besides the step, the solvers report the change of the quadratic model of the cost function
+/// which they minimize, `m(p) - f(x) = g^T p + 1/2 p^T B p`, as cost function value.
+pub trait ArgminTrustRegion<P, F>: Clone + Serialize {
     /// Set the initial step length
     fn set_radius(&mut self, radius: F);
+
+    /// Length of a step in the norm in which the trust region is measured. The radius of the trust
+    /// region is updated based on this length. Defaults to the Euclidean norm.
+    fn step_norm(&self, step: &P) -> Result<F, Error>
+    where
+        P: ArgminNorm<F>,
+    {
+        Ok(step.norm())
+    }
 }
 //
 /// Common interface for beta update methods (Nonlinear-CG)
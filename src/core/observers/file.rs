@@ -7,7 +7,8 @@
 
 //! # Output parameter vectors to file
 
-use crate::core::{ArgminKV, ArgminOp, Error, IterState, Observe};
+use crate::core::capabilities::CostFunction;
+use crate::core::{ArgminKV, Error, IterState, Observe};
 use serde::{Deserialize, Serialize};
 use std::default::Default;
 use std::fs::File;
@@ -41,7 +42,7 @@ pub struct WriteToFile<O> {
     _param: std::marker::PhantomData<O>,
 }
 
-impl<O: ArgminOp> WriteToFile<O> {
+impl<O: CostFunction> WriteToFile<O> {
     /// Create a new `WriteToFile` struct
     pub fn new(dir: &str, prefix: &str) -> Self {
         WriteToFile {
@@ -59,7 +60,7 @@ impl<O: ArgminOp> WriteToFile<O> {
     }
 }
 
-impl<O: ArgminOp> Observe<O> for WriteToFile<O> {
+impl<O: CostFunction> Observe<O> for WriteToFile<O> {
     fn observe_iter(&mut self, state: &IterState<O>, _kv: &ArgminKV) -> Result<(), Error> {
         let param = state.get_param();
         let iter = state.get_iter();
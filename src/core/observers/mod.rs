@@ -16,7 +16,8 @@ pub mod slog_logger;
 #[cfg(feature = "visualizer")]
 pub mod visualizer;
 
-use crate::core::{ArgminKV, ArgminOp, Error, IterState};
+use crate::core::capabilities::CostFunction;
+use crate::core::{ArgminKV, Error, IterState};
 use serde::{Deserialize, Serialize};
 use std::default::Default;
 use std::sync::{Arc, Mutex};
@@ -27,7 +28,7 @@ pub use slog_logger::*;
 pub use visualizer::*;
 
 /// Defines the interface every Observer needs to expose
-pub trait Observe<O: ArgminOp> {
+pub trait Observe<O: CostFunction> {
     /// Called once at the beginning of the execution of the solver.
     ///
     /// Parameters:
@@ -53,13 +54,19 @@ type ObserversVec<O> = Vec<(Arc<Mutex<dyn Observe<O>>>, ObserverMode)>;
 
 /// Container for observers which acts just like a single `Observe`r by implementing `Observe` on
 /// it.
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct Observer<O> {
     /// Vector of `Observe`rs with the corresponding `ObserverMode`
     observers: ObserversVec<O>,
 }
 
-impl<O: ArgminOp> Observer<O> {
+impl<O> Default for Observer<O> {
+    fn default() -> Self {
+        Observer { observers: vec![] }
+    }
+}
+
+impl<O: CostFunction> Observer<O> {
     /// Constructor
     pub fn new() -> Self {
         Observer { observers: vec![] }
@@ -83,7 +90,7 @@ impl<O: ArgminOp> Observer<O> {
 
 /// By implementing `Observe` for `Observer` we basically allow a set of `Observer`s to be used
 /// just like a single `Observe`r.
-impl<O: ArgminOp> Observe<O> for Observer<O> {
+impl<O: CostFunction> Observe<O> for Observer<O> {
     /// Initial observation
     /// This is called after the initialization in an `Executor` and gets the name of the solver as
     /// string and a `ArgminKV` which includes some solver-specific information.
@@ -7,7 +7,8 @@
 
 //! # Loggers based on the `slog` crate
 
-use crate::core::{ArgminKV, ArgminOp, Error, IterState, Observe};
+use crate::core::capabilities::CostFunction;
+use crate::core::{ArgminKV, Error, IterState, Observe};
 use slog;
 use slog::{info, o, Drain, Record, Serializer, KV};
 use slog_async;
@@ -105,7 +106,7 @@ impl KV for ArgminSlogKV {
     }
 }
 
-impl<O: ArgminOp> KV for IterState<O> {
+impl<O: CostFunction> KV for IterState<O> {
     fn serialize(&self, _record: &Record, serializer: &mut dyn Serializer) -> slog::Result {
         serializer.emit_str(
             "modify_func_count",
@@ -115,6 +116,10 @@ impl<O: ArgminOp> KV for IterState<O> {
             "hessian_func_count",
             &self.get_hessian_func_count().to_string(),
         )?;
+        serializer.emit_str(
+            "hessian_vec_func_count",
+            &self.get_hessian_vec_func_count().to_string(),
+        )?;
         serializer.emit_str(
             "jacobian_func_count",
             &self.get_jacobian_func_count().to_string(),
@@ -134,7 +139,7 @@ impl<'a> From<&'a ArgminKV> for ArgminSlogKV {
     }
 }
 
-impl<O: ArgminOp> Observe<O> for ArgminSlogLogger {
+impl<O: CostFunction> Observe<O> for ArgminSlogLogger {
     /// Log general info
     fn observe_init(&self, msg: &str, kv: &ArgminKV) -> Result<(), Error> {
         info!(self.logger, "{}", msg; ArgminSlogKV::from(kv));
@@ -5,16 +5,16 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::core::{ArgminOp, Error};
+use crate::core::capabilities::{Anneal, CostFunction, Gradient, Hessian, Jacobian, StochasticOp};
+use crate::core::{ArgminMul, ArgminNorm, ArgminScaledAdd, ArgminSub, Error};
 use serde::{Deserialize, Serialize};
-use std::default::Default;
 
 /// This wraps an operator and keeps track of how often the cost, gradient and Hessian have been
 /// computed and how often the modify function has been called. Usually, this is an implementation
 /// detail unless a solver is needed within another solver (such as a line search within a gradient
 /// descent method), then it may be necessary to wrap the operator in an OpWrapper.
-#[derive(Serialize, Deserialize, Clone, Debug, Default)]
-pub struct OpWrapper<O: ArgminOp> {
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OpWrapper<O: CostFunction> {
     /// Operator
     pub op: Option<O>,
     /// Number of cost function evaluations
@@ -23,13 +23,29 @@ pub struct OpWrapper<O: ArgminOp> {
     pub grad_func_count: u64,
     /// Number of Hessian function evaluations
     pub hessian_func_count: u64,
+    /// Number of Hessian-vector product evaluations
+    pub hessian_vec_func_count: u64,
     /// Number of Jacobian function evaluations
     pub jacobian_func_count: u64,
     /// Number of `modify` function evaluations
     pub modify_func_count: u64,
 }
 
-impl<O: ArgminOp> OpWrapper<O> {
+impl<O: CostFunction> Default for OpWrapper<O> {
+    fn default() -> Self {
+        OpWrapper {
+            op: None,
+            cost_func_count: 0,
+            grad_func_count: 0,
+            hessian_func_count: 0,
+            hessian_vec_func_count: 0,
+            jacobian_func_count: 0,
+            modify_func_count: 0,
+        }
+    }
+}
+
+impl<O: CostFunction> OpWrapper<O> {
     /// Constructor
     pub fn new(op: O) -> Self {
         OpWrapper {
@@ -37,6 +53,7 @@ impl<O: ArgminOp> OpWrapper<O> {
             cost_func_count: 0,
             grad_func_count: 0,
             hessian_func_count: 0,
+            hessian_vec_func_count: 0,
             jacobian_func_count: 0,
             modify_func_count: 0,
         }
@@ -50,6 +67,7 @@ impl<O: ArgminOp> OpWrapper<O> {
             cost_func_count: 0,
             grad_func_count: 0,
             hessian_func_count: 0,
+            hessian_vec_func_count: 0,
             jacobian_func_count: 0,
             modify_func_count: 0,
         }
@@ -61,30 +79,6 @@ impl<O: ArgminOp> OpWrapper<O> {
         self.op.as_ref().unwrap().apply(param)
     }
 
-    /// Calls the `gradient` method of `op` and increments `gradient_func_count`.
-    pub fn gradient(&mut self, param: &O::Param) -> Result<O::Param, Error> {
-        self.grad_func_count += 1;
-        self.op.as_ref().unwrap().gradient(param)
-    }
-
-    /// Calls the `hessian` method of `op` and increments `hessian_func_count`.
-    pub fn hessian(&mut self, param: &O::Param) -> Result<O::Hessian, Error> {
-        self.hessian_func_count += 1;
-        self.op.as_ref().unwrap().hessian(param)
-    }
-
-    /// Calls the `jacobian` method of `op` and increments `jacobian_func_count`.
-    pub fn jacobian(&mut self, param: &O::Param) -> Result<O::Jacobian, Error> {
-        self.jacobian_func_count += 1;
-        self.op.as_ref().unwrap().jacobian(param)
-    }
-
-    /// Calls the `modify` method of `op` and increments `modify_func_count`.
-    pub fn modify(&mut self, param: &O::Param, extent: O::Float) -> Result<O::Param, Error> {
-        self.modify_func_count += 1;
-        self.op.as_ref().unwrap().modify(param, extent)
-    }
-
     /// Moves the operator out of the struct and replaces it with `None`
     pub fn take_op(&mut self) -> Option<O> {
         self.op.take()
@@ -97,15 +91,17 @@ impl<O: ArgminOp> OpWrapper<O> {
         self.cost_func_count += other.cost_func_count;
         self.grad_func_count += other.grad_func_count;
         self.hessian_func_count += other.hessian_func_count;
+        self.hessian_vec_func_count += other.hessian_vec_func_count;
         self.jacobian_func_count += other.jacobian_func_count;
         self.modify_func_count += other.modify_func_count;
     }
 
     /// Adds function evaluation counts of another operator.
-    pub fn consume_func_counts<O2: ArgminOp>(&mut self, other: OpWrapper<O2>) {
+    pub fn consume_func_counts<O2: CostFunction>(&mut self, other: OpWrapper<O2>) {
         self.cost_func_count += other.cost_func_count;
         self.grad_func_count += other.grad_func_count;
         self.hessian_func_count += other.hessian_func_count;
+        self.hessian_vec_func_count += other.hessian_vec_func_count;
         self.jacobian_func_count += other.jacobian_func_count;
         self.modify_func_count += other.modify_func_count;
     }
@@ -115,6 +111,7 @@ impl<O: ArgminOp> OpWrapper<O> {
         self.cost_func_count = 0;
         self.grad_func_count = 0;
         self.hessian_func_count = 0;
+        self.hessian_vec_func_count = 0;
         self.jacobian_func_count = 0;
         self.modify_func_count = 0;
         self
@@ -126,8 +123,82 @@ impl<O: ArgminOp> OpWrapper<O> {
     }
 }
 
-/// The OpWrapper<O> should behave just like any other `ArgminOp`
-impl<O: ArgminOp> ArgminOp for OpWrapper<O> {
+impl<O: Gradient> OpWrapper<O> {
+    /// Calls the `gradient` method of `op` and increments `gradient_func_count`.
+    pub fn gradient(&mut self, param: &O::Param) -> Result<O::Param, Error> {
+        self.grad_func_count += 1;
+        self.op.as_ref().unwrap().gradient(param)
+    }
+
+    /// Calls the `hessian_vec` method of `op`, increments `hessian_vec_func_count` and adds the
+    /// gradient evaluations of `hessian_vec` to `grad_func_count`.
+    pub fn hessian_vec(
+        &mut self,
+        param: &O::Param,
+        grad: &O::Param,
+        vec: &O::Param,
+    ) -> Result<O::Param, Error>
+    where
+        O::Param: ArgminScaledAdd<O::Param, O::Float, O::Param>
+            + ArgminSub<O::Param, O::Param>
+            + ArgminMul<O::Float, O::Param>
+            + ArgminNorm<O::Float>,
+    {
+        self.hessian_vec_func_count += 1;
+        self.grad_func_count += O::HESSIAN_VEC_GRAD_EVALS;
+        self.op.as_ref().unwrap().hessian_vec(param, grad, vec)
+    }
+}
+
+impl<O: Hessian> OpWrapper<O> {
+    /// Calls the `hessian` method of `op` and increments `hessian_func_count`.
+    pub fn hessian(&mut self, param: &O::Param) -> Result<O::Hessian, Error> {
+        self.hessian_func_count += 1;
+        self.op.as_ref().unwrap().hessian(param)
+    }
+}
+
+impl<O: Jacobian> OpWrapper<O> {
+    /// Calls the `jacobian` method of `op` and increments `jacobian_func_count`.
+    pub fn jacobian(&mut self, param: &O::Param) -> Result<O::Jacobian, Error> {
+        self.jacobian_func_count += 1;
+        self.op.as_ref().unwrap().jacobian(param)
+    }
+}
+
+impl<O: Anneal> OpWrapper<O> {
+    /// Calls the `modify` method of `op` and increments `modify_func_count`.
+    pub fn modify(&mut self, param: &O::Param, extent: O::Float) -> Result<O::Param, Error> {
+        self.modify_func_count += 1;
+        self.op.as_ref().unwrap().modify(param, extent)
+    }
+}
+
+impl<O: StochasticOp> OpWrapper<O> {
+    /// Returns the number of terms of `op`.
+    pub fn num_terms(&self) -> usize {
+        self.op.as_ref().unwrap().num_terms()
+    }
+
+    /// Calls the `apply_batch` method of `op` and increments `cost_func_count`.
+    pub fn apply_batch(&mut self, param: &O::Param, indices: &[usize]) -> Result<O::Output, Error> {
+        self.cost_func_count += 1;
+        self.op.as_ref().unwrap().apply_batch(param, indices)
+    }
+
+    /// Calls the `gradient_batch` method of `op` and increments `grad_func_count`.
+    pub fn gradient_batch(
+        &mut self,
+        param: &O::Param,
+        indices: &[usize],
+    ) -> Result<O::Param, Error> {
+        self.grad_func_count += 1;
+        self.op.as_ref().unwrap().gradient_batch(param, indices)
+    }
+}
+
+/// The OpWrapper<O> should behave just like any other operator
+impl<O: CostFunction> CostFunction for OpWrapper<O> {
     type Param = O::Param;
     type Output = O::Output;
     type Hessian = O::Hessian;
@@ -137,20 +208,59 @@ impl<O: ArgminOp> ArgminOp for OpWrapper<O> {
     fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
         self.op.as_ref().unwrap().apply(param)
     }
+}
+
+impl<O: Gradient> Gradient for OpWrapper<O> {
+    const HESSIAN_VEC_GRAD_EVALS: u64 = O::HESSIAN_VEC_GRAD_EVALS;
 
     fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
         self.op.as_ref().unwrap().gradient(param)
     }
 
+    fn hessian_vec(
+        &self,
+        param: &Self::Param,
+        grad: &Self::Param,
+        vec: &Self::Param,
+    ) -> Result<Self::Param, Error>
+    where
+        Self::Param: ArgminScaledAdd<Self::Param, Self::Float, Self::Param>
+            + ArgminSub<Self::Param, Self::Param>
+            + ArgminMul<Self::Float, Self::Param>
+            + ArgminNorm<Self::Float>,
+    {
+        self.op.as_ref().unwrap().hessian_vec(param, grad, vec)
+    }
+}
+
+impl<O: Hessian> Hessian for OpWrapper<O> {
     fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
         self.op.as_ref().unwrap().hessian(param)
     }
+}
 
+impl<O: Jacobian> Jacobian for OpWrapper<O> {
     fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
         self.op.as_ref().unwrap().jacobian(param)
     }
+}
 
+impl<O: Anneal> Anneal for OpWrapper<O> {
     fn modify(&self, param: &Self::Param, extent: Self::Float) -> Result<Self::Param, Error> {
         self.op.as_ref().unwrap().modify(param, extent)
     }
 }
+
+impl<O: StochasticOp> StochasticOp for OpWrapper<O> {
+    fn num_terms(&self) -> usize {
+        self.op.as_ref().unwrap().num_terms()
+    }
+
+    fn apply_batch(&self, param: &Self::Param, indices: &[usize]) -> Result<Self::Output, Error> {
+        self.op.as_ref().unwrap().apply_batch(param, indices)
+    }
+
+    fn gradient_batch(&self, param: &Self::Param, indices: &[usize]) -> Result<Self::Param, Error> {
+        self.op.as_ref().unwrap().gradient_batch(param, indices)
+    }
+}
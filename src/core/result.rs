@@ -83,19 +83,20 @@
 //!
 //! More details can be found in the `IterState` documentation.
 
+use crate::core::capabilities::CostFunction;
 use crate::prelude::*;
 use std::cmp::Ordering;
 
 /// Final struct returned by the `run` method of `Executor`.
 #[derive(Clone)]
-pub struct ArgminResult<O: ArgminOp> {
+pub struct ArgminResult<O: CostFunction> {
     /// operator
     pub operator: O,
     /// iteration state
     pub state: IterState<O>,
 }
 
-impl<O: ArgminOp> ArgminResult<O> {
+impl<O: CostFunction> ArgminResult<O> {
     /// Constructor
     pub fn new(operator: O, state: IterState<O>) -> Self {
         ArgminResult { operator, state }
@@ -114,7 +115,7 @@ impl<O: ArgminOp> ArgminResult<O> {
 
 impl<O> std::fmt::Display for ArgminResult<O>
 where
-    O: ArgminOp,
+    O: CostFunction,
     O::Param: std::fmt::Debug,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -133,15 +134,15 @@ where
     }
 }
 
-impl<O: ArgminOp> PartialEq for ArgminResult<O> {
+impl<O: CostFunction> PartialEq for ArgminResult<O> {
     fn eq(&self, other: &ArgminResult<O>) -> bool {
         (self.state.get_cost() - other.state.get_cost()).abs() < O::Float::epsilon()
     }
 }
 
-impl<O: ArgminOp> Eq for ArgminResult<O> {}
+impl<O: CostFunction> Eq for ArgminResult<O> {}
 
-impl<O: ArgminOp> Ord for ArgminResult<O> {
+impl<O: CostFunction> Ord for ArgminResult<O> {
     fn cmp(&self, other: &ArgminResult<O>) -> Ordering {
         let t = self.state.get_cost() - other.state.get_cost();
         if t.abs() < O::Float::epsilon() {
@@ -154,7 +155,7 @@ impl<O: ArgminOp> Ord for ArgminResult<O> {
     }
 }
 
-impl<O: ArgminOp> PartialOrd for ArgminResult<O> {
+impl<O: CostFunction> PartialOrd for ArgminResult<O> {
     fn partial_cmp(&self, other: &ArgminResult<O>) -> Option<Ordering> {
         Some(self.cmp(other))
     }
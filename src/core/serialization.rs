@@ -174,7 +174,7 @@ mod tests {
 
     impl<O> Solver<O> for PhonySolver
     where
-        O: ArgminOp,
+        O: CostFunction,
     {
         fn next_iter(
             &mut self,
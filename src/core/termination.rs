@@ -23,6 +23,8 @@ pub enum TerminationReason {
     NotTerminated,
     /// Maximum number of iterations reached
     MaxItersReached,
+    /// Maximum number of epochs reached
+    MaxEpochsReached,
     /// Target cost function value reached
     TargetCostReached,
     /// Target precision reached
@@ -37,6 +39,10 @@ pub enum TerminationReason {
     LineSearchConditionMet,
     /// Target tolerance reached
     TargetToleranceReached,
+    /// Norm of the residual below tolerance
+    ResidualToleranceReached,
+    /// Norm of the step below tolerance
+    StepToleranceReached,
     /// Aborted
     Aborted,
 }
@@ -52,6 +58,7 @@ impl TerminationReason {
         match *self {
             TerminationReason::NotTerminated => "Not terminated",
             TerminationReason::MaxItersReached => "Maximum number of iterations reached",
+            TerminationReason::MaxEpochsReached => "Maximum number of epochs reached",
             TerminationReason::TargetCostReached => "Target cost value reached",
             TerminationReason::TargetPrecisionReached => "Target precision reached",
             TerminationReason::NoChangeInCost => "No change in cost function value",
@@ -59,6 +66,8 @@ impl TerminationReason {
             TerminationReason::BestStallIterExceeded => "Best stall iterations exceeded",
             TerminationReason::LineSearchConditionMet => "Line search condition met",
             TerminationReason::TargetToleranceReached => "Target tolerance reached",
+            TerminationReason::ResidualToleranceReached => "Residual tolerance reached",
+            TerminationReason::StepToleranceReached => "Step tolerance reached",
             TerminationReason::Aborted => "Optimization aborted",
         }
     }
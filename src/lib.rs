@@ -53,8 +53,14 @@
 //!   - [Dogleg method](solver/trustregion/dogleg/struct.Dogleg.html)
 //!   - [Steihaug method](solver/trustregion/steihaug/struct.Steihaug.html)
 //! - [Steepest descent](solver/gradientdescent/steepestdescent/struct.SteepestDescent.html)
+//! - [Nesterov's accelerated gradient](solver/gradientdescent/nesterov/struct.NesterovAcceleratedGradient.html)
+//! - [Barzilai-Borwein spectral gradient](solver/gradientdescent/barzilaiborwein/struct.BarzilaiBorwein.html)
+//! - [Coordinate descent](solver/coordinatedescent/struct.CoordinateDescent.html)
 //! - [Conjugate gradient method](solver/conjugategradient/cg/struct.ConjugateGradient.html)
 //! - [Nonlinear conjugate gradient method](solver/conjugategradient/nonlinear_cg/struct.NonlinearConjugateGradient.html)
+//! - [Krylov methods for non-symmetric linear systems](solver/krylov/index.html)
+//!   - [GMRES](solver/krylov/gmres/struct.GMRES.html)
+//!   - [BiCGSTAB](solver/krylov/bicgstab/struct.BiCGSTAB.html)
 //! - [Newton methods](solver/newton/index.html)
 //!   - [Newton's method](solver/newton/newton_method/struct.Newton.html)
 //!   - [Newton-CG](solver/newton/newton_cg/struct.NewtonCG.html)
@@ -64,14 +70,43 @@
 //!   - [DFP](solver/quasinewton/dfp/struct.DFP.html)
 //!   - [SR1](solver/quasinewton/sr1/struct.SR1.html)
 //!   - [SR1-TrustRegion](solver/quasinewton/sr1_trustregion/struct.SR1TrustRegion.html)
+//! - [Nonlinear systems of equations](solver/nonlinearsystem/index.html)
+//!   - [Newton-Raphson method](solver/nonlinearsystem/newton_raphson/struct.NewtonRaphson.html)
+//!   - [Broyden's method](solver/nonlinearsystem/broyden/struct.Broyden.html)
 //! - [Gauss-Newton method](solver/gaussnewton/gaussnewton/struct.GaussNewton.html)
 //! - [Gauss-Newton method with linesearch](solver/gaussnewton/gaussnewton_linesearch/struct.GaussNewtonLS.html)
 //! - [Golden-section search](solver/goldensectionsearch/struct.GoldenSectionSearch.html)
 //! - [Landweber iteration](solver/landweber/struct.Landweber.html)
+//! - [Proximal gradient methods](solver/proximal/index.html)
+//!   - [ISTA](solver/proximal/ista/struct.ISTA.html)
+//!   - [FISTA](solver/proximal/fista/struct.FISTA.html)
+//! - [Alternating direction method of multipliers (ADMM)](solver/admm/admm_method/struct.ADMM.html)
 //! - [Brent's method](solver/brent/struct.Brent.html)
+//! - [Brent's method for minimization](solver/brent/brentopt/struct.BrentOpt.html)
+//!   - [With derivatives](solver/brent/brentopt_deriv/struct.BrentOptDeriv.html)
+//! - [Root finding](solver/rootfinding/index.html)
+//!   - [Bisection](solver/rootfinding/bisection/struct.Bisection.html)
+//!   - [Illinois](solver/rootfinding/illinois/struct.Illinois.html)
+//!   - [Ridders' method](solver/rootfinding/ridders/struct.Ridders.html)
+//!   - [ITP method](solver/rootfinding/itp/struct.ITP.html)
+//!   - [Secant method](solver/rootfinding/secant/struct.Secant.html)
+//!   - [Safeguarded Newton](solver/rootfinding/newton_safeguarded/struct.SafeguardedNewton.html)
 //! - [Nelder-Mead method](solver/neldermead/struct.NelderMead.html)
+//! - [Direct search methods](solver/directsearch/index.html)
+//!   - [Powell's method](solver/directsearch/powell/struct.Powell.html)
+//!   - [Hooke-Jeeves method](solver/directsearch/hookejeeves/struct.HookeJeeves.html)
+//!   - [Generalized pattern search](solver/directsearch/patternsearch/struct.PatternSearch.html)
+//! - [Derivative-free trust region method](solver/trustregion/dfo/struct.DFOTrustRegion.html)
 //! - [Simulated Annealing](solver/simulatedannealing/struct.SimulatedAnnealing.html)
 //! - [Particle Swarm Optimization](solver/particleswarm/struct.ParticleSwarm.html)
+//! - [Bayesian optimization](solver/bayesopt/bayesopt_method/struct.BayesianOptimization.html)
+//! - [Stochastic first-order methods](solver/stochastic/index.html)
+//!   - [Stochastic gradient descent](solver/stochastic/sgd/struct.SGD.html)
+//!   - [Adam](solver/stochastic/adam/struct.Adam.html)
+//!   - [AdaGrad](solver/stochastic/adagrad/struct.AdaGrad.html)
+//!   - [RMSProp](solver/stochastic/rmsprop/struct.RMSProp.html)
+//!   - [SVRG](solver/stochastic/svrg/struct.SVRG.html)
+//!   - [SAGA](solver/stochastic/saga/struct.SAGA.html)
 //!
 //! # Usage
 //!
@@ -98,6 +133,11 @@
 //!    result) after pressing Ctrl+C.
 //! - `ndarrayl`: Support for `ndarray`, `ndarray-linalg` and `ndarray-rand`.
 //! - `nalgebral`: Support for `nalgebra`.
+//! - `sprsl`: Support for sparse Hessians and Jacobians via `sprs`.
+//!
+//! Without any of these features, parameter vectors and matrices can be represented as `Vec<f>` and
+//! `Vec<Vec<f>>`. Inverses and decompositions (Cholesky, LU and QR) of `Vec<Vec<f>>` are implemented
+//! in pure Rust and do not require any native libraries.
 //!
 //! Using the `ndarrayl` feature on Windows might require to explicitly choose the `ndarray-linalg`
 //! BLAS backend in the `Cargo.toml`:
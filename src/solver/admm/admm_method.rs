@@ -0,0 +1,368 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Stephen Boyd, Neal Parikh, Eric Chu, Borja Peleato and Jonathan Eckstein (2011).
+//! Distributed Optimization and Statistical Learning via the Alternating Direction Method of
+//! Multipliers. Foundations and Trends in Machine Learning 3(1), 1-122.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::admm::{ADMMSubproblem, Identity, LinearMap};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::default::Default;
+
+/// Alternating direction method of multipliers (ADMM)
+///
+/// Solves problems of the form
+///
+/// `min_{x, z} f(x) + g(z)` subject to `A x = z`,
+///
+/// i.e. `min_x f(x) + g(A x)`, by alternating between the subproblems [0]
+///
+/// `x_{k+1} = argmin_x f(x) + rho/2 * ||A x - z_k + u_k||^2`
+///
+/// `z_{k+1} = argmin_z g(z) + rho/2 * ||A x_{k+1} - z + u_k||^2`
+///
+/// `u_{k+1} = u_k + A x_{k+1} - z_{k+1}`,
+///
+/// where `u` is the scaled dual variable. The subproblems are solved by `ADMMSubproblem`s,
+/// either in closed form via a proximal operator (`ProxStep`) or by any argmin solver
+/// (`SolverStep`). The linear map `A` implements `LinearMap`.
+///
+/// The operator passed to the executor computes the full objective `f(x) + g(A x)`, which is
+/// evaluated once per iteration for reporting. The primal residual `r = A x - z` and the dual
+/// residual `s = rho * A^T (z_{k+1} - z_k)` are reported in the KV store, together with the
+/// penalty parameter `rho` (default: 1). The solver terminates once
+///
+/// `||r|| <= eps_abs + eps_rel * max(||A x||, ||z||)` and
+/// `||s|| <= eps_abs + eps_rel * ||rho * A^T u||`
+///
+/// hold (`eps_abs` and `eps_rel` default to `1e-6`). With `with_adaptive_penalty(mu, tau)`,
+/// `rho` is multiplied (divided) by `tau` whenever `||r||` exceeds `mu * ||s||`
+/// (`||s||` exceeds `mu * ||r||`) [0].
+///
+/// # References:
+///
+/// [0] Stephen Boyd, Neal Parikh, Eric Chu, Borja Peleato and Jonathan Eckstein (2011).
+/// Distributed Optimization and Statistical Learning via the Alternating Direction Method of
+/// Multipliers. Foundations and Trends in Machine Learning 3(1), 1-122.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ADMM<P, F, A, SX, SZ> {
+    /// linear map of the constraint
+    map: A,
+    /// solver of the x-subproblem
+    x_update: SX,
+    /// solver of the z-subproblem
+    z_update: SZ,
+    /// penalty parameter
+    rho: F,
+    /// residual balancing parameters `(mu, tau)`
+    adaptive: Option<(F, F)>,
+    /// absolute tolerance
+    eps_abs: F,
+    /// relative tolerance
+    eps_rel: F,
+    /// z
+    z: P,
+    /// scaled dual variable
+    u: P,
+    /// whether the tolerances have been met
+    converged: bool,
+}
+
+impl<P, F, A, SX, SZ> ADMM<P, F, A, SX, SZ>
+where
+    P: Default,
+    F: ArgminFloat,
+{
+    /// Constructor
+    pub fn new(map: A, x_update: SX, z_update: SZ) -> Self {
+        ADMM {
+            map,
+            x_update,
+            z_update,
+            rho: F::from_f64(1.0).unwrap(),
+            adaptive: None,
+            eps_abs: F::from_f64(1e-6).unwrap(),
+            eps_rel: F::from_f64(1e-6).unwrap(),
+            z: P::default(),
+            u: P::default(),
+            converged: false,
+        }
+    }
+
+    /// Set the (initial) penalty parameter
+    pub fn with_rho(mut self, rho: F) -> Result<Self, Error> {
+        if rho <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "ADMM: rho must be > 0.".to_string(),
+            }
+            .into());
+        }
+        self.rho = rho;
+        Ok(self)
+    }
+
+    /// Adapt the penalty parameter by residual balancing (typical values: `mu = 10`, `tau = 2`)
+    pub fn with_adaptive_penalty(mut self, mu: F, tau: F) -> Result<Self, Error> {
+        if mu <= F::from_f64(1.0).unwrap() || tau <= F::from_f64(1.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "ADMM: mu and tau must be > 1.".to_string(),
+            }
+            .into());
+        }
+        self.adaptive = Some((mu, tau));
+        Ok(self)
+    }
+
+    /// Set the absolute and relative tolerances of the stopping criterion
+    pub fn with_tolerances(mut self, eps_abs: F, eps_rel: F) -> Result<Self, Error> {
+        if eps_abs < F::from_f64(0.0).unwrap() || eps_rel < F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "ADMM: tolerances must be >= 0.".to_string(),
+            }
+            .into());
+        }
+        self.eps_abs = eps_abs;
+        self.eps_rel = eps_rel;
+        Ok(self)
+    }
+}
+
+impl<O, P, F, A, SX, SZ> Solver<O> for ADMM<P, F, A, SX, SZ>
+where
+    O: CostFunction<Param = P, Output = F, Float = F>,
+    P: Clone
+        + Default
+        + Serialize
+        + DeserializeOwned
+        + ArgminZeroLike
+        + ArgminAdd<P, P>
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminNorm<F>,
+    F: ArgminFloat,
+    A: LinearMap<P> + Clone + Serialize + DeserializeOwned,
+    SX: ADMMSubproblem<P, F, A> + Clone + Serialize + DeserializeOwned,
+    SZ: ADMMSubproblem<P, F, Identity> + Clone + Serialize + DeserializeOwned,
+{
+    const NAME: &'static str = "ADMM";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        self.z = self.map.apply(&param)?;
+        self.u = self.z.zero_like();
+        self.converged = false;
+        let cost = op.apply(&param)?;
+        Ok(Some(ArgminIterData::new().param(param).cost(cost)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let x = self.x_update.solve(
+            op,
+            &self.map,
+            &self.z.sub(&self.u),
+            self.rho,
+            &state.get_param(),
+        )?;
+        let ax = self.map.apply(&x)?;
+        let z = self
+            .z_update
+            .solve(op, &Identity::new(), &ax.add(&self.u), self.rho, &self.z)?;
+        let r = ax.sub(&z);
+        self.u = self.u.add(&r);
+
+        let primal_residual = r.norm();
+        let dual_residual = self.map.adjoint(&z.sub(&self.z))?.norm() * self.rho;
+        self.z = z;
+
+        let eps_pri = self.eps_abs + self.eps_rel * ax.norm().max(self.z.norm());
+        let eps_dual = self.eps_abs + self.eps_rel * self.map.adjoint(&self.u)?.norm() * self.rho;
+        self.converged = primal_residual <= eps_pri && dual_residual <= eps_dual;
+
+        // residual balancing; the scaled dual variable has to be rescaled with rho
+        if let Some((mu, tau)) = self.adaptive {
+            if primal_residual > mu * dual_residual {
+                self.rho = self.rho * tau;
+                self.u = self.u.mul(&(F::from_f64(1.0).unwrap() / tau));
+            } else if dual_residual > mu * primal_residual {
+                self.rho = self.rho / tau;
+                self.u = self.u.mul(&tau);
+            }
+        }
+
+        let cost = op.apply(&x)?;
+        Ok(ArgminIterData::new().param(x).cost(cost).kv(make_kv!(
+            "primal_residual" => primal_residual;
+            "dual_residual" => dual_residual;
+            "rho" => self.rho;
+        )))
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason {
+        if self.converged {
+            return TerminationReason::TargetPrecisionReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::admm::{MatrixMap, ProxStep, SolverStep};
+    use crate::solver::gradientdescent::NesterovAcceleratedGradient;
+    use crate::solver::proximal::{L1Norm, ProximalOperator};
+    use crate::test_trait_impl;
+
+    type LeastSquares = FnOp<Vec<f64>, f64, f64, FnOpFunc<Vec<f64>, Vec<f64>>>;
+
+    type LassoADMM = ADMM<
+        Vec<f64>,
+        f64,
+        Identity,
+        SolverStep<LeastSquares, NesterovAcceleratedGradient<Vec<f64>, f64>>,
+        ProxStep<L1Norm<f64>>,
+    >;
+
+    test_trait_impl!(admm, LassoADMM);
+
+    fn least_squares(b: Vec<f64>) -> LeastSquares {
+        let b2 = b.clone();
+        FnOp::new(move |x: &Vec<f64>| {
+            Ok(0.5
+                * x.iter()
+                    .zip(b.iter())
+                    .map(|(x, b)| (x - b).powi(2))
+                    .sum::<f64>())
+        })
+        .gradient(move |x: &Vec<f64>| Ok(x.iter().zip(b2.iter()).map(|(x, b)| x - b).collect()))
+    }
+
+    #[test]
+    fn test_lasso() {
+        // 0.5 * ||x - b||^2 + lambda * ||x||_1 is solved by soft thresholding b
+        let b = vec![3.0, -0.5, 1.5, -2.0];
+        let lambda = 1.0;
+        let f = least_squares(b.clone());
+        let g = L1Norm::new(lambda).unwrap();
+        let cost_g = g;
+        let full = FnOp::new(move |x: &Vec<f64>| {
+            Ok(CostFunction::apply(&f, x)? + ProximalOperator::<_, f64>::value(&cost_g, x))
+        });
+        for &adaptive in [false, true].iter() {
+            let mut solver: LassoADMM = ADMM::new(
+                Identity::new(),
+                SolverStep::new(
+                    least_squares(b.clone()),
+                    NesterovAcceleratedGradient::new()
+                        .with_tol_grad(1e-12)
+                        .unwrap(),
+                ),
+                ProxStep::new(g),
+            )
+            .with_rho(10.0)
+            .unwrap()
+            .with_tolerances(1e-9, 1e-9)
+            .unwrap();
+            if adaptive {
+                solver = solver.with_adaptive_penalty(10.0, 2.0).unwrap();
+            }
+            let res = Executor::new(full.clone(), solver, vec![0.0; 4])
+                .max_iters(1000)
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                TerminationReason::TargetPrecisionReached
+            );
+            // evaluations of the x-subproblems are counted as well
+            assert!(res.state.get_cost_func_count() > res.state.get_iter());
+            for (x, e) in res
+                .state
+                .get_best_param()
+                .iter()
+                .zip([2.0, 0.0, 0.5, -1.0].iter())
+            {
+                assert!((x - e).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_total_variation() {
+        // 1D total variation denoising of a noisy step
+        let b = vec![0.1, -0.1, 0.05, 1.1, 0.9, 1.0];
+        let n = b.len();
+        let mut d = vec![vec![0.0; n]; n - 1];
+        for (i, row) in d.iter_mut().enumerate() {
+            row[i] = -1.0;
+            row[i + 1] = 1.0;
+        }
+        let lambda = 0.05;
+        let solver = ADMM::new(
+            MatrixMap::new(d),
+            SolverStep::new(
+                least_squares(b.clone()),
+                NesterovAcceleratedGradient::new()
+                    .with_tol_grad(1e-10)
+                    .unwrap(),
+            )
+            .max_iters(1000),
+            ProxStep::new(L1Norm::new(lambda).unwrap()),
+        )
+        .with_adaptive_penalty(10.0, 2.0)
+        .unwrap()
+        .with_tolerances(1e-8, 1e-8)
+        .unwrap();
+        let b2 = b.clone();
+        let full = FnOp::new(move |x: &Vec<f64>| {
+            Ok(0.5
+                * x.iter()
+                    .zip(b2.iter())
+                    .map(|(x, b)| (x - b).powi(2))
+                    .sum::<f64>()
+                + lambda * x.windows(2).map(|w| (w[1] - w[0]).abs()).sum::<f64>())
+        });
+        let res = Executor::new(full, solver, b.clone())
+            .max_iters(5000)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        let x = res.state.get_best_param();
+        // the jump is preserved while the noise is smoothed out
+        assert!(x[3] - x[2] > 0.8);
+        let tv = |x: &[f64]| x.windows(2).map(|w| (w[1] - w[0]).abs()).sum::<f64>();
+        assert!(tv(&x) < tv(&b));
+        assert!(res.state.get_best_cost() < lambda * tv(&b));
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let solver: LassoADMM = ADMM::new(
+            Identity::new(),
+            SolverStep::new(least_squares(vec![0.0]), NesterovAcceleratedGradient::new()),
+            ProxStep::new(L1Norm::new(1.0).unwrap()),
+        );
+        assert!(solver.clone().with_rho(0.0).is_err());
+        assert!(solver.clone().with_adaptive_penalty(1.0, 2.0).is_err());
+        assert!(solver.with_tolerances(-1.0, 0.0).is_err());
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Alternating direction method of multipliers
+//!
+//! * [ADMM](admm_method/struct.ADMM.html)
+//!
+//! ADMM splits problems `min_x f(x) + g(A x)` into subproblems for `f` and `g`, which are
+//! solved by [`ADMMSubproblem`](subproblem/trait.ADMMSubproblem.html)s: either in closed form
+//! with a proximal operator ([`ProxStep`](subproblem/struct.ProxStep.html)) or by any argmin
+//! solver ([`SolverStep`](subproblem/struct.SolverStep.html)). This covers for instance lasso,
+//! consensus and total variation problems.
+//!
+//! # References:
+//!
+//! [0] Stephen Boyd, Neal Parikh, Eric Chu, Borja Peleato and Jonathan Eckstein (2011).
+//! Distributed Optimization and Statistical Learning via the Alternating Direction Method of
+//! Multipliers. Foundations and Trends in Machine Learning 3(1), 1-122.
+
+/// ADMM
+pub mod admm_method;
+/// Subproblems and linear maps
+pub mod subproblem;
+
+pub use self::admm_method::*;
+pub use self::subproblem::*;
@@ -0,0 +1,299 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::capabilities::{CostFunction, Gradient};
+use crate::prelude::*;
+use crate::solver::proximal::ProximalOperator;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Linear map `A` of the coupling constraint `A x = z` of `ADMM`
+pub trait LinearMap<P> {
+    /// Computes `A x`
+    fn apply(&self, x: &P) -> Result<P, Error>;
+
+    /// Computes `A^T y`
+    fn adjoint(&self, y: &P) -> Result<P, Error>;
+}
+
+/// Identity map, for constraints of the form `x = z`
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct Identity {}
+
+impl Identity {
+    /// Constructor
+    pub fn new() -> Self {
+        Identity {}
+    }
+}
+
+impl<P: Clone> LinearMap<P> for Identity {
+    fn apply(&self, x: &P) -> Result<P, Error> {
+        Ok(x.clone())
+    }
+
+    fn adjoint(&self, y: &P) -> Result<P, Error> {
+        Ok(y.clone())
+    }
+}
+
+/// Linear map given by a matrix, for instance a finite difference matrix for total variation
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MatrixMap<M> {
+    /// matrix
+    matrix: M,
+    /// transposed matrix
+    transposed: M,
+}
+
+impl<M: Clone + ArgminTranspose<M>> MatrixMap<M> {
+    /// Constructor
+    pub fn new(matrix: M) -> Self {
+        MatrixMap {
+            transposed: matrix.clone().t(),
+            matrix,
+        }
+    }
+}
+
+impl<M, P> LinearMap<P> for MatrixMap<M>
+where
+    M: ArgminDot<P, P>,
+{
+    fn apply(&self, x: &P) -> Result<P, Error> {
+        Ok(self.matrix.dot(x))
+    }
+
+    fn adjoint(&self, y: &P) -> Result<P, Error> {
+        Ok(self.transposed.dot(y))
+    }
+}
+
+/// Solves one of the subproblems of `ADMM`,
+///
+/// `argmin_x h(x) + rho/2 * ||M x - v||^2`,
+///
+/// where `h` is the part of the objective handled by the subproblem and `M` is a linear map.
+pub trait ADMMSubproblem<P, F, M> {
+    /// Solves the subproblem starting from `init`. Function evaluations are added to the counts
+    /// of `op`.
+    fn solve<O: CostFunction>(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        map: &M,
+        v: &P,
+        rho: F,
+        init: &P,
+    ) -> Result<P, Error>;
+}
+
+/// Solves a subproblem with `M = I` in closed form with a proximal operator:
+/// `x = prox(v, 1/rho)`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProxStep<G> {
+    /// proximal operator of `h`
+    prox: G,
+}
+
+impl<G> ProxStep<G> {
+    /// Constructor
+    pub fn new(prox: G) -> Self {
+        ProxStep { prox }
+    }
+}
+
+impl<P, F, G> ADMMSubproblem<P, F, Identity> for ProxStep<G>
+where
+    F: ArgminFloat,
+    G: ProximalOperator<P, F>,
+{
+    fn solve<O: CostFunction>(
+        &mut self,
+        _op: &mut OpWrapper<O>,
+        _map: &Identity,
+        v: &P,
+        rho: F,
+        _init: &P,
+    ) -> Result<P, Error> {
+        Ok(self.prox.prox(v, F::from_f64(1.0).unwrap() / rho))
+    }
+}
+
+/// Solves a subproblem by running an argmin solver on `AugmentedOp` for at most `max_iters`
+/// iterations. The operator is cloned for every subproblem, therefore it should be cheap to clone
+/// (for instance by sharing its data via `Arc`). The operator is not part of checkpoints, hence
+/// solvers using a `SolverStep` cannot be resumed from a checkpoint.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(bound(serialize = "S: Serialize", deserialize = "S: Deserialize<'de>"))]
+pub struct SolverStep<O, S> {
+    /// operator computing `h` and its gradient; not serialized, because operators built from
+    /// closures cannot be serialized
+    #[serde(skip)]
+    op: Option<O>,
+    /// solver
+    solver: S,
+    /// maximum number of iterations per subproblem
+    max_iters: u64,
+}
+
+impl<O, S> SolverStep<O, S> {
+    /// Constructor
+    pub fn new(op: O, solver: S) -> Self {
+        SolverStep {
+            op: Some(op),
+            solver,
+            max_iters: 100,
+        }
+    }
+
+    /// Set the maximum number of iterations per subproblem (default: 100)
+    pub fn max_iters(mut self, max_iters: u64) -> Self {
+        self.max_iters = max_iters;
+        self
+    }
+}
+
+impl<O, S, M, P, F> ADMMSubproblem<P, F, M> for SolverStep<O, S>
+where
+    O: CostFunction<Param = P, Output = F, Float = F> + Gradient + Clone,
+    S: Solver<OpWrapper<AugmentedOp<O, M, P, F>>> + Clone,
+    M: LinearMap<P> + Clone,
+    P: Clone
+        + Serialize
+        + DeserializeOwned
+        + ArgminSub<P, P>
+        + ArgminNorm<F>
+        + ArgminScaledAdd<P, F, P>,
+    F: ArgminFloat,
+{
+    fn solve<O2: CostFunction>(
+        &mut self,
+        op: &mut OpWrapper<O2>,
+        map: &M,
+        v: &P,
+        rho: F,
+        init: &P,
+    ) -> Result<P, Error> {
+        let inner = self.op.clone().ok_or_else(|| ArgminError::NotInitialized {
+            text: "SolverStep: operator is missing after resuming from a checkpoint.".to_string(),
+        })?;
+        let aug = AugmentedOp {
+            op: inner,
+            map: map.clone(),
+            v: v.clone(),
+            rho,
+        };
+        let res = Executor::new(OpWrapper::new(aug), self.solver.clone(), init.clone())
+            .max_iters(self.max_iters)
+            .ctrlc(false)
+            .timer(false)
+            .run()?;
+        op.consume_func_counts(res.operator);
+        Ok(res.state.get_best_param())
+    }
+}
+
+/// Objective of an `ADMM` subproblem, `h(x) + rho/2 * ||M x - v||^2`, where `h` is computed by
+/// the operator `op`
+#[derive(Clone)]
+pub struct AugmentedOp<O, M, P, F> {
+    /// operator computing `h`
+    op: O,
+    /// linear map `M`
+    map: M,
+    /// target `v`
+    v: P,
+    /// penalty parameter
+    rho: F,
+}
+
+impl<O, M, P, F> CostFunction for AugmentedOp<O, M, P, F>
+where
+    O: CostFunction<Param = P, Output = F, Float = F>,
+    M: LinearMap<P>,
+    P: Clone + Serialize + DeserializeOwned + ArgminSub<P, P> + ArgminNorm<F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Output = F;
+    type Hessian = O::Hessian;
+    type Jacobian = O::Jacobian;
+    type Float = F;
+
+    fn apply(&self, x: &P) -> Result<F, Error> {
+        let r = self.map.apply(x)?.sub(&self.v).norm();
+        Ok(self.op.apply(x)? + F::from_f64(0.5).unwrap() * self.rho * r * r)
+    }
+}
+
+impl<O, M, P, F> Gradient for AugmentedOp<O, M, P, F>
+where
+    O: CostFunction<Param = P, Output = F, Float = F> + Gradient,
+    M: LinearMap<P>,
+    P: Clone
+        + Serialize
+        + DeserializeOwned
+        + ArgminSub<P, P>
+        + ArgminNorm<F>
+        + ArgminScaledAdd<P, F, P>,
+    F: ArgminFloat,
+{
+    fn gradient(&self, x: &P) -> Result<P, Error> {
+        let r = self.map.apply(x)?.sub(&self.v);
+        Ok(self
+            .op
+            .gradient(x)?
+            .scaled_add(&self.rho, &self.map.adjoint(&r)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::proximal::L1Norm;
+
+    #[test]
+    fn test_maps() {
+        let map = MatrixMap::new(vec![vec![1.0, 2.0], vec![0.0, 3.0], vec![1.0, 0.0]]);
+        let y: Vec<f64> = LinearMap::apply(&map, &vec![1.0, 1.0]).unwrap();
+        assert_eq!(y.len(), 3);
+        assert!((y[0] - 3.0).abs() < 1e-12 && (y[1] - 3.0).abs() < 1e-12);
+        let x: Vec<f64> = map.adjoint(&vec![1.0, 1.0, 1.0]).unwrap();
+        assert!((x[0] - 2.0).abs() < 1e-12 && (x[1] - 5.0).abs() < 1e-12);
+        let x: Vec<f64> = LinearMap::apply(&Identity::new(), &vec![1.0, 2.0]).unwrap();
+        assert_eq!(x.len(), 2);
+    }
+
+    #[test]
+    fn test_augmented_op() {
+        let aug = AugmentedOp {
+            op: FnOp::new(|x: &Vec<f64>| Ok(x[0].powi(2)))
+                .gradient(|x: &Vec<f64>| Ok(vec![2.0 * x[0]])),
+            map: Identity::new(),
+            v: vec![1.0],
+            rho: 2.0,
+        };
+        // x^2 + (x - 1)^2
+        assert!((aug.apply(&vec![2.0]).unwrap() - 5.0).abs() < 1e-12);
+        assert!((aug.gradient(&vec![2.0]).unwrap()[0] - 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_prox_step() {
+        let mut op = OpWrapper::new(MinimalNoOperator::new());
+        let mut step = ProxStep::new(L1Norm::new(1.0).unwrap());
+        let x = step
+            .solve(
+                &mut op,
+                &Identity::new(),
+                &vec![3.0, -0.2],
+                2.0,
+                &vec![0.0, 0.0],
+            )
+            .unwrap();
+        assert!((x[0] - 2.5).abs() < 1e-12 && x[1].abs() < 1e-12);
+    }
+}
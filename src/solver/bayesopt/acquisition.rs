@@ -0,0 +1,171 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Donald R. Jones, Matthias Schonlau and William J. Welch (1998). Efficient Global
+//! Optimization of Expensive Black-Box Functions. Journal of Global Optimization 13, 455-492.
+//!
+//! [1] Niranjan Srinivas, Andreas Krause, Sham M. Kakade and Matthias Seeger (2010). Gaussian
+//! Process Optimization in the Bandit Setting: No Regret and Experimental Design. Proceedings of
+//! the 27th International Conference on Machine Learning, 1015-1022.
+//!
+//! [2] Harold J. Kushner (1964). A New Method of Locating the Maximum Point of an Arbitrary
+//! Multipeak Curve in the Presence of Noise. Journal of Basic Engineering 86(1), 97-106.
+
+use crate::core::ArgminFloat;
+use serde::{Deserialize, Serialize};
+
+/// Acquisition functions of `BayesianOptimization`
+///
+/// An acquisition function rates a point by the posterior mean `mu` and standard deviation
+/// `sigma` of the surrogate at that point, given the lowest observed cost function value
+/// `f_best`. The next point is chosen by maximizing the acquisition function.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Acquisition<F> {
+    /// Expected improvement `E[max(f_best - xi - f, 0)]` [0]. The parameter `xi >= 0` favors
+    /// exploration.
+    ExpectedImprovement(F),
+    /// Upper confidence bound of the negated cost function, `kappa * sigma - mu` [1]. Larger
+    /// values of `kappa >= 0` favor exploration.
+    UpperConfidenceBound(F),
+    /// Probability of improvement `P(f < f_best - xi)` [2]. The parameter `xi >= 0` favors
+    /// exploration.
+    ProbabilityOfImprovement(F),
+}
+
+impl<F: ArgminFloat> Default for Acquisition<F> {
+    fn default() -> Self {
+        Acquisition::ExpectedImprovement(F::from_f64(0.0).unwrap())
+    }
+}
+
+impl<F: ArgminFloat> Acquisition<F> {
+    /// Value of the acquisition function
+    pub fn value(&self, mu: F, sigma: F, f_best: F) -> F {
+        let zero = F::from_f64(0.0).unwrap();
+        match *self {
+            Acquisition::ExpectedImprovement(xi) => {
+                let improvement = f_best - xi - mu;
+                if sigma > zero {
+                    let z = improvement / sigma;
+                    improvement * normal_cdf(z) + sigma * normal_pdf(z)
+                } else {
+                    improvement.max(zero)
+                }
+            }
+            Acquisition::UpperConfidenceBound(kappa) => kappa * sigma - mu,
+            Acquisition::ProbabilityOfImprovement(xi) => {
+                let improvement = f_best - xi - mu;
+                if sigma > zero {
+                    normal_cdf(improvement / sigma)
+                } else if improvement > zero {
+                    F::from_f64(1.0).unwrap()
+                } else {
+                    zero
+                }
+            }
+        }
+    }
+
+    /// Checks that the parameter of the acquisition function is nonnegative
+    pub(crate) fn check(&self) -> bool {
+        let p = match *self {
+            Acquisition::ExpectedImprovement(p)
+            | Acquisition::UpperConfidenceBound(p)
+            | Acquisition::ProbabilityOfImprovement(p) => p,
+        };
+        p >= F::from_f64(0.0).unwrap() && p.is_finite()
+    }
+}
+
+/// Density of the standard normal distribution
+fn normal_pdf<F: ArgminFloat>(z: F) -> F {
+    (-F::from_f64(0.5).unwrap() * z * z).exp() / (F::from_f64(2.0).unwrap() * F::PI()).sqrt()
+}
+
+/// Cumulative distribution function of the standard normal distribution
+fn normal_cdf<F: ArgminFloat>(z: F) -> F {
+    F::from_f64(0.5).unwrap() * erfc(-z / F::SQRT_2())
+}
+
+/// Complementary error function with a relative error below `1.2e-7` (Numerical Recipes, 2nd
+/// edition, `erfcc`)
+fn erfc<F: ArgminFloat>(x: F) -> F {
+    const COEFFICIENTS: [f64; 10] = [
+        -1.265_512_23,
+        1.000_023_68,
+        0.374_091_96,
+        0.096_784_18,
+        -0.186_288_06,
+        0.278_868_07,
+        -1.135_203_98,
+        1.488_515_87,
+        -0.822_152_23,
+        0.170_872_77,
+    ];
+    let one = F::from_f64(1.0).unwrap();
+    let z = x.abs();
+    let t = one / (one + F::from_f64(0.5).unwrap() * z);
+    let poly = COEFFICIENTS
+        .iter()
+        .rev()
+        .fold(F::from_f64(0.0).unwrap(), |acc, c| {
+            F::from_f64(*c).unwrap() + t * acc
+        });
+    let value = t * (-z * z + poly).exp();
+    if x >= F::from_f64(0.0).unwrap() {
+        value
+    } else {
+        F::from_f64(2.0).unwrap() - value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normal_cdf() {
+        assert!((normal_cdf(0.0f64) - 0.5).abs() < 1e-7);
+        assert!((normal_cdf(1.0f64) - 0.841_344_746_068_543).abs() < 1e-7);
+        assert!((normal_cdf(-2.0f64) - 0.022_750_131_948_179).abs() < 1e-8);
+        assert!(normal_cdf(-40.0f64) >= 0.0);
+        assert!((normal_cdf(40.0f64) - 1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_expected_improvement() {
+        let ei = Acquisition::ExpectedImprovement(0.0);
+        // closed form at mu = f_best: sigma / sqrt(2 pi)
+        assert!(
+            (ei.value(1.0f64, 2.0, 1.0) - 2.0 / (2.0 * std::f64::consts::PI).sqrt()).abs() < 1e-7
+        );
+        // without uncertainty, the expected improvement is the improvement
+        assert!((ei.value(0.5f64, 0.0, 1.0) - 0.5).abs() < 1e-15);
+        assert!(ei.value(1.5f64, 0.0, 1.0).abs() < 1e-15);
+        // more uncertainty increases the expected improvement
+        assert!(ei.value(1.5f64, 1.0, 1.0) > ei.value(1.5, 0.5, 1.0));
+    }
+
+    #[test]
+    fn test_upper_confidence_bound() {
+        let ucb = Acquisition::UpperConfidenceBound(2.0);
+        assert!((ucb.value(1.0f64, 0.5, 0.0) - 0.0).abs() < 1e-15);
+        assert!((ucb.value(-1.0f64, 0.5, 0.0) - 2.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_probability_of_improvement() {
+        let pi = Acquisition::ProbabilityOfImprovement(0.0);
+        assert!((pi.value(1.0f64, 1.0, 1.0) - 0.5).abs() < 1e-7);
+        assert!((pi.value(0.0f64, 1.0, 1.0) - 0.841_344_746_068_543).abs() < 1e-7);
+        assert!((pi.value(0.0f64, 0.0, 1.0) - 1.0).abs() < f64::EPSILON);
+        assert!(pi.value(2.0f64, 0.0, 1.0).abs() < f64::EPSILON);
+        assert!(!Acquisition::ProbabilityOfImprovement(-0.1f64).check());
+    }
+}
@@ -0,0 +1,636 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Donald R. Jones, Matthias Schonlau and William J. Welch (1998). Efficient Global
+//! Optimization of Expensive Black-Box Functions. Journal of Global Optimization 13, 455-492.
+//!
+//! [1] Bobak Shahriari, Kevin Swersky, Ziyu Wang, Ryan P. Adams and Nando de Freitas (2016).
+//! Taking the Human Out of the Loop: A Review of Bayesian Optimization. Proceedings of the IEEE
+//! 104(1), 148-175.
+//!
+//! [2] David Ginsbourger, Rodolphe Le Riche and Laurent Carraro (2010). Kriging Is Well-Suited to
+//! Parallelize Optimization. In: Computational Intelligence in Expensive Optimization Problems,
+//! Springer, 131-162.
+
+use super::{Acquisition, GaussianProcess, Kernel};
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::directsearch::PatternSearch;
+use rand::prelude::*;
+use rand_xorshift::XorShiftRng;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Initial design of `BayesianOptimization`, evaluated before the surrogate is used
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum InitialDesign<P> {
+    /// Given number of points drawn uniformly at random from the box
+    Random(usize),
+    /// Latin hypercube sample with the given number of points: every dimension of the box is
+    /// divided into as many intervals as there are points and every interval contains exactly
+    /// one point
+    LatinHypercube(usize),
+    /// Given points (projected onto the box)
+    Points(Vec<P>),
+}
+
+/// Bayesian optimization
+///
+/// Global optimization of expensive cost functions within the box `[lower, upper]` [0, 1]. The
+/// cost function is modeled by a `GaussianProcess` surrogate, whose hyperparameters are refitted
+/// in every iteration by maximizing the marginal likelihood with `LBFGS` (at most
+/// `hyperparameter_iters` iterations, default: 50). The next point is chosen by maximizing an
+/// `Acquisition` function (default: expected improvement) of the surrogate. The acquisition
+/// function is first evaluated at `num_candidates` (default: 1000) random points and the best
+/// observed point; `PatternSearch` is then started from the `num_starts` (default: 5) best of
+/// them.
+///
+/// Before the surrogate is used, the initial parameter vector and the points of the
+/// `InitialDesign` (default: Latin hypercube sample with `2n + 1` points, where `n` is the
+/// number of parameters) are evaluated.
+///
+/// With `with_batch_size`, every iteration evaluates a batch of points. The batch is built by
+/// the kriging believer heuristic [2]: after a point has been chosen, the surrogate is
+/// conditioned on its predicted value, which steers the next point of the batch elsewhere.
+///
+/// Each iteration only evaluates a single design point or batch, and the solver stores all
+/// observations, the hyperparameters of the surrogate and its random number generator (which can
+/// be seeded with `with_seed`). Checkpoints therefore allow resuming long runs exactly. Inputs
+/// are scaled to the unit cube internally.
+///
+/// The solver runs until `max_iters` is reached or the cost function value falls below
+/// `target_cost`. The observers receive whether the iteration evaluated design points
+/// (`design`), the maximal acquisition function value (`acquisition`), the log marginal
+/// likelihood of the surrogate (`log_marginal_likelihood`) and the number of observations
+/// (`observations`).
+///
+/// # References:
+///
+/// [0] Donald R. Jones, Matthias Schonlau and William J. Welch (1998). Efficient Global
+/// Optimization of Expensive Black-Box Functions. Journal of Global Optimization 13, 455-492.
+///
+/// [1] Bobak Shahriari, Kevin Swersky, Ziyu Wang, Ryan P. Adams and Nando de Freitas (2016).
+/// Taking the Human Out of the Loop: A Review of Bayesian Optimization. Proceedings of the IEEE
+/// 104(1), 148-175.
+///
+/// [2] David Ginsbourger, Rodolphe Le Riche and Laurent Carraro (2010). Kriging Is Well-Suited to
+/// Parallelize Optimization. In: Computational Intelligence in Expensive Optimization Problems,
+/// Springer, 131-162.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BayesianOptimization<P, F> {
+    /// lower bounds
+    lower: P,
+    /// upper bounds
+    upper: P,
+    /// acquisition function
+    acquisition: Acquisition<F>,
+    /// initial design
+    initial_design: Option<InitialDesign<P>>,
+    /// number of points evaluated per iteration
+    batch_size: usize,
+    /// number of random points at which the acquisition function is evaluated
+    num_candidates: usize,
+    /// number of starting points for maximizing the acquisition function
+    num_starts: usize,
+    /// maximum number of iterations for fitting the hyperparameters
+    hyperparameter_iters: u64,
+    /// surrogate
+    gp: GaussianProcess<F>,
+    /// evaluated points (scaled to the unit cube)
+    x: Vec<Vec<F>>,
+    /// cost function values at the evaluated points
+    y: Vec<F>,
+    /// design points which have not been evaluated yet (scaled to the unit cube)
+    pending: Vec<Vec<F>>,
+    /// random number generator
+    rng: XorShiftRng,
+}
+
+impl<P, F> BayesianOptimization<P, F>
+where
+    P: Clone + ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    /// Constructor. Requires `lower < upper` in every component.
+    pub fn new(lower: P, upper: P) -> Result<Self, Error> {
+        let n = lower.num_components();
+        let invalid = |i| {
+            let (l, u) = (lower.component(i), upper.component(i));
+            !(l.is_finite() && u.is_finite()) || l >= u
+        };
+        if n == 0 || n != upper.num_components() || (0..n).any(invalid) {
+            return Err(ArgminError::InvalidParameter {
+                text: "BayesianOptimization: lower bounds must be finite and below upper bounds."
+                    .to_string(),
+            }
+            .into());
+        }
+        Ok(BayesianOptimization {
+            lower,
+            upper,
+            acquisition: Acquisition::default(),
+            initial_design: None,
+            batch_size: 1,
+            num_candidates: 1000,
+            num_starts: 5,
+            hyperparameter_iters: 50,
+            gp: GaussianProcess::new(Kernel::default()),
+            x: vec![],
+            y: vec![],
+            pending: vec![],
+            rng: XorShiftRng::from_entropy(),
+        })
+    }
+
+    /// Set kernel of the surrogate (default: `Kernel::Matern52`)
+    pub fn with_kernel(mut self, kernel: Kernel) -> Self {
+        self.gp = GaussianProcess::new(kernel);
+        self
+    }
+
+    /// Set acquisition function
+    pub fn with_acquisition(mut self, acquisition: Acquisition<F>) -> Result<Self, Error> {
+        if !acquisition.check() {
+            return Err(ArgminError::InvalidParameter {
+                text: "BayesianOptimization: parameter of acquisition function must be >= 0."
+                    .to_string(),
+            }
+            .into());
+        }
+        self.acquisition = acquisition;
+        Ok(self)
+    }
+
+    /// Set initial design
+    pub fn with_initial_design(mut self, design: InitialDesign<P>) -> Result<Self, Error> {
+        let invalid = match &design {
+            InitialDesign::Random(n) | InitialDesign::LatinHypercube(n) => *n == 0,
+            InitialDesign::Points(points) => {
+                points.is_empty()
+                    || points
+                        .iter()
+                        .any(|p| p.num_components() != self.lower.num_components())
+            }
+        };
+        if invalid {
+            return Err(ArgminError::InvalidParameter {
+                text: "BayesianOptimization: initial design must have at least one point and \
+                       points must match the bounds."
+                    .to_string(),
+            }
+            .into());
+        }
+        self.initial_design = Some(design);
+        Ok(self)
+    }
+
+    /// Set number of points evaluated per iteration (default: 1)
+    pub fn with_batch_size(mut self, batch_size: usize) -> Result<Self, Error> {
+        if batch_size == 0 {
+            return Err(ArgminError::InvalidParameter {
+                text: "BayesianOptimization: batch size must be > 0.".to_string(),
+            }
+            .into());
+        }
+        self.batch_size = batch_size;
+        Ok(self)
+    }
+
+    /// Set number of random points at which the acquisition function is evaluated (default: 1000)
+    pub fn with_num_candidates(mut self, num_candidates: usize) -> Self {
+        self.num_candidates = num_candidates;
+        self
+    }
+
+    /// Set number of starting points for maximizing the acquisition function (default: 5)
+    pub fn with_num_starts(mut self, num_starts: usize) -> Result<Self, Error> {
+        if num_starts == 0 {
+            return Err(ArgminError::InvalidParameter {
+                text: "BayesianOptimization: number of starting points must be > 0.".to_string(),
+            }
+            .into());
+        }
+        self.num_starts = num_starts;
+        Ok(self)
+    }
+
+    /// Set maximum number of iterations for fitting the hyperparameters of the surrogate. With
+    /// `0`, the hyperparameters are kept at their defaults.
+    pub fn with_hyperparameter_iters(mut self, iters: u64) -> Self {
+        self.hyperparameter_iters = iters;
+        self
+    }
+
+    /// Seed the random number generator
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = XorShiftRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Scales `p` to the unit cube, projecting it onto the box first
+    fn to_unit(&self, p: &P) -> Vec<F> {
+        (0..p.num_components())
+            .map(|i| {
+                let (l, u) = (self.lower.component(i), self.upper.component(i));
+                (p.component(i).max(l).min(u) - l) / (u - l)
+            })
+            .collect()
+    }
+
+    /// Converts a point of the unit cube to a parameter vector
+    fn to_param(&self, x: &[F]) -> P {
+        let mut p = self.lower.clone();
+        for (i, xi) in x.iter().enumerate() {
+            let (l, u) = (self.lower.component(i), self.upper.component(i));
+            p.set_component(i, l + *xi * (u - l));
+        }
+        p
+    }
+
+    /// Point drawn uniformly at random from the unit cube
+    fn random_point(&mut self, n: usize) -> Vec<F> {
+        (0..n)
+            .map(|_| F::from_f64(self.rng.gen::<f64>()).unwrap())
+            .collect()
+    }
+
+    /// Points of the initial design, scaled to the unit cube
+    fn design(&mut self, n: usize) -> Vec<Vec<F>> {
+        let design = self
+            .initial_design
+            .clone()
+            .unwrap_or(InitialDesign::LatinHypercube(2 * n + 1));
+        match design {
+            InitialDesign::Random(m) => (0..m).map(|_| self.random_point(n)).collect(),
+            InitialDesign::LatinHypercube(m) => {
+                let mut points = vec![vec![F::from_f64(0.0).unwrap(); n]; m];
+                let width = F::from_usize(m).unwrap();
+                for i in 0..n {
+                    let mut intervals: Vec<usize> = (0..m).collect();
+                    intervals.shuffle(&mut self.rng);
+                    for (point, k) in points.iter_mut().zip(intervals) {
+                        let offset = F::from_f64(self.rng.gen::<f64>()).unwrap();
+                        point[i] = (F::from_usize(k).unwrap() + offset) / width;
+                    }
+                }
+                points
+            }
+            InitialDesign::Points(points) => points.iter().map(|p| self.to_unit(p)).collect(),
+        }
+    }
+}
+
+impl<P, F> BayesianOptimization<P, F>
+where
+    P: Clone + ArgminComponents<F>,
+    F: ArgminFloat,
+    Vec<F>: ArgminDot<Vec<F>, F> + ArgminSub<Vec<F>, Vec<F>> + ArgminMul<F, Vec<F>> + ArgminRandom,
+    Vec<Vec<F>>: ArgminCholesky<Cholesky<Vec<Vec<F>>>>,
+    Cholesky<Vec<Vec<F>>>: ArgminDecompositionSolve<Vec<F>>,
+{
+    /// Maximizes the acquisition function of `gp` over the unit cube and returns the maximizer
+    /// and the maximal value
+    fn maximize_acquisition(
+        &mut self,
+        gp: &GaussianProcess<F>,
+        x: &[Vec<F>],
+        y: &[F],
+    ) -> Result<(Vec<F>, F), Error> {
+        let n = x[0].len();
+        let (best, f_best) =
+            x.iter().zip(y.iter()).fold(
+                (&x[0], y[0]),
+                |acc, (xi, yi)| if *yi < acc.1 { (xi, *yi) } else { acc },
+            );
+        let op = AcquisitionOp {
+            gp,
+            acquisition: self.acquisition,
+            f_best,
+        };
+
+        let mut candidates = vec![best.clone()];
+        for _ in 0..self.num_candidates {
+            let point = self.random_point(n);
+            candidates.push(point);
+        }
+        let mut rated = candidates
+            .into_iter()
+            .map(|c| Ok((op.apply(&c)?, c)))
+            .collect::<Result<Vec<(F, Vec<F>)>, Error>>()?;
+        rated.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let zeros = vec![F::from_f64(0.0).unwrap(); n];
+        let ones = vec![F::from_f64(1.0).unwrap(); n];
+        let mut result = rated[0].clone();
+        for (_, start) in rated.into_iter().take(self.num_starts) {
+            let solver = PatternSearch::new()
+                .with_initial_step(F::from_f64(0.1).unwrap())?
+                .with_tol(F::from_f64(1e-5).unwrap())?
+                .with_bounds(zeros.clone(), ones.clone())?;
+            let res = Executor::new(op.clone(), solver, start)
+                .max_iters(1000)
+                .ctrlc(false)
+                .timer(false)
+                .run()?;
+            if res.state.get_best_cost() < result.0 {
+                result = (res.state.get_best_cost(), res.state.get_best_param());
+            }
+        }
+        Ok((result.1, -result.0))
+    }
+}
+
+impl<O, P, F> Solver<O> for BayesianOptimization<P, F>
+where
+    O: CostFunction<Param = P, Output = F, Float = F>,
+    P: Clone + Serialize + DeserializeOwned + ArgminComponents<F>,
+    F: ArgminFloat,
+    Vec<F>: ArgminSub<Vec<F>, Vec<F>>
+        + ArgminMul<F, Vec<F>>
+        + ArgminRandom
+        + ArgminDot<Vec<F>, F>
+        + ArgminScaledAdd<Vec<F>, F, Vec<F>>
+        + ArgminNorm<F>
+        + ArgminMulAssign<F>
+        + ArgminScaledAddAssign<Vec<F>, F>
+        + ArgminScaledSubAssign<Vec<F>, F>,
+    Vec<Vec<F>>: ArgminCholesky<Cholesky<Vec<Vec<F>>>>,
+    Cholesky<Vec<Vec<F>>>: ArgminDecompositionSolve<Vec<F>>,
+{
+    const NAME: &'static str = "Bayesian Optimization";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        // a solver with observations was restored from a checkpoint and continues where it
+        // stopped
+        if !self.x.is_empty() {
+            return Ok(None);
+        }
+        let param = state.get_param();
+        let n = self.lower.num_components();
+        if param.num_components() != n {
+            return Err(ArgminError::InvalidParameter {
+                text: "BayesianOptimization: bounds do not match the parameter vector.".to_string(),
+            }
+            .into());
+        }
+        let x0 = self.to_unit(&param);
+        let param = self.to_param(&x0);
+        let cost = op.apply(&param)?;
+        self.x = vec![x0];
+        self.y = vec![cost];
+        self.pending = self.design(n);
+        Ok(Some(ArgminIterData::new().param(param).cost(cost)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        _state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let design = !self.pending.is_empty();
+        let mut acquisition = F::nan();
+        let batch = if design {
+            let m = self.batch_size.min(self.pending.len());
+            self.pending.drain(..m).collect()
+        } else {
+            self.gp.fit(self.x.clone(), self.y.clone())?;
+            if self.hyperparameter_iters > 0 {
+                self.gp
+                    .optimize_hyperparameters(self.hyperparameter_iters)?;
+            }
+            // kriging believer: condition a copy of the surrogate on the predicted values of the
+            // points chosen so far
+            let mut gp = self.gp.clone();
+            let (mut x, mut y) = (self.x.clone(), self.y.clone());
+            let mut batch = vec![];
+            for k in 0..self.batch_size {
+                if k > 0 {
+                    gp.fit(x.clone(), y.clone())?;
+                }
+                let (point, value) = self.maximize_acquisition(&gp, &x, &y)?;
+                if k == 0 {
+                    acquisition = value;
+                }
+                x.push(point.clone());
+                y.push(gp.predict(&point).0);
+                batch.push(point);
+            }
+            batch
+        };
+
+        let mut best: Option<(P, F)> = None;
+        for point in batch {
+            let param = self.to_param(&point);
+            let cost = op.apply(&param)?;
+            self.x.push(point);
+            self.y.push(cost);
+            if best.as_ref().map(|b| cost < b.1).unwrap_or(true) {
+                best = Some((param, cost));
+            }
+        }
+        let (param, cost) = best.unwrap();
+
+        let log_likelihood = if design {
+            F::nan()
+        } else {
+            self.gp.log_marginal_likelihood()
+        };
+        Ok(ArgminIterData::new().param(param).cost(cost).kv(make_kv!(
+            "design" => design;
+            "acquisition" => acquisition;
+            "log_marginal_likelihood" => log_likelihood;
+            "observations" => self.y.len();
+        )))
+    }
+}
+
+/// Negative acquisition function of a Gaussian process
+#[derive(Clone)]
+struct AcquisitionOp<'a, F> {
+    /// surrogate
+    gp: &'a GaussianProcess<F>,
+    /// acquisition function
+    acquisition: Acquisition<F>,
+    /// lowest observed cost function value
+    f_best: F,
+}
+
+impl<'a, F> CostFunction for AcquisitionOp<'a, F>
+where
+    F: ArgminFloat,
+    Vec<F>: ArgminDot<Vec<F>, F>,
+{
+    type Param = Vec<F>;
+    type Output = F;
+    type Hessian = ();
+    type Jacobian = ();
+    type Float = F;
+
+    fn apply(&self, x: &Vec<F>) -> Result<F, Error> {
+        let (mu, sigma) = self.gp.predict(x);
+        Ok(-self.acquisition.value(mu, sigma, self.f_best))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(bayesian_optimization, BayesianOptimization<Vec<f64>, f64>);
+
+    /// Branin function on `[-5, 10] x [0, 15]` with global minimum `0.397887` at three points
+    fn branin() -> FnOp<Vec<f64>, f64> {
+        FnOp::new(|x: &Vec<f64>| {
+            let pi = std::f64::consts::PI;
+            let b = 5.1 / (4.0 * pi * pi);
+            let c = 5.0 / pi;
+            let t = 1.0 / (8.0 * pi);
+            Ok((x[1] - b * x[0].powi(2) + c * x[0] - 6.0).powi(2)
+                + 10.0 * (1.0 - t) * x[0].cos()
+                + 10.0)
+        })
+    }
+
+    fn solver() -> BayesianOptimization<Vec<f64>, f64> {
+        BayesianOptimization::new(vec![-5.0, 0.0], vec![10.0, 15.0])
+            .unwrap()
+            .with_num_candidates(200)
+            .with_seed(42)
+    }
+
+    #[test]
+    fn test_branin() {
+        for kernel in [Kernel::RBF, Kernel::Matern32, Kernel::Matern52].iter() {
+            let res = Executor::new(branin(), solver().with_kernel(*kernel), vec![0.0, 0.0])
+                .max_iters(30)
+                .run()
+                .unwrap();
+            assert!(res.state.get_best_cost() < 0.45);
+            assert_eq!(res.state.get_cost_func_count(), 31);
+        }
+    }
+
+    #[test]
+    fn test_acquisition() {
+        let acquisitions = vec![
+            Acquisition::UpperConfidenceBound(2.0),
+            Acquisition::ProbabilityOfImprovement(0.1),
+        ];
+        for acquisition in acquisitions {
+            let solver = solver().with_acquisition(acquisition).unwrap();
+            let res = Executor::new(branin(), solver, vec![0.0, 0.0])
+                .max_iters(30)
+                .run()
+                .unwrap();
+            assert!(res.state.get_best_cost() < 1.0);
+        }
+    }
+
+    #[test]
+    fn test_batch() {
+        let design =
+            InitialDesign::Points(vec![vec![-5.0, 0.0], vec![10.0, 15.0], vec![10.0, 0.0]]);
+        let solver = solver()
+            .with_initial_design(design)
+            .unwrap()
+            .with_batch_size(2)
+            .unwrap();
+        let res = Executor::new(branin(), solver, vec![0.0, 0.0])
+            .max_iters(15)
+            .run()
+            .unwrap();
+        // two iterations evaluate the three design points, every other one a batch of two
+        assert_eq!(res.state.get_cost_func_count(), 1 + 3 + 2 * 13);
+        assert!(res.state.get_best_cost() < 1.0);
+    }
+
+    #[test]
+    fn test_design() {
+        let mut solver = solver()
+            .with_initial_design(InitialDesign::LatinHypercube(10))
+            .unwrap();
+        let points = solver.design(2);
+        assert_eq!(points.len(), 10);
+        for i in 0..2 {
+            let mut intervals: Vec<usize> = points.iter().map(|p| (p[i] * 10.0) as usize).collect();
+            intervals.sort_unstable();
+            assert_eq!(intervals, (0..10).collect::<Vec<usize>>());
+        }
+        let mut solver = solver
+            .with_initial_design(InitialDesign::Points(vec![vec![-5.0, 30.0]]))
+            .unwrap();
+        assert_eq!(solver.design(2), vec![vec![0.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let uninterrupted = Executor::new(branin(), solver(), vec![0.0, 0.0])
+            .max_iters(12)
+            .run()
+            .unwrap();
+
+        let dir = std::env::temp_dir().join("argmin_bayesopt_checkpoint");
+        Executor::new(branin(), solver(), vec![0.0, 0.0])
+            .max_iters(8)
+            .checkpoint_dir(dir.to_str().unwrap())
+            .checkpoint_name("bayesopt")
+            .checkpoint_mode(CheckpointMode::Always)
+            .run()
+            .unwrap();
+        let resumed: Executor<_, BayesianOptimization<Vec<f64>, f64>> =
+            Executor::from_checkpoint(dir.join("bayesopt.arg"), branin()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        let resumed = resumed.max_iters(12).run().unwrap();
+
+        assert_eq!(resumed.state.get_iter(), 12);
+        assert_eq!(
+            resumed.state.get_best_param(),
+            uninterrupted.state.get_best_param()
+        );
+        assert!(
+            (resumed.state.get_best_cost() - uninterrupted.state.get_best_cost()).abs()
+                < f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(
+            BayesianOptimization::<Vec<f64>, f64>::new(vec![0.0, 1.0], vec![1.0, 1.0]).is_err()
+        );
+        assert!(BayesianOptimization::<Vec<f64>, f64>::new(vec![0.0], vec![1.0, 1.0]).is_err());
+        let solver = solver();
+        assert!(solver.clone().with_batch_size(0).is_err());
+        assert!(solver.clone().with_num_starts(0).is_err());
+        assert!(solver
+            .clone()
+            .with_acquisition(Acquisition::ExpectedImprovement(-1.0))
+            .is_err());
+        assert!(solver
+            .clone()
+            .with_initial_design(InitialDesign::Random(0))
+            .is_err());
+        assert!(solver
+            .clone()
+            .with_initial_design(InitialDesign::Points(vec![vec![0.0]]))
+            .is_err());
+        assert!(solver
+            .clone()
+            .with_initial_design(InitialDesign::Points(vec![]))
+            .is_err());
+        let res = Executor::new(branin(), solver, vec![0.0])
+            .max_iters(1)
+            .run();
+        assert!(res.is_err());
+    }
+}
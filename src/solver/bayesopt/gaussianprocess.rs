@@ -0,0 +1,570 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Carl Edward Rasmussen and Christopher K. I. Williams (2006). Gaussian Processes for
+//! Machine Learning. MIT Press. ISBN 0-262-18253-X.
+
+use super::Kernel;
+use crate::core::capabilities::{CostFunction, Gradient};
+use crate::prelude::*;
+use crate::solver::linesearch::MoreThuenteLineSearch;
+use crate::solver::quasinewton::LBFGS;
+use serde::{Deserialize, Serialize};
+
+/// Variance added to the diagonal of the covariance matrix for numerical stability
+const JITTER: f64 = 1e-8;
+
+/// Mean and standard deviation of the log-normal priors on the lengthscales, the signal variance
+/// and the noise variance
+const PRIORS: [(f64, f64); 3] = [(0.5, 1.5), (1.0, 1.5), (1e-6, 5.0)];
+
+/// Gaussian process regression model
+///
+/// Models a function by a Gaussian process with constant mean and a stationary covariance
+/// function given by the `Kernel` with one lengthscale per dimension [0]. The observations are
+/// standardized to zero mean and unit variance; the signal variance and the noise variance refer
+/// to the standardized observations.
+///
+/// The hyperparameters are fitted with `optimize_hyperparameters`, which maximizes the
+/// logarithm of the marginal likelihood with `LBFGS`. Weak log-normal priors keep the
+/// hyperparameters in a sensible range if the data carries little information about them. The
+/// defaults and the priors assume that the inputs are scaled to the unit cube, as is done by
+/// `BayesianOptimization`.
+///
+/// # References:
+///
+/// [0] Carl Edward Rasmussen and Christopher K. I. Williams (2006). Gaussian Processes for
+/// Machine Learning. MIT Press. ISBN 0-262-18253-X.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GaussianProcess<F> {
+    /// covariance function
+    kernel: Kernel,
+    /// lengthscales (one per dimension)
+    lengthscales: Vec<F>,
+    /// signal variance
+    signal_variance: F,
+    /// noise variance
+    noise_variance: F,
+    /// inputs
+    x: Vec<Vec<F>>,
+    /// standardized observations
+    y: Vec<F>,
+    /// mean of the observations
+    y_mean: F,
+    /// standard deviation of the observations
+    y_std: F,
+    /// Cholesky factor of the covariance matrix
+    l: Vec<Vec<F>>,
+    /// covariance matrix times `alpha` equals `y`
+    alpha: Vec<F>,
+}
+
+impl<F: ArgminFloat> GaussianProcess<F> {
+    /// Constructor
+    pub fn new(kernel: Kernel) -> Self {
+        GaussianProcess {
+            kernel,
+            lengthscales: vec![],
+            signal_variance: F::from_f64(PRIORS[1].0).unwrap(),
+            noise_variance: F::from_f64(PRIORS[2].0).unwrap(),
+            x: vec![],
+            y: vec![],
+            y_mean: F::from_f64(0.0).unwrap(),
+            y_std: F::from_f64(1.0).unwrap(),
+            l: vec![],
+            alpha: vec![],
+        }
+    }
+
+    /// Set lengthscales. By default, all lengthscales are `0.5`.
+    pub fn with_lengthscales(mut self, lengthscales: Vec<F>) -> Result<Self, Error> {
+        if lengthscales.is_empty() || lengthscales.iter().any(|l| !is_positive(*l)) {
+            return Err(ArgminError::InvalidParameter {
+                text: "GaussianProcess: lengthscales must be positive and finite.".to_string(),
+            }
+            .into());
+        }
+        self.lengthscales = lengthscales;
+        Ok(self)
+    }
+
+    /// Set signal variance (default: 1)
+    pub fn with_signal_variance(mut self, signal_variance: F) -> Result<Self, Error> {
+        if !is_positive(signal_variance) {
+            return Err(ArgminError::InvalidParameter {
+                text: "GaussianProcess: signal variance must be positive and finite.".to_string(),
+            }
+            .into());
+        }
+        self.signal_variance = signal_variance;
+        Ok(self)
+    }
+
+    /// Set noise variance (default: `1e-6`)
+    pub fn with_noise_variance(mut self, noise_variance: F) -> Result<Self, Error> {
+        if !is_positive(noise_variance) {
+            return Err(ArgminError::InvalidParameter {
+                text: "GaussianProcess: noise variance must be positive and finite.".to_string(),
+            }
+            .into());
+        }
+        self.noise_variance = noise_variance;
+        Ok(self)
+    }
+
+    /// Returns the kernel
+    pub fn kernel(&self) -> Kernel {
+        self.kernel
+    }
+
+    /// Returns the lengthscales
+    pub fn lengthscales(&self) -> &[F] {
+        &self.lengthscales
+    }
+
+    /// Returns the signal variance
+    pub fn signal_variance(&self) -> F {
+        self.signal_variance
+    }
+
+    /// Returns the noise variance
+    pub fn noise_variance(&self) -> F {
+        self.noise_variance
+    }
+
+    /// Returns the number of observations
+    pub fn num_observations(&self) -> usize {
+        self.x.len()
+    }
+
+    /// Covariance of `a` and `b` without noise
+    fn covariance(&self, a: &[F], b: &[F], lengthscales: &[F], signal_variance: F) -> F {
+        signal_variance * self.kernel.correlation(scaled_distance(a, b, lengthscales))
+    }
+
+    /// Logarithms of the hyperparameters
+    fn theta(&self) -> Vec<F> {
+        self.lengthscales
+            .iter()
+            .chain([self.signal_variance, self.noise_variance].iter())
+            .map(|v| v.ln())
+            .collect()
+    }
+
+    /// Sets the hyperparameters from their logarithms
+    fn set_theta(&mut self, theta: &[F]) {
+        let d = self.lengthscales.len();
+        self.lengthscales = theta[..d].iter().map(|t| t.exp()).collect();
+        self.signal_variance = theta[d].exp();
+        self.noise_variance = theta[d + 1].exp();
+    }
+}
+
+impl<F> GaussianProcess<F>
+where
+    F: ArgminFloat,
+    Vec<F>: ArgminDot<Vec<F>, F>,
+{
+    /// Mean and standard deviation of the posterior distribution of the function value at `x`
+    pub fn predict(&self, x: &[F]) -> (F, F) {
+        let zero = F::from_f64(0.0).unwrap();
+        if self.x.is_empty() {
+            return (self.y_mean, self.y_std * self.signal_variance.sqrt());
+        }
+        let k: Vec<F> = self
+            .x
+            .iter()
+            .map(|xi| self.covariance(xi, x, &self.lengthscales, self.signal_variance))
+            .collect();
+        let mean = k.dot(&self.alpha);
+        let v = forward_substitution(&self.l, &k);
+        let variance = (self.signal_variance - v.dot(&v)).max(zero);
+        (
+            self.y_mean + self.y_std * mean,
+            self.y_std * variance.sqrt(),
+        )
+    }
+
+    /// Logarithm of the marginal likelihood of the standardized observations
+    pub fn log_marginal_likelihood(&self) -> F {
+        let half = F::from_f64(0.5).unwrap();
+        let n = F::from_usize(self.y.len()).unwrap();
+        let log_det = self
+            .l
+            .iter()
+            .enumerate()
+            .fold(F::from_f64(0.0).unwrap(), |acc, (i, row)| acc + row[i].ln());
+        -half * self.y.dot(&self.alpha)
+            - log_det
+            - half * n * (F::from_f64(2.0).unwrap() * F::PI()).ln()
+    }
+}
+
+impl<F> GaussianProcess<F>
+where
+    F: ArgminFloat,
+    Vec<F>: ArgminDot<Vec<F>, F>,
+    Vec<Vec<F>>: ArgminCholesky<Cholesky<Vec<Vec<F>>>>,
+    Cholesky<Vec<Vec<F>>>: ArgminDecompositionSolve<Vec<F>>,
+{
+    /// Conditions the Gaussian process on the observations `y` at the inputs `x` with the current
+    /// hyperparameters
+    pub fn fit(&mut self, x: Vec<Vec<F>>, y: Vec<F>) -> Result<(), Error> {
+        if x.is_empty() || x.len() != y.len() {
+            return Err(ArgminError::InvalidParameter {
+                text: "GaussianProcess: number of inputs and observations must match and be > 0."
+                    .to_string(),
+            }
+            .into());
+        }
+        let d = x[0].len();
+        if d == 0 || x.iter().any(|xi| xi.len() != d) {
+            return Err(ArgminError::InvalidParameter {
+                text: "GaussianProcess: inputs must have the same, non-zero dimension.".to_string(),
+            }
+            .into());
+        }
+        if self.lengthscales.is_empty() {
+            self.lengthscales = vec![F::from_f64(PRIORS[0].0).unwrap(); d];
+        } else if self.lengthscales.len() != d {
+            return Err(ArgminError::InvalidParameter {
+                text: "GaussianProcess: lengthscales do not match the inputs.".to_string(),
+            }
+            .into());
+        }
+
+        let zero = F::from_f64(0.0).unwrap();
+        let n = F::from_usize(y.len()).unwrap();
+        let mean = y.iter().fold(zero, |acc, yi| acc + *yi) / n;
+        let variance = y
+            .iter()
+            .fold(zero, |acc, yi| acc + (*yi - mean) * (*yi - mean))
+            / n;
+        let std = if variance.sqrt() > F::epsilon() * mean.abs().max(F::from_f64(1.0).unwrap()) {
+            variance.sqrt()
+        } else {
+            F::from_f64(1.0).unwrap()
+        };
+        self.y = y.iter().map(|yi| (*yi - mean) / std).collect();
+        self.y_mean = mean;
+        self.y_std = std;
+        self.x = x;
+        self.factorize()
+    }
+
+    /// Maximizes the logarithm of the marginal likelihood (plus the log-prior) with respect to
+    /// the hyperparameters using at most `max_iters` iterations of `LBFGS`, started from the
+    /// current hyperparameters and from the defaults. The hyperparameters are only changed if
+    /// this improves the objective.
+    pub fn optimize_hyperparameters(&mut self, max_iters: u64) -> Result<(), Error>
+    where
+        Vec<F>: ArgminSub<Vec<F>, Vec<F>>
+            + ArgminDot<Vec<F>, F>
+            + ArgminScaledAdd<Vec<F>, F, Vec<F>>
+            + ArgminNorm<F>
+            + ArgminMulAssign<F>
+            + ArgminScaledAddAssign<Vec<F>, F>
+            + ArgminScaledSubAssign<Vec<F>, F>,
+    {
+        if self.x.is_empty() {
+            return Err(ArgminError::NotInitialized {
+                text: "GaussianProcess: call `fit` before optimizing the hyperparameters."
+                    .to_string(),
+            }
+            .into());
+        }
+        let current = self.theta();
+        let mut defaults = GaussianProcess::new(self.kernel);
+        defaults.lengthscales = vec![F::from_f64(PRIORS[0].0).unwrap(); self.lengthscales.len()];
+        let mut best = (current.clone(), self.objective(&current, false)?.0);
+
+        for start in [current, defaults.theta()].iter() {
+            let op = LikelihoodOp { gp: self };
+            // the hyperparameters are not needed to high accuracy
+            let solver = LBFGS::new(MoreThuenteLineSearch::new(), 7)
+                .with_tol_grad(F::from_f64(1e-3).unwrap());
+            // failures (for instance due to extreme hyperparameters) leave the current ones
+            let res = match Executor::new(op, solver, start.clone())
+                .max_iters(max_iters)
+                .ctrlc(false)
+                .timer(false)
+                .run()
+            {
+                Ok(res) => res,
+                Err(_) => continue,
+            };
+            let cost = res.state.get_best_cost();
+            if cost < best.1 {
+                best = (res.state.get_best_param(), cost);
+            }
+        }
+
+        self.set_theta(&best.0);
+        self.factorize()
+    }
+
+    /// Computes the Cholesky factor and `alpha` with the current hyperparameters
+    fn factorize(&mut self) -> Result<(), Error> {
+        let chol = self.decompose(
+            &self.lengthscales,
+            self.signal_variance,
+            self.noise_variance,
+        )?;
+        self.alpha = chol.solve(&self.y)?;
+        self.l = chol.l;
+        Ok(())
+    }
+
+    /// Cholesky decomposition of the covariance matrix for the given hyperparameters
+    fn decompose(
+        &self,
+        lengthscales: &[F],
+        signal_variance: F,
+        noise_variance: F,
+    ) -> Result<Cholesky<Vec<Vec<F>>>, Error> {
+        let diagonal = noise_variance + F::from_f64(JITTER).unwrap();
+        let k: Vec<Vec<F>> = self
+            .x
+            .iter()
+            .enumerate()
+            .map(|(i, xi)| {
+                self.x
+                    .iter()
+                    .enumerate()
+                    .map(|(j, xj)| {
+                        let c = self.covariance(xi, xj, lengthscales, signal_variance);
+                        if i == j {
+                            c + diagonal
+                        } else {
+                            c
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        k.cholesky()
+    }
+
+    /// Negative logarithm of the marginal likelihood plus the negative log-prior and, if
+    /// requested, its gradient with respect to the logarithms of the hyperparameters `theta`
+    fn objective(&self, theta: &[F], gradient: bool) -> Result<(F, Option<Vec<F>>), Error> {
+        let half = F::from_f64(0.5).unwrap();
+        let d = self.lengthscales.len();
+        let n = self.x.len();
+        let lengthscales: Vec<F> = theta[..d].iter().map(|t| t.exp()).collect();
+        let signal_variance = theta[d].exp();
+        let noise_variance = theta[d + 1].exp();
+        let chol = self.decompose(&lengthscales, signal_variance, noise_variance)?;
+        let alpha = chol.solve(&self.y)?;
+
+        let log_det = (0..n).fold(F::from_f64(0.0).unwrap(), |acc, i| acc + chol.l[i][i].ln());
+        let mut cost = half * self.y.dot(&alpha)
+            + log_det
+            + half * F::from_usize(n).unwrap() * (F::from_f64(2.0).unwrap() * F::PI()).ln();
+        for (j, t) in theta.iter().enumerate() {
+            let (mean, sd) = prior(j, d);
+            cost = cost + half * ((*t - mean) / sd).powi(2);
+        }
+        if !gradient {
+            return Ok((cost, None));
+        }
+
+        // W = alpha alpha^T - K^{-1}; the gradient of the negative log marginal likelihood is
+        // -1/2 tr(W dK/dtheta_j)
+        let mut w = vec![vec![F::from_f64(0.0).unwrap(); n]; n];
+        for i in 0..n {
+            let mut e = vec![F::from_f64(0.0).unwrap(); n];
+            e[i] = F::from_f64(1.0).unwrap();
+            let column = chol.solve(&e)?;
+            for (j, c) in column.into_iter().enumerate() {
+                w[j][i] = alpha[i] * alpha[j] - c;
+            }
+        }
+        let mut grad = vec![F::from_f64(0.0).unwrap(); d + 2];
+        for (a, (xa, wa)) in self.x.iter().zip(w.iter()).enumerate() {
+            for (xb, wab) in self.x.iter().zip(wa.iter()) {
+                let r = scaled_distance(xa, xb, &lengthscales);
+                // derivative of signal_variance * k(r) with respect to the log-lengthscales
+                let dk = signal_variance * self.kernel.derivative_ratio(r);
+                for (((g, xai), xbi), l) in grad.iter_mut().zip(xa).zip(xb).zip(&lengthscales) {
+                    let di = (*xai - *xbi) / *l;
+                    *g = *g + *wab * dk * di * di;
+                }
+                grad[d] = grad[d] - *wab * signal_variance * self.kernel.correlation(r);
+            }
+            grad[d + 1] = grad[d + 1] - wa[a] * noise_variance;
+        }
+        for (j, t) in theta.iter().enumerate() {
+            let (mean, sd) = prior(j, d);
+            grad[j] = half * grad[j] + (*t - mean) / (sd * sd);
+        }
+        Ok((cost, Some(grad)))
+    }
+}
+
+/// Mean and standard deviation of the prior on the logarithm of hyperparameter `j`
+fn prior<F: ArgminFloat>(j: usize, d: usize) -> (F, F) {
+    let (mean, sd) = PRIORS[if j < d { 0 } else { j - d + 1 }];
+    (F::from_f64(mean.ln()).unwrap(), F::from_f64(sd).unwrap())
+}
+
+/// Negative logarithm of the marginal likelihood plus negative log-prior of a Gaussian process as
+/// a function of the logarithms of the hyperparameters
+struct LikelihoodOp<'a, F> {
+    /// Gaussian process
+    gp: &'a GaussianProcess<F>,
+}
+
+impl<'a, F> CostFunction for LikelihoodOp<'a, F>
+where
+    F: ArgminFloat,
+    Vec<F>: ArgminDot<Vec<F>, F>,
+    Vec<Vec<F>>: ArgminCholesky<Cholesky<Vec<Vec<F>>>>,
+    Cholesky<Vec<Vec<F>>>: ArgminDecompositionSolve<Vec<F>>,
+{
+    type Param = Vec<F>;
+    type Output = F;
+    type Hessian = ();
+    type Jacobian = ();
+    type Float = F;
+
+    fn apply(&self, theta: &Vec<F>) -> Result<F, Error> {
+        Ok(self.gp.objective(theta, false)?.0)
+    }
+}
+
+impl<'a, F> Gradient for LikelihoodOp<'a, F>
+where
+    F: ArgminFloat,
+    Vec<F>: ArgminDot<Vec<F>, F>,
+    Vec<Vec<F>>: ArgminCholesky<Cholesky<Vec<Vec<F>>>>,
+    Cholesky<Vec<Vec<F>>>: ArgminDecompositionSolve<Vec<F>>,
+{
+    fn gradient(&self, theta: &Vec<F>) -> Result<Vec<F>, Error> {
+        Ok(self.gp.objective(theta, true)?.1.unwrap())
+    }
+}
+
+/// Whether `v` is positive and finite
+fn is_positive<F: ArgminFloat>(v: F) -> bool {
+    v > F::from_f64(0.0).unwrap() && v.is_finite()
+}
+
+/// Distance of `a` and `b` with each dimension scaled by its lengthscale
+fn scaled_distance<F: ArgminFloat>(a: &[F], b: &[F], lengthscales: &[F]) -> F {
+    a.iter()
+        .zip(b.iter())
+        .zip(lengthscales.iter())
+        .fold(F::from_f64(0.0).unwrap(), |acc, ((x, y), l)| {
+            let d = (*x - *y) / *l;
+            acc + d * d
+        })
+        .sqrt()
+}
+
+/// Solves `L v = b` for a lower triangular `L`
+fn forward_substitution<F: ArgminFloat>(l: &[Vec<F>], b: &[F]) -> Vec<F> {
+    let mut v = b.to_vec();
+    for i in 0..v.len() {
+        for k in 0..i {
+            v[i] = v[i] - l[i][k] * v[k];
+        }
+        v[i] = v[i] / l[i][i];
+    }
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data() -> (Vec<Vec<f64>>, Vec<f64>) {
+        let x: Vec<Vec<f64>> = (0..8)
+            .map(|i| {
+                let t = i as f64 / 7.0;
+                vec![t, (3.0 * t).sin().abs()]
+            })
+            .collect();
+        let y = x
+            .iter()
+            .map(|x| (4.0 * x[0]).sin() + x[1].powi(2))
+            .collect();
+        (x, y)
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let (x, y) = data();
+        for kernel in [Kernel::RBF, Kernel::Matern32, Kernel::Matern52].iter() {
+            let mut gp = GaussianProcess::new(*kernel);
+            gp.fit(x.clone(), y.clone()).unwrap();
+            for (xi, yi) in x.iter().zip(y.iter()) {
+                let (mean, sd) = gp.predict(xi);
+                assert!((mean - yi).abs() < 1e-3);
+                assert!(sd < 1e-2);
+            }
+            // far away from the data, the prediction reverts to the prior
+            let (mean, sd) = gp.predict(&[20.0, 20.0]);
+            let y_mean = y.iter().sum::<f64>() / y.len() as f64;
+            assert!((mean - y_mean).abs() < 1e-6);
+            assert!(sd > 0.1);
+        }
+    }
+
+    #[test]
+    fn test_gradient() {
+        let (x, y) = data();
+        for kernel in [Kernel::RBF, Kernel::Matern32, Kernel::Matern52].iter() {
+            let mut gp = GaussianProcess::new(*kernel)
+                .with_noise_variance(1e-2)
+                .unwrap();
+            gp.fit(x.clone(), y.clone()).unwrap();
+            let theta = vec![-0.3, 0.2, 0.1, -3.0];
+            let grad = gp.objective(&theta, true).unwrap().1.unwrap();
+            let h = 1e-6;
+            for j in 0..theta.len() {
+                let mut tp = theta.clone();
+                tp[j] += h;
+                let mut tm = theta.clone();
+                tm[j] -= h;
+                let fd = (gp.objective(&tp, false).unwrap().0
+                    - gp.objective(&tm, false).unwrap().0)
+                    / (2.0 * h);
+                assert!((grad[j] - fd).abs() < 1e-5 * (1.0 + fd.abs()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_optimize_hyperparameters() {
+        let (x, y) = data();
+        let mut gp = GaussianProcess::new(Kernel::Matern52)
+            .with_lengthscales(vec![5.0, 5.0])
+            .unwrap();
+        gp.fit(x, y).unwrap();
+        let before = gp.objective(&gp.theta(), false).unwrap().0;
+        gp.optimize_hyperparameters(100).unwrap();
+        let after = gp.objective(&gp.theta(), false).unwrap().0;
+        assert!(after < before);
+        assert!(gp.log_marginal_likelihood().is_finite());
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let gp: GaussianProcess<f64> = GaussianProcess::new(Kernel::RBF);
+        assert!(gp.clone().with_lengthscales(vec![]).is_err());
+        assert!(gp.clone().with_lengthscales(vec![1.0, -1.0]).is_err());
+        assert!(gp.clone().with_signal_variance(0.0).is_err());
+        assert!(gp.clone().with_noise_variance(f64::INFINITY).is_err());
+        let mut gp = gp.with_lengthscales(vec![1.0]).unwrap();
+        assert!(gp.clone().optimize_hyperparameters(10).is_err());
+        assert!(gp.fit(vec![vec![0.0, 1.0]], vec![1.0]).is_err());
+        assert!(gp.fit(vec![vec![0.0]], vec![1.0, 2.0]).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Carl Edward Rasmussen and Christopher K. I. Williams (2006). Gaussian Processes for
+//! Machine Learning. MIT Press. ISBN 0-262-18253-X.
+
+use crate::core::ArgminFloat;
+use serde::{Deserialize, Serialize};
+
+/// Stationary covariance functions of a `GaussianProcess`
+///
+/// The covariance of two points `x` and `y` is `signal_variance * k(r)` with the scaled distance
+/// `r = sqrt(sum_i ((x_i - y_i) / l_i)^2)`, where `l_i` are the lengthscales [0].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Kernel {
+    /// Squared exponential kernel `k(r) = exp(-r^2 / 2)`, which yields infinitely smooth samples
+    RBF,
+    /// Matérn kernel with `nu = 3/2`, `k(r) = (1 + sqrt(3) r) exp(-sqrt(3) r)`
+    Matern32,
+    /// Matérn kernel with `nu = 5/2`, `k(r) = (1 + sqrt(5) r + 5/3 r^2) exp(-sqrt(5) r)`
+    #[default]
+    Matern52,
+}
+
+impl Kernel {
+    /// Correlation `k(r)` at scaled distance `r`
+    pub fn correlation<F: ArgminFloat>(&self, r: F) -> F {
+        let one = F::from_f64(1.0).unwrap();
+        match self {
+            Kernel::RBF => (-F::from_f64(0.5).unwrap() * r * r).exp(),
+            Kernel::Matern32 => {
+                let a = F::from_f64(3.0).unwrap().sqrt() * r;
+                (one + a) * (-a).exp()
+            }
+            Kernel::Matern52 => {
+                let a = F::from_f64(5.0).unwrap().sqrt() * r;
+                (one + a + a * a / F::from_f64(3.0).unwrap()) * (-a).exp()
+            }
+        }
+    }
+
+    /// Derivative of the correlation divided by the scaled distance, `k'(r) / r`, which is finite
+    /// at `r = 0` for all kernels
+    pub fn derivative_ratio<F: ArgminFloat>(&self, r: F) -> F {
+        match self {
+            Kernel::RBF => -(-F::from_f64(0.5).unwrap() * r * r).exp(),
+            Kernel::Matern32 => {
+                let a = F::from_f64(3.0).unwrap().sqrt() * r;
+                -F::from_f64(3.0).unwrap() * (-a).exp()
+            }
+            Kernel::Matern52 => {
+                let a = F::from_f64(5.0).unwrap().sqrt() * r;
+                -F::from_f64(5.0 / 3.0).unwrap() * (F::from_f64(1.0).unwrap() + a) * (-a).exp()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correlation() {
+        for kernel in [Kernel::RBF, Kernel::Matern32, Kernel::Matern52].iter() {
+            assert!((kernel.correlation(0.0f64) - 1.0).abs() < 1e-15);
+            assert!(kernel.correlation(1.0f64) < 1.0);
+            assert!(kernel.correlation(10.0f64) < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_derivative_ratio() {
+        let h = 1e-6;
+        for kernel in [Kernel::RBF, Kernel::Matern32, Kernel::Matern52].iter() {
+            for r in [0.1f64, 0.7, 2.0].iter() {
+                let fd = (kernel.correlation(r + h) - kernel.correlation(r - h)) / (2.0 * h);
+                assert!((kernel.derivative_ratio(*r) * r - fd).abs() < 1e-8);
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Bayesian optimization
+//!
+//! Global optimization of expensive black-box functions within a box by means of a Gaussian
+//! process surrogate.
+//!
+//! * [Bayesian optimization](bayesopt_method/struct.BayesianOptimization.html)
+//! * [Gaussian process regression](gaussianprocess/struct.GaussianProcess.html) with the
+//!   [kernels](kernel/enum.Kernel.html) RBF, Matérn 3/2 and Matérn 5/2
+//! * [Acquisition functions](acquisition/enum.Acquisition.html): expected improvement, upper
+//!   confidence bound and probability of improvement
+//!
+//! # References:
+//!
+//! [0] Bobak Shahriari, Kevin Swersky, Ziyu Wang, Ryan P. Adams and Nando de Freitas (2016).
+//! Taking the Human Out of the Loop: A Review of Bayesian Optimization. Proceedings of the IEEE
+//! 104(1), 148-175.
+
+/// Acquisition functions
+pub mod acquisition;
+/// Bayesian optimization solver
+pub mod bayesopt_method;
+/// Gaussian process regression
+pub mod gaussianprocess;
+/// Covariance functions
+pub mod kernel;
+
+pub use self::acquisition::*;
+pub use self::bayesopt_method::*;
+pub use self::gaussianprocess::*;
+pub use self::kernel::*;
@@ -0,0 +1,129 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Validation of lower and upper bounds and handling of points outside of them, shared by the
+//! solvers which support bound constraints.
+
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Treatment of points outside of the bounds
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoundHandling {
+    /// Set components outside of the bounds to the violated bound
+    #[default]
+    Clip,
+    /// Reflect components outside of the bounds at the lower and then at the upper bound.
+    /// Components which are still outside of the bounds afterwards are clipped.
+    Reflect,
+    /// Draw components outside of the bounds uniformly from the bounds
+    RandomReinit,
+}
+
+/// Checks that `lower` and `upper` have the same number of components and that `lower <= upper`
+/// holds componentwise
+pub(crate) fn check_bounds<P, F>(name: &str, lower: &P, upper: &P) -> Result<(), Error>
+where
+    P: ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    let n = lower.num_components();
+    let invalid = |i| {
+        let (l, u) = (lower.component(i), upper.component(i));
+        l.is_nan() || u.is_nan() || l > u
+    };
+    if n != upper.num_components() || (0..n).any(invalid) {
+        return Err(ArgminError::InvalidParameter {
+            text: format!("{}: lower bounds must not exceed upper bounds.", name),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Checks that the bounds have as many components as the parameter vector `x`
+pub(crate) fn check_bounds_dim<P, F>(name: &str, lower: &P, x: &P) -> Result<(), Error>
+where
+    P: ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    if lower.num_components() != x.num_components() {
+        return Err(ArgminError::InvalidParameter {
+            text: format!("{}: bounds do not match the parameter vector.", name),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Moves `x` into the box `[lower, upper]` according to `handling`
+pub fn apply_bounds<P, F>(x: P, lower: &P, upper: &P, handling: BoundHandling) -> P
+where
+    P: ArgminSub<P, P> + ArgminMul<F, P> + ArgminMinMax + ArgminRandom + ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    let x = match handling {
+        BoundHandling::Clip => x,
+        BoundHandling::Reflect => {
+            // 2 * max(x, l) - x reflects components below l at l
+            let two = F::from_f64(2.0).unwrap();
+            let x = P::max(&x, lower).mul(&two).sub(&x);
+            P::min(&x, upper).mul(&two).sub(&x)
+        }
+        BoundHandling::RandomReinit => {
+            let random = P::rand_from_range(lower, upper);
+            let mut x = x;
+            for i in 0..x.num_components() {
+                let xi = x.component(i);
+                if xi < lower.component(i) || xi > upper.component(i) {
+                    x.set_component(i, random.component(i));
+                }
+            }
+            x
+        }
+    };
+    P::max(&P::min(&x, upper), lower)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_bounds() {
+        assert!(check_bounds::<_, f64>("x", &vec![0.0, 1.0], &vec![1.0, 1.0]).is_ok());
+        assert!(check_bounds::<_, f64>("x", &vec![0.0, 1.0], &vec![1.0, 0.0]).is_err());
+        assert!(check_bounds::<_, f64>("x", &vec![0.0], &vec![1.0, 1.0]).is_err());
+        assert!(check_bounds::<_, f64>("x", &vec![std::f64::NAN], &vec![1.0]).is_err());
+        assert!(check_bounds_dim::<_, f64>("x", &vec![0.0], &vec![1.0]).is_ok());
+        assert!(check_bounds_dim::<_, f64>("x", &vec![0.0], &vec![1.0, 1.0]).is_err());
+    }
+
+    #[test]
+    fn test_apply_bounds() {
+        let lower = vec![0.0f64; 3];
+        let upper = vec![1.0f64; 3];
+        let x = vec![-0.25, 1.5, 0.5];
+        assert_eq!(
+            apply_bounds(x.clone(), &lower, &upper, BoundHandling::Clip),
+            vec![0.0, 1.0, 0.5]
+        );
+        assert_eq!(
+            apply_bounds(x.clone(), &lower, &upper, BoundHandling::Reflect),
+            vec![0.25, 0.5, 0.5]
+        );
+        // reflected component violates the other bound
+        assert_eq!(
+            apply_bounds(vec![-3.0, 0.0, 1.0], &lower, &upper, BoundHandling::Reflect),
+            vec![0.0, 0.0, 1.0]
+        );
+        let y = apply_bounds(x, &lower, &upper, BoundHandling::RandomReinit);
+        assert!(y[0] >= 0.0 && y[0] <= 1.0);
+        assert!(y[1] >= 0.0 && y[1] <= 1.0);
+        assert!((y[2] - 0.5).abs() < std::f64::EPSILON);
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+//! Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+//! ISBN 978-0-521-88068-8.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+
+/// golden ratio
+const GOLD: f64 = 1.618_033_988_749_895;
+/// maximum magnification of a parabolic step
+const GLIMIT: f64 = 100.0;
+/// prevents division by zero
+const TINY: f64 = 1e-20;
+
+/// Find points `a < b < c` with `f(b) <= f(a)` and `f(b) <= f(c)`, which therefore bracket a
+/// minimum, starting from `x0` and `x0 + step`. The search goes downhill with golden-section
+/// and parabolic extrapolation steps (`mnbrak` in [0]) and fails if no bracket is found within
+/// `max_iters` steps.
+///
+/// `a` and `c` can be passed to `BrentOpt`, `BrentOptDeriv` or `GoldenSectionSearch` as interval
+/// and `b` as initial parameter.
+///
+/// # References:
+///
+/// [0] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+/// Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+/// ISBN 978-0-521-88068-8.
+pub fn bracket_minimum<O, F>(op: &O, x0: F, step: F, max_iters: u64) -> Result<(F, F, F), Error>
+where
+    O: CostFunction<Param = F, Output = F, Float = F>,
+    F: ArgminFloat,
+{
+    if step == F::from_f64(0.0).unwrap() || !step.is_finite() {
+        return Err(ArgminError::InvalidParameter {
+            text: "bracket_minimum: step must be finite and nonzero.".to_string(),
+        }
+        .into());
+    }
+    let gold = F::from_f64(GOLD).unwrap();
+    let glimit = F::from_f64(GLIMIT).unwrap();
+    let tiny = F::from_f64(TINY).unwrap();
+    let zero = F::from_f64(0.0).unwrap();
+
+    let mut a = x0;
+    let mut b = x0 + step;
+    let mut fa = op.apply(&a)?;
+    let mut fb = op.apply(&b)?;
+    // go downhill from a to b
+    if fb > fa {
+        std::mem::swap(&mut a, &mut b);
+        std::mem::swap(&mut fa, &mut fb);
+    }
+    let mut c = b + gold * (b - a);
+    let mut fc = op.apply(&c)?;
+
+    let ordered = |a: F, b: F, c: F| (a.min(c), b, a.max(c));
+
+    for _ in 0..max_iters {
+        if fb <= fc {
+            return Ok(ordered(a, b, c));
+        }
+        // parabolic extrapolation from a, b and c
+        let r = (b - a) * (fb - fc);
+        let q = (b - c) * (fb - fa);
+        let denom = F::from_f64(2.0).unwrap() * (q - r).abs().max(tiny).copysign(q - r);
+        let mut u = b - ((b - c) * q - (b - a) * r) / denom;
+        let ulim = b + glimit * (c - b);
+        let mut fu;
+        if (b - u) * (u - c) > zero {
+            // u between b and c
+            fu = op.apply(&u)?;
+            if fu < fc {
+                return Ok(ordered(b, u, c));
+            } else if fu > fb {
+                return Ok(ordered(a, b, u));
+            }
+            u = c + gold * (c - b);
+            fu = op.apply(&u)?;
+        } else if (c - u) * (u - ulim) > zero {
+            // u between c and its limit
+            fu = op.apply(&u)?;
+            if fu < fc {
+                b = c;
+                c = u;
+                u = c + gold * (c - b);
+                fb = fc;
+                fc = fu;
+                fu = op.apply(&u)?;
+            }
+        } else if (u - ulim) * (ulim - c) >= zero {
+            // limit u to its maximum value
+            u = ulim;
+            fu = op.apply(&u)?;
+        } else {
+            // reject parabolic u, use golden section step
+            u = c + gold * (c - b);
+            fu = op.apply(&u)?;
+        }
+        a = b;
+        b = c;
+        c = u;
+        fa = fb;
+        fb = fc;
+        fc = fu;
+    }
+    if fb <= fc && fb <= fa {
+        return Ok(ordered(a, b, c));
+    }
+    Err(ArgminError::ConditionViolated {
+        text: "bracket_minimum: no bracket found, the function may be unbounded.".to_string(),
+    }
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::brent::BrentOpt;
+
+    fn check_bracket<O>(op: &O, (a, b, c): (f64, f64, f64))
+    where
+        O: CostFunction<Param = f64, Output = f64, Float = f64>,
+    {
+        assert!(a < b && b < c);
+        let fb = op.apply(&b).unwrap();
+        assert!(fb <= op.apply(&a).unwrap());
+        assert!(fb <= op.apply(&c).unwrap());
+    }
+
+    #[test]
+    fn test_bracket_minimum() {
+        let op = FnOp::new(|x: &f64| Ok((x - 10.0).powi(2)));
+        check_bracket(&op, bracket_minimum(&op, 0.0, 0.1, 50).unwrap());
+        check_bracket(&op, bracket_minimum(&op, 20.0, 0.1, 50).unwrap());
+        check_bracket(&op, bracket_minimum(&op, 0.0, -0.1, 50).unwrap());
+        check_bracket(&op, bracket_minimum(&op, 10.0, 1.0, 50).unwrap());
+    }
+
+    #[test]
+    fn test_bracket_and_minimize() {
+        let op = FnOp::new(|x: &f64| Ok(x.cosh() + 0.5 * x));
+        let (a, b, c) = bracket_minimum(&op, 3.0, 0.5, 50).unwrap();
+        let res = Executor::new(op, BrentOpt::new(a, c), b)
+            .max_iters(100)
+            .run()
+            .unwrap();
+        // f'(x) = sinh(x) + 0.5 = 0
+        assert!((res.state.get_best_param() - (-0.5f64).asinh()).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_unbounded() {
+        let op = FnOp::new(|x: &f64| Ok(-x));
+        assert!(bracket_minimum(&op, 0.0, 1.0, 10).is_err());
+        assert!(bracket_minimum(&op, 0.0, 0.0, 10).is_err());
+    }
+}
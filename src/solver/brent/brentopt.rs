@@ -0,0 +1,280 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Richard P. Brent (1973). Algorithms for Minimization without Derivatives.
+//! Prentice-Hall. ISBN 0-13-022335-2.
+//!
+//! [1] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+//! Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+//! ISBN 978-0-521-88068-8.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 1 - 1 / golden ratio
+const CGOLD: f64 = 0.381_966_011_250_105_1;
+
+/// Brent's method for minimization
+///
+/// Finds a local minimum of a function of one variable inside the interval `[min, max]` by
+/// combining parabolic interpolation through the three best points with golden-section steps. It
+/// converges superlinearly for smooth functions while never being much slower than
+/// golden-section search.
+///
+/// The initial parameter is used as the first point if it lies inside the interval, otherwise
+/// the interval is divided by the golden section. A suitable interval can be found with
+/// [`bracket_minimum`](../bracket/fn.bracket_minimum.html).
+///
+/// The solver terminates once the minimum is located to within `tol * |x| + 1e-10`
+/// (relative tolerance `tol`, defaults to `sqrt(EPSILON)`).
+///
+/// # References:
+///
+/// [0] Richard P. Brent (1973). Algorithms for Minimization without Derivatives.
+/// Prentice-Hall. ISBN 0-13-022335-2.
+///
+/// [1] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+/// Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+/// ISBN 978-0-521-88068-8.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BrentOpt<F> {
+    /// relative tolerance
+    tol: F,
+    /// lower boundary of the interval
+    a: F,
+    /// upper boundary of the interval
+    b: F,
+    /// point with the lowest function value
+    x: F,
+    /// point with the second lowest function value
+    w: F,
+    /// previous value of `w`
+    v: F,
+    /// function value at `x`
+    fx: F,
+    /// function value at `w`
+    fw: F,
+    /// function value at `v`
+    fv: F,
+    /// last step
+    d: F,
+    /// step before the last step
+    e: F,
+}
+
+impl<F: ArgminFloat> BrentOpt<F> {
+    /// Constructor
+    /// The values `min` and `max` must bracket the minimum.
+    pub fn new(min: F, max: F) -> BrentOpt<F> {
+        BrentOpt {
+            tol: F::epsilon().sqrt(),
+            a: min.min(max),
+            b: min.max(max),
+            x: F::nan(),
+            w: F::nan(),
+            v: F::nan(),
+            fx: F::nan(),
+            fw: F::nan(),
+            fv: F::nan(),
+            d: F::from_f64(0.0).unwrap(),
+            e: F::from_f64(0.0).unwrap(),
+        }
+    }
+
+    /// Set relative tolerance
+    pub fn with_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "BrentOpt: tol must be positive.".to_string(),
+            }
+            .into());
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// absolute tolerance at the current point
+    fn tol1(&self) -> F {
+        self.tol * self.x.abs() + F::from_f64(1e-10).unwrap()
+    }
+}
+
+impl<O, F> Solver<O> for BrentOpt<F>
+where
+    O: CostFunction<Param = F, Output = F, Float = F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "BrentOpt";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let x = state.get_param();
+        self.x = if x > self.a && x < self.b {
+            x
+        } else {
+            self.a + F::from_f64(CGOLD).unwrap() * (self.b - self.a)
+        };
+        self.w = self.x;
+        self.v = self.x;
+        self.fx = op.apply(&self.x)?;
+        self.fw = self.fx;
+        self.fv = self.fx;
+        Ok(Some(ArgminIterData::new().param(self.x).cost(self.fx)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        // BrentOpt maintains its own state
+        _state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let zero = F::from_f64(0.0).unwrap();
+        let half = F::from_f64(0.5).unwrap();
+        let cgold = F::from_f64(CGOLD).unwrap();
+        let xm = half * (self.a + self.b);
+        let tol1 = self.tol1();
+        let tol2 = F::from_f64(2.0).unwrap() * tol1;
+
+        let mut parabolic = false;
+        if self.e.abs() > tol1 {
+            // fit parabola through x, w and v
+            let r = (self.x - self.w) * (self.fx - self.fv);
+            let mut q = (self.x - self.v) * (self.fx - self.fw);
+            let mut p = (self.x - self.v) * q - (self.x - self.w) * r;
+            q = F::from_f64(2.0).unwrap() * (q - r);
+            if q > zero {
+                p = -p;
+            }
+            q = q.abs();
+            let etemp = self.e;
+            self.e = self.d;
+            // accept the parabolic step only if it falls into the interval and is shorter than
+            // half of the step before the last one
+            if !(p.abs() >= (half * q * etemp).abs()
+                || p <= q * (self.a - self.x)
+                || p >= q * (self.b - self.x))
+            {
+                parabolic = true;
+                self.d = p / q;
+                let u = self.x + self.d;
+                if u - self.a < tol2 || self.b - u < tol2 {
+                    self.d = tol1.copysign(xm - self.x);
+                }
+            }
+        }
+        if !parabolic {
+            self.e = if self.x >= xm {
+                self.a - self.x
+            } else {
+                self.b - self.x
+            };
+            self.d = cgold * self.e;
+        }
+
+        let u = if self.d.abs() >= tol1 {
+            self.x + self.d
+        } else {
+            self.x + tol1.copysign(self.d)
+        };
+        let fu = op.apply(&u)?;
+
+        if fu <= self.fx {
+            if u >= self.x {
+                self.a = self.x;
+            } else {
+                self.b = self.x;
+            }
+            self.v = self.w;
+            self.fv = self.fw;
+            self.w = self.x;
+            self.fw = self.fx;
+            self.x = u;
+            self.fx = fu;
+        } else {
+            if u < self.x {
+                self.a = u;
+            } else {
+                self.b = u;
+            }
+            if fu <= self.fw || self.w == self.x {
+                self.v = self.w;
+                self.fv = self.fw;
+                self.w = u;
+                self.fw = fu;
+            } else if fu <= self.fv || self.v == self.x || self.v == self.w {
+                self.v = u;
+                self.fv = fu;
+            }
+        }
+
+        Ok(ArgminIterData::new()
+            .param(self.x)
+            .cost(self.fx)
+            .kv(make_kv!("parabolic" => parabolic;)))
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason {
+        let half = F::from_f64(0.5).unwrap();
+        let xm = half * (self.a + self.b);
+        if (self.x - xm).abs() <= F::from_f64(2.0).unwrap() * self.tol1() - half * (self.b - self.a)
+        {
+            TerminationReason::TargetToleranceReached
+        } else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(brentopt, BrentOpt<f64>);
+
+    #[test]
+    fn test_minimum() {
+        // minimum at x = 1 / sqrt(3)
+        let op = FnOp::new(|x: &f64| Ok(x.powi(3) - x));
+        let res = Executor::new(op, BrentOpt::new(0.0, 2.0), 1.5)
+            .max_iters(100)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetToleranceReached
+        );
+        assert!((res.state.get_best_param() - 1.0 / 3.0f64.sqrt()).abs() < 1e-7);
+        assert!(res.state.get_iter() < 20);
+    }
+
+    #[test]
+    fn test_init_outside_interval() {
+        let op = FnOp::new(|x: &f64| Ok((x - 0.3).powi(2) + 0.1 * x.powi(4)));
+        let res = Executor::new(op, BrentOpt::new(-1.0, 1.0), 5.0)
+            .max_iters(100)
+            .run()
+            .unwrap();
+        let x = res.state.get_best_param();
+        // f'(x) = 2 (x - 0.3) + 0.4 x^3 = 0
+        assert!((2.0 * (x - 0.3) + 0.4 * x.powi(3)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let brent: BrentOpt<f64> = BrentOpt::new(0.0, 1.0);
+        assert!(brent.clone().with_tol(0.0).is_err());
+        let BrentOpt { tol, .. } = brent.with_tol(1e-4).unwrap();
+        assert!((tol - 1e-4).abs() < std::f64::EPSILON);
+    }
+}
@@ -0,0 +1,301 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+//! Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+//! ISBN 978-0-521-88068-8.
+
+use crate::core::capabilities::{CostFunction, Gradient};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Brent's method for minimization using derivatives
+///
+/// Variant of [`BrentOpt`](../brentopt/struct.BrentOpt.html) which uses the derivative computed
+/// by `gradient`. The sign of the derivative decides which part of the interval is searched and
+/// secant steps on the derivative replace the parabolic interpolation. If the secant steps are not
+/// acceptable, the interval is bisected (`dbrent` in [0]).
+///
+/// The solver terminates once the minimum is located to within `tol * |x| + 1e-10`
+/// (relative tolerance `tol`, defaults to `sqrt(EPSILON)`).
+///
+/// # References:
+///
+/// [0] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+/// Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+/// ISBN 978-0-521-88068-8.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BrentOptDeriv<F> {
+    /// relative tolerance
+    tol: F,
+    /// lower boundary of the interval
+    a: F,
+    /// upper boundary of the interval
+    b: F,
+    /// point with the lowest function value
+    x: F,
+    /// point with the second lowest function value
+    w: F,
+    /// previous value of `w`
+    v: F,
+    /// function value at `x`
+    fx: F,
+    /// function value at `w`
+    fw: F,
+    /// function value at `v`
+    fv: F,
+    /// derivative at `x`
+    dx: F,
+    /// derivative at `w`
+    dw: F,
+    /// derivative at `v`
+    dv: F,
+    /// last step
+    d: F,
+    /// step before the last step
+    e: F,
+}
+
+impl<F: ArgminFloat> BrentOptDeriv<F> {
+    /// Constructor
+    /// The values `min` and `max` must bracket the minimum.
+    pub fn new(min: F, max: F) -> BrentOptDeriv<F> {
+        BrentOptDeriv {
+            tol: F::epsilon().sqrt(),
+            a: min.min(max),
+            b: min.max(max),
+            x: F::nan(),
+            w: F::nan(),
+            v: F::nan(),
+            fx: F::nan(),
+            fw: F::nan(),
+            fv: F::nan(),
+            dx: F::nan(),
+            dw: F::nan(),
+            dv: F::nan(),
+            d: F::from_f64(0.0).unwrap(),
+            e: F::from_f64(0.0).unwrap(),
+        }
+    }
+
+    /// Set relative tolerance
+    pub fn with_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "BrentOptDeriv: tol must be positive.".to_string(),
+            }
+            .into());
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// absolute tolerance at the current point
+    fn tol1(&self) -> F {
+        self.tol * self.x.abs() + F::from_f64(1e-10).unwrap()
+    }
+
+    /// Distance to the end of the interval in the downhill direction
+    fn downhill(&self) -> F {
+        if self.dx >= F::from_f64(0.0).unwrap() {
+            self.a - self.x
+        } else {
+            self.b - self.x
+        }
+    }
+}
+
+impl<O, F> Solver<O> for BrentOptDeriv<F>
+where
+    O: CostFunction<Param = F, Output = F, Float = F> + Gradient,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "BrentOptDeriv";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let x = state.get_param();
+        self.x = if x > self.a && x < self.b {
+            x
+        } else {
+            F::from_f64(0.5).unwrap() * (self.a + self.b)
+        };
+        self.w = self.x;
+        self.v = self.x;
+        self.fx = op.apply(&self.x)?;
+        self.fw = self.fx;
+        self.fv = self.fx;
+        self.dx = op.gradient(&self.x)?;
+        self.dw = self.dx;
+        self.dv = self.dx;
+        Ok(Some(ArgminIterData::new().param(self.x).cost(self.fx)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        // BrentOptDeriv maintains its own state
+        _state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let zero = F::from_f64(0.0).unwrap();
+        let half = F::from_f64(0.5).unwrap();
+        let xm = half * (self.a + self.b);
+        let tol1 = self.tol1();
+        let tol2 = F::from_f64(2.0).unwrap() * tol1;
+
+        let mut secant = false;
+        if self.e.abs() > tol1 {
+            // secant steps on the derivative through (x, w) and (x, v)
+            let mut d1 = F::from_f64(2.0).unwrap() * (self.b - self.a);
+            let mut d2 = d1;
+            if self.dw != self.dx {
+                d1 = (self.w - self.x) * self.dx / (self.dx - self.dw);
+            }
+            if self.dv != self.dx {
+                d2 = (self.v - self.x) * self.dx / (self.dx - self.dv);
+            }
+            let u1 = self.x + d1;
+            let u2 = self.x + d2;
+            // acceptable steps stay in the interval and go downhill
+            let ok1 = (self.a - u1) * (u1 - self.b) > zero && self.dx * d1 <= zero;
+            let ok2 = (self.a - u2) * (u2 - self.b) > zero && self.dx * d2 <= zero;
+            let olde = self.e;
+            self.e = self.d;
+            if ok1 || ok2 {
+                let d = if ok1 && ok2 {
+                    if d1.abs() < d2.abs() {
+                        d1
+                    } else {
+                        d2
+                    }
+                } else if ok1 {
+                    d1
+                } else {
+                    d2
+                };
+                if d.abs() <= (half * olde).abs() {
+                    secant = true;
+                    self.d = d;
+                    let u = self.x + self.d;
+                    if u - self.a < tol2 || self.b - u < tol2 {
+                        self.d = tol1.copysign(xm - self.x);
+                    }
+                }
+            }
+        }
+        if !secant {
+            self.e = self.downhill();
+            self.d = half * self.e;
+        }
+
+        let (u, fu) = if self.d.abs() >= tol1 {
+            let u = self.x + self.d;
+            (u, op.apply(&u)?)
+        } else {
+            let u = self.x + tol1.copysign(self.d);
+            let fu = op.apply(&u)?;
+            // the minimal step goes uphill, therefore x is the minimum
+            if fu > self.fx {
+                return Ok(ArgminIterData::new()
+                    .param(self.x)
+                    .cost(self.fx)
+                    .termination_reason(TerminationReason::TargetToleranceReached));
+            }
+            (u, fu)
+        };
+        let du = op.gradient(&u)?;
+
+        if fu <= self.fx {
+            if u >= self.x {
+                self.a = self.x;
+            } else {
+                self.b = self.x;
+            }
+            self.v = self.w;
+            self.fv = self.fw;
+            self.dv = self.dw;
+            self.w = self.x;
+            self.fw = self.fx;
+            self.dw = self.dx;
+            self.x = u;
+            self.fx = fu;
+            self.dx = du;
+        } else {
+            if u < self.x {
+                self.a = u;
+            } else {
+                self.b = u;
+            }
+            if fu <= self.fw || self.w == self.x {
+                self.v = self.w;
+                self.fv = self.fw;
+                self.dv = self.dw;
+                self.w = u;
+                self.fw = fu;
+                self.dw = du;
+            } else if fu < self.fv || self.v == self.x || self.v == self.w {
+                self.v = u;
+                self.fv = fu;
+                self.dv = du;
+            }
+        }
+
+        Ok(ArgminIterData::new()
+            .param(self.x)
+            .cost(self.fx)
+            .kv(make_kv!("secant" => secant;)))
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason {
+        let half = F::from_f64(0.5).unwrap();
+        let xm = half * (self.a + self.b);
+        if (self.x - xm).abs() <= F::from_f64(2.0).unwrap() * self.tol1() - half * (self.b - self.a)
+        {
+            TerminationReason::TargetToleranceReached
+        } else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(brentopt_deriv, BrentOptDeriv<f64>);
+
+    #[test]
+    fn test_minimum() {
+        // minimum at x = 1 / sqrt(3)
+        let op =
+            FnOp::new(|x: &f64| Ok(x.powi(3) - x)).gradient(|x: &f64| Ok(3.0 * x.powi(2) - 1.0));
+        let res = Executor::new(op, BrentOptDeriv::new(0.0, 2.0), 1.5)
+            .max_iters(100)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetToleranceReached
+        );
+        assert!((res.state.get_best_param() - 1.0 / 3.0f64.sqrt()).abs() < 1e-7);
+        assert!(res.state.get_iter() < 20);
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let brent: BrentOptDeriv<f64> = BrentOptDeriv::new(0.0, 1.0);
+        assert!(brent.clone().with_tol(0.0).is_err());
+        let BrentOptDeriv { tol, .. } = brent.with_tol(1e-4).unwrap();
+        assert!((tol - 1e-4).abs() < std::f64::EPSILON);
+    }
+}
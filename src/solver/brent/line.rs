@@ -0,0 +1,122 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::brent::{bracket_minimum, BrentOpt};
+use std::cell::Cell;
+
+/// Maximum number of iterations of `bracket_minimum` in a line minimization
+const BRACKET_MAX_ITERS: u64 = 50;
+/// Maximum number of iterations of `BrentOpt` in a line minimization
+const LINE_MAX_ITERS: u64 = 100;
+
+/// Function `g(op, t)` of one variable which evaluates the cost function of `op`
+struct Line<'a, O, G> {
+    /// operator
+    op: &'a O,
+    /// function of `t`
+    g: G,
+    /// number of evaluations of `g`
+    evals: Cell<u64>,
+}
+
+impl<'a, O, F, G> CostFunction for Line<'a, O, G>
+where
+    O: CostFunction<Output = F, Float = F>,
+    F: ArgminFloat,
+    G: Fn(&O, F) -> Result<F, Error>,
+{
+    type Param = F;
+    type Output = F;
+    type Hessian = ();
+    type Jacobian = ();
+    type Float = F;
+
+    fn apply(&self, t: &F) -> Result<F, Error> {
+        self.evals.set(self.evals.get() + 1);
+        (self.g)(self.op, *t)
+    }
+}
+
+/// Minimizes `g(op, t)` over `t` in `[tmin, tmax]`, where `g` evaluates the cost function of
+/// `op`, for instance along a line or a coordinate. The minimum is bracketed with
+/// `bracket_minimum` starting from `t0` and `t0 + step`, the bracket is restricted to
+/// `[tmin, tmax]` and the minimum is located with `BrentOpt`. `g` has to handle `t` outside of
+/// `[tmin, tmax]`, which may be evaluated during bracketing.
+///
+/// Returns the best `t` and `g(op, t)`. Every evaluation of `g` is added to the cost function
+/// evaluation count of `op`.
+pub(crate) fn line_minimum<O, F, G>(
+    op: &mut OpWrapper<O>,
+    g: G,
+    t0: F,
+    step: F,
+    (tmin, tmax): (F, F),
+) -> Result<(F, F), Error>
+where
+    O: CostFunction<Output = F, Float = F>,
+    F: ArgminFloat,
+    G: Fn(&O, F) -> Result<F, Error>,
+{
+    let line = Line {
+        op: op.op.as_ref().unwrap(),
+        g,
+        evals: Cell::new(0),
+    };
+    let (a, b, c) = bracket_minimum(&line, t0, step, BRACKET_MAX_ITERS)?;
+    // keep both ends inside of `[tmin, tmax]`, also if the bracket lies entirely outside of it
+    let (a, c) = (a.max(tmin).min(tmax), c.min(tmax).max(tmin));
+    let res = Executor::new(line, BrentOpt::new(a, c), b.max(a).min(c))
+        .max_iters(LINE_MAX_ITERS)
+        .ctrlc(false)
+        .timer(false)
+        .run()?;
+    // `evals` also counts the evaluations of `bracket_minimum`
+    op.cost_func_count += res.operator.evals.get();
+    Ok((res.state.get_best_param(), res.state.get_best_cost()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_minimum() {
+        let mut op = OpWrapper::new(FnOp::new(|x: &Vec<f64>| Ok((x[0] - 2.0).powi(2) + x[1])));
+        let along = |op: &FnOp<Vec<f64>, f64>, t: f64| CostFunction::apply(op, &vec![t, 1.0]);
+        let (t, cost) = line_minimum(
+            &mut op,
+            along,
+            0.0,
+            1.0,
+            (std::f64::NEG_INFINITY, std::f64::INFINITY),
+        )
+        .unwrap();
+        assert!((t - 2.0).abs() < 1e-6);
+        assert!((cost - 1.0).abs() < 1e-10);
+        let evals = op.cost_func_count;
+        assert!(evals > 0);
+        // the minimum lies outside of the range
+        let (t, _) = line_minimum(&mut op, along, 0.0, 1.0, (-1.0, 1.5)).unwrap();
+        assert!((t - 1.5).abs() < 1e-6);
+        assert!(op.cost_func_count > evals);
+    }
+
+    #[test]
+    fn test_line_minimum_bracket_outside_of_range() {
+        let mut op = OpWrapper::new(FnOp::new(|x: &Vec<f64>| Ok((x[0] - 2.0).powi(2))));
+        let along = |op: &FnOp<Vec<f64>, f64>, t: f64| CostFunction::apply(op, &vec![t]);
+        // the bracket found from 0 lies entirely above `tmax`
+        let (t, cost) = line_minimum(&mut op, along, 0.0, 1.0, (-1.0, 0.5)).unwrap();
+        assert!((t - 0.5).abs() < 1e-6);
+        assert!((cost - 2.25).abs() < 1e-6);
+        // ... and entirely below `tmin`
+        let (t, _) = line_minimum(&mut op, along, 4.0, -1.0, (3.0, 5.0)).unwrap();
+        assert!((t - 3.0).abs() < 1e-6);
+    }
+}
@@ -15,9 +15,27 @@
 //!
 //! https://en.wikipedia.org/wiki/Brent%27s_method
 //!
+//! The module also contains Brent's method for minimization of a function of one variable
+//! (`BrentOpt`), a variant using derivatives (`BrentOptDeriv`) and `bracket_minimum`, which finds
+//! an interval containing a minimum.
+
+/// Bracketing of a minimum
+pub mod bracket;
+/// Brent's method for minimization
+pub mod brentopt;
+/// Brent's method for minimization using derivatives
+pub mod brentopt_deriv;
+/// Minimization along lines and coordinates
+mod line;
+
+pub use self::bracket::*;
+pub use self::brentopt::*;
+pub use self::brentopt_deriv::*;
+pub(crate) use self::line::line_minimum;
 
 /// Implementation of Brent's optimization method,
 /// see https://en.wikipedia.org/wiki/Brent%27s_method
+use crate::core::capabilities::CostFunction;
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -81,7 +99,7 @@ impl<F: ArgminFloat> Brent<F> {
 
 impl<O, F> Solver<O> for Brent<F>
 where
-    O: ArgminOp<Param = F, Output = F, Float = F>,
+    O: CostFunction<Param = F, Output = F, Float = F>,
     F: ArgminFloat,
 {
     const NAME: &'static str = "Brent";
@@ -10,7 +10,9 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::CostFunction;
 use crate::prelude::*;
+use crate::solver::conjugategradient::{ArgminPreconditioner, IdentityPreconditioner};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::default::Default;
@@ -19,6 +21,9 @@ use std::fmt::Debug;
 /// The conjugate gradient method is a solver for systems of linear equations with a symmetric and
 /// positive-definite matrix.
 ///
+/// Badly conditioned systems can be preconditioned via `with_preconditioner` (Algorithm 5.3 in
+/// [0]). By default, no preconditioning is performed.
+///
 /// [Example](https://github.com/argmin-rs/argmin/blob/master/examples/conjugategradient.rs)
 ///
 /// # References:
@@ -26,30 +31,42 @@ use std::fmt::Debug;
 /// [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 /// Springer. ISBN 0-387-30303-0.
 #[derive(Clone, Serialize, Deserialize)]
-pub struct ConjugateGradient<P, S> {
+pub struct ConjugateGradient<P, S, F = S, M = IdentityPreconditioner> {
     /// b (right hand side)
     b: P,
     /// residual
     r: P,
+    /// preconditioned residual M^{-1} * r
+    y: P,
     /// p
     p: P,
     /// previous p
     p_prev: P,
-    /// r^T * r
+    /// preconditioner
+    preconditioner: M,
+    /// tolerance for the relative residual ||r|| / ||b||
+    tol: Option<F>,
+    /// ||b||
+    b_norm: F,
+    /// r^T * y
     #[serde(skip)]
-    rtr: S,
+    rty: S,
     /// alpha
     #[serde(skip)]
     alpha: S,
     /// beta
     #[serde(skip)]
     beta: S,
+    /// p^T * A * p of the previous search direction
+    #[serde(skip)]
+    curvature: S,
 }
 
-impl<P, S> ConjugateGradient<P, S>
+impl<P, S, F> ConjugateGradient<P, S, F, IdentityPreconditioner>
 where
     P: Clone + Default,
     S: Default,
+    F: ArgminFloat,
 {
     /// Constructor
     ///
@@ -60,13 +77,54 @@ where
         Ok(ConjugateGradient {
             b,
             r: P::default(),
+            y: P::default(),
             p: P::default(),
             p_prev: P::default(),
-            rtr: S::default(),
+            preconditioner: IdentityPreconditioner,
+            tol: None,
+            b_norm: F::nan(),
+            rty: S::default(),
             alpha: S::default(),
             beta: S::default(),
+            curvature: S::default(),
         })
     }
+}
+
+impl<P, S, F, M> ConjugateGradient<P, S, F, M>
+where
+    P: Clone,
+    F: ArgminFloat,
+{
+    /// Set preconditioner
+    pub fn with_preconditioner<M2>(self, preconditioner: M2) -> ConjugateGradient<P, S, F, M2> {
+        ConjugateGradient {
+            b: self.b,
+            r: self.r,
+            y: self.y,
+            p: self.p,
+            p_prev: self.p_prev,
+            preconditioner,
+            tol: self.tol,
+            b_norm: self.b_norm,
+            rty: self.rty,
+            alpha: self.alpha,
+            beta: self.beta,
+            curvature: self.curvature,
+        }
+    }
+
+    /// Set tolerance for the relative residual. The solver terminates once `||r|| <= tol * ||b||`.
+    pub fn with_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "ConjugateGradient: tol must be positive.".to_string(),
+            }
+            .into());
+        }
+        self.tol = Some(tol);
+        Ok(self)
+    }
 
     /// Return the current search direction (This is needed by NewtonCG for instance)
     pub fn p(&self) -> P {
@@ -78,15 +136,24 @@ where
         self.p_prev.clone()
     }
 
+    /// Return `p^T * A * p` of the previous search direction (This is needed by NewtonCG for
+    /// instance)
+    pub fn curvature(&self) -> S
+    where
+        S: Clone,
+    {
+        self.curvature.clone()
+    }
+
     /// Return the current residual (This is needed by NewtonCG for instance)
     pub fn residual(&self) -> P {
         self.r.clone()
     }
 }
 
-impl<P, O, S, F> Solver<O> for ConjugateGradient<P, S>
+impl<P, O, S, F, M> Solver<O> for ConjugateGradient<P, S, F, M>
 where
-    O: ArgminOp<Param = P, Output = P, Float = F>,
+    O: CostFunction<Param = P, Output = P, Float = F>,
     P: Clone
         + Serialize
         + DeserializeOwned
@@ -95,9 +162,13 @@ where
         + ArgminScaledAdd<O::Param, S, O::Param>
         + ArgminAdd<O::Param, O::Param>
         + ArgminConj
-        + ArgminMul<O::Float, O::Param>,
+        + ArgminMul<O::Float, O::Param>
+        + ArgminMulAssign<S>
+        + ArgminSubAssign<O::Param>
+        + ArgminScaledAddAssign<O::Param, S>,
     S: Debug + ArgminDiv<S, S> + ArgminNorm<O::Float> + ArgminConj,
     F: ArgminFloat,
+    M: ArgminPreconditioner<P> + Serialize + DeserializeOwned,
 {
     const NAME: &'static str = "Conjugate Gradient";
 
@@ -109,9 +180,11 @@ where
         let init_param = state.get_param();
         let ap = op.apply(&init_param)?;
         let r0 = self.b.sub(&ap).mul(&(F::from_f64(-1.0).unwrap()));
-        self.r = r0.clone();
-        self.p = r0.mul(&(F::from_f64(-1.0).unwrap()));
-        self.rtr = self.r.dot(&self.r.conj());
+        self.y = self.preconditioner.precondition(&r0)?;
+        self.p = self.y.mul(&(F::from_f64(-1.0).unwrap()));
+        self.rty = r0.dot(&self.y.conj());
+        self.r = r0;
+        self.b_norm = self.b.dot(&self.b.conj()).norm().sqrt();
         Ok(None)
     }
 
@@ -123,16 +196,17 @@ where
     ) -> Result<ArgminIterData<O>, Error> {
         self.p_prev = self.p.clone();
         let apk = op.apply(&self.p)?;
-        self.alpha = self.rtr.div(&self.p.dot(&apk.conj()));
-        let new_param = state.get_param().scaled_add(&self.alpha, &self.p);
-        self.r = self.r.scaled_add(&self.alpha, &apk);
-        let rtr_n = self.r.dot(&self.r.conj());
-        self.beta = rtr_n.div(&self.rtr);
-        self.rtr = rtr_n;
-        self.p = self
-            .r
-            .mul(&(F::from_f64(-1.0).unwrap()))
-            .scaled_add(&self.beta, &self.p);
+        self.curvature = self.p.dot(&apk.conj());
+        self.alpha = self.rty.div(&self.curvature);
+        let mut new_param = state.get_param();
+        new_param.scaled_add_assign(&self.alpha, &self.p);
+        self.r.scaled_add_assign(&self.alpha, &apk);
+        self.y = self.preconditioner.precondition(&self.r)?;
+        let rty_n = self.r.dot(&self.y.conj());
+        self.beta = rty_n.div(&self.rty);
+        self.rty = rty_n;
+        self.p.mul_assign(&self.beta);
+        self.p.sub_assign(&self.y);
         let norm = self.r.dot(&self.r.conj());
 
         Ok(ArgminIterData::new()
@@ -141,12 +215,117 @@ where
             .cost(norm.norm())
             .kv(make_kv!("alpha" => self.alpha; "beta" => self.beta;)))
     }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
+        match self.tol {
+            // the cost is ||r||^2
+            Some(tol) if state.get_cost().sqrt() <= tol * self.b_norm => {
+                TerminationReason::TargetPrecisionReached
+            }
+            _ => TerminationReason::NotTerminated,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solver::conjugategradient::{IncompleteCholesky, JacobiPreconditioner};
     use crate::test_trait_impl;
 
     test_trait_impl!(conjugate_gradient, ConjugateGradient<Vec<f64>, f64>);
+
+    test_trait_impl!(
+        conjugate_gradient_jacobi,
+        ConjugateGradient<Vec<f64>, f64, f64, JacobiPreconditioner<Vec<f64>>>
+    );
+
+    /// Badly conditioned tridiagonal system matrix
+    #[derive(Clone, Default, Serialize, Deserialize)]
+    struct Tridiagonal {
+        a: Vec<Vec<f64>>,
+    }
+
+    impl Tridiagonal {
+        fn new(n: usize) -> Self {
+            let mut a = vec![vec![0.0; n]; n];
+            for i in 0..n {
+                a[i][i] = 2.0 + ((i + 1) as f64).powi(3);
+                if i > 0 {
+                    a[i][i - 1] = -1.0;
+                    a[i - 1][i] = -1.0;
+                }
+            }
+            Tridiagonal { a }
+        }
+    }
+
+    impl CostFunction for Tridiagonal {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, p: &Vec<f64>) -> Result<Vec<f64>, Error> {
+            Ok(self.a.dot(p))
+        }
+    }
+
+    fn solve<M>(cg: ConjugateGradient<Vec<f64>, f64, f64, M>, op: &Tridiagonal, b: &[f64]) -> u64
+    where
+        M: ArgminPreconditioner<Vec<f64>> + Clone + Serialize + DeserializeOwned,
+    {
+        let n = b.len();
+        let res = Executor::new(op.clone(), cg.with_tol(1e-10).unwrap(), vec![0.0; n])
+            .max_iters(1000)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        let ax = op.a.dot(&res.state.get_best_param());
+        for (ax, b) in ax.iter().zip(b.iter()) {
+            assert!((ax - b).abs() < 1e-6);
+        }
+        res.state.get_iter()
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let cg: ConjugateGradient<Vec<f64>, f64> = ConjugateGradient::new(vec![1.0]).unwrap();
+        assert!(cg.clone().with_tol(0.0).is_err());
+        assert!(cg.clone().with_tol(-1.0).is_err());
+        let ConjugateGradient { tol, .. } = cg.with_tol(1e-4).unwrap();
+        assert_eq!(tol, Some(1e-4));
+    }
+
+    #[test]
+    fn test_preconditioners() {
+        let n = 50;
+        let op = Tridiagonal::new(n);
+        let b = vec![1.0; n];
+        let plain = solve(ConjugateGradient::new(b.clone()).unwrap(), &op, &b);
+
+        let diag = (0..n).map(|i| op.a[i][i]).collect();
+        let jacobi = solve(
+            ConjugateGradient::new(b.clone())
+                .unwrap()
+                .with_preconditioner(JacobiPreconditioner::new(diag)),
+            &op,
+            &b,
+        );
+        assert!(jacobi < plain);
+
+        // IC(0) of a tridiagonal matrix is its exact Cholesky factorization
+        let ic = solve(
+            ConjugateGradient::new(b.clone())
+                .unwrap()
+                .with_preconditioner(IncompleteCholesky::new(&op.a).unwrap()),
+            &op,
+            &b,
+        );
+        assert!(ic <= 2);
+    }
 }
@@ -9,6 +9,8 @@
 //!
 //! * [Conjugate Gradients](cg/struct.ConjugateGradient.html)
 //! * [Nonlinear Conjugate Gradients](nonlinear_cg/struct.NonlinearConjugateGradient.html)
+//! * [Preconditioners](preconditioner/index.html) for `ConjugateGradient`, `NewtonCG` and
+//!   `Steihaug`
 //!
 //! # References:
 //!
@@ -24,6 +26,10 @@ pub mod nonlinear_cg;
 /// Beta update methods for nonlinear CG
 pub mod beta;
 
+/// Preconditioners
+pub mod preconditioner;
+
 pub use self::beta::*;
 pub use self::cg::*;
 pub use self::nonlinear_cg::*;
+pub use self::preconditioner::*;
@@ -13,6 +13,7 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::{CostFunction, Gradient};
 use crate::prelude::*;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -84,7 +85,7 @@ where
 
 impl<O, P, L, B, F> Solver<O> for NonlinearConjugateGradient<P, L, B, F>
 where
-    O: ArgminOp<Param = P, Output = F, Float = F>,
+    O: CostFunction<Param = P, Output = F, Float = F> + Gradient,
     P: Clone
         + Default
         + Serialize
@@ -94,6 +95,8 @@ where
         + ArgminScaledAdd<O::Param, O::Float, O::Param>
         + ArgminAdd<O::Param, O::Param>
         + ArgminMul<F, O::Param>
+        + ArgminMulAssign<F>
+        + ArgminSubAssign<O::Param>
         + ArgminDot<O::Param, O::Float>
         + ArgminNorm<O::Float>,
     O::Hessian: Default,
@@ -166,9 +169,8 @@ where
         }
 
         // Update of p
-        self.p = new_grad
-            .mul(&(F::from_f64(-1.0).unwrap()))
-            .add(&self.p.mul(&self.beta));
+        self.p.mul_assign(&self.beta);
+        self.p.sub_assign(&new_grad);
 
         // Housekeeping
         let cost = op.apply(&xk1)?;
@@ -0,0 +1,356 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Preconditioners for conjugate gradient type methods
+//!
+//! # References:
+//!
+//! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+//! Springer. ISBN 0-387-30303-0.
+//!
+//! [1] Yousef Saad (2003). Iterative Methods for Sparse Linear Systems. SIAM.
+//! ISBN 0-89871-534-2.
+
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A preconditioner `M` approximates the system matrix `A` such that `M^{-1} * r` is cheap to
+/// compute. For `ConjugateGradient`, `NewtonCG` and `Steihaug` it must be symmetric and positive
+/// definite.
+pub trait ArgminPreconditioner<P> {
+    /// Compute `M^{-1} * r`
+    fn precondition(&self, r: &P) -> Result<P, Error>;
+
+    /// Compute `M * p`. This is needed to measure steps in the norm `||p||_M = sqrt(p^T M p)`.
+    fn mul_vec(&self, p: &P) -> Result<P, Error>;
+}
+
+/// The identity preconditioner `M = I`. Using it is equivalent to not preconditioning at all.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct IdentityPreconditioner;
+
+impl<P: Clone> ArgminPreconditioner<P> for IdentityPreconditioner {
+    fn precondition(&self, r: &P) -> Result<P, Error> {
+        Ok(r.clone())
+    }
+
+    fn mul_vec(&self, p: &P) -> Result<P, Error> {
+        Ok(p.clone())
+    }
+}
+
+/// The Jacobi (diagonal) preconditioner `M = diag(A)`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct JacobiPreconditioner<P> {
+    /// Diagonal of `A`
+    diag: P,
+}
+
+impl<P> JacobiPreconditioner<P> {
+    /// Constructor
+    ///
+    /// Parameters:
+    ///
+    /// `diag`: diagonal of `A`. All entries must be positive.
+    pub fn new(diag: P) -> Self {
+        JacobiPreconditioner { diag }
+    }
+}
+
+impl<P> ArgminPreconditioner<P> for JacobiPreconditioner<P>
+where
+    P: ArgminDiv<P, P> + ArgminMul<P, P>,
+{
+    fn precondition(&self, r: &P) -> Result<P, Error> {
+        Ok(r.div(&self.diag))
+    }
+
+    fn mul_vec(&self, p: &P) -> Result<P, Error> {
+        Ok(p.mul(&self.diag))
+    }
+}
+
+/// Incomplete Cholesky factorization without fill-in (IC(0)), `M = L * L^T`, where `L` has the
+/// same sparsity pattern as the lower triangle of `A` [1].
+///
+/// The factorization may break down even for positive definite `A`, in which case the constructor
+/// returns an error.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct IncompleteCholesky<F> {
+    /// Rows of `L` as `(column, value)` pairs sorted by column. The last entry of each row is the
+    /// diagonal.
+    l: Vec<Vec<(usize, F)>>,
+}
+
+impl<F: ArgminFloat> IncompleteCholesky<F> {
+    /// Compute the IC(0) factorization of a dense matrix. Only the lower triangle of `a` is used;
+    /// zero entries are not filled in.
+    pub fn new(a: &[Vec<F>]) -> Result<Self, Error> {
+        let n = a.len();
+        let mut lower = Vec::with_capacity(n);
+        for (i, row) in a.iter().enumerate() {
+            if row.len() != n {
+                return Err(ArgminError::InvalidParameter {
+                    text: "IncompleteCholesky: matrix must be square.".to_string(),
+                }
+                .into());
+            }
+            lower.push(
+                row.iter()
+                    .cloned()
+                    .enumerate()
+                    .take(i + 1)
+                    .filter(|&(j, a_ij)| j == i || a_ij != F::from_f64(0.0).unwrap())
+                    .collect(),
+            );
+        }
+        Self::factorize(lower)
+    }
+
+    /// Compute the IC(0) factorization of a `sprs` sparse matrix. Only the lower triangle of `a`
+    /// is used.
+    #[cfg(feature = "sprsl")]
+    pub fn from_sprs(a: &sprs::CsMat<F>) -> Result<Self, Error> {
+        let (n, m) = a.shape();
+        if n != m {
+            return Err(ArgminError::InvalidParameter {
+                text: "IncompleteCholesky: matrix must be square.".to_string(),
+            }
+            .into());
+        }
+        let mut lower: Vec<Vec<(usize, F)>> = vec![vec![]; n];
+        for (&a_ij, (i, j)) in a.iter() {
+            if j <= i {
+                lower[i].push((j, a_ij));
+            }
+        }
+        for row in lower.iter_mut() {
+            row.sort_by_key(|&(j, _)| j);
+        }
+        Self::factorize(lower)
+    }
+
+    /// IC(0) factorization of the lower triangle of `A`, given row-wise as `(column, value)` pairs
+    /// sorted by column
+    fn factorize(lower: Vec<Vec<(usize, F)>>) -> Result<Self, Error> {
+        let n = lower.len();
+        let mut l: Vec<Vec<(usize, F)>> = Vec::with_capacity(n);
+        for (i, row) in lower.into_iter().enumerate() {
+            let mut l_row: Vec<(usize, F)> = Vec::with_capacity(row.len());
+            let mut d = F::from_f64(0.0).unwrap();
+            for (j, a_ij) in row {
+                if j < i {
+                    let l_j = &l[j];
+                    let s = sparse_dot(&l_row, &l_j[..l_j.len() - 1]);
+                    l_row.push((j, (a_ij - s) / l_j[l_j.len() - 1].1));
+                } else {
+                    d = a_ij;
+                }
+            }
+            for &(_, l_ij) in l_row.iter() {
+                d = d - l_ij * l_ij;
+            }
+            if d.is_nan() || d <= F::from_f64(0.0).unwrap() {
+                return Err(ArgminError::InvalidParameter {
+                    text: "IncompleteCholesky: factorization broke down, matrix may not be \
+                           positive definite."
+                        .to_string(),
+                }
+                .into());
+            }
+            l_row.push((i, d.sqrt()));
+            l.push(l_row);
+        }
+        Ok(IncompleteCholesky { l })
+    }
+}
+
+/// Dot product of two sparse rows given as `(column, value)` pairs sorted by column
+fn sparse_dot<F: ArgminFloat>(a: &[(usize, F)], b: &[(usize, F)]) -> F {
+    let mut s = F::from_f64(0.0).unwrap();
+    let (mut ia, mut ib) = (0, 0);
+    while ia < a.len() && ib < b.len() {
+        match a[ia].0.cmp(&b[ib].0) {
+            std::cmp::Ordering::Less => ia += 1,
+            std::cmp::Ordering::Greater => ib += 1,
+            std::cmp::Ordering::Equal => {
+                s = s + a[ia].1 * b[ib].1;
+                ia += 1;
+                ib += 1;
+            }
+        }
+    }
+    s
+}
+
+impl<F: ArgminFloat> IncompleteCholesky<F> {
+    /// Checks that `v` has the dimension of the factorization
+    fn check_dim(&self, v: &[F]) -> Result<(), Error> {
+        if v.len() != self.l.len() {
+            return Err(ArgminError::InvalidParameter {
+                text: "IncompleteCholesky: dimensions of matrix and vector do not match."
+                    .to_string(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl<F: ArgminFloat> ArgminPreconditioner<Vec<F>> for IncompleteCholesky<F> {
+    fn precondition(&self, r: &Vec<F>) -> Result<Vec<F>, Error> {
+        self.check_dim(r)?;
+        // L y = r
+        let mut z = r.clone();
+        for (i, row) in self.l.iter().enumerate() {
+            let (&(_, l_ii), off_diag) = row.split_last().unwrap();
+            for &(k, l_ik) in off_diag {
+                z[i] = z[i] - l_ik * z[k];
+            }
+            z[i] = z[i] / l_ii;
+        }
+        // L^T z = y
+        for (i, row) in self.l.iter().enumerate().rev() {
+            let (&(_, l_ii), off_diag) = row.split_last().unwrap();
+            z[i] = z[i] / l_ii;
+            for &(k, l_ik) in off_diag {
+                z[k] = z[k] - l_ik * z[i];
+            }
+        }
+        Ok(z)
+    }
+
+    fn mul_vec(&self, p: &Vec<F>) -> Result<Vec<F>, Error> {
+        self.check_dim(p)?;
+        // y = L^T p
+        let mut y = vec![F::from_f64(0.0).unwrap(); p.len()];
+        for (i, row) in self.l.iter().enumerate() {
+            for &(k, l_ik) in row {
+                y[k] = y[k] + l_ik * p[i];
+            }
+        }
+        // L y
+        Ok(self
+            .l
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .fold(F::from_f64(0.0).unwrap(), |acc, &(k, l_ik)| {
+                        acc + l_ik * y[k]
+                    })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(identity_preconditioner, IdentityPreconditioner);
+
+    test_trait_impl!(jacobi_preconditioner, JacobiPreconditioner<Vec<f64>>);
+
+    test_trait_impl!(incomplete_cholesky, IncompleteCholesky<f64>);
+
+    #[test]
+    fn test_identity() {
+        let r = vec![1.0f64, -2.0, 3.0];
+        assert_eq!(IdentityPreconditioner.precondition(&r).unwrap(), r);
+        assert_eq!(IdentityPreconditioner.mul_vec(&r).unwrap(), r);
+    }
+
+    #[test]
+    fn test_jacobi() {
+        let m = JacobiPreconditioner::new(vec![2.0f64, 4.0, 0.5]);
+        let z = m.precondition(&vec![1.0f64, 2.0, 3.0]).unwrap();
+        assert_eq!(z, vec![0.5, 0.5, 6.0]);
+        assert_eq!(m.mul_vec(&z).unwrap(), vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_incomplete_cholesky_mul_vec() {
+        let a = vec![
+            vec![4.0f64, 1.0, 1.0],
+            vec![1.0, 4.0, 0.0],
+            vec![1.0, 0.0, 4.0],
+        ];
+        let ic = IncompleteCholesky::new(&a).unwrap();
+        let p = vec![1.0f64, -2.0, 3.0];
+        let z = ic.precondition(&ic.mul_vec(&p).unwrap()).unwrap();
+        for (z, p) in z.iter().zip(p.iter()) {
+            assert!((z - p).abs() < 1e-12);
+        }
+        assert!(ic.mul_vec(&vec![1.0]).is_err());
+    }
+
+    #[test]
+    fn test_incomplete_cholesky_dense_is_exact() {
+        // Without zeros in the lower triangle, IC(0) is the full Cholesky factorization.
+        let a = vec![
+            vec![4.0f64, 2.0, 0.4],
+            vec![2.0, 5.0, 1.0],
+            vec![0.4, 1.0, 3.0],
+        ];
+        let b = vec![1.0f64, 2.0, 3.0];
+        let x = IncompleteCholesky::new(&a)
+            .unwrap()
+            .precondition(&b)
+            .unwrap();
+        for (i, row) in a.iter().enumerate() {
+            let ax: f64 = row.iter().zip(x.iter()).map(|(a, x)| a * x).sum();
+            assert!((ax - b[i]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_incomplete_cholesky_no_fill_in() {
+        let a = vec![
+            vec![4.0f64, 1.0, 1.0],
+            vec![1.0, 4.0, 0.0],
+            vec![1.0, 0.0, 4.0],
+        ];
+        let ic = IncompleteCholesky::new(&a).unwrap();
+        assert_eq!(ic.l[2].len(), 2);
+        assert_eq!(ic.l[2][0].0, 0);
+        assert_eq!(ic.l[2][1].0, 2);
+    }
+
+    #[test]
+    fn test_incomplete_cholesky_breakdown() {
+        let a = vec![vec![1.0f64, 2.0], vec![2.0, 1.0]];
+        assert!(IncompleteCholesky::new(&a).is_err());
+    }
+
+    #[test]
+    fn test_incomplete_cholesky_non_square() {
+        let a = vec![vec![1.0f64, 2.0], vec![2.0]];
+        assert!(IncompleteCholesky::new(&a).is_err());
+    }
+
+    #[cfg(feature = "sprsl")]
+    #[test]
+    fn test_incomplete_cholesky_sprs() {
+        let a = vec![
+            vec![4.0f64, 1.0, 1.0],
+            vec![1.0, 4.0, 0.0],
+            vec![1.0, 0.0, 4.0],
+        ];
+        let mut tri = sprs::TriMat::new((3, 3));
+        for (i, row) in a.iter().enumerate() {
+            for (j, &v) in row.iter().enumerate() {
+                if v != 0.0 {
+                    tri.add_triplet(i, j, v);
+                }
+            }
+        }
+        let sparse = IncompleteCholesky::from_sprs(&tri.to_csr()).unwrap();
+        let dense = IncompleteCholesky::new(&a).unwrap();
+        assert_eq!(sparse, dense);
+    }
+}
@@ -0,0 +1,728 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Coordinate descent
+//!
+//! [CoordinateDescent](struct.CoordinateDescent.html)
+//!
+//! # References:
+//!
+//! [0] Stephen J. Wright (2015). Coordinate descent algorithms. Mathematical Programming 151,
+//! 3-34.
+//!
+//! [1] Jerome Friedman, Trevor Hastie and Rob Tibshirani (2010). Regularization Paths for
+//! Generalized Linear Models via Coordinate Descent. Journal of Statistical Software 33(1), 1-22.
+//!
+//! [2] Julie Nutini, Mark Schmidt, Issam H. Laradji, Michael Friedlander and Hoyt Koepke (2015).
+//! Coordinate Descent Converges Faster with the Gauss-Southwell Rule Than Random Selection.
+//! Proceedings of the 32nd International Conference on Machine Learning, 1632-1641.
+
+use crate::core::capabilities::{CostFunction, Gradient};
+use crate::prelude::*;
+use crate::solver::brent::line_minimum;
+use rand::prelude::*;
+use rand_xorshift::XorShiftRng;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Rule which selects the block updated in an iteration of `CoordinateDescent`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CoordinateSelection {
+    /// Go through the blocks in their order
+    Cyclic,
+    /// Draw a block uniformly at random
+    Random,
+    /// Choose the block with the largest (minimum norm sub-)gradient
+    GaussSouthwell,
+}
+
+/// Update of the coordinates in a block of `CoordinateDescent`
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CoordinateStep<F> {
+    /// Minimize the objective along each coordinate of the block with `BrentOpt`
+    Exact,
+    /// Partial gradient step with the given step length, followed by soft-thresholding if an L1
+    /// penalty is used
+    Gradient(F),
+}
+
+/// Gradient of the operator used by `CoordinateDescent` for gradient steps and Gauss-Southwell
+/// selection
+///
+/// `CostOnly` (the default) never computes the gradient and works with any operator. With
+/// `WithGradient` (see `CoordinateDescent::with_gradient`), the gradient is computed by
+/// `Gradient::gradient`.
+pub trait CoordinateGradient<O: CostFunction> {
+    /// Whether the gradient is available
+    fn available(&self) -> bool;
+
+    /// Computes the gradient at `param`
+    fn gradient(&self, op: &mut OpWrapper<O>, param: &O::Param) -> Result<O::Param, Error>;
+}
+
+/// The gradient of the operator is not used
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostOnly;
+
+impl<O: CostFunction> CoordinateGradient<O> for CostOnly {
+    fn available(&self) -> bool {
+        false
+    }
+
+    fn gradient(&self, _op: &mut OpWrapper<O>, _param: &O::Param) -> Result<O::Param, Error> {
+        Err(ArgminError::NotImplemented {
+            text: "CoordinateDescent: gradient requires `with_gradient`.".to_string(),
+        }
+        .into())
+    }
+}
+
+/// The gradient is computed by `Gradient::gradient`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct WithGradient;
+
+impl<O: Gradient> CoordinateGradient<O> for WithGradient {
+    fn available(&self) -> bool {
+        true
+    }
+
+    fn gradient(&self, op: &mut OpWrapper<O>, param: &O::Param) -> Result<O::Param, Error> {
+        op.gradient(param)
+    }
+}
+
+/// Coordinate descent
+///
+/// Minimizes `f(x) + lambda * ||x||_1` by updating one block of coordinates per iteration while
+/// keeping all others fixed. The parameter vector must implement `ArgminComponents`, which is
+/// the case for `Vec<f64>`. By default, every coordinate forms its own block; arbitrary blocks
+/// can be given with `with_blocks`.
+///
+/// The block is chosen according to `with_selection` (default: `CoordinateSelection::Cyclic`).
+/// `CoordinateSelection::Random` draws blocks from a random number generator which can be seeded
+/// with `with_seed`. `CoordinateSelection::GaussSouthwell` picks the block with the largest norm
+/// of the minimum norm subgradient [2].
+///
+/// The coordinates of the block are updated according to `with_step` (default:
+/// `CoordinateStep::Exact`). Exact steps bracket the minimum along each coordinate with
+/// `bracket_minimum` and locate it with `BrentOpt`. With an L1 penalty, zero is accepted instead
+/// if it is at least as good, which keeps the solution sparse. `CoordinateStep::Gradient(alpha)`
+/// performs the proximal gradient step
+///
+/// `x_i = S(x_i - alpha * df/dx_i, alpha * lambda)`
+///
+/// on the coordinates of the block, where `S` is the soft-thresholding operator [1]. `alpha`
+/// should not exceed the inverse of the coordinate-wise Lipschitz constants.
+///
+/// The gradient is only needed for gradient steps and Gauss-Southwell selection, which require
+/// `with_gradient` and an operator implementing `Gradient`. Exact steps with cyclic or random
+/// selection work with operators which only provide a cost function.
+///
+/// The solver terminates with `TerminationReason::StepToleranceReached` if no coordinate
+/// changed by more than `tol` (default: `sqrt(EPSILON)`) during the last sweep, which ends once
+/// every block was updated at least once. With Gauss-Southwell selection, only the last update is
+/// considered since blocks with small gradients may never be selected.
+///
+/// # References:
+///
+/// [0] Stephen J. Wright (2015). Coordinate descent algorithms. Mathematical Programming 151,
+/// 3-34.
+///
+/// [1] Jerome Friedman, Trevor Hastie and Rob Tibshirani (2010). Regularization Paths for
+/// Generalized Linear Models via Coordinate Descent. Journal of Statistical Software 33(1), 1-22.
+///
+/// [2] Julie Nutini, Mark Schmidt, Issam H. Laradji, Michael Friedlander and Hoyt Koepke (2015).
+/// Coordinate Descent Converges Faster with the Gauss-Southwell Rule Than Random Selection.
+/// Proceedings of the 32nd International Conference on Machine Learning, 1632-1641.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CoordinateDescent<F, G = CostOnly> {
+    /// gradient of the operator
+    gradient: G,
+    /// block selection rule
+    selection: CoordinateSelection,
+    /// coordinate update
+    step: CoordinateStep<F>,
+    /// weight of the L1 penalty
+    lambda: F,
+    /// step tolerance
+    tol: F,
+    /// blocks of coordinates
+    blocks: Vec<Vec<usize>>,
+    /// position of the next block for cyclic selection
+    next_block: usize,
+    /// blocks updated in the current sweep
+    visited: Vec<bool>,
+    /// number of blocks not yet updated in the current sweep
+    unvisited: usize,
+    /// largest change of a coordinate in the current sweep
+    sweep_change: F,
+    /// largest change of a coordinate in the last completed sweep
+    last_sweep_change: F,
+    /// largest change of a coordinate in the last iteration
+    last_change: F,
+    /// random number generator
+    rng: XorShiftRng,
+}
+
+impl<F: ArgminFloat> CoordinateDescent<F> {
+    /// Constructor
+    pub fn new() -> Self {
+        CoordinateDescent {
+            gradient: CostOnly,
+            selection: CoordinateSelection::Cyclic,
+            step: CoordinateStep::Exact,
+            lambda: F::from_f64(0.0).unwrap(),
+            tol: F::epsilon().sqrt(),
+            blocks: vec![],
+            next_block: 0,
+            visited: vec![],
+            unvisited: 0,
+            sweep_change: F::from_f64(0.0).unwrap(),
+            last_sweep_change: F::infinity(),
+            last_change: F::infinity(),
+            rng: XorShiftRng::from_entropy(),
+        }
+    }
+
+    /// Use the gradient of the operator, which is required for gradient steps and
+    /// Gauss-Southwell selection
+    pub fn with_gradient(self) -> CoordinateDescent<F, WithGradient> {
+        CoordinateDescent {
+            gradient: WithGradient,
+            selection: self.selection,
+            step: self.step,
+            lambda: self.lambda,
+            tol: self.tol,
+            blocks: self.blocks,
+            next_block: self.next_block,
+            visited: self.visited,
+            unvisited: self.unvisited,
+            sweep_change: self.sweep_change,
+            last_sweep_change: self.last_sweep_change,
+            last_change: self.last_change,
+            rng: self.rng,
+        }
+    }
+}
+
+impl<F: ArgminFloat, G> CoordinateDescent<F, G> {
+    /// Set block selection rule
+    pub fn with_selection(mut self, selection: CoordinateSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Set coordinate update
+    pub fn with_step(mut self, step: CoordinateStep<F>) -> Result<Self, Error> {
+        if let CoordinateStep::Gradient(alpha) = step {
+            if alpha <= F::from_f64(0.0).unwrap() || !alpha.is_finite() {
+                return Err(ArgminError::InvalidParameter {
+                    text: "CoordinateDescent: step length must be positive and finite.".to_string(),
+                }
+                .into());
+            }
+        }
+        self.step = step;
+        Ok(self)
+    }
+
+    /// Set weight `lambda` of the L1 penalty `lambda * ||x||_1`
+    pub fn with_l1(mut self, lambda: F) -> Result<Self, Error> {
+        if lambda < F::from_f64(0.0).unwrap() || !lambda.is_finite() {
+            return Err(ArgminError::InvalidParameter {
+                text: "CoordinateDescent: lambda must be non-negative and finite.".to_string(),
+            }
+            .into());
+        }
+        self.lambda = lambda;
+        Ok(self)
+    }
+
+    /// Set the blocks of coordinates. Every block must be non-empty and no coordinate may appear
+    /// more than once. Coordinates which are not part of any block are never changed.
+    pub fn with_blocks(mut self, blocks: Vec<Vec<usize>>) -> Result<Self, Error> {
+        let mut seen = std::collections::HashSet::new();
+        if blocks.is_empty()
+            || blocks.iter().any(|b| b.is_empty())
+            || !blocks.iter().flatten().all(|i| seen.insert(*i))
+        {
+            return Err(ArgminError::InvalidParameter {
+                text: "CoordinateDescent: blocks must be non-empty and must not overlap."
+                    .to_string(),
+            }
+            .into());
+        }
+        self.blocks = blocks;
+        Ok(self)
+    }
+
+    /// Seed the random number generator
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = XorShiftRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Set step tolerance
+    pub fn with_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "CoordinateDescent: tol must be positive.".to_string(),
+            }
+            .into());
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Whether the gradient is needed by the coordinate update or the block selection rule
+    fn needs_gradient(&self) -> bool {
+        matches!(self.step, CoordinateStep::Gradient(_))
+            || self.selection == CoordinateSelection::GaussSouthwell
+    }
+
+    /// Norm of the minimum norm subgradient of `f + lambda * ||.||_1` restricted to a block
+    fn block_score<P: ArgminComponents<F>>(&self, block: &[usize], x: &P, grad: &P) -> F {
+        let zero = F::from_f64(0.0).unwrap();
+        block
+            .iter()
+            .map(|&i| {
+                let g = grad.component(i);
+                let xi = x.component(i);
+                let s = if xi != zero {
+                    g + self.lambda.copysign(xi)
+                } else {
+                    (g.abs() - self.lambda).max(zero)
+                };
+                s * s
+            })
+            .fold(zero, |acc, s| acc + s)
+            .sqrt()
+    }
+
+    /// Index of the block updated next
+    fn select_block<P: ArgminComponents<F>>(&mut self, x: &P, grad: Option<&P>) -> usize {
+        match self.selection {
+            CoordinateSelection::Cyclic => {
+                let k = self.next_block;
+                self.next_block = (k + 1) % self.blocks.len();
+                k
+            }
+            CoordinateSelection::Random => self.rng.gen_range(0..self.blocks.len()),
+            CoordinateSelection::GaussSouthwell => {
+                let grad = grad.unwrap();
+                let mut best = (0, F::neg_infinity());
+                for (k, block) in self.blocks.iter().enumerate() {
+                    let score = self.block_score(block, x, grad);
+                    if score > best.1 {
+                        best = (k, score);
+                    }
+                }
+                best.0
+            }
+        }
+    }
+
+    /// Cost function value including the L1 penalty
+    fn cost<O, P>(&self, op: &mut OpWrapper<O>, x: &P) -> Result<F, Error>
+    where
+        O: CostFunction<Param = P, Output = F, Float = F>,
+        P: ArgminComponents<F>,
+    {
+        let zero = F::from_f64(0.0).unwrap();
+        let mut cost = op.apply(x)?;
+        if self.lambda > zero {
+            let l1 = (0..x.num_components()).fold(zero, |acc, i| acc + x.component(i).abs());
+            cost = cost + self.lambda * l1;
+        }
+        Ok(cost)
+    }
+}
+
+impl<F: ArgminFloat> Default for CoordinateDescent<F> {
+    fn default() -> Self {
+        CoordinateDescent::new()
+    }
+}
+
+/// Minimizes `f(x) + lambda * |x_i|` along coordinate `i` and returns the new value of `x_i`
+fn exact_coordinate_step<O, P, F>(
+    op: &mut OpWrapper<O>,
+    x: &P,
+    i: usize,
+    lambda: F,
+) -> Result<F, Error>
+where
+    O: CostFunction<Param = P, Output = F, Float = F>,
+    P: Clone + Serialize + DeserializeOwned + ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    let zero = F::from_f64(0.0).unwrap();
+    let along = |op: &O, t: F| {
+        let mut x = x.clone();
+        x.set_component(i, t);
+        Ok(op.apply(&x)? + lambda * t.abs())
+    };
+    let xi = x.component(i);
+    let step = F::from_f64(0.1).unwrap() * xi.abs().max(F::from_f64(1.0).unwrap());
+    let (t, cost) = line_minimum(op, along, xi, step, (F::neg_infinity(), F::infinity()))?;
+    // the nonsmooth minimum of the L1 penalty at zero is only approached by BrentOpt
+    if lambda > zero && t != zero {
+        let mut x0 = x.clone();
+        x0.set_component(i, zero);
+        if op.apply(&x0)? <= cost {
+            return Ok(zero);
+        }
+    }
+    Ok(t)
+}
+
+impl<O, P, F, G> Solver<O> for CoordinateDescent<F, G>
+where
+    O: CostFunction<Param = P, Output = F, Float = F>,
+    P: Clone + Serialize + DeserializeOwned + ArgminComponents<F>,
+    F: ArgminFloat,
+    G: CoordinateGradient<O> + Clone + Serialize + DeserializeOwned,
+{
+    const NAME: &'static str = "Coordinate Descent";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        let n = param.num_components();
+        if self.blocks.is_empty() {
+            self.blocks = (0..n).map(|i| vec![i]).collect();
+        }
+        if self.blocks.is_empty() || self.blocks.iter().flatten().any(|&i| i >= n) {
+            return Err(ArgminError::InvalidParameter {
+                text: "CoordinateDescent: blocks do not match the parameter vector.".to_string(),
+            }
+            .into());
+        }
+        if self.needs_gradient() && !self.gradient.available() {
+            return Err(ArgminError::InvalidParameter {
+                text: "CoordinateDescent: gradient steps and Gauss-Southwell selection require \
+                       `with_gradient`."
+                    .to_string(),
+            }
+            .into());
+        }
+        self.next_block = 0;
+        self.visited = vec![false; self.blocks.len()];
+        self.unvisited = self.blocks.len();
+        self.sweep_change = F::from_f64(0.0).unwrap();
+        self.last_sweep_change = F::infinity();
+        self.last_change = F::infinity();
+        let cost = self.cost(op, &param)?;
+        Ok(Some(ArgminIterData::new().param(param).cost(cost)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let zero = F::from_f64(0.0).unwrap();
+        let mut x = state.get_param();
+        let grad = if self.needs_gradient() {
+            Some(self.gradient.gradient(op, &x)?)
+        } else {
+            None
+        };
+        let k = self.select_block(&x, grad.as_ref());
+        let block = self.blocks[k].clone();
+
+        let mut change = zero;
+        for &i in block.iter() {
+            let xi = x.component(i);
+            let new_xi = match self.step {
+                CoordinateStep::Exact => exact_coordinate_step(op, &x, i, self.lambda)?,
+                CoordinateStep::Gradient(alpha) => {
+                    let v = xi - alpha * grad.as_ref().unwrap().component(i);
+                    let thresh = alpha * self.lambda;
+                    (v.abs() - thresh).max(zero).copysign(v)
+                }
+            };
+            change = change.max((new_xi - xi).abs());
+            x.set_component(i, new_xi);
+        }
+
+        // a sweep is completed once every block was updated at least once
+        self.last_change = change;
+        self.sweep_change = self.sweep_change.max(change);
+        if !self.visited[k] {
+            self.visited[k] = true;
+            self.unvisited -= 1;
+        }
+        if self.unvisited == 0 {
+            self.last_sweep_change = self.sweep_change;
+            self.sweep_change = zero;
+            self.visited.iter_mut().for_each(|v| *v = false);
+            self.unvisited = self.blocks.len();
+        }
+
+        let cost = self.cost(op, &x)?;
+        Ok(ArgminIterData::new()
+            .param(x)
+            .cost(cost)
+            .kv(make_kv!("block" => k; "change" => change;)))
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason {
+        let change = match self.selection {
+            // the greedy choice has the largest potential for progress
+            CoordinateSelection::GaussSouthwell => self.last_change,
+            _ => self.last_sweep_change,
+        };
+        if change <= self.tol {
+            return TerminationReason::StepToleranceReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(coordinate_descent, CoordinateDescent<f64>);
+    test_trait_impl!(
+        coordinate_descent_gradient,
+        CoordinateDescent<f64, WithGradient>
+    );
+
+    /// `f(x) = 1/2 x^T A x - b^T x` with a symmetric positive definite `A`
+    fn quadratic() -> FnOp<Vec<f64>, f64, f64, FnOpFunc<Vec<f64>, Vec<f64>>> {
+        fn ax(x: &[f64]) -> Vec<f64> {
+            vec![
+                4.0 * x[0] + x[1],
+                x[0] + 3.0 * x[1] - x[2],
+                -x[1] + 2.0 * x[2],
+            ]
+        }
+        let b = vec![1.0, 2.0, -1.0];
+        let b2 = b.clone();
+        FnOp::new(move |x: &Vec<f64>| {
+            let y = ax(x);
+            Ok((0..3).map(|i| 0.5 * x[i] * y[i] - b[i] * x[i]).sum())
+        })
+        .gradient(move |x: &Vec<f64>| {
+            let y = ax(x);
+            Ok((0..3).map(|i| y[i] - b2[i]).collect())
+        })
+    }
+
+    /// Solution of `A x = b` of `quadratic`
+    const QUADRATIC_SOLUTION: [f64; 3] = [2.0 / 18.0, 10.0 / 18.0, -4.0 / 18.0];
+
+    fn check_quadratic<G>(solver: CoordinateDescent<f64, G>)
+    where
+        G: CoordinateGradient<FnOp<Vec<f64>, f64, f64, FnOpFunc<Vec<f64>, Vec<f64>>>>
+            + Clone
+            + Serialize
+            + DeserializeOwned,
+    {
+        let res = Executor::new(quadratic(), solver, vec![2.0, -1.0, 3.0])
+            .max_iters(1000)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::StepToleranceReached
+        );
+        let x = res.state.get_best_param();
+        for i in 0..3 {
+            assert!((x[i] - QUADRATIC_SOLUTION[i]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_exact_steps() {
+        check_quadratic(CoordinateDescent::new());
+        check_quadratic(
+            CoordinateDescent::new()
+                .with_selection(CoordinateSelection::Random)
+                .with_seed(7),
+        );
+        check_quadratic(
+            CoordinateDescent::new()
+                .with_gradient()
+                .with_selection(CoordinateSelection::GaussSouthwell),
+        );
+    }
+
+    #[test]
+    fn test_gradient_steps() {
+        for selection in [
+            CoordinateSelection::Cyclic,
+            CoordinateSelection::Random,
+            CoordinateSelection::GaussSouthwell,
+        ]
+        .iter()
+        {
+            check_quadratic(
+                CoordinateDescent::new()
+                    .with_gradient()
+                    .with_selection(*selection)
+                    .with_step(CoordinateStep::Gradient(0.25))
+                    .unwrap()
+                    .with_seed(3),
+            );
+        }
+    }
+
+    #[test]
+    fn test_blocks() {
+        check_quadratic(
+            CoordinateDescent::new()
+                .with_blocks(vec![vec![0, 2], vec![1]])
+                .unwrap(),
+        );
+        check_quadratic(
+            CoordinateDescent::new()
+                .with_gradient()
+                .with_blocks(vec![vec![2, 1], vec![0]])
+                .unwrap()
+                .with_step(CoordinateStep::Gradient(0.2))
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_lasso() {
+        // 1/2 ||x - c||^2 + lambda ||x||_1 is solved by soft-thresholding c
+        let c = vec![3.0, -0.5, 0.2, -2.0];
+        let lambda = 1.0;
+        let expected: Vec<f64> = c
+            .iter()
+            .map(|ci: &f64| (ci.abs() - lambda).max(0.0).copysign(*ci))
+            .collect();
+        let (c1, c2) = (c.clone(), c.clone());
+        let op = FnOp::new(move |x: &Vec<f64>| {
+            Ok(x.iter()
+                .zip(c1.iter())
+                .map(|(xi, ci)| 0.5 * (xi - ci).powi(2))
+                .sum())
+        })
+        .gradient(move |x: &Vec<f64>| {
+            Ok(x.iter().zip(c2.iter()).map(|(xi, ci)| xi - ci).collect())
+        });
+        for step in [CoordinateStep::Exact, CoordinateStep::Gradient(1.0)].iter() {
+            let solver = CoordinateDescent::new()
+                .with_gradient()
+                .with_step(*step)
+                .unwrap()
+                .with_l1(lambda)
+                .unwrap();
+            let res = Executor::new(op.clone(), solver, vec![1.0; 4])
+                .max_iters(100)
+                .run()
+                .unwrap();
+            let x = res.state.get_best_param();
+            for i in 0..4 {
+                assert!((x[i] - expected[i]).abs() < 1e-6);
+            }
+            // coordinates which are thresholded to zero are exactly zero
+            assert_eq!(x[1].abs().to_bits(), 0.0f64.to_bits());
+            assert_eq!(x[2].abs().to_bits(), 0.0f64.to_bits());
+        }
+    }
+
+    /// Operator which only implements `CostFunction`
+    #[derive(Clone, Serialize, Deserialize)]
+    struct CostOnlyOp {}
+
+    impl CostFunction for CostOnlyOp {
+        type Param = Vec<f64>;
+        type Output = f64;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, x: &Vec<f64>) -> Result<f64, Error> {
+            Ok((x[0] - 1.0).powi(2) + (x[1] + x[0]).powi(2))
+        }
+    }
+
+    #[test]
+    fn test_cost_function_only() {
+        // exact steps with cyclic or random selection never compute the gradient
+        for selection in [CoordinateSelection::Cyclic, CoordinateSelection::Random].iter() {
+            let solver = CoordinateDescent::new()
+                .with_selection(*selection)
+                .with_seed(5);
+            let res = Executor::new(CostOnlyOp {}, solver, vec![0.0, 0.0])
+                .max_iters(1000)
+                .run()
+                .unwrap();
+            let x = res.state.get_best_param();
+            assert!((x[0] - 1.0).abs() < 1e-5);
+            assert!((x[1] + 1.0).abs() < 1e-5);
+            assert_eq!(res.state.get_grad_func_count(), 0);
+            assert!(res.state.get_cost_func_count() > 0);
+        }
+        // gradient steps and Gauss-Southwell selection require the gradient
+        let solver = CoordinateDescent::new()
+            .with_step(CoordinateStep::Gradient(0.1))
+            .unwrap();
+        assert!(Executor::new(CostOnlyOp {}, solver, vec![0.0, 0.0])
+            .max_iters(10)
+            .run()
+            .is_err());
+        let solver = CoordinateDescent::new().with_selection(CoordinateSelection::GaussSouthwell);
+        assert!(Executor::new(CostOnlyOp {}, solver, vec![0.0, 0.0])
+            .max_iters(10)
+            .run()
+            .is_err());
+    }
+
+    #[test]
+    fn test_seeded_runs_are_reproducible() {
+        let run = || {
+            let solver = CoordinateDescent::new()
+                .with_gradient()
+                .with_selection(CoordinateSelection::Random)
+                .with_step(CoordinateStep::Gradient(0.25))
+                .unwrap()
+                .with_seed(42);
+            Executor::new(quadratic(), solver, vec![2.0, -1.0, 3.0])
+                .max_iters(10)
+                .run()
+                .unwrap()
+                .state
+                .get_best_param()
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let solver: CoordinateDescent<f64> = CoordinateDescent::new();
+        assert!(solver
+            .clone()
+            .with_step(CoordinateStep::Gradient(0.0))
+            .is_err());
+        assert!(solver.clone().with_l1(-1.0).is_err());
+        assert!(solver.clone().with_tol(0.0).is_err());
+        assert!(solver.clone().with_blocks(vec![]).is_err());
+        assert!(solver.clone().with_blocks(vec![vec![0], vec![]]).is_err());
+        assert!(solver
+            .clone()
+            .with_blocks(vec![vec![0, 1], vec![1]])
+            .is_err());
+        let res = Executor::new(
+            quadratic(),
+            solver.with_blocks(vec![vec![0], vec![3]]).unwrap(),
+            vec![0.0; 3],
+        )
+        .max_iters(10)
+        .run();
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,296 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Robert Hooke and T. A. Jeeves (1961). "Direct Search" Solution of Numerical and
+//! Statistical Problems. Journal of the ACM 8(2), 212-229.
+
+use super::{clip, eval_component, init_param};
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::bounds::check_bounds;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Hooke-Jeeves method
+///
+/// Alternates exploratory moves and pattern moves [0]. An exploratory move changes each
+/// coordinate in turn by `+delta` or, if that does not decrease the cost function value, by
+/// `-delta`, and keeps every change which improves the cost function value. If the exploratory
+/// move around the current base point is successful, the result becomes the new base point and
+/// the next exploratory move starts from the pattern point `2 * x_new - x_old`, which extrapolates
+/// the last successful step. If an exploration around a pattern point fails, the method falls
+/// back to exploring around the base point. If that fails as well, the mesh size `delta` is
+/// multiplied by `contraction` (default: 0.5).
+///
+/// The initial mesh size is set with `with_initial_step` (default: 1). Bounds can be given with
+/// `with_bounds`; trial points are projected onto them.
+///
+/// The method only evaluates the cost function and terminates with
+/// `TerminationReason::StepToleranceReached` once the mesh size falls below `tol` (default:
+/// `sqrt(EPSILON)`).
+///
+/// # References:
+///
+/// [0] Robert Hooke and T. A. Jeeves (1961). "Direct Search" Solution of Numerical and
+/// Statistical Problems. Journal of the ACM 8(2), 212-229.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HookeJeeves<P, F> {
+    /// mesh size
+    delta: F,
+    /// factor by which the mesh size is reduced
+    contraction: F,
+    /// tolerance for the mesh size
+    tol: F,
+    /// lower and upper bounds
+    bounds: Option<(P, P)>,
+    /// previous base point, if the last iteration was successful
+    prev_base: Option<P>,
+}
+
+impl<P, F> HookeJeeves<P, F>
+where
+    P: ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    /// Constructor
+    pub fn new() -> Self {
+        HookeJeeves {
+            delta: F::from_f64(1.0).unwrap(),
+            contraction: F::from_f64(0.5).unwrap(),
+            tol: F::epsilon().sqrt(),
+            bounds: None,
+            prev_base: None,
+        }
+    }
+
+    /// Set initial mesh size
+    pub fn with_initial_step(mut self, delta: F) -> Result<Self, Error> {
+        if delta <= F::from_f64(0.0).unwrap() || !delta.is_finite() {
+            return Err(ArgminError::InvalidParameter {
+                text: "HookeJeeves: initial step must be positive and finite.".to_string(),
+            }
+            .into());
+        }
+        self.delta = delta;
+        Ok(self)
+    }
+
+    /// Set factor by which the mesh size is reduced. Must be in `(0, 1)`.
+    pub fn with_contraction(mut self, contraction: F) -> Result<Self, Error> {
+        if contraction <= F::from_f64(0.0).unwrap() || contraction >= F::from_f64(1.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "HookeJeeves: contraction must be in (0, 1).".to_string(),
+            }
+            .into());
+        }
+        self.contraction = contraction;
+        Ok(self)
+    }
+
+    /// Set tolerance for the mesh size
+    pub fn with_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "HookeJeeves: tol must be positive.".to_string(),
+            }
+            .into());
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set lower and upper bounds
+    pub fn with_bounds(mut self, lower: P, upper: P) -> Result<Self, Error> {
+        check_bounds("HookeJeeves", &lower, &upper)?;
+        self.bounds = Some((lower, upper));
+        Ok(self)
+    }
+}
+
+impl<P, F> Default for HookeJeeves<P, F>
+where
+    P: ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        HookeJeeves::new()
+    }
+}
+
+impl<P, F> HookeJeeves<P, F>
+where
+    P: Clone + ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    /// Exploratory move around `x`
+    fn explore<O>(&self, op: &mut OpWrapper<O>, mut x: P, mut fx: F) -> Result<(P, F), Error>
+    where
+        O: CostFunction<Param = P, Output = F, Float = F>,
+    {
+        for i in 0..x.num_components() {
+            let xi = x.component(i);
+            for value in [xi + self.delta, xi - self.delta].iter() {
+                if let Some((y, fy)) = eval_component(op, &self.bounds, &x, i, *value)? {
+                    if fy < fx {
+                        x = y;
+                        fx = fy;
+                        break;
+                    }
+                }
+            }
+        }
+        Ok((x, fx))
+    }
+}
+
+impl<O, P, F> Solver<O> for HookeJeeves<P, F>
+where
+    O: CostFunction<Param = P, Output = F, Float = F>,
+    P: Clone
+        + Serialize
+        + DeserializeOwned
+        + ArgminComponents<F>
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminMinMax
+        + ArgminRandom,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Hooke-Jeeves";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = init_param("HookeJeeves", &self.bounds, state.get_param())?;
+        self.prev_base = None;
+        let cost = op.apply(&param)?;
+        Ok(Some(ArgminIterData::new().param(param).cost(cost)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let base = state.get_param();
+        let f_base = state.get_cost();
+
+        let pattern_move = self.prev_base.is_some();
+        let (start, f_start) = match self.prev_base.take() {
+            Some(prev) => {
+                let mut x = base.clone();
+                for i in 0..x.num_components() {
+                    let two = F::from_f64(2.0).unwrap();
+                    x.set_component(i, two * base.component(i) - prev.component(i));
+                }
+                let x = clip(&self.bounds, x);
+                let fx = op.apply(&x)?;
+                (x, fx)
+            }
+            None => (base.clone(), f_base),
+        };
+
+        let (x, fx) = self.explore(op, start, f_start)?;
+        let (x, fx) = if fx < f_base {
+            self.prev_base = Some(base);
+            (x, fx)
+        } else {
+            // a failed exploration around a pattern point is retried around the base point in
+            // the next iteration
+            if !pattern_move {
+                self.delta = self.delta * self.contraction;
+            }
+            (base, f_base)
+        };
+
+        Ok(ArgminIterData::new()
+            .param(x)
+            .cost(fx)
+            .kv(make_kv!("mesh_size" => self.delta; "pattern_move" => pattern_move;)))
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason {
+        if self.delta < self.tol {
+            return TerminationReason::StepToleranceReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+    use argmin_testfunctions::rosenbrock_2d;
+
+    test_trait_impl!(hooke_jeeves, HookeJeeves<Vec<f64>, f64>);
+
+    #[test]
+    fn test_rosenbrock() {
+        let op = FnOp::new(|x: &Vec<f64>| Ok(rosenbrock_2d(x, 1.0, 100.0)));
+        let res = Executor::new(op, HookeJeeves::new(), vec![-1.2, 1.0])
+            .max_iters(10_000)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::StepToleranceReached
+        );
+        let x = res.state.get_best_param();
+        assert!((x[0] - 1.0).abs() < 1e-4);
+        assert!((x[1] - 1.0).abs() < 1e-4);
+        assert_eq!(res.state.get_grad_func_count(), 0);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let solver = HookeJeeves::new()
+            .with_bounds(vec![-2.0, -2.0], vec![0.5, 2.0])
+            .unwrap();
+        let op = FnOp::new(|x: &Vec<f64>| Ok(rosenbrock_2d(x, 1.0, 100.0)));
+        let res = Executor::new(op, solver, vec![-1.2, 1.0])
+            .max_iters(10_000)
+            .run()
+            .unwrap();
+        let x = res.state.get_best_param();
+        assert!((x[0] - 0.5).abs() < 1e-6);
+        assert!((x[1] - 0.25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_mesh_size() {
+        // the minimum lies on the initial mesh, which therefore never needs to be refined more
+        // than required by the tolerance
+        let op = FnOp::new(|x: &Vec<f64>| Ok((x[0] - 3.0).abs() + (x[1] + 2.0).abs()));
+        let solver = HookeJeeves::new().with_tol(0.1).unwrap();
+        let res = Executor::new(op, solver, vec![0.0, 0.0])
+            .max_iters(1000)
+            .run()
+            .unwrap();
+        assert_eq!(res.state.get_best_param(), vec![3.0, -2.0]);
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::StepToleranceReached
+        );
+        assert!(res.state.get_iter() < 20);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let solver: HookeJeeves<Vec<f64>, f64> = HookeJeeves::new();
+        assert!(solver.clone().with_initial_step(-1.0).is_err());
+        assert!(solver.clone().with_contraction(1.0).is_err());
+        assert!(solver.clone().with_tol(0.0).is_err());
+        assert!(solver
+            .clone()
+            .with_bounds(vec![0.0], vec![0.0, 1.0])
+            .is_err());
+    }
+}
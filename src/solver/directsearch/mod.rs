@@ -0,0 +1,109 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Direct search methods
+//!
+//! Derivative-free local solvers which only evaluate the cost function. They are deterministic
+//! and support bound constraints: trial points outside of the bounds are projected onto them.
+//!
+//! * [Powell's conjugate direction method](powell/struct.Powell.html)
+//! * [Hooke-Jeeves method](hookejeeves/struct.HookeJeeves.html)
+//! * [Generalized pattern search](patternsearch/struct.PatternSearch.html)
+//!
+//! # References:
+//!
+//! [0] Tamara G. Kolda, Robert Michael Lewis and Virginia Torczon (2003). Optimization by Direct
+//! Search: New Perspectives on Some Classical and Modern Methods. SIAM Review 45(3), 385-482.
+
+/// Hooke-Jeeves method
+pub mod hookejeeves;
+/// Generalized pattern search
+pub mod patternsearch;
+/// Powell's conjugate direction method
+pub mod powell;
+
+pub use self::hookejeeves::*;
+pub use self::patternsearch::*;
+pub use self::powell::*;
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::bounds::{apply_bounds, check_bounds_dim, BoundHandling};
+
+/// Clips `x` to the bounds, if any
+fn clip<P, F>(bounds: &Option<(P, P)>, x: P) -> P
+where
+    P: ArgminSub<P, P> + ArgminMul<F, P> + ArgminMinMax + ArgminRandom + ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    match bounds {
+        Some((lower, upper)) => apply_bounds(x, lower, upper, BoundHandling::Clip),
+        None => x,
+    }
+}
+
+/// Checks that the bounds match the parameter vector and clips it to them
+fn init_param<P, F>(name: &str, bounds: &Option<(P, P)>, x: P) -> Result<P, Error>
+where
+    P: ArgminSub<P, P> + ArgminMul<F, P> + ArgminMinMax + ArgminRandom + ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    if let Some((lower, _)) = bounds {
+        check_bounds_dim(name, lower, &x)?;
+    }
+    Ok(clip(bounds, x))
+}
+
+/// Cost function value at the point obtained by setting component `i` of `x` to `value`
+/// (projected onto the bounds). Returns `None` if the projected point equals `x`.
+fn eval_component<O, P, F>(
+    op: &mut OpWrapper<O>,
+    bounds: &Option<(P, P)>,
+    x: &P,
+    i: usize,
+    value: F,
+) -> Result<Option<(P, F)>, Error>
+where
+    O: CostFunction<Param = P, Output = F, Float = F>,
+    P: Clone + ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    let value = match bounds {
+        Some((lower, upper)) => value.max(lower.component(i)).min(upper.component(i)),
+        None => value,
+    };
+    if value == x.component(i) {
+        return Ok(None);
+    }
+    let mut y = x.clone();
+    y.set_component(i, value);
+    let cost = op.apply(&y)?;
+    Ok(Some((y, cost)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_param() {
+        let bounds = Some((vec![0.0, 0.0], vec![1.0, 2.0]));
+        assert_eq!(
+            init_param("x", &bounds, vec![-1.0, 3.0]).unwrap(),
+            vec![0.0, 2.0]
+        );
+        assert_eq!(
+            init_param("x", &bounds, vec![0.5, 1.0]).unwrap(),
+            vec![0.5, 1.0]
+        );
+        assert_eq!(
+            init_param("x", &None, vec![-1.0, 3.0]).unwrap(),
+            vec![-1.0, 3.0]
+        );
+        assert!(init_param("x", &bounds, vec![0.0]).is_err());
+    }
+}
@@ -0,0 +1,308 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Virginia Torczon (1997). On the Convergence of Pattern Search Algorithms. SIAM Journal on
+//! Optimization 7(1), 1-25.
+//!
+//! [1] Tamara G. Kolda, Robert Michael Lewis and Virginia Torczon (2003). Optimization by Direct
+//! Search: New Perspectives on Some Classical and Modern Methods. SIAM Review 45(3), 385-482.
+
+use super::{eval_component, init_param};
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::bounds::check_bounds;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Generalized pattern search
+///
+/// Polls the `2n` points `x +- delta * e_i` of the compass pattern around the current point `x`
+/// [0, 1]. If a poll point decreases the cost function value, it becomes the new current point
+/// and the mesh size `delta` is multiplied by `expansion` (default: 1, i.e. the mesh size is
+/// kept). Otherwise the mesh size is multiplied by `contraction` (default: 0.5).
+///
+/// By default, polling is opportunistic: it stops at the first improving point, and the next poll
+/// starts with the direction which was successful last. With `complete_poll`, all points are
+/// evaluated and the best one is accepted.
+///
+/// The initial mesh size is set with `with_initial_step` (default: 1). Bounds can be given with
+/// `with_bounds`; poll points are projected onto them.
+///
+/// The method only evaluates the cost function and terminates with
+/// `TerminationReason::StepToleranceReached` once the mesh size falls below `tol` (default:
+/// `sqrt(EPSILON)`).
+///
+/// # References:
+///
+/// [0] Virginia Torczon (1997). On the Convergence of Pattern Search Algorithms. SIAM Journal on
+/// Optimization 7(1), 1-25.
+///
+/// [1] Tamara G. Kolda, Robert Michael Lewis and Virginia Torczon (2003). Optimization by Direct
+/// Search: New Perspectives on Some Classical and Modern Methods. SIAM Review 45(3), 385-482.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PatternSearch<P, F> {
+    /// mesh size
+    delta: F,
+    /// factor by which the mesh size is increased after a successful poll
+    expansion: F,
+    /// factor by which the mesh size is reduced after an unsuccessful poll
+    contraction: F,
+    /// tolerance for the mesh size
+    tol: F,
+    /// evaluate all poll points
+    complete_poll: bool,
+    /// lower and upper bounds
+    bounds: Option<(P, P)>,
+    /// index of the last successful poll direction
+    last_success: usize,
+}
+
+impl<P, F> PatternSearch<P, F>
+where
+    P: ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    /// Constructor
+    pub fn new() -> Self {
+        PatternSearch {
+            delta: F::from_f64(1.0).unwrap(),
+            expansion: F::from_f64(1.0).unwrap(),
+            contraction: F::from_f64(0.5).unwrap(),
+            tol: F::epsilon().sqrt(),
+            complete_poll: false,
+            bounds: None,
+            last_success: 0,
+        }
+    }
+
+    /// Set initial mesh size
+    pub fn with_initial_step(mut self, delta: F) -> Result<Self, Error> {
+        if delta <= F::from_f64(0.0).unwrap() || !delta.is_finite() {
+            return Err(ArgminError::InvalidParameter {
+                text: "PatternSearch: initial step must be positive and finite.".to_string(),
+            }
+            .into());
+        }
+        self.delta = delta;
+        Ok(self)
+    }
+
+    /// Set factor by which the mesh size is increased after a successful poll. Must be `>= 1`.
+    pub fn with_expansion(mut self, expansion: F) -> Result<Self, Error> {
+        if expansion < F::from_f64(1.0).unwrap() || !expansion.is_finite() {
+            return Err(ArgminError::InvalidParameter {
+                text: "PatternSearch: expansion must be >= 1 and finite.".to_string(),
+            }
+            .into());
+        }
+        self.expansion = expansion;
+        Ok(self)
+    }
+
+    /// Set factor by which the mesh size is reduced after an unsuccessful poll. Must be in
+    /// `(0, 1)`.
+    pub fn with_contraction(mut self, contraction: F) -> Result<Self, Error> {
+        if contraction <= F::from_f64(0.0).unwrap() || contraction >= F::from_f64(1.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "PatternSearch: contraction must be in (0, 1).".to_string(),
+            }
+            .into());
+        }
+        self.contraction = contraction;
+        Ok(self)
+    }
+
+    /// Set tolerance for the mesh size
+    pub fn with_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "PatternSearch: tol must be positive.".to_string(),
+            }
+            .into());
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Evaluate all poll points instead of stopping at the first improvement
+    pub fn complete_poll(mut self) -> Self {
+        self.complete_poll = true;
+        self
+    }
+
+    /// Set lower and upper bounds
+    pub fn with_bounds(mut self, lower: P, upper: P) -> Result<Self, Error> {
+        check_bounds("PatternSearch", &lower, &upper)?;
+        self.bounds = Some((lower, upper));
+        Ok(self)
+    }
+}
+
+impl<P, F> Default for PatternSearch<P, F>
+where
+    P: ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        PatternSearch::new()
+    }
+}
+
+impl<O, P, F> Solver<O> for PatternSearch<P, F>
+where
+    O: CostFunction<Param = P, Output = F, Float = F>,
+    P: Clone
+        + Serialize
+        + DeserializeOwned
+        + ArgminComponents<F>
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminMinMax
+        + ArgminRandom,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Pattern Search";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = init_param("PatternSearch", &self.bounds, state.get_param())?;
+        self.last_success = 0;
+        let cost = op.apply(&param)?;
+        Ok(Some(ArgminIterData::new().param(param).cost(cost)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let x = state.get_param();
+        let fx = state.get_cost();
+        let num_directions = 2 * x.num_components();
+
+        // direction k moves component k / 2, odd directions in negative direction
+        let mut best: Option<(usize, P, F)> = None;
+        for j in 0..num_directions {
+            let k = (self.last_success + j) % num_directions;
+            let i = k / 2;
+            let value = if k % 2 == 1 {
+                x.component(i) - self.delta
+            } else {
+                x.component(i) + self.delta
+            };
+            if let Some((y, fy)) = eval_component(op, &self.bounds, &x, i, value)? {
+                let best_cost = best.as_ref().map(|b| b.2).unwrap_or(fx);
+                if fy < best_cost {
+                    best = Some((k, y, fy));
+                    if !self.complete_poll {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let success = best.is_some();
+        let (x, fx) = match best {
+            Some((k, y, fy)) => {
+                self.last_success = k;
+                self.delta = self.delta * self.expansion;
+                (y, fy)
+            }
+            None => {
+                self.delta = self.delta * self.contraction;
+                (x, fx)
+            }
+        };
+
+        Ok(ArgminIterData::new()
+            .param(x)
+            .cost(fx)
+            .kv(make_kv!("mesh_size" => self.delta; "success" => success;)))
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason {
+        if self.delta < self.tol {
+            return TerminationReason::StepToleranceReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(pattern_search, PatternSearch<Vec<f64>, f64>);
+
+    /// Convex quadratic with minimum at `(1, -0.5, 2)`
+    fn quadratic() -> FnOp<Vec<f64>, f64> {
+        FnOp::new(|x: &Vec<f64>| {
+            Ok((x[0] - 1.0).powi(2)
+                + 2.0 * (x[1] + 0.5).powi(2)
+                + 0.5 * (x[2] - 2.0).powi(2)
+                + 0.5 * (x[0] - 1.0) * (x[2] - 2.0))
+        })
+    }
+
+    #[test]
+    fn test_quadratic() {
+        let solvers = vec![
+            PatternSearch::new(),
+            PatternSearch::new().complete_poll(),
+            PatternSearch::new().with_expansion(2.0).unwrap(),
+        ];
+        for solver in solvers {
+            let res = Executor::new(quadratic(), solver, vec![0.0, 0.0, 0.0])
+                .max_iters(10_000)
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                TerminationReason::StepToleranceReached
+            );
+            let x = res.state.get_best_param();
+            assert!((x[0] - 1.0).abs() < 1e-6);
+            assert!((x[1] + 0.5).abs() < 1e-6);
+            assert!((x[2] - 2.0).abs() < 1e-6);
+            assert_eq!(res.state.get_grad_func_count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let solver = PatternSearch::new()
+            .with_bounds(vec![-1.0, -1.0, 2.5], vec![1.0, 0.0, 5.0])
+            .unwrap();
+        let res = Executor::new(quadratic(), solver, vec![0.0, 0.0, 3.0])
+            .max_iters(10_000)
+            .run()
+            .unwrap();
+        let x = res.state.get_best_param();
+        // x[2] is at its lower bound, where x[0] minimizes (x[0] - 1)^2 + 0.25 * (x[0] - 1)
+        assert!((x[0] - 0.875).abs() < 1e-6);
+        assert!((x[1] + 0.5).abs() < 1e-6);
+        assert!((x[2] - 2.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let solver: PatternSearch<Vec<f64>, f64> = PatternSearch::new();
+        assert!(solver.clone().with_initial_step(0.0).is_err());
+        assert!(solver.clone().with_expansion(0.5).is_err());
+        assert!(solver.clone().with_contraction(0.0).is_err());
+        assert!(solver.clone().with_tol(-1.0).is_err());
+        assert!(solver
+            .clone()
+            .with_bounds(vec![0.0, 2.0], vec![1.0, 1.0])
+            .is_err());
+    }
+}
@@ -0,0 +1,374 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] M. J. D. Powell (1964). An efficient method for finding the minimum of a function of
+//! several variables without calculating derivatives. The Computer Journal 7(2), 155-162.
+//!
+//! [1] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+//! Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+//! ISBN 978-0-521-88068-8.
+
+use super::{clip, init_param};
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::bounds::check_bounds;
+use crate::solver::brent::line_minimum;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Powell's conjugate direction method
+///
+/// Starting from the coordinate directions scaled by `with_initial_step` (default: 1), every
+/// iteration minimizes the cost function along each direction of the set in turn. Each line
+/// minimization brackets the minimum with `bracket_minimum` and locates it with `BrentOpt`. The
+/// overall displacement of the iteration then replaces the direction of the largest decrease,
+/// unless Powell's heuristic indicates that this would make the directions linearly dependent
+/// ([1], Section 10.7).
+///
+/// Bounds can be given with `with_bounds`. Line minimizations are then restricted to the part of
+/// the line which lies inside the bounds.
+///
+/// The method only evaluates the cost function. Once the decrease of the cost function value in
+/// an iteration satisfies `2 * (f_old - f_new) <= tol * (|f_old| + |f_new|)` (default `tol`:
+/// `sqrt(EPSILON)`), the direction set is reset to the coordinate directions. The method
+/// terminates if this criterion is met by an iteration with the coordinate directions.
+///
+/// # References:
+///
+/// [0] M. J. D. Powell (1964). An efficient method for finding the minimum of a function of
+/// several variables without calculating derivatives. The Computer Journal 7(2), 155-162.
+///
+/// [1] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+/// Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+/// ISBN 978-0-521-88068-8.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Powell<P, F> {
+    /// length of the initial directions
+    initial_step: F,
+    /// relative tolerance on the decrease of the cost function value
+    tol: F,
+    /// lower and upper bounds
+    bounds: Option<(P, P)>,
+    /// direction set
+    directions: Vec<P>,
+    /// direction set consists of the initial coordinate directions
+    coordinate_directions: bool,
+}
+
+impl<P, F> Powell<P, F>
+where
+    P: ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    /// Constructor
+    pub fn new() -> Self {
+        Powell {
+            initial_step: F::from_f64(1.0).unwrap(),
+            tol: F::epsilon().sqrt(),
+            bounds: None,
+            directions: vec![],
+            coordinate_directions: false,
+        }
+    }
+
+    /// Set length of the initial directions
+    pub fn with_initial_step(mut self, step: F) -> Result<Self, Error> {
+        if step <= F::from_f64(0.0).unwrap() || !step.is_finite() {
+            return Err(ArgminError::InvalidParameter {
+                text: "Powell: initial step must be positive and finite.".to_string(),
+            }
+            .into());
+        }
+        self.initial_step = step;
+        Ok(self)
+    }
+
+    /// Set relative tolerance on the decrease of the cost function value
+    pub fn with_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "Powell: tol must be positive.".to_string(),
+            }
+            .into());
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set lower and upper bounds
+    pub fn with_bounds(mut self, lower: P, upper: P) -> Result<Self, Error> {
+        check_bounds("Powell", &lower, &upper)?;
+        self.bounds = Some((lower, upper));
+        Ok(self)
+    }
+}
+
+impl<P, F> Default for Powell<P, F>
+where
+    P: ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        Powell::new()
+    }
+}
+
+impl<P, F> Powell<P, F>
+where
+    P: Clone + Serialize + DeserializeOwned + ArgminComponents<F> + ArgminScaledAdd<P, F, P>,
+    F: ArgminFloat,
+{
+    /// Sets the direction set to the coordinate directions
+    fn reset_directions(&mut self, x: &P) {
+        let n = x.num_components();
+        let zero = F::from_f64(0.0).unwrap();
+        self.directions = (0..n)
+            .map(|i| {
+                let mut d = x.clone();
+                (0..n).for_each(|j| d.set_component(j, zero));
+                d.set_component(i, self.initial_step);
+                d
+            })
+            .collect();
+        self.coordinate_directions = true;
+    }
+
+    /// Range of `t` for which `x + t * d` lies inside the bounds
+    fn feasible_range(&self, x: &P, d: &P) -> (F, F) {
+        let zero = F::from_f64(0.0).unwrap();
+        let mut range = (F::neg_infinity(), F::infinity());
+        if let Some((lower, upper)) = &self.bounds {
+            for i in 0..x.num_components() {
+                let di = d.component(i);
+                if di == zero {
+                    continue;
+                }
+                let t1 = (lower.component(i) - x.component(i)) / di;
+                let t2 = (upper.component(i) - x.component(i)) / di;
+                range.0 = range.0.max(t1.min(t2));
+                range.1 = range.1.min(t1.max(t2));
+            }
+        }
+        // rounding may exclude the starting point
+        (range.0.min(zero), range.1.max(zero))
+    }
+
+    /// Minimizes the cost function along `x + t * d`. Returns the new point and its cost function
+    /// value, or `x` and `fx` if no decrease was found.
+    fn line_minimization<O>(
+        &self,
+        op: &mut OpWrapper<O>,
+        x: &P,
+        fx: F,
+        d: &P,
+    ) -> Result<(P, F), Error>
+    where
+        O: CostFunction<Param = P, Output = F, Float = F>,
+    {
+        let (tmin, tmax) = self.feasible_range(x, d);
+        if tmin == tmax {
+            return Ok((x.clone(), fx));
+        }
+        // points outside of the feasible part of the line are moved to its ends
+        let point = |t: F| x.scaled_add(&t.max(tmin).min(tmax), d);
+        let (t, cost) = line_minimum(
+            op,
+            |op: &O, t| op.apply(&point(t)),
+            F::from_f64(0.0).unwrap(),
+            F::from_f64(1.0).unwrap(),
+            (tmin, tmax),
+        )?;
+        if cost < fx {
+            Ok((point(t), cost))
+        } else {
+            Ok((x.clone(), fx))
+        }
+    }
+}
+
+impl<O, P, F> Solver<O> for Powell<P, F>
+where
+    O: CostFunction<Param = P, Output = F, Float = F>,
+    P: Clone
+        + Serialize
+        + DeserializeOwned
+        + ArgminComponents<F>
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminMinMax
+        + ArgminRandom
+        + ArgminScaledAdd<P, F, P>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Powell";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = init_param("Powell", &self.bounds, state.get_param())?;
+        self.reset_directions(&param);
+        let cost = op.apply(&param)?;
+        Ok(Some(ArgminIterData::new().param(param).cost(cost)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let x0 = state.get_param();
+        let f0 = state.get_cost();
+
+        // minimize along every direction and remember the largest decrease
+        let mut x = x0.clone();
+        let mut fx = f0;
+        let mut largest = (0, F::from_f64(0.0).unwrap());
+        for (i, d) in self.directions.iter().enumerate() {
+            let (x_new, f_new) = self.line_minimization(op, &x, fx, d)?;
+            if fx - f_new > largest.1 {
+                largest = (i, fx - f_new);
+            }
+            x = x_new;
+            fx = f_new;
+        }
+
+        let two = F::from_f64(2.0).unwrap();
+        if two * (f0 - fx) <= self.tol * (f0.abs() + fx.abs()) + F::min_positive_value() {
+            if self.coordinate_directions {
+                return Ok(ArgminIterData::new()
+                    .param(x)
+                    .cost(fx)
+                    .termination_reason(TerminationReason::TargetToleranceReached));
+            }
+            // the accumulated directions may have lost the ability to move along some coordinates
+            self.reset_directions(&x);
+            return Ok(ArgminIterData::new()
+                .param(x)
+                .cost(fx)
+                .kv(make_kv!("direction_replaced" => false; "reset" => true;)));
+        }
+
+        // replace the direction of the largest decrease by the average direction of the iteration
+        let d_new = x.sub(&x0);
+        let xe = clip(
+            &self.bounds,
+            x.scaled_add(&F::from_f64(1.0).unwrap(), &d_new),
+        );
+        let fe = op.apply(&xe)?;
+        let mut replaced = false;
+        if fe < f0 {
+            let t = two * (f0 - two * fx + fe) * (f0 - fx - largest.1).powi(2)
+                - largest.1 * (f0 - fe).powi(2);
+            if t < F::from_f64(0.0).unwrap() {
+                let (x_new, f_new) = self.line_minimization(op, &x, fx, &d_new)?;
+                x = x_new;
+                fx = f_new;
+                self.directions.remove(largest.0);
+                self.directions.push(d_new);
+                self.coordinate_directions = false;
+                replaced = true;
+            }
+        }
+
+        Ok(ArgminIterData::new()
+            .param(x)
+            .cost(fx)
+            .kv(make_kv!("direction_replaced" => replaced; "reset" => false;)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+    use argmin_testfunctions::rosenbrock_2d;
+
+    test_trait_impl!(powell, Powell<Vec<f64>, f64>);
+
+    #[test]
+    fn test_quadratic() {
+        // a quadratic is minimized after n iterations with exact line minimizations
+        let op = FnOp::new(|x: &Vec<f64>| {
+            Ok(
+                x[0].powi(2) + 2.0 * x[1].powi(2) + 3.0 * x[2].powi(2) + x[0] * x[1] - x[1] * x[2]
+                    + x[0]
+                    - 2.0 * x[2],
+            )
+        });
+        let res = Executor::new(op, Powell::new(), vec![1.0, 1.0, 1.0])
+            .max_iters(100)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetToleranceReached
+        );
+        // gradient vanishes at the minimum
+        let x = res.state.get_best_param();
+        assert!((2.0 * x[0] + x[1] + 1.0).abs() < 1e-5);
+        assert!((4.0 * x[1] + x[0] - x[2]).abs() < 1e-5);
+        assert!((6.0 * x[2] - x[1] - 2.0).abs() < 1e-5);
+        assert!(res.state.get_iter() < 10);
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let op = FnOp::new(|x: &Vec<f64>| Ok(rosenbrock_2d(x, 1.0, 100.0)));
+        let res = Executor::new(op, Powell::new(), vec![-1.2, 1.0])
+            .max_iters(200)
+            .run()
+            .unwrap();
+        let x = res.state.get_best_param();
+        assert!((x[0] - 1.0).abs() < 1e-4);
+        assert!((x[1] - 1.0).abs() < 1e-4);
+        assert_eq!(res.state.get_grad_func_count(), 0);
+    }
+
+    #[test]
+    fn test_bounds() {
+        // the unconstrained minimum (1, 1) is cut off by x[0] <= 0.5
+        let solver = Powell::new()
+            .with_bounds(vec![-2.0, -2.0], vec![0.5, 2.0])
+            .unwrap();
+        let op = FnOp::new(|x: &Vec<f64>| Ok(rosenbrock_2d(x, 1.0, 100.0)));
+        let res = Executor::new(op, solver, vec![-1.2, 1.0])
+            .max_iters(200)
+            .run()
+            .unwrap();
+        let x = res.state.get_best_param();
+        assert!((x[0] - 0.5).abs() < 1e-6);
+        assert!((x[1] - 0.25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let run = || {
+            let op = FnOp::new(|x: &Vec<f64>| Ok(rosenbrock_2d(x, 1.0, 100.0)));
+            Executor::new(op, Powell::new(), vec![-1.2, 1.0])
+                .max_iters(5)
+                .run()
+                .unwrap()
+                .state
+                .get_best_param()
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let solver: Powell<Vec<f64>, f64> = Powell::new();
+        assert!(solver.clone().with_initial_step(0.0).is_err());
+        assert!(solver.clone().with_tol(0.0).is_err());
+        assert!(solver
+            .clone()
+            .with_bounds(vec![1.0, 0.0], vec![0.0, 0.0])
+            .is_err());
+    }
+}
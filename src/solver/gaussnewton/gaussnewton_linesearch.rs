@@ -10,6 +10,7 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::{CostFunction, Gradient, Jacobian};
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::default::Default;
@@ -54,17 +55,20 @@ impl<L, F: ArgminFloat> GaussNewtonLS<L, F> {
 
 impl<O, L, F> Solver<O> for GaussNewtonLS<L, F>
 where
-    O: ArgminOp<Float = F>,
+    O: CostFunction<Float = F> + Jacobian,
     O::Param: std::fmt::Debug
         + ArgminScaledSub<O::Param, O::Float, O::Param>
         + ArgminSub<O::Param, O::Param>
         + ArgminMul<O::Float, O::Param>,
     O::Output: ArgminNorm<O::Float>,
     O::Jacobian: ArgminTranspose<O::Jacobian>
-        + ArgminInv<O::Jacobian>
+        + ArgminLinSolve<O::Param>
+        + ArgminEye
+        + ArgminAdd<O::Jacobian, O::Jacobian>
+        + ArgminMul<O::Float, O::Jacobian>
+        + ArgminMaxAbsDiagonal<O::Float>
         + ArgminDot<O::Jacobian, O::Jacobian>
-        + ArgminDot<O::Output, O::Param>
-        + ArgminDot<O::Param, O::Param>,
+        + ArgminDot<O::Output, O::Param>,
     L: Clone + ArgminLineSearch<O::Param, O::Float> + Solver<OpWrapper<LineSearchOP<O>>>,
     F: ArgminFloat,
 {
@@ -81,7 +85,10 @@ where
         let jacobian_t = jacobian.clone().t();
         let grad = jacobian_t.dot(&residuals);
 
-        let p = jacobian_t.dot(&jacobian).inv()?.dot(&grad);
+        // J^T J is singular if the Jacobian is rank deficient, in which case it is regularized
+        let (p, tau) = jacobian_t
+            .dot(&jacobian)
+            .solve_regularized(&grad, F::from_f64(1e-3).unwrap())?;
 
         self.linesearch
             .set_search_direction(p.mul(&(F::from_f64(-1.0).unwrap())));
@@ -112,7 +119,10 @@ where
         op.op = Some(line_op.take_op().unwrap().op);
         op.consume_func_counts(line_op);
 
-        Ok(ArgminIterData::new().param(next_param).cost(next_cost))
+        Ok(ArgminIterData::new()
+            .param(next_param)
+            .cost(next_cost)
+            .kv(make_kv!("regularization" => tau;)))
     }
 
     fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
@@ -129,10 +139,9 @@ pub struct LineSearchOP<O> {
     pub op: O,
 }
 
-impl<O> ArgminOp for LineSearchOP<O>
+impl<O> CostFunction for LineSearchOP<O>
 where
-    O: ArgminOp,
-    O::Jacobian: ArgminTranspose<O::Jacobian> + ArgminDot<O::Output, O::Param>,
+    O: CostFunction,
     O::Output: ArgminNorm<O::Float>,
 {
     type Param = O::Param;
@@ -144,15 +153,24 @@ where
     fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
         Ok(self.op.apply(p)?.norm())
     }
+}
 
+impl<O> Gradient for LineSearchOP<O>
+where
+    O: Jacobian,
+    O::Jacobian: ArgminTranspose<O::Jacobian> + ArgminDot<O::Output, O::Param>,
+    O::Output: ArgminNorm<O::Float>,
+{
     fn gradient(&self, p: &Self::Param) -> Result<Self::Param, Error> {
         Ok(self.op.jacobian(p)?.t().dot(&self.op.apply(p)?))
     }
+}
 
-    fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
-        self.op.hessian(p)
-    }
-
+impl<O> Jacobian for LineSearchOP<O>
+where
+    O: Jacobian,
+    O::Output: ArgminNorm<O::Float>,
+{
     fn jacobian(&self, p: &Self::Param) -> Result<Self::Jacobian, Error> {
         self.op.jacobian(p)
     }
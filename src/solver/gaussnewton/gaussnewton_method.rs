@@ -10,6 +10,7 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::{CostFunction, Jacobian};
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::default::Default;
@@ -72,16 +73,19 @@ impl<F: ArgminFloat> Default for GaussNewton<F> {
 
 impl<O, F> Solver<O> for GaussNewton<F>
 where
-    O: ArgminOp<Float = F>,
+    O: CostFunction<Float = F> + Jacobian,
     O::Param: ArgminScaledSub<O::Param, O::Float, O::Param>
         + ArgminSub<O::Param, O::Param>
         + ArgminMul<O::Float, O::Param>,
     O::Output: ArgminNorm<O::Float>,
     O::Jacobian: ArgminTranspose<O::Jacobian>
-        + ArgminInv<O::Jacobian>
+        + ArgminLinSolve<O::Param>
+        + ArgminEye
+        + ArgminAdd<O::Jacobian, O::Jacobian>
+        + ArgminMul<O::Float, O::Jacobian>
+        + ArgminMaxAbsDiagonal<O::Float>
         + ArgminDot<O::Jacobian, O::Jacobian>
-        + ArgminDot<O::Output, O::Param>
-        + ArgminDot<O::Param, O::Param>,
+        + ArgminDot<O::Output, O::Param>,
     F: ArgminFloat,
 {
     const NAME: &'static str = "Gauss-Newton method";
@@ -95,18 +99,19 @@ where
         let residuals = op.apply(&param)?;
         let jacobian = op.jacobian(&param)?;
 
-        let p = jacobian
+        // J^T J is singular if the Jacobian is rank deficient, in which case it is regularized
+        let (p, tau) = jacobian
             .clone()
             .t()
             .dot(&jacobian)
-            .inv()?
-            .dot(&jacobian.t().dot(&residuals));
+            .solve_regularized(&jacobian.t().dot(&residuals), F::from_f64(1e-3).unwrap())?;
 
         let new_param = param.sub(&p.mul(&self.gamma));
 
         Ok(ArgminIterData::new()
             .param(new_param)
-            .cost(residuals.norm()))
+            .cost(residuals.norm())
+            .kv(make_kv!("regularization" => tau;)))
     }
 
     fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
@@ -141,4 +146,31 @@ mod tests {
 
         assert!((g - gamma).abs() < std::f64::EPSILON);
     }
+
+    #[cfg(feature = "sprsl")]
+    #[test]
+    fn test_sparse_jacobian() {
+        use sprs::CsMat;
+
+        // Linear least squares problem with residuals A x - b, where
+        // A = [[1, 0], [0, 2], [1, 1]] and b = [1, 2, 3]
+        let op = FnOp::<Vec<f64>, Vec<f64>, f64>::from_operator(|x: &Vec<f64>| {
+            Ok(vec![x[0] - 1.0, 2.0 * x[1] - 2.0, x[0] + x[1] - 3.0])
+        })
+        .jacobian(|_: &Vec<f64>| {
+            Ok(CsMat::new(
+                (3, 2),
+                vec![0, 1, 2, 4],
+                vec![0, 1, 0, 1],
+                vec![1.0, 2.0, 1.0, 1.0],
+            ))
+        });
+        let res = Executor::new(op, GaussNewton::new(), vec![0.0, 0.0])
+            .max_iters(1)
+            .run()
+            .unwrap();
+        let param = res.state.get_param();
+        assert!((param[0] - 13.0 / 9.0).abs() < 1e-10);
+        assert!((param[1] - 10.0 / 9.0).abs() < 1e-10);
+    }
 }
@@ -9,6 +9,7 @@
 //!
 //! [Wikipedia](https://en.wikipedia.org/wiki/Golden-section_search)
 
+use crate::core::capabilities::CostFunction;
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -83,7 +84,7 @@ where
 
 impl<O, F> Solver<O> for GoldenSectionSearch<F>
 where
-    O: ArgminOp<Output = F, Param = F, Float = F>,
+    O: CostFunction<Output = F, Param = F, Float = F>,
     F: ArgminFloat,
 {
     const NAME: &'static str = "Golden-section search";
@@ -0,0 +1,301 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Barzilai-Borwein spectral gradient method
+//!
+//! [BarzilaiBorwein](struct.BarzilaiBorwein.html)
+//!
+//! # References:
+//!
+//! [0] Jonathan Barzilai and Jonathan M. Borwein (1988). Two-Point Step Size Gradient Methods.
+//! IMA Journal of Numerical Analysis 8(1), 141-148.
+//!
+//! [1] Marcos Raydan (1997). The Barzilai and Borwein Gradient Method for the Large Scale
+//! Unconstrained Minimization Problem. SIAM Journal on Optimization 7(1), 26-33.
+
+use crate::core::capabilities::{CostFunction, Gradient};
+use crate::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Step length formulas of the Barzilai-Borwein method
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BBStep {
+    /// `alpha = s^T s / s^T y`
+    Long,
+    /// `alpha = s^T y / y^T y`
+    Short,
+    /// Alternates between `Short` and `Long`
+    Alternating,
+}
+
+/// Barzilai-Borwein spectral gradient method
+///
+/// Takes steps `x_{k+1} = x_k - lambda * alpha_k * g_k`, where the step length `alpha_k` is
+/// computed from `s = x_k - x_{k-1}` and `y = g_k - g_{k-1}` according to the formula chosen
+/// with `with_step` (default: `BBStep::Long`) [0]. `alpha_k` is limited to
+/// `[1e-10, 1e10]`; if the curvature `s^T y` is not positive, `alpha_k` is reset to
+/// `1 / ||g_k||`.
+///
+/// The iteration is safeguarded by a non-monotone line search [1]: `lambda` is halved, starting
+/// from 1, until
+///
+/// `f(x_{k+1}) <= max_{0 <= j < M} f(x_{k-j}) - gamma * lambda * alpha_k * ||g_k||^2`
+///
+/// holds, where `M` (default: 10) is set with `with_memory` and `gamma = 1e-4`. The solver
+/// terminates if the norm of the gradient falls below `tol_grad`.
+///
+/// # References:
+///
+/// [0] Jonathan Barzilai and Jonathan M. Borwein (1988). Two-Point Step Size Gradient Methods.
+/// IMA Journal of Numerical Analysis 8(1), 141-148.
+///
+/// [1] Marcos Raydan (1997). The Barzilai and Borwein Gradient Method for the Large Scale
+/// Unconstrained Minimization Problem. SIAM Journal on Optimization 7(1), 26-33.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BarzilaiBorwein<F> {
+    /// step length formula
+    step: BBStep,
+    /// number of previous cost function values considered by the line search
+    memory: usize,
+    /// sufficient decrease parameter
+    gamma: F,
+    /// maximum number of backtracking steps
+    max_backtracks: u64,
+    /// tolerance for the norm of the gradient
+    tol_grad: F,
+    /// current step length
+    alpha: F,
+    /// previous cost function values
+    cost_history: VecDeque<F>,
+}
+
+/// lower limit of the step length
+const ALPHA_MIN: f64 = 1e-10;
+/// upper limit of the step length
+const ALPHA_MAX: f64 = 1e10;
+
+impl<F: ArgminFloat> BarzilaiBorwein<F> {
+    /// Constructor
+    pub fn new() -> Self {
+        BarzilaiBorwein {
+            step: BBStep::Long,
+            memory: 10,
+            gamma: F::from_f64(1e-4).unwrap(),
+            max_backtracks: 50,
+            tol_grad: F::epsilon().sqrt(),
+            alpha: F::nan(),
+            cost_history: VecDeque::new(),
+        }
+    }
+
+    /// Set the step length formula
+    pub fn with_step(mut self, step: BBStep) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Set the number of previous cost function values considered by the non-monotone line
+    /// search. With a memory of 1, the line search is monotone.
+    pub fn with_memory(mut self, memory: usize) -> Result<Self, Error> {
+        if memory == 0 {
+            return Err(ArgminError::InvalidParameter {
+                text: "BarzilaiBorwein: memory must be > 0.".to_string(),
+            }
+            .into());
+        }
+        self.memory = memory;
+        Ok(self)
+    }
+
+    /// Sets tolerance for the stopping criterion based on the norm of the gradient
+    pub fn with_tol_grad(mut self, tol_grad: F) -> Result<Self, Error> {
+        if tol_grad < F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "BarzilaiBorwein: tol_grad must be >= 0.".to_string(),
+            }
+            .into());
+        }
+        self.tol_grad = tol_grad;
+        Ok(self)
+    }
+
+    /// Limits `alpha` to `[ALPHA_MIN, ALPHA_MAX]`
+    fn clamp_alpha(alpha: F) -> F {
+        alpha
+            .max(F::from_f64(ALPHA_MIN).unwrap())
+            .min(F::from_f64(ALPHA_MAX).unwrap())
+    }
+}
+
+impl<F: ArgminFloat> Default for BarzilaiBorwein<F> {
+    fn default() -> Self {
+        BarzilaiBorwein::new()
+    }
+}
+
+impl<O, P, F> Solver<O> for BarzilaiBorwein<F>
+where
+    O: CostFunction<Param = P, Output = F, Float = F> + Gradient,
+    P: Clone
+        + Serialize
+        + DeserializeOwned
+        + ArgminSub<P, P>
+        + ArgminDot<P, F>
+        + ArgminScaledSub<P, F, P>
+        + ArgminNorm<F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Barzilai-Borwein";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        let cost = op.apply(&param)?;
+        let grad = op.gradient(&param)?;
+        self.alpha = Self::clamp_alpha(F::from_f64(1.0).unwrap() / grad.norm());
+        self.cost_history = VecDeque::with_capacity(self.memory);
+        self.cost_history.push_back(cost);
+        Ok(Some(
+            ArgminIterData::new().param(param).cost(cost).grad(grad),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let param = state.get_param();
+        let grad = state.get_grad().unwrap();
+        let grad_norm_sq = grad.dot(&grad);
+        let max_cost = self
+            .cost_history
+            .iter()
+            .fold(F::neg_infinity(), |acc, &c| acc.max(c));
+
+        // non-monotone backtracking
+        let mut lambda = F::from_f64(1.0).unwrap();
+        let mut backtracks = 0;
+        let (new_param, new_cost) = loop {
+            let new_param = param.scaled_sub(&(lambda * self.alpha), &grad);
+            let new_cost = op.apply(&new_param)?;
+            if new_cost <= max_cost - self.gamma * lambda * self.alpha * grad_norm_sq {
+                break (new_param, new_cost);
+            }
+            if backtracks >= self.max_backtracks {
+                return Ok(ArgminIterData::new()
+                    .termination_reason(TerminationReason::NoChangeInCost)
+                    .kv(make_kv!("backtracks" => backtracks;)));
+            }
+            lambda = lambda * F::from_f64(0.5).unwrap();
+            backtracks += 1;
+        };
+        let new_grad = op.gradient(&new_param)?;
+
+        let s = new_param.sub(&param);
+        let y = new_grad.sub(&grad);
+        let sy = s.dot(&y);
+        let step = lambda * self.alpha;
+        self.alpha = if sy <= F::from_f64(0.0).unwrap() {
+            F::from_f64(1.0).unwrap() / new_grad.norm()
+        } else {
+            let long = match self.step {
+                BBStep::Long => true,
+                BBStep::Short => false,
+                BBStep::Alternating => state.get_iter() % 2 == 1,
+            };
+            if long {
+                s.dot(&s) / sy
+            } else {
+                sy / y.dot(&y)
+            }
+        };
+        self.alpha = Self::clamp_alpha(self.alpha);
+
+        if self.cost_history.len() >= self.memory {
+            self.cost_history.pop_front();
+        }
+        self.cost_history.push_back(new_cost);
+
+        Ok(ArgminIterData::new()
+            .param(new_param)
+            .cost(new_cost)
+            .grad(new_grad)
+            .kv(make_kv!("step_length" => step; "backtracks" => backtracks;)))
+    }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
+        if state.get_grad().unwrap().norm() < self.tol_grad {
+            return TerminationReason::TargetPrecisionReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+    use argmin_testfunctions::{rosenbrock_2d, rosenbrock_2d_derivative};
+
+    test_trait_impl!(barzilai_borwein, BarzilaiBorwein<f64>);
+
+    #[test]
+    fn test_rosenbrock() {
+        for &step in [BBStep::Long, BBStep::Short, BBStep::Alternating].iter() {
+            let solver = BarzilaiBorwein::new()
+                .with_step(step)
+                .with_tol_grad(1e-8)
+                .unwrap();
+            let op = FnOp::new(|x: &Vec<f64>| Ok(rosenbrock_2d(x, 1.0, 100.0)))
+                .gradient(|x: &Vec<f64>| Ok(rosenbrock_2d_derivative(x, 1.0, 100.0)));
+            let res = Executor::new(op, solver, vec![-1.2, 1.0])
+                .max_iters(5000)
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                TerminationReason::TargetPrecisionReached
+            );
+            let p = res.state.get_best_param();
+            assert!((p[0] - 1.0).abs() < 1e-6);
+            assert!((p[1] - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_quadratic() {
+        // diagonal quadratic with condition number 1000
+        let diag: Vec<f64> = (0..20).map(|i| 1000f64.powf(i as f64 / 19.0)).collect();
+        let d1 = diag.clone();
+        let op = FnOp::new(move |x: &Vec<f64>| {
+            Ok(0.5 * x.iter().zip(d1.iter()).map(|(x, d)| d * x * x).sum::<f64>())
+        })
+        .gradient(move |x: &Vec<f64>| Ok(x.iter().zip(diag.iter()).map(|(x, d)| d * x).collect()));
+        let solver = BarzilaiBorwein::new().with_tol_grad(1e-8).unwrap();
+        let res = Executor::new(op, solver, vec![1.0; 20])
+            .max_iters(2000)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        assert!(res.state.get_best_param().iter().all(|x| x.abs() < 1e-8));
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let bb: BarzilaiBorwein<f64> = BarzilaiBorwein::new();
+        assert!(bb.clone().with_memory(0).is_err());
+        assert!(bb.with_tol_grad(-1.0).is_err());
+    }
+}
@@ -7,13 +7,19 @@
 
 //! Gradient descent methods
 //!
-//! [Steepest Descent](steepestdescent/struct.SteepestDescent.html)
+//! * [Steepest Descent](steepestdescent/struct.SteepestDescent.html)
+//! * [Nesterov's accelerated gradient](nesterov/struct.NesterovAcceleratedGradient.html)
+//! * [Barzilai-Borwein](barzilaiborwein/struct.BarzilaiBorwein.html)
 //!
 //! # References:
 //!
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+pub mod barzilaiborwein;
+pub mod nesterov;
 pub mod steepestdescent;
 
+pub use self::barzilaiborwein::*;
+pub use self::nesterov::*;
 pub use self::steepestdescent::*;
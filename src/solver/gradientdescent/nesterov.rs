@@ -0,0 +1,314 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Nesterov's accelerated gradient method
+//!
+//! [NesterovAcceleratedGradient](struct.NesterovAcceleratedGradient.html)
+//!
+//! # References:
+//!
+//! [0] Yurii Nesterov (1983). A method for solving the convex programming problem with
+//! convergence rate O(1/k^2). Soviet Mathematics Doklady 27, 372-376.
+//!
+//! [1] Amir Beck and Marc Teboulle (2009). A Fast Iterative Shrinkage-Thresholding Algorithm for
+//! Linear Inverse Problems. SIAM Journal on Imaging Sciences 2(1), 183-202.
+//!
+//! [2] Brendan O'Donoghue and Emmanuel Candès (2015). Adaptive Restart for Accelerated Gradient
+//! Schemes. Foundations of Computational Mathematics 15, 715-732.
+
+use crate::core::capabilities::{CostFunction, Gradient};
+use crate::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::default::Default;
+
+/// Adaptive restart schemes for `NesterovAcceleratedGradient`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RestartScheme {
+    /// Never restart the momentum
+    Never,
+    /// Restart if the cost function value increases
+    Function,
+    /// Restart if the momentum points uphill, i.e. if `g(y_k)^T (x_{k+1} - x_k) > 0`
+    Gradient,
+}
+
+/// Nesterov's accelerated gradient method
+///
+/// Takes gradient steps of length `1/L` from an extrapolated point `y_k` [0, 1]:
+///
+/// `x_{k+1} = y_k - 1/L * g(y_k)`
+///
+/// `t_{k+1} = (1 + sqrt(1 + 4 * t_k^2)) / 2`
+///
+/// `y_{k+1} = x_{k+1} + (t_k - 1) / t_{k+1} * (x_{k+1} - x_k)`
+///
+/// The Lipschitz constant `L` of the gradient is estimated by backtracking: Starting from the
+/// value given by `with_lipschitz` (default: 1), it is multiplied by `eta` (default: 2) until
+/// `f(x_{k+1}) <= f(y_k) - 1/(2L) * ||g(y_k)||^2` holds. The estimate never decreases.
+///
+/// The momentum is reset (`t_k = 1`, `y_{k+1} = x_{k+1}`) according to the adaptive restart
+/// scheme [2] set with `with_restart` (default: `RestartScheme::Gradient`). The solver
+/// terminates if the norm of the gradient at `x_{k+1}` falls below `tol_grad`. This requires one
+/// gradient evaluation at `x_{k+1}` in addition to the one at `y_k` per iteration.
+///
+/// # References:
+///
+/// [0] Yurii Nesterov (1983). A method for solving the convex programming problem with
+/// convergence rate O(1/k^2). Soviet Mathematics Doklady 27, 372-376.
+///
+/// [1] Amir Beck and Marc Teboulle (2009). A Fast Iterative Shrinkage-Thresholding Algorithm for
+/// Linear Inverse Problems. SIAM Journal on Imaging Sciences 2(1), 183-202.
+///
+/// [2] Brendan O'Donoghue and Emmanuel Candès (2015). Adaptive Restart for Accelerated Gradient
+/// Schemes. Foundations of Computational Mathematics 15, 715-732.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NesterovAcceleratedGradient<P, F> {
+    /// estimate of the Lipschitz constant of the gradient
+    lipschitz: F,
+    /// factor by which the Lipschitz estimate is increased
+    eta: F,
+    /// restart scheme
+    restart: RestartScheme,
+    /// tolerance for the norm of the gradient
+    tol_grad: F,
+    /// extrapolated point
+    y: P,
+    /// momentum parameter
+    t: F,
+}
+
+impl<P, F> NesterovAcceleratedGradient<P, F>
+where
+    P: Default,
+    F: ArgminFloat,
+{
+    /// Constructor
+    pub fn new() -> Self {
+        NesterovAcceleratedGradient {
+            lipschitz: F::from_f64(1.0).unwrap(),
+            eta: F::from_f64(2.0).unwrap(),
+            restart: RestartScheme::Gradient,
+            tol_grad: F::epsilon().sqrt(),
+            y: P::default(),
+            t: F::from_f64(1.0).unwrap(),
+        }
+    }
+
+    /// Set the initial estimate of the Lipschitz constant
+    pub fn with_lipschitz(mut self, lipschitz: F) -> Result<Self, Error> {
+        if lipschitz <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "NesterovAcceleratedGradient: Lipschitz constant must be > 0.".to_string(),
+            }
+            .into());
+        }
+        self.lipschitz = lipschitz;
+        Ok(self)
+    }
+
+    /// Set the factor by which the Lipschitz estimate is increased during backtracking
+    pub fn with_backtracking_factor(mut self, eta: F) -> Result<Self, Error> {
+        if eta <= F::from_f64(1.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "NesterovAcceleratedGradient: backtracking factor must be > 1.".to_string(),
+            }
+            .into());
+        }
+        self.eta = eta;
+        Ok(self)
+    }
+
+    /// Set the adaptive restart scheme
+    pub fn with_restart(mut self, restart: RestartScheme) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    /// Sets tolerance for the stopping criterion based on the norm of the gradient
+    pub fn with_tol_grad(mut self, tol_grad: F) -> Result<Self, Error> {
+        if tol_grad < F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "NesterovAcceleratedGradient: tol_grad must be >= 0.".to_string(),
+            }
+            .into());
+        }
+        self.tol_grad = tol_grad;
+        Ok(self)
+    }
+}
+
+impl<P, F> Default for NesterovAcceleratedGradient<P, F>
+where
+    P: Default,
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        NesterovAcceleratedGradient::new()
+    }
+}
+
+impl<O, P, F> Solver<O> for NesterovAcceleratedGradient<P, F>
+where
+    O: CostFunction<Param = P, Output = F, Float = F> + Gradient,
+    P: Clone
+        + Default
+        + Serialize
+        + DeserializeOwned
+        + ArgminSub<P, P>
+        + ArgminDot<P, F>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledSub<P, F, P>
+        + ArgminNorm<F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Nesterov Accelerated Gradient";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        let cost = op.apply(&param)?;
+        let grad = op.gradient(&param)?;
+        self.y = param.clone();
+        self.t = F::from_f64(1.0).unwrap();
+        Ok(Some(
+            ArgminIterData::new().param(param).cost(cost).grad(grad),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let one = F::from_f64(1.0).unwrap();
+        let half = F::from_f64(0.5).unwrap();
+        let x_prev = state.get_param();
+        let cost_prev = state.get_cost();
+
+        let cost_y = op.apply(&self.y)?;
+        let grad_y = op.gradient(&self.y)?;
+        let grad_norm_sq = grad_y.dot(&grad_y);
+
+        // backtracking on the Lipschitz constant
+        let (x, cost) = loop {
+            let x = self.y.scaled_sub(&(one / self.lipschitz), &grad_y);
+            let cost = op.apply(&x)?;
+            if cost <= cost_y - half / self.lipschitz * grad_norm_sq {
+                break (x, cost);
+            }
+            self.lipschitz = self.lipschitz * self.eta;
+            if !self.lipschitz.is_finite() {
+                return Err(ArgminError::ConditionViolated {
+                    text: "NesterovAcceleratedGradient: backtracking failed.".to_string(),
+                }
+                .into());
+            }
+        };
+
+        let step = x.sub(&x_prev);
+        let restart = match self.restart {
+            RestartScheme::Never => false,
+            RestartScheme::Function => cost > cost_prev,
+            RestartScheme::Gradient => grad_y.dot(&step) > F::from_f64(0.0).unwrap(),
+        };
+        if restart {
+            self.t = one;
+            self.y = x.clone();
+        } else {
+            let t_next = half * (one + (one + F::from_f64(4.0).unwrap() * self.t * self.t).sqrt());
+            self.y = x.scaled_add(&((self.t - one) / t_next), &step);
+            self.t = t_next;
+        }
+
+        let grad = op.gradient(&x)?;
+
+        Ok(ArgminIterData::new()
+            .param(x)
+            .cost(cost)
+            .grad(grad)
+            .kv(make_kv!("lipschitz" => self.lipschitz; "restart" => restart;)))
+    }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
+        if state.get_grad().unwrap().norm() < self.tol_grad {
+            return TerminationReason::TargetPrecisionReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+    use argmin_testfunctions::{rosenbrock_2d, rosenbrock_2d_derivative};
+
+    test_trait_impl!(nesterov, NesterovAcceleratedGradient<Vec<f64>, f64>);
+
+    #[test]
+    fn test_rosenbrock() {
+        for &restart in [RestartScheme::Function, RestartScheme::Gradient].iter() {
+            let solver = NesterovAcceleratedGradient::new()
+                .with_restart(restart)
+                .with_tol_grad(1e-8)
+                .unwrap();
+            let op = FnOp::new(|x: &Vec<f64>| Ok(rosenbrock_2d(x, 1.0, 100.0)))
+                .gradient(|x: &Vec<f64>| Ok(rosenbrock_2d_derivative(x, 1.0, 100.0)));
+            let res = Executor::new(op, solver, vec![-1.2, 1.0])
+                .max_iters(20000)
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                TerminationReason::TargetPrecisionReached
+            );
+            let p = res.state.get_best_param();
+            assert!((p[0] - 1.0).abs() < 1e-6);
+            assert!((p[1] - 1.0).abs() < 1e-6);
+            // the reported gradient belongs to the reported parameter vector
+            let g = res.state.get_grad().unwrap();
+            let expected = rosenbrock_2d_derivative(&res.state.get_param(), 1.0, 100.0);
+            assert!(g
+                .iter()
+                .zip(expected.iter())
+                .all(|(a, b)| (a - b).abs() < 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_restart_speeds_up_convergence() {
+        // strongly convex quadratic with condition number 1000
+        let op = || {
+            FnOp::new(|x: &Vec<f64>| Ok(0.5 * (x[0].powi(2) + 1000.0 * x[1].powi(2))))
+                .gradient(|x: &Vec<f64>| Ok(vec![x[0], 1000.0 * x[1]]))
+        };
+        let iters = |restart| {
+            let solver = NesterovAcceleratedGradient::new()
+                .with_restart(restart)
+                .with_tol_grad(1e-8)
+                .unwrap();
+            Executor::new(op(), solver, vec![1.0, 1.0])
+                .max_iters(100000)
+                .run()
+                .unwrap()
+                .state
+                .get_iter()
+        };
+        assert!(iters(RestartScheme::Gradient) < iters(RestartScheme::Never));
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let nag: NesterovAcceleratedGradient<Vec<f64>, f64> = NesterovAcceleratedGradient::new();
+        assert!(nag.clone().with_lipschitz(0.0).is_err());
+        assert!(nag.clone().with_backtracking_factor(1.0).is_err());
+        assert!(nag.with_tol_grad(-1.0).is_err());
+    }
+}
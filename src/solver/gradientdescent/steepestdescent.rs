@@ -14,6 +14,7 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::{CostFunction, Gradient};
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -41,7 +42,7 @@ impl<L> SteepestDescent<L> {
 
 impl<O, L, F> Solver<O> for SteepestDescent<L>
 where
-    O: ArgminOp<Output = F, Float = F>,
+    O: CostFunction<Output = F, Float = F> + Gradient,
     O::Param: Clone
         + Default
         + Serialize
@@ -0,0 +1,348 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Yousef Saad (2003). Iterative Methods for Sparse Linear Systems. SIAM.
+//! ISBN 0-89871-534-2.
+//!
+//! [1] H. A. van der Vorst (1992). Bi-CGSTAB: A Fast and Smoothly Converging Variant of Bi-CG for
+//! the Solution of Nonsymmetric Linear Systems. SIAM J. Sci. Stat. Comput. 13(2), 631–644.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::conjugategradient::{ArgminPreconditioner, IdentityPreconditioner};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// The biconjugate gradient stabilized method (BiCGSTAB) solves `A * x = b` for general
+/// (non-symmetric) square matrices `A`. The product `A * x` is computed by `apply`; each iteration
+/// requires two products.
+///
+/// The cost is the norm of the residual, which is also reported in the `residual` key of the
+/// observers. A preconditioner can be set via `with_preconditioner`. It is applied from the right.
+///
+/// If `rho` or `omega` vanish, the method is restarted from the current solution. If the shadow
+/// residual is orthogonal to `A * p`, `ArgminError::ConditionViolated` is returned.
+///
+/// The solver terminates once `||b - A * x|| <= tol * ||b||`.
+///
+/// [Example](https://github.com/argmin-rs/argmin/blob/master/examples/bicgstab.rs)
+///
+/// # References:
+///
+/// [0] Yousef Saad (2003). Iterative Methods for Sparse Linear Systems. SIAM.
+/// ISBN 0-89871-534-2.
+///
+/// [1] H. A. van der Vorst (1992). Bi-CGSTAB: A Fast and Smoothly Converging Variant of Bi-CG for
+/// the Solution of Nonsymmetric Linear Systems. SIAM J. Sci. Stat. Comput. 13(2), 631–644.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BiCGSTAB<P, F, M = IdentityPreconditioner> {
+    /// b (right hand side)
+    b: P,
+    /// ||b||
+    b_norm: F,
+    /// tolerance for the relative residual
+    tol: F,
+    /// preconditioner
+    preconditioner: M,
+    /// residual
+    r: P,
+    /// shadow residual
+    r_hat: P,
+    /// search direction
+    p: P,
+    /// A * M^{-1} * p
+    v: P,
+    /// rho
+    rho: F,
+    /// alpha
+    alpha: F,
+    /// omega
+    omega: F,
+}
+
+impl<P, F> BiCGSTAB<P, F, IdentityPreconditioner>
+where
+    P: Default,
+    F: ArgminFloat,
+{
+    /// Constructor
+    ///
+    /// Parameters:
+    ///
+    /// `b`: right hand side of `A * x = b`
+    pub fn new(b: P) -> Result<Self, Error> {
+        Ok(BiCGSTAB {
+            b,
+            b_norm: F::nan(),
+            tol: F::epsilon().sqrt(),
+            preconditioner: IdentityPreconditioner,
+            r: P::default(),
+            r_hat: P::default(),
+            p: P::default(),
+            v: P::default(),
+            rho: F::nan(),
+            alpha: F::nan(),
+            omega: F::nan(),
+        })
+    }
+}
+
+impl<P, F, M> BiCGSTAB<P, F, M>
+where
+    P: Clone + ArgminZeroLike,
+    F: ArgminFloat,
+{
+    /// Set preconditioner
+    pub fn with_preconditioner<M2>(self, preconditioner: M2) -> BiCGSTAB<P, F, M2> {
+        BiCGSTAB {
+            b: self.b,
+            b_norm: self.b_norm,
+            tol: self.tol,
+            preconditioner,
+            r: self.r,
+            r_hat: self.r_hat,
+            p: self.p,
+            v: self.v,
+            rho: self.rho,
+            alpha: self.alpha,
+            omega: self.omega,
+        }
+    }
+
+    /// Set tolerance for the relative residual. Defaults to `sqrt(EPSILON)`.
+    pub fn with_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "BiCGSTAB: tol must be positive.".to_string(),
+            }
+            .into());
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// (Re)start the iteration with the shadow residual set to the current residual
+    fn restart(&mut self) {
+        self.r_hat = self.r.clone();
+        self.p = self.r.zero_like();
+        self.v = self.r.zero_like();
+        self.rho = F::from_f64(1.0).unwrap();
+        self.alpha = F::from_f64(1.0).unwrap();
+        self.omega = F::from_f64(1.0).unwrap();
+    }
+}
+
+impl<O, P, F, M> Solver<O> for BiCGSTAB<P, F, M>
+where
+    O: CostFunction<Param = P, Output = P, Float = F>,
+    P: Clone
+        + Serialize
+        + DeserializeOwned
+        + ArgminSub<P, P>
+        + ArgminDot<P, F>
+        + ArgminNorm<F>
+        + ArgminZeroLike
+        + ArgminAddAssign<P>
+        + ArgminMulAssign<F>
+        + ArgminScaledAddAssign<P, F>
+        + ArgminScaledSubAssign<P, F>,
+    F: ArgminFloat,
+    M: ArgminPreconditioner<P> + Clone + Serialize + DeserializeOwned,
+{
+    const NAME: &'static str = "BiCGSTAB";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        self.b_norm = self.b.norm();
+        self.r = self.b.sub(&op.apply(&state.get_param())?);
+        self.restart();
+        Ok(None)
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let zero = F::from_f64(0.0).unwrap();
+        let mut x = state.get_param();
+
+        let mut rho = self.r_hat.dot(&self.r);
+        if rho == zero || self.omega == zero {
+            self.restart();
+            rho = self.r_hat.dot(&self.r);
+            if rho == zero {
+                // residual is zero
+                return Ok(ArgminIterData::new()
+                    .param(x)
+                    .cost(zero)
+                    .kv(make_kv!("residual" => zero;))
+                    .termination_reason(TerminationReason::TargetPrecisionReached));
+            }
+        }
+        let beta = (rho / self.rho) * (self.alpha / self.omega);
+        self.rho = rho;
+
+        // p = r + beta * (p - omega * v)
+        self.p.scaled_sub_assign(&self.omega, &self.v);
+        self.p.mul_assign(&beta);
+        self.p.add_assign(&self.r);
+
+        let p_hat = self.preconditioner.precondition(&self.p)?;
+        self.v = op.apply(&p_hat)?;
+        let r_hat_v = self.r_hat.dot(&self.v);
+        // r_hat and v are (numerically) orthogonal, alpha cannot be computed
+        if r_hat_v.is_nan() || r_hat_v.abs() <= F::epsilon() * self.r_hat.norm() * self.v.norm() {
+            return Err(ArgminError::ConditionViolated {
+                text: "BiCGSTAB: breakdown, shadow residual is orthogonal to A * p.".to_string(),
+            }
+            .into());
+        }
+        self.alpha = rho / r_hat_v;
+        x.scaled_add_assign(&self.alpha, &p_hat);
+
+        // s = r - alpha * v; the residual is stored in r right away
+        self.r.scaled_sub_assign(&self.alpha, &self.v);
+        let s_norm = self.r.norm();
+        if s_norm <= self.tol * self.b_norm {
+            return Ok(ArgminIterData::new()
+                .param(x)
+                .cost(s_norm)
+                .kv(make_kv!("residual" => s_norm;)));
+        }
+
+        let s_hat = self.preconditioner.precondition(&self.r)?;
+        let t = op.apply(&s_hat)?;
+        let tt = t.dot(&t);
+        self.omega = if tt > zero { t.dot(&self.r) / tt } else { zero };
+        x.scaled_add_assign(&self.omega, &s_hat);
+        self.r.scaled_sub_assign(&self.omega, &t);
+        let residual = self.r.norm();
+
+        Ok(ArgminIterData::new()
+            .param(x)
+            .cost(residual)
+            .kv(make_kv!("residual" => residual;)))
+    }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
+        if state.get_cost() <= self.tol * self.b_norm {
+            TerminationReason::TargetPrecisionReached
+        } else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::conjugategradient::JacobiPreconditioner;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(bicgstab, BiCGSTAB<Vec<f64>, f64>);
+
+    /// Non-symmetric tridiagonal matrix of a discretized convection-diffusion equation
+    #[derive(Clone, Default, Serialize, Deserialize)]
+    struct ConvectionDiffusion {
+        a: Vec<Vec<f64>>,
+    }
+
+    impl ConvectionDiffusion {
+        fn new(n: usize) -> Self {
+            let mut a = vec![vec![0.0; n]; n];
+            for i in 0..n {
+                a[i][i] = 2.0 + i as f64;
+                if i > 0 {
+                    a[i][i - 1] = -1.5;
+                    a[i - 1][i] = -0.5;
+                }
+            }
+            ConvectionDiffusion { a }
+        }
+    }
+
+    impl CostFunction for ConvectionDiffusion {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, p: &Vec<f64>) -> Result<Vec<f64>, Error> {
+            Ok(self.a.dot(p))
+        }
+    }
+
+    fn check_solution(op: &ConvectionDiffusion, x: &[f64], b: &[f64]) {
+        let ax = op.a.dot(&x.to_vec());
+        for (ax, b) in ax.iter().zip(b.iter()) {
+            assert!((ax - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let bicgstab: BiCGSTAB<Vec<f64>, f64> = BiCGSTAB::new(vec![1.0]).unwrap();
+        assert!(bicgstab.clone().with_tol(0.0).is_err());
+        let BiCGSTAB { tol, .. } = bicgstab.with_tol(1e-4).unwrap();
+        assert!((tol - 1e-4).abs() < std::f64::EPSILON);
+    }
+
+    #[test]
+    fn test_solve() {
+        let n = 20;
+        let op = ConvectionDiffusion::new(n);
+        let b = (0..n).map(|i| i as f64).collect::<Vec<f64>>();
+        let res = Executor::new(op.clone(), BiCGSTAB::new(b.clone()).unwrap(), vec![0.0; n])
+            .max_iters(1000)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        check_solution(&op, &res.state.get_best_param(), &b);
+    }
+
+    #[test]
+    fn test_breakdown() {
+        // r_hat = b is orthogonal to A * b for the rotation A
+        let op = ConvectionDiffusion {
+            a: vec![vec![0.0, 1.0], vec![-1.0, 0.0]],
+        };
+        let res = Executor::new(op, BiCGSTAB::new(vec![1.0, 0.0]).unwrap(), vec![0.0; 2])
+            .max_iters(10)
+            .run();
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_preconditioned() {
+        let n = 20;
+        let op = ConvectionDiffusion::new(n);
+        let b = vec![1.0; n];
+        let diag = (0..n).map(|i| op.a[i][i]).collect();
+        let solver = BiCGSTAB::new(b.clone())
+            .unwrap()
+            .with_preconditioner(JacobiPreconditioner::new(diag));
+        let res = Executor::new(op.clone(), solver, vec![0.0; n])
+            .max_iters(1000)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        check_solution(&op, &res.state.get_best_param(), &b);
+    }
+}
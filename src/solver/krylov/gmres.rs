@@ -0,0 +1,374 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Yousef Saad (2003). Iterative Methods for Sparse Linear Systems. SIAM.
+//! ISBN 0-89871-534-2.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::conjugategradient::{ArgminPreconditioner, IdentityPreconditioner};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Restarted GMRES(m) solves `A * x = b` for general (non-symmetric) square matrices `A`. The
+/// product `A * x` is computed by `apply`.
+///
+/// Each iteration performs one Arnoldi step and minimizes the residual over the current Krylov
+/// subspace. After `restart` iterations, the Krylov basis is discarded and a new cycle is started
+/// from the current solution. The cost is the norm of the residual, which is also reported in the
+/// `residual` key of the observers.
+///
+/// A preconditioner can be set via `with_preconditioner`. It is applied from the right, therefore
+/// the reported residuals are residuals of the original system.
+///
+/// The solver terminates once `||b - A * x|| <= tol * ||b||`.
+///
+/// [Example](https://github.com/argmin-rs/argmin/blob/master/examples/gmres.rs)
+///
+/// # References:
+///
+/// [0] Yousef Saad (2003). Iterative Methods for Sparse Linear Systems. SIAM.
+/// ISBN 0-89871-534-2.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GMRES<P, F, M = IdentityPreconditioner> {
+    /// b (right hand side)
+    b: P,
+    /// ||b||
+    b_norm: F,
+    /// number of iterations after which the Krylov basis is discarded
+    restart: usize,
+    /// tolerance for the relative residual
+    tol: F,
+    /// preconditioner
+    preconditioner: M,
+    /// solution at the start of the current cycle
+    x0: P,
+    /// orthonormal basis of the Krylov subspace
+    v: Vec<P>,
+    /// columns of the upper triangular matrix obtained from the Hessenberg matrix by Givens
+    /// rotations
+    h: Vec<Vec<F>>,
+    /// cosines of the Givens rotations
+    cs: Vec<F>,
+    /// sines of the Givens rotations
+    sn: Vec<F>,
+    /// rotated right hand side of the least squares problem
+    g: Vec<F>,
+}
+
+impl<P, F> GMRES<P, F, IdentityPreconditioner>
+where
+    P: Default,
+    F: ArgminFloat,
+{
+    /// Constructor
+    ///
+    /// Parameters:
+    ///
+    /// `b`: right hand side of `A * x = b`
+    ///
+    /// `restart`: number of iterations after which GMRES is restarted. Must be at least 1.
+    pub fn new(b: P, restart: usize) -> Result<Self, Error> {
+        if restart < 1 {
+            return Err(ArgminError::InvalidParameter {
+                text: "GMRES: restart must be at least 1.".to_string(),
+            }
+            .into());
+        }
+        Ok(GMRES {
+            b,
+            b_norm: F::nan(),
+            restart,
+            tol: F::epsilon().sqrt(),
+            preconditioner: IdentityPreconditioner,
+            x0: P::default(),
+            v: vec![],
+            h: vec![],
+            cs: vec![],
+            sn: vec![],
+            g: vec![],
+        })
+    }
+}
+
+impl<P, F, M> GMRES<P, F, M>
+where
+    F: ArgminFloat,
+{
+    /// Set preconditioner
+    pub fn with_preconditioner<M2>(self, preconditioner: M2) -> GMRES<P, F, M2> {
+        GMRES {
+            b: self.b,
+            b_norm: self.b_norm,
+            restart: self.restart,
+            tol: self.tol,
+            preconditioner,
+            x0: self.x0,
+            v: self.v,
+            h: self.h,
+            cs: self.cs,
+            sn: self.sn,
+            g: self.g,
+        }
+    }
+
+    /// Set tolerance for the relative residual. Defaults to `sqrt(EPSILON)`.
+    pub fn with_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "GMRES: tol must be positive.".to_string(),
+            }
+            .into());
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Solve the upper triangular system `R * y = g`
+    fn solve_triangular(&self) -> Vec<F> {
+        let k = self.h.len();
+        let mut y = self.g[..k].to_vec();
+        for i in (0..k).rev() {
+            for j in (i + 1)..k {
+                y[i] = y[i] - self.h[j][i] * y[j];
+            }
+            y[i] = y[i] / self.h[i][i];
+        }
+        y
+    }
+}
+
+impl<O, P, F, M> Solver<O> for GMRES<P, F, M>
+where
+    O: CostFunction<Param = P, Output = P, Float = F>,
+    P: Clone
+        + Serialize
+        + DeserializeOwned
+        + ArgminSub<P, P>
+        + ArgminAdd<P, P>
+        + ArgminMul<F, P>
+        + ArgminDot<P, F>
+        + ArgminNorm<F>
+        + ArgminZeroLike
+        + ArgminScaledAddAssign<P, F>
+        + ArgminScaledSubAssign<P, F>,
+    F: ArgminFloat,
+    M: ArgminPreconditioner<P> + Clone + Serialize + DeserializeOwned,
+{
+    const NAME: &'static str = "GMRES";
+
+    fn init(
+        &mut self,
+        _op: &mut OpWrapper<O>,
+        _state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        self.b_norm = self.b.norm();
+        self.v.clear();
+        Ok(None)
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let zero = F::from_f64(0.0).unwrap();
+
+        // start a new cycle
+        if self.v.is_empty() {
+            self.x0 = state.get_param();
+            let r = self.b.sub(&op.apply(&self.x0)?);
+            let beta = r.norm();
+            if beta <= zero {
+                return Ok(ArgminIterData::new()
+                    .param(self.x0.clone())
+                    .cost(beta)
+                    .kv(make_kv!("residual" => beta;))
+                    .termination_reason(TerminationReason::TargetPrecisionReached));
+            }
+            self.v.push(r.mul(&(F::from_f64(1.0).unwrap() / beta)));
+            self.h.clear();
+            self.cs.clear();
+            self.sn.clear();
+            self.g = vec![beta];
+        }
+        let j = self.h.len();
+
+        // Arnoldi step with modified Gram-Schmidt
+        let mut w = op.apply(&self.preconditioner.precondition(&self.v[j])?)?;
+        let mut hcol = Vec::with_capacity(j + 2);
+        for v_i in self.v.iter() {
+            let h_ij = w.dot(v_i);
+            w.scaled_sub_assign(&h_ij, v_i);
+            hcol.push(h_ij);
+        }
+        let h_next = w.norm();
+
+        // apply previous Givens rotations to the new column, then eliminate h_next
+        for i in 0..j {
+            let t = self.cs[i] * hcol[i] + self.sn[i] * hcol[i + 1];
+            hcol[i + 1] = -self.sn[i] * hcol[i] + self.cs[i] * hcol[i + 1];
+            hcol[i] = t;
+        }
+        let denom = hcol[j].hypot(h_next);
+        if denom <= zero {
+            return Err(ArgminError::ConditionViolated {
+                text: "GMRES: breakdown, system matrix may be singular.".to_string(),
+            }
+            .into());
+        }
+        let (c, s) = (hcol[j] / denom, h_next / denom);
+        hcol[j] = denom;
+        self.cs.push(c);
+        self.sn.push(s);
+        let g_j = self.g[j];
+        self.g[j] = c * g_j;
+        self.g.push(-s * g_j);
+        self.h.push(hcol);
+        let residual = self.g[j + 1].abs();
+
+        // x = x0 + M^{-1} * V * y
+        let y = self.solve_triangular();
+        let mut vy = self.v[0].zero_like();
+        for (y_i, v_i) in y.iter().zip(self.v.iter()) {
+            vy.scaled_add_assign(y_i, v_i);
+        }
+        let x = self.x0.add(&self.preconditioner.precondition(&vy)?);
+
+        // restart after `restart` iterations or if the Krylov subspace is invariant under A, in
+        // which case x is the exact solution
+        if j + 1 >= self.restart || h_next <= zero {
+            self.v.clear();
+        } else {
+            self.v.push(w.mul(&(F::from_f64(1.0).unwrap() / h_next)));
+        }
+
+        Ok(ArgminIterData::new()
+            .param(x)
+            .cost(residual)
+            .kv(make_kv!("residual" => residual;)))
+    }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
+        if state.get_cost() <= self.tol * self.b_norm {
+            TerminationReason::TargetPrecisionReached
+        } else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::conjugategradient::JacobiPreconditioner;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(gmres, GMRES<Vec<f64>, f64>);
+
+    /// Non-symmetric tridiagonal matrix of a discretized convection-diffusion equation
+    #[derive(Clone, Default, Serialize, Deserialize)]
+    struct ConvectionDiffusion {
+        a: Vec<Vec<f64>>,
+    }
+
+    impl ConvectionDiffusion {
+        fn new(n: usize) -> Self {
+            let mut a = vec![vec![0.0; n]; n];
+            for i in 0..n {
+                a[i][i] = 2.0 + i as f64;
+                if i > 0 {
+                    a[i][i - 1] = -1.5;
+                    a[i - 1][i] = -0.5;
+                }
+            }
+            ConvectionDiffusion { a }
+        }
+    }
+
+    impl CostFunction for ConvectionDiffusion {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, p: &Vec<f64>) -> Result<Vec<f64>, Error> {
+            Ok(self.a.dot(p))
+        }
+    }
+
+    fn check_solution(op: &ConvectionDiffusion, x: &[f64], b: &[f64]) {
+        let ax = op.a.dot(&x.to_vec());
+        for (ax, b) in ax.iter().zip(b.iter()) {
+            assert!((ax - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_parameters() {
+        assert!(GMRES::<Vec<f64>, f64>::new(vec![1.0], 0).is_err());
+        let gmres: GMRES<Vec<f64>, f64> = GMRES::new(vec![1.0], 10).unwrap();
+        assert!(gmres.clone().with_tol(0.0).is_err());
+        let GMRES { tol, .. } = gmres.with_tol(1e-4).unwrap();
+        assert!((tol - 1e-4).abs() < std::f64::EPSILON);
+    }
+
+    #[test]
+    fn test_full() {
+        let n = 20;
+        let op = ConvectionDiffusion::new(n);
+        let b = vec![1.0; n];
+        let res = Executor::new(op.clone(), GMRES::new(b.clone(), n).unwrap(), vec![0.0; n])
+            .max_iters(n as u64)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        check_solution(&op, &res.state.get_best_param(), &b);
+    }
+
+    #[test]
+    fn test_restarted() {
+        let n = 20;
+        let op = ConvectionDiffusion::new(n);
+        let b = (0..n).map(|i| i as f64).collect::<Vec<f64>>();
+        let res = Executor::new(op.clone(), GMRES::new(b.clone(), 4).unwrap(), vec![0.0; n])
+            .max_iters(1000)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        check_solution(&op, &res.state.get_best_param(), &b);
+    }
+
+    #[test]
+    fn test_preconditioned() {
+        let n = 20;
+        let op = ConvectionDiffusion::new(n);
+        let b = vec![1.0; n];
+        let diag = (0..n).map(|i| op.a[i][i]).collect();
+        let solver = GMRES::new(b.clone(), 4)
+            .unwrap()
+            .with_preconditioner(JacobiPreconditioner::new(diag));
+        let res = Executor::new(op.clone(), solver, vec![0.0; n])
+            .max_iters(1000)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        check_solution(&op, &res.state.get_best_param(), &b);
+    }
+}
@@ -0,0 +1,29 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Krylov subspace methods for non-symmetric linear systems
+//!
+//! * [GMRES](gmres/struct.GMRES.html)
+//! * [BiCGSTAB](bicgstab/struct.BiCGSTAB.html)
+//!
+//! Like [`ConjugateGradient`](../conjugategradient/cg/struct.ConjugateGradient.html), these
+//! solvers only require `apply` to compute the product `A * x` of the system matrix with a
+//! vector. Both can be preconditioned with any
+//! [`ArgminPreconditioner`](../conjugategradient/preconditioner/trait.ArgminPreconditioner.html).
+//!
+//! # References:
+//!
+//! [0] Yousef Saad (2003). Iterative Methods for Sparse Linear Systems. SIAM.
+//! ISBN 0-89871-534-2.
+
+/// Biconjugate gradient stabilized method
+pub mod bicgstab;
+/// Generalized minimal residual method
+pub mod gmres;
+
+pub use self::bicgstab::*;
+pub use self::gmres::*;
@@ -15,6 +15,7 @@
 //! kind. Amer. J. Math. 73, 615–624
 //! [1] https://en.wikipedia.org/wiki/Landweber_iteration
 
+use crate::core::capabilities::{CostFunction, Gradient};
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -47,7 +48,7 @@ impl<F> Landweber<F> {
 
 impl<O, F> Solver<O> for Landweber<F>
 where
-    O: ArgminOp<Float = F>,
+    O: CostFunction<Float = F> + Gradient,
     O::Param: ArgminScaledSub<O::Param, O::Float, O::Param>,
     F: ArgminFloat,
 {
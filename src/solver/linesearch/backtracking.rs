@@ -7,6 +7,7 @@
 
 //! * [Backtracking line search](struct.BacktrackingLineSearch.html)
 
+use crate::core::capabilities::{CostFunction, Gradient};
 use crate::prelude::*;
 use crate::solver::linesearch::condition::*;
 use serde::de::DeserializeOwned;
@@ -102,7 +103,7 @@ where
         + ArgminSub<P, P>
         + ArgminDot<P, F>
         + ArgminScaledAdd<P, F, P>,
-    O: ArgminOp<Param = P, Output = F, Float = F>,
+    O: CostFunction<Param = P, Output = F, Float = F> + Gradient,
     L: LineSearchCondition<P, F>,
     F: ArgminFloat,
 {
@@ -15,6 +15,7 @@
 //! and an efficient line search." SIAM J. Optim. 16(1), 2006, 170-192.
 //! DOI: https://doi.org/10.1137/030601880
 
+use crate::core::capabilities::{CostFunction, Gradient};
 use crate::prelude::*;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -249,7 +250,7 @@ where
         Ok(self)
     }
 
-    fn update<O: ArgminOp<Param = P, Output = F>>(
+    fn update<O: CostFunction<Param = P, Output = F> + Gradient>(
         &mut self,
         op: &mut OpWrapper<O>,
         (a_x, a_f, a_g): Triplet<F>,
@@ -309,7 +310,7 @@ where
     }
 
     /// double secant step
-    fn secant2<O: ArgminOp<Param = P, Output = F>>(
+    fn secant2<O: CostFunction<Param = P, Output = F> + Gradient>(
         &mut self,
         op: &mut OpWrapper<O>,
         (a_x, a_f, a_g): Triplet<F>,
@@ -351,7 +352,7 @@ where
         }
     }
 
-    fn calc<O: ArgminOp<Param = P, Output = F>>(
+    fn calc<O: CostFunction<Param = P, Output = F>>(
         &mut self,
         op: &mut OpWrapper<O>,
         alpha: F,
@@ -360,7 +361,7 @@ where
         op.apply(&tmp)
     }
 
-    fn calc_grad<O: ArgminOp<Param = P, Output = F>>(
+    fn calc_grad<O: CostFunction<Param = P, Output = F> + Gradient>(
         &mut self,
         op: &mut OpWrapper<O>,
         alpha: F,
@@ -421,7 +422,7 @@ where
 
 impl<P, O, F> Solver<O> for HagerZhangLineSearch<P, F>
 where
-    O: ArgminOp<Param = P, Output = F, Float = F>,
+    O: CostFunction<Param = P, Output = F, Float = F> + Gradient,
     P: Clone
         + Default
         + Serialize
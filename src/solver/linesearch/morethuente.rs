@@ -18,6 +18,7 @@
 //! decrease." ACM Trans. Math. Softw. 20, 3 (September 1994), 286-307.
 //! DOI: https://doi.org/10.1145/192115.192132
 
+use crate::core::capabilities::{CostFunction, Gradient};
 use crate::prelude::*;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -210,7 +211,7 @@ where
 
 impl<P, O, F> Solver<O> for MoreThuenteLineSearch<P, F>
 where
-    O: ArgminOp<Param = P, Output = F, Float = F>,
+    O: CostFunction<Param = P, Output = F, Float = F> + Gradient,
     P: Clone
         + Serialize
         + DeserializeOwned
@@ -5,16 +5,26 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+pub mod admm;
+pub mod bayesopt;
+pub mod bounds;
 pub mod brent;
 pub mod conjugategradient;
+pub mod coordinatedescent;
+pub mod directsearch;
 pub mod gaussnewton;
 pub mod goldensectionsearch;
 pub mod gradientdescent;
+pub mod krylov;
 pub mod landweber;
 pub mod linesearch;
 pub mod neldermead;
 pub mod newton;
+pub mod nonlinearsystem;
 pub mod particleswarm;
+pub mod proximal;
 pub mod quasinewton;
+pub mod rootfinding;
 pub mod simulatedannealing;
+pub mod stochastic;
 pub mod trustregion;
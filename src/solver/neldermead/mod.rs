@@ -8,8 +8,14 @@
 //! # References:
 //!
 //! [Wikipedia](https://en.wikipedia.org/wiki/Nelder%E2%80%93Mead_method)
+//!
+//! [1] Fuchang Gao and Lixing Han (2012). Implementing the Nelder-Mead simplex algorithm with
+//! adaptive parameters. Computational Optimization and Applications 51, 259-277.
 
+use crate::core::capabilities::CostFunction;
 use crate::prelude::*;
+pub use crate::solver::bounds::BoundHandling;
+use crate::solver::bounds::{apply_bounds, check_bounds, check_bounds_dim};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::default::Default;
 
@@ -31,11 +37,30 @@ use std::default::Default;
 /// 3) Contraction: (Parameter `rho`, default `0.5`)
 /// 4) Shrink: (Parameter `sigma`, default `0.5`)
 ///
+/// With `adaptive`, the parameters are chosen depending on the dimension `n` of the problem as
+/// `alpha = 1`, `gamma = 1 + 2/n`, `rho = 0.75 - 1/(2n)` and `sigma = 1 - 1/n` [1], which improves
+/// the performance for higher dimensional problems.
+///
+/// The initial simplex is either given explicitly via `with_initial_params` or constructed from a
+/// single point and per-dimension step sizes via `with_initial_point`.
+///
+/// The method terminates if the sample standard deviation of the cost function values at the
+/// vertices falls below `sd_tolerance` or if the diameter of the simplex (the largest distance of
+/// a vertex to the best vertex) falls below `diameter_tolerance`. With `max_restarts`, the simplex
+/// is rebuilt around the best vertex with the shape of the initial simplex instead of terminating,
+/// as long as the previous restart improved the cost function value.
+///
+/// Bounds can be enforced via `with_bounds` by clipping trial points to the bounds, by reflecting
+/// them at the bounds or by redrawing violating components randomly (see `BoundHandling`).
+///
 /// [Example](https://github.com/argmin-rs/argmin/blob/master/examples/neldermead.rs)
 ///
 /// # References:
 ///
 /// [Wikipedia](https://en.wikipedia.org/wiki/Nelder%E2%80%93Mead_method)
+///
+/// [1] Fuchang Gao and Lixing Han (2012). Implementing the Nelder-Mead simplex algorithm with
+/// adaptive parameters. Computational Optimization and Applications 51, 259-277.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct NelderMead<P, F> {
     /// alpha
@@ -50,6 +75,22 @@ pub struct NelderMead<P, F> {
     params: Vec<(P, F)>,
     /// Sample standard deviation tolerance
     sd_tolerance: F,
+    /// Simplex diameter tolerance
+    diameter_tolerance: F,
+    /// Choose parameters depending on the dimension
+    adaptive: bool,
+    /// Offsets of the vertices of the initial simplex to its first vertex
+    offsets: Vec<P>,
+    /// Maximum number of restarts
+    max_restarts: u64,
+    /// Number of restarts so far
+    restarts: u64,
+    /// Best cost function value at the last restart
+    restart_cost: F,
+    /// Lower and upper bounds
+    bounds: Option<(P, P)>,
+    /// How trial points outside of the bounds are handled
+    bound_handling: BoundHandling,
 }
 
 impl<P, F> NelderMead<P, F>
@@ -66,6 +107,14 @@ where
             sigma: F::from_f64(0.5).unwrap(),
             params: vec![],
             sd_tolerance: F::epsilon(),
+            diameter_tolerance: F::from_f64(0.0).unwrap(),
+            adaptive: false,
+            offsets: vec![],
+            max_restarts: 0,
+            restarts: 0,
+            restart_cost: F::infinity(),
+            bounds: None,
+            bound_handling: BoundHandling::Clip,
         }
     }
 
@@ -75,12 +124,79 @@ where
         self
     }
 
+    /// Construct the initial simplex from the point `x0` and the vertices `x0 + steps[i] * e_i`,
+    /// where `e_i` is the `i`-th unit vector.
+    pub fn with_initial_point(mut self, x0: P, steps: P) -> Result<Self, Error>
+    where
+        P: ArgminComponents<F>,
+    {
+        let n = x0.num_components();
+        if steps.num_components() != n {
+            return Err(ArgminError::InvalidParameter {
+                text: "Nelder-Mead: steps must have the same dimension as the initial point."
+                    .to_string(),
+            }
+            .into());
+        }
+        if (0..n).any(|i| steps.component(i) == F::from_f64(0.0).unwrap()) {
+            return Err(ArgminError::InvalidParameter {
+                text: "Nelder-Mead: steps must be nonzero.".to_string(),
+            }
+            .into());
+        }
+        let mut params = Vec::with_capacity(n + 1);
+        params.push((x0.clone(), F::nan()));
+        for i in 0..n {
+            let mut xi = x0.clone();
+            xi.set_component(i, x0.component(i) + steps.component(i));
+            params.push((xi, F::nan()));
+        }
+        self.params = params;
+        Ok(self)
+    }
+
     /// Set Sample standard deviation tolerance
     pub fn sd_tolerance(mut self, tol: F) -> Self {
         self.sd_tolerance = tol;
         self
     }
 
+    /// Set simplex diameter tolerance
+    pub fn diameter_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "Nelder-Mead: diameter tolerance must be >= 0.".to_string(),
+            }
+            .into());
+        }
+        self.diameter_tolerance = tol;
+        Ok(self)
+    }
+
+    /// Choose alpha, gamma, rho and sigma depending on the dimension of the problem. Overrides
+    /// values set via `alpha`, `gamma`, `rho` and `sigma`.
+    pub fn adaptive(mut self) -> Self {
+        self.adaptive = true;
+        self
+    }
+
+    /// Set maximum number of restarts
+    pub fn max_restarts(mut self, max_restarts: u64) -> Self {
+        self.max_restarts = max_restarts;
+        self
+    }
+
+    /// Set lower and upper bounds
+    pub fn with_bounds(mut self, lower: P, upper: P, handling: BoundHandling) -> Result<Self, Error>
+    where
+        P: ArgminComponents<F>,
+    {
+        check_bounds("Nelder-Mead", &lower, &upper)?;
+        self.bounds = Some((lower, upper));
+        self.bound_handling = handling;
+        Ok(self)
+    }
+
     /// set alpha
     pub fn alpha(mut self, alpha: F) -> Result<Self, Error> {
         if alpha <= F::from_f64(0.0).unwrap() {
@@ -160,6 +276,17 @@ where
         x0.add(&x.sub(x0).mul(&self.rho))
     }
 
+    /// Set parameters depending on the dimension `n`
+    fn set_adaptive_params(&mut self, n: usize) {
+        let n = F::from_usize(n).unwrap();
+        let one = F::from_f64(1.0).unwrap();
+        let two = F::from_f64(2.0).unwrap();
+        self.alpha = one;
+        self.gamma = one + two / n;
+        self.rho = F::from_f64(0.75).unwrap() - one / (two * n);
+        self.sigma = one - one / n;
+    }
+
     /// Shrink
     fn shrink<S>(&mut self, mut cost: S) -> Result<(), Error>
     where
@@ -192,14 +319,18 @@ where
 
 impl<O, P, F> Solver<O> for NelderMead<P, F>
 where
-    O: ArgminOp<Output = F, Param = P, Float = F>,
+    O: CostFunction<Output = F, Param = P, Float = F>,
     P: Clone
         + Serialize
         + DeserializeOwned
         + ArgminScaledSub<O::Param, O::Float, O::Param>
         + ArgminSub<O::Param, O::Param>
         + ArgminAdd<O::Param, O::Param>
-        + ArgminMul<O::Float, O::Param>,
+        + ArgminMul<O::Float, O::Param>
+        + ArgminNorm<O::Float>
+        + ArgminMinMax
+        + ArgminRandom
+        + ArgminComponents<O::Float>,
     F: ArgminFloat + std::iter::Sum<O::Float>,
 {
     const NAME: &'static str = "Nelder-Mead method";
@@ -209,14 +340,31 @@ where
         op: &mut OpWrapper<O>,
         _state: &IterState<O>,
     ) -> Result<Option<ArgminIterData<O>>, Error> {
-        self.params = self
-            .params
+        if self.params.len() < 2 {
+            return Err(ArgminError::InvalidParameter {
+                text: "Nelder-Mead: initial simplex required (see `with_initial_params` and \
+                       `with_initial_point`)."
+                    .to_string(),
+            }
+            .into());
+        }
+        if let Some((lower, _)) = &self.bounds {
+            for (p, _) in self.params.iter() {
+                check_bounds_dim("Nelder-Mead", lower, p)?;
+            }
+        }
+        if self.adaptive {
+            self.set_adaptive_params(self.params.len() - 1);
+        }
+        let params = std::mem::take(&mut self.params);
+        for (p, _) in params {
+            let p = self.apply_bounds(p);
+            let c = op.apply(&p)?;
+            self.params.push((p, c));
+        }
+        self.offsets = self.params[1..]
             .iter()
-            .cloned()
-            .map(|(p, _)| {
-                let c = op.apply(&p).unwrap();
-                (p, c)
-            })
+            .map(|(p, _)| p.sub(&self.params[0].0))
             .collect();
         self.sort_param_vecs();
 
@@ -236,7 +384,7 @@ where
 
         let x0 = self.calculate_centroid();
 
-        let xr = self.reflect(&x0, &self.params[num_param - 1].0);
+        let xr = self.apply_bounds(self.reflect(&x0, &self.params[num_param - 1].0));
         let xr_cost = op.apply(&xr)?;
 
         let action = if xr_cost < self.params[num_param - 2].1 && xr_cost >= self.params[0].1 {
@@ -246,7 +394,7 @@ where
             "reflection"
         } else if xr_cost < self.params[0].1 {
             // expansion
-            let xe = self.expand(&x0, &xr);
+            let xe = self.apply_bounds(self.expand(&x0, &xr));
             let xe_cost = op.apply(&xe)?;
             if xe_cost < xr_cost {
                 self.params.last_mut().unwrap().0 = xe;
@@ -256,43 +404,108 @@ where
                 self.params.last_mut().unwrap().1 = xr_cost;
             }
             "expansion"
-        } else if xr_cost >= self.params[num_param - 2].1 {
+        } else {
             // contraction
             let xc = self.contract(&x0, &self.params[num_param - 1].0);
             let xc_cost = op.apply(&xc)?;
             if xc_cost < self.params[num_param - 1].1 {
                 self.params.last_mut().unwrap().0 = xc;
                 self.params.last_mut().unwrap().1 = xc_cost;
+                "contraction"
+            } else {
+                // shrink
+                self.shrink(|x| op.apply(x))?;
+                "shrink"
             }
-            "contraction"
-        } else {
-            // shrink
-            self.shrink(|x| op.apply(x))?;
-            "shrink"
         };
 
         self.sort_param_vecs();
 
+        let restart = self.converged()
+            && self.restarts < self.max_restarts
+            && self.params[0].1 < self.restart_cost;
+        if restart {
+            self.restart(op)?;
+        }
+
         Ok(ArgminIterData::new()
             .param(self.params[0].0.clone())
             .cost(self.params[0].1)
-            .kv(make_kv!("action" => action;)))
+            .kv(make_kv!("action" => action; "restarts" => self.restarts;)))
     }
 
     fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason {
+        if self.converged() {
+            return TerminationReason::TargetToleranceReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+impl<P, F> NelderMead<P, F>
+where
+    P: Clone
+        + ArgminAdd<P, P>
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminNorm<F>
+        + ArgminMinMax
+        + ArgminRandom
+        + ArgminComponents<F>,
+    F: ArgminFloat + std::iter::Sum<F>,
+{
+    /// Enforce the bounds on `x`
+    fn apply_bounds(&self, x: P) -> P {
+        match &self.bounds {
+            None => x,
+            Some((lower, upper)) => apply_bounds(x, lower, upper, self.bound_handling),
+        }
+    }
+
+    /// Sample standard deviation of the cost function values
+    fn cost_sd(&self) -> F {
         let n = F::from_usize(self.params.len()).unwrap();
         let c0: F = self.params.iter().map(|(_, c)| *c).sum::<F>() / n;
-        let s: F = (F::from_f64(1.0).unwrap() / (n - F::from_f64(1.0).unwrap())
+        (F::from_f64(1.0).unwrap() / (n - F::from_f64(1.0).unwrap())
             * self
                 .params
                 .iter()
                 .map(|(_, c)| (*c - c0).powi(2))
                 .sum::<F>())
-        .sqrt();
-        if s < self.sd_tolerance {
-            return TerminationReason::TargetToleranceReached;
+        .sqrt()
+    }
+
+    /// Largest distance of a vertex to the best vertex
+    fn diameter(&self) -> F {
+        self.params[1..]
+            .iter()
+            .map(|(p, _)| p.sub(&self.params[0].0).norm())
+            .fold(F::from_f64(0.0).unwrap(), |a, b| a.max(b))
+    }
+
+    /// Check the convergence criteria
+    fn converged(&self) -> bool {
+        self.cost_sd() < self.sd_tolerance || self.diameter() < self.diameter_tolerance
+    }
+
+    /// Rebuild the simplex around the best vertex
+    fn restart<O>(&mut self, op: &mut OpWrapper<O>) -> Result<(), Error>
+    where
+        O: CostFunction<Output = F, Param = P, Float = F>,
+    {
+        self.restarts += 1;
+        self.restart_cost = self.params[0].1;
+        let best = self.params[0].clone();
+        let mut params = Vec::with_capacity(self.params.len());
+        params.push(best.clone());
+        for offset in self.offsets.iter() {
+            let p = self.apply_bounds(best.0.add(offset));
+            let c = op.apply(&p)?;
+            params.push((p, c));
         }
-        TerminationReason::NotTerminated
+        self.params = params;
+        self.sort_param_vecs();
+        Ok(())
     }
 }
 
@@ -300,7 +513,191 @@ where
 mod tests {
     use super::*;
     use crate::test_trait_impl;
+    use argmin_testfunctions::rosenbrock_2d;
     type Operator = MinimalNoOperator;
 
     test_trait_impl!(nelder_mead, NelderMead<Operator, f64>);
+
+    #[test]
+    fn test_initial_point() {
+        let solver = NelderMead::new()
+            .with_initial_point(vec![-1.2, 1.0], vec![0.5, 0.5])
+            .unwrap()
+            .sd_tolerance(1e-12);
+        let op = FnOp::new(|p: &Vec<f64>| Ok(rosenbrock_2d(p, 1.0, 100.0)));
+        let res = Executor::new(op, solver, vec![])
+            .max_iters(1000)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetToleranceReached
+        );
+        let p = res.state.get_best_param();
+        assert!((p[0] - 1.0).abs() < 1e-4);
+        assert!((p[1] - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_initial_point_errors() {
+        let solver: NelderMead<Vec<f64>, f64> = NelderMead::new();
+        assert!(solver
+            .clone()
+            .with_initial_point(vec![0.0, 0.0], vec![1.0])
+            .is_err());
+        assert!(solver
+            .clone()
+            .with_initial_point(vec![0.0, 0.0], vec![1.0, 0.0])
+            .is_err());
+        assert!(solver.clone().diameter_tolerance(-1.0).is_err());
+        // no initial simplex
+        let op = FnOp::new(|p: &Vec<f64>| Ok(p[0]));
+        assert!(Executor::new(op, solver, vec![0.0]).run().is_err());
+    }
+
+    #[derive(Clone, Serialize, Deserialize)]
+    struct Failing {}
+
+    impl ArgminOp for Failing {
+        type Param = Vec<f64>;
+        type Output = f64;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, _p: &Self::Param) -> Result<Self::Output, Error> {
+            Err(ArgminError::ConditionViolated {
+                text: "cost".to_string(),
+            }
+            .into())
+        }
+    }
+
+    #[test]
+    fn test_error_propagation() {
+        let op = Failing {};
+        let solver = NelderMead::new()
+            .with_initial_point(vec![0.0], vec![1.0])
+            .unwrap();
+        assert!(Executor::new(op, solver, vec![]).run().is_err());
+    }
+
+    #[test]
+    fn test_adaptive() {
+        let n = 10;
+        let op = FnOp::new(|p: &Vec<f64>| {
+            Ok(p.iter()
+                .enumerate()
+                .map(|(i, x)| (i as f64 + 1.0) * (x - 1.0).powi(2))
+                .sum::<f64>())
+        });
+        let solver = NelderMead::new()
+            .with_initial_point(vec![0.0; n], vec![0.5; n])
+            .unwrap()
+            .adaptive()
+            .sd_tolerance(0.0)
+            .diameter_tolerance(1e-8)
+            .unwrap();
+        let res = Executor::new(op, solver, vec![])
+            .max_iters(20000)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetToleranceReached
+        );
+        for x in res.state.get_best_param() {
+            assert!((x - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        for handling in [BoundHandling::Clip, BoundHandling::Reflect] {
+            let op = FnOp::new(|p: &Vec<f64>| Ok((p[0] - 3.0).powi(2) + (p[1] + 1.0).powi(2)));
+            let solver = NelderMead::new()
+                .with_initial_point(vec![0.0, 0.0], vec![0.5, 0.5])
+                .unwrap()
+                .with_bounds(vec![-2.0, 0.0], vec![2.0, 2.0], handling)
+                .unwrap()
+                .sd_tolerance(1e-14);
+            let res = Executor::new(op, solver, vec![])
+                .max_iters(1000)
+                .run()
+                .unwrap();
+            let p = res.state.get_best_param();
+            assert!((p[0] - 2.0).abs() < 1e-5);
+            assert!(p[1].abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_apply_bounds() {
+        let solver: NelderMead<Vec<f64>, f64> = NelderMead::new()
+            .with_bounds(
+                vec![0.0, 0.0, 0.0],
+                vec![1.0, 1.0, 1.0],
+                BoundHandling::Reflect,
+            )
+            .unwrap();
+        assert_eq!(
+            solver.apply_bounds(vec![-0.25, 1.5, 0.5]),
+            vec![0.25, 0.5, 0.5]
+        );
+        // reflected component violates the other bound
+        assert_eq!(
+            solver.apply_bounds(vec![-3.0, 0.0, 1.0]),
+            vec![0.0, 0.0, 1.0]
+        );
+        let solver = solver
+            .with_bounds(vec![0.0; 3], vec![1.0; 3], BoundHandling::Clip)
+            .unwrap();
+        assert_eq!(
+            solver.apply_bounds(vec![-0.25, 1.5, 0.5]),
+            vec![0.0, 1.0, 0.5]
+        );
+    }
+
+    #[test]
+    fn test_bounds_errors() {
+        let solver: NelderMead<Vec<f64>, f64> = NelderMead::new();
+        let handling = BoundHandling::RandomReinit;
+        assert!(solver
+            .clone()
+            .with_bounds(vec![0.0, 1.0], vec![1.0, 0.0], handling)
+            .is_err());
+        assert!(solver
+            .clone()
+            .with_bounds(vec![0.0], vec![1.0, 1.0], handling)
+            .is_err());
+        // bounds do not match the initial simplex
+        let solver = solver
+            .with_initial_point(vec![0.5, 0.5], vec![0.1, 0.1])
+            .unwrap()
+            .with_bounds(vec![0.0], vec![1.0], handling)
+            .unwrap();
+        let op = FnOp::new(|p: &Vec<f64>| Ok(p[0]));
+        assert!(Executor::new(op, solver, vec![]).run().is_err());
+    }
+
+    #[test]
+    fn test_restarts() {
+        let op = FnOp::new(|p: &Vec<f64>| Ok(rosenbrock_2d(p, 1.0, 100.0)));
+        let solver = NelderMead::new()
+            .with_initial_point(vec![-1.2, 1.0], vec![0.5, 0.5])
+            .unwrap()
+            .diameter_tolerance(1e-3)
+            .unwrap()
+            .sd_tolerance(0.0);
+        let res = Executor::new(op.clone(), solver.clone().max_restarts(10), vec![])
+            .max_iters(1000)
+            .run()
+            .unwrap();
+        let res_no_restart = Executor::new(op, solver, vec![])
+            .max_iters(1000)
+            .run()
+            .unwrap();
+        assert!(res.state.get_best_cost() <= res_no_restart.state.get_best_cost());
+        assert!(res.state.get_iter() > res_no_restart.state.get_iter());
+    }
 }
@@ -12,13 +12,22 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::{CostFunction, ExactHessian, Gradient, HessianProduct};
 use crate::prelude::*;
-use crate::solver::conjugategradient::ConjugateGradient;
+use crate::solver::conjugategradient::{
+    ArgminPreconditioner, ConjugateGradient, IdentityPreconditioner,
+};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 /// The Newton-CG method (also called truncated Newton method) uses a modified CG to solve the
 /// Newton equations approximately. After a search direction is found, a line search is performed.
 ///
+/// The inner CG iterations can be preconditioned via `with_preconditioner`. By default, they
+/// multiply with the Hessian given by `Hessian::hessian`. With
+/// `with_hessian_product(HessianFree)`, only Hessian-vector products computed by
+/// `Gradient::hessian_vec` are used and the Hessian is never formed.
+///
 /// [Example](https://github.com/argmin-rs/argmin/blob/master/examples/newton_cg.rs)
 ///
 /// # References:
@@ -26,9 +35,13 @@ use serde::{Deserialize, Serialize};
 /// [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 /// Springer. ISBN 0-387-30303-0.
 #[derive(Clone, Serialize, Deserialize)]
-pub struct NewtonCG<L, F> {
+pub struct NewtonCG<L, F, M = IdentityPreconditioner, H = ExactHessian> {
     /// line search
     linesearch: L,
+    /// preconditioner of the CG subproblem
+    preconditioner: M,
+    /// computation of the products with the Hessian
+    hessian_product: H,
     /// curvature_threshold
     curvature_threshold: F,
     /// Tolerance for the stopping criterion based on cost difference
@@ -40,10 +53,36 @@ impl<L, F: ArgminFloat> NewtonCG<L, F> {
     pub fn new(linesearch: L) -> Self {
         NewtonCG {
             linesearch,
+            preconditioner: IdentityPreconditioner,
+            hessian_product: ExactHessian,
             curvature_threshold: F::from_f64(0.0).unwrap(),
             tol: F::epsilon(),
         }
     }
+}
+
+impl<L, F: ArgminFloat, M, H> NewtonCG<L, F, M, H> {
+    /// Set preconditioner of the CG subproblem
+    pub fn with_preconditioner<M2>(self, preconditioner: M2) -> NewtonCG<L, F, M2, H> {
+        NewtonCG {
+            linesearch: self.linesearch,
+            preconditioner,
+            hessian_product: self.hessian_product,
+            curvature_threshold: self.curvature_threshold,
+            tol: self.tol,
+        }
+    }
+
+    /// Set how the products with the Hessian are computed (default: `ExactHessian`)
+    pub fn with_hessian_product<H2>(self, hessian_product: H2) -> NewtonCG<L, F, M, H2> {
+        NewtonCG {
+            linesearch: self.linesearch,
+            preconditioner: self.preconditioner,
+            hessian_product,
+            curvature_threshold: self.curvature_threshold,
+            tol: self.tol,
+        }
+    }
 
     /// Set curvature threshold
     pub fn curvature_threshold(mut self, threshold: F) -> Self {
@@ -64,9 +103,9 @@ impl<L, F: ArgminFloat> NewtonCG<L, F> {
     }
 }
 
-impl<O, L, F> Solver<O> for NewtonCG<L, F>
+impl<O, L, F, M, H> Solver<O> for NewtonCG<L, F, M, H>
 where
-    O: ArgminOp<Output = F, Float = F>,
+    O: CostFunction<Output = F, Float = F> + Gradient,
     O::Param: Send
         + Sync
         + Clone
@@ -77,19 +116,16 @@ where
         + ArgminDot<O::Param, O::Float>
         + ArgminScaledAdd<O::Param, O::Float, O::Param>
         + ArgminMul<F, O::Param>
+        + ArgminMulAssign<F>
+        + ArgminSubAssign<O::Param>
+        + ArgminScaledAddAssign<O::Param, O::Float>
         + ArgminConj
         + ArgminZeroLike
         + ArgminNorm<O::Float>,
-    O::Hessian: Send
-        + Sync
-        + Default
-        + Clone
-        + Serialize
-        + Default
-        + ArgminInv<O::Hessian>
-        + ArgminDot<O::Param, O::Param>,
     L: Clone + ArgminLineSearch<O::Param, O::Float> + Solver<OpWrapper<O>>,
     F: ArgminFloat + Default + ArgminDiv<O::Float, O::Float> + ArgminNorm<O::Float> + ArgminConj,
+    M: ArgminPreconditioner<O::Param> + Clone + Serialize + DeserializeOwned,
+    H: HessianProduct<O> + Clone + Serialize + DeserializeOwned,
 {
     const NAME: &'static str = "Newton-CG";
 
@@ -100,42 +136,62 @@ where
     ) -> Result<ArgminIterData<O>, Error> {
         let param = state.get_param();
         let grad = op.gradient(&param)?;
-        let hessian = op.hessian(&param)?;
+
+        let hessian = self.hessian_product.hessian(op, &param)?;
+        let hessian_free = hessian.is_none();
 
         // Solve CG subproblem
-        let cg_op: CGSubProblem<O::Param, O::Hessian, O::Float> =
-            CGSubProblem::new(hessian.clone());
+        let cg_op = CGSubProblem::new(
+            op.take_op().unwrap(),
+            param.clone(),
+            grad.clone(),
+            hessian,
+            self.hessian_product.clone(),
+        );
         let mut cg_op = OpWrapper::new(cg_op);
 
-        let mut x_p = param.zero_like();
-        let mut x: O::Param = param.zero_like();
-        let mut cg = ConjugateGradient::new(grad.mul(&(F::from_f64(-1.0).unwrap())))?;
-
-        let mut cg_state = IterState::new(x_p.clone());
-        cg.init(&mut cg_op, &cg_state)?;
-        let grad_norm = grad.norm();
-        for iter in 0.. {
-            let data = cg.next_iter(&mut cg_op, &cg_state)?;
-            x = data.get_param().unwrap();
-            let p = cg.p_prev();
-            let curvature = p.dot(&hessian.dot(&p));
-            if curvature <= self.curvature_threshold {
-                if iter == 0 {
-                    x = grad.mul(&(F::from_f64(-1.0).unwrap()));
-                } else {
-                    x = x_p;
+        let x = (|| -> Result<O::Param, Error> {
+            let mut x_p = param.zero_like();
+            let mut x: O::Param = param.zero_like();
+            let mut cg = ConjugateGradient::new(grad.mul(&(F::from_f64(-1.0).unwrap())))?
+                .with_preconditioner(self.preconditioner.clone());
+
+            let mut cg_state = IterState::new(x_p.clone());
+            cg.init(&mut cg_op, &cg_state)?;
+            let grad_norm = grad.norm();
+            for iter in 0.. {
+                let data = cg.next_iter(&mut cg_op, &cg_state)?;
+                x = data.get_param().unwrap();
+                let curvature = cg.curvature();
+                if curvature <= self.curvature_threshold {
+                    if iter == 0 {
+                        x = grad.mul(&(F::from_f64(-1.0).unwrap()));
+                    } else {
+                        x = x_p;
+                    }
+                    break;
                 }
-                break;
-            }
-            if data.get_cost().unwrap()
-                <= F::from_f64(0.5).unwrap().min(grad_norm.sqrt()) * grad_norm
-            {
-                break;
+                if data.get_cost().unwrap()
+                    <= F::from_f64(0.5).unwrap().min(grad_norm.sqrt()) * grad_norm
+                {
+                    break;
+                }
+                cg_state.param(x.clone());
+                cg_state.cost(data.get_cost().unwrap());
+                x_p = x.clone();
             }
-            cg_state.param(x.clone());
-            cg_state.cost(data.get_cost().unwrap());
-            x_p = x.clone();
+            Ok(x)
+        })();
+
+        // The operator must be handed back before any error is propagated, otherwise it is lost.
+        // Without a Hessian, every application of the subproblem operator is one Hessian-vector
+        // product.
+        if hessian_free {
+            op.hessian_vec_func_count += cg_op.cost_func_count;
+            op.grad_func_count += cg_op.cost_func_count * O::HESSIAN_VEC_GRAD_EVALS;
         }
+        op.op = Some(cg_op.get_op().op);
+        let x = x?;
 
         // perform line search
         self.linesearch.set_search_direction(x);
@@ -173,48 +229,63 @@ where
     }
 }
 
+/// Operator of the CG subproblem `H(x) * p = -g(x)`. Products with the Hessian `H(x)` are
+/// computed by `hessian_product`.
 #[derive(Clone, Default, Serialize, Deserialize)]
-struct CGSubProblem<T, H, F> {
-    hessian: H,
-    phantom: std::marker::PhantomData<T>,
-    float: std::marker::PhantomData<F>,
+struct CGSubProblem<O: CostFunction, H> {
+    /// Operator of the outer problem
+    op: O,
+    /// Point at which the Hessian is evaluated
+    param: O::Param,
+    /// Gradient at `param`
+    grad: O::Param,
+    /// Hessian at `param`, if it is formed
+    hessian: Option<O::Hessian>,
+    /// computation of the products with the Hessian
+    hessian_product: H,
 }
 
-impl<T, H, F> CGSubProblem<T, H, F>
-where
-    T: Clone + Send + Sync,
-    H: Clone + Default + ArgminDot<T, T> + Send + Sync,
-{
+impl<O: CostFunction, H> CGSubProblem<O, H> {
     /// constructor
-    pub fn new(hessian: H) -> Self {
+    pub fn new(
+        op: O,
+        param: O::Param,
+        grad: O::Param,
+        hessian: Option<O::Hessian>,
+        hessian_product: H,
+    ) -> Self {
         CGSubProblem {
+            op,
+            param,
+            grad,
             hessian,
-            phantom: std::marker::PhantomData,
-            float: std::marker::PhantomData,
+            hessian_product,
         }
     }
 }
 
-impl<T, H, F> ArgminOp for CGSubProblem<T, H, F>
+impl<O, H> CostFunction for CGSubProblem<O, H>
 where
-    T: Clone + Default + Send + Sync + Serialize + serde::de::DeserializeOwned,
-    H: Clone + Default + ArgminDot<T, T> + Send + Sync + Serialize + serde::de::DeserializeOwned,
-    F: ArgminFloat,
+    O: CostFunction,
+    H: HessianProduct<O> + Clone + Serialize + DeserializeOwned,
 {
-    type Param = T;
-    type Output = T;
+    type Param = O::Param;
+    type Output = O::Param;
     type Hessian = ();
     type Jacobian = ();
-    type Float = F;
+    type Float = O::Float;
 
-    fn apply(&self, p: &T) -> Result<T, Error> {
-        Ok(self.hessian.dot(p))
+    fn apply(&self, p: &O::Param) -> Result<O::Param, Error> {
+        self.hessian_product
+            .product(&self.op, &self.param, &self.grad, self.hessian.as_ref(), p)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::capabilities::HessianFree;
+    use crate::solver::conjugategradient::JacobiPreconditioner;
     use crate::solver::linesearch::MoreThuenteLineSearch;
     use crate::test_trait_impl;
 
@@ -223,7 +294,42 @@ mod tests {
         NewtonCG<MoreThuenteLineSearch<Vec<f64>, f64>, f64>
     );
 
-    test_trait_impl!(cg_subproblem, CGSubProblem<Vec<f64>, Vec<Vec<f64>>, f64>);
+    test_trait_impl!(
+        cg_subproblem,
+        CGSubProblem<MinimalNoOperator, ExactHessian>
+    );
+
+    /// f(x) = 1/2 x^T A x - b^T x with A = diag(1, 2, ..., n), which only provides a gradient
+    #[derive(Clone, Default, Serialize, Deserialize)]
+    struct Quadratic {
+        b: Vec<f64>,
+    }
+
+    impl CostFunction for Quadratic {
+        type Param = Vec<f64>;
+        type Output = f64;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, p: &Vec<f64>) -> Result<f64, Error> {
+            Ok(p.iter()
+                .zip(self.b.iter())
+                .enumerate()
+                .map(|(i, (x, b))| 0.5 * (i + 1) as f64 * x.powi(2) - b * x)
+                .sum())
+        }
+    }
+
+    impl Gradient for Quadratic {
+        fn gradient(&self, p: &Vec<f64>) -> Result<Vec<f64>, Error> {
+            Ok(p.iter()
+                .zip(self.b.iter())
+                .enumerate()
+                .map(|(i, (x, b))| (i + 1) as f64 * x - b)
+                .collect())
+        }
+    }
 
     #[test]
     fn test_tolerance() {
@@ -236,4 +342,44 @@ mod tests {
 
         assert!((t - tol1).abs() < std::f64::EPSILON);
     }
+
+    #[test]
+    fn test_hessian_free() {
+        let n = 10;
+        let op = Quadratic { b: vec![1.0; n] };
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, f64> = MoreThuenteLineSearch::new();
+        let solver = NewtonCG::new(linesearch).with_hessian_product(HessianFree);
+        let res = Executor::new(op, solver, vec![0.0; n])
+            .max_iters(100)
+            .run()
+            .unwrap();
+
+        for (i, x) in res.state.get_best_param().iter().enumerate() {
+            assert!((x - 1.0 / (i + 1) as f64).abs() < 1e-6);
+        }
+        assert_eq!(res.state.get_hessian_func_count(), 0);
+        assert!(res.state.get_hessian_vec_func_count() > 0);
+        // every finite difference product evaluates the gradient once
+        assert!(res.state.get_grad_func_count() > res.state.get_hessian_vec_func_count());
+    }
+
+    #[test]
+    fn test_preconditioned() {
+        let n = 10;
+        let op = Quadratic { b: vec![1.0; n] };
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, f64> = MoreThuenteLineSearch::new();
+        // only approximates the Hessian, which avoids hitting the minimum exactly
+        let diag = (1..=n).map(|i| (i as f64).sqrt()).collect();
+        let solver = NewtonCG::new(linesearch)
+            .with_preconditioner(JacobiPreconditioner::new(diag))
+            .with_hessian_product(HessianFree);
+        let res = Executor::new(op, solver, vec![0.0; n])
+            .max_iters(100)
+            .run()
+            .unwrap();
+
+        for (i, x) in res.state.get_best_param().iter().enumerate() {
+            assert!((x - 1.0 / (i + 1) as f64).abs() < 1e-6);
+        }
+    }
 }
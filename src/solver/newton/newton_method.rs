@@ -10,6 +10,7 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::{CostFunction, Gradient, Hessian};
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::default::Default;
@@ -58,9 +59,13 @@ impl<F: ArgminFloat> Default for Newton<F> {
 
 impl<O, F> Solver<O> for Newton<F>
 where
-    O: ArgminOp<Float = F>,
+    O: CostFunction<Float = F> + Gradient + Hessian,
     O::Param: ArgminScaledSub<O::Param, O::Float, O::Param>,
-    O::Hessian: ArgminInv<O::Hessian> + ArgminDot<O::Param, O::Param>,
+    O::Hessian: ArgminLinSolve<O::Param>
+        + ArgminEye
+        + ArgminAdd<O::Hessian, O::Hessian>
+        + ArgminMul<O::Float, O::Hessian>
+        + ArgminMaxAbsDiagonal<O::Float>,
     F: ArgminFloat,
 {
     const NAME: &'static str = "Newton method";
@@ -73,8 +78,13 @@ where
         let param = state.get_param();
         let grad = op.gradient(&param)?;
         let hessian = op.hessian(&param)?;
-        let new_param = param.scaled_sub(&self.gamma, &hessian.inv()?.dot(&grad));
-        Ok(ArgminIterData::new().param(new_param))
+        // Singular or indefinite Hessians are regularized by adding a multiple of the identity,
+        // which is relative to the largest absolute diagonal entry of the Hessian
+        let (step, tau) = hessian.solve_regularized(&grad, F::from_f64(1e-3).unwrap())?;
+        let new_param = param.scaled_sub(&self.gamma, &step);
+        Ok(ArgminIterData::new()
+            .param(new_param)
+            .kv(make_kv!("regularization" => tau;)))
     }
 }
 
@@ -84,4 +94,72 @@ mod tests {
     use crate::test_trait_impl;
 
     test_trait_impl!(newton_method, Newton<f64>);
+
+    #[test]
+    fn test_vec_backend() {
+        // f(x) = 1/2 x^T A x - b^T x with A = [[4, 1], [1, 3]] and b = [1, 2]
+        let op = FnOp::new(|x: &Vec<f64>| {
+            Ok(
+                0.5 * (4.0 * x[0].powi(2) + 2.0 * x[0] * x[1] + 3.0 * x[1].powi(2))
+                    - x[0]
+                    - 2.0 * x[1],
+            )
+        })
+        .gradient(|x: &Vec<f64>| Ok(vec![4.0 * x[0] + x[1] - 1.0, x[0] + 3.0 * x[1] - 2.0]))
+        .hessian(|_: &Vec<f64>| Ok(vec![vec![4.0, 1.0], vec![1.0, 3.0]]));
+        let res = Executor::new(op, Newton::new(), vec![5.0, -3.0])
+            .max_iters(1)
+            .run()
+            .unwrap();
+        let param = res.state.get_param();
+        assert!((param[0] - 1.0 / 11.0).abs() < 1e-10);
+        assert!((param[1] - 7.0 / 11.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_indefinite_hessian() {
+        // f(x) = -1/2 x_0^2 + 2 x_1^2 has a saddle point at the origin
+        let op = FnOp::new(|x: &Vec<f64>| Ok(-0.5 * x[0].powi(2) + 2.0 * x[1].powi(2)))
+            .gradient(|x: &Vec<f64>| Ok(vec![-x[0], 4.0 * x[1]]))
+            .hessian(|_: &Vec<f64>| Ok(vec![vec![-1.0, 0.0], vec![0.0, 4.0]]));
+        let res = Executor::new(op, Newton::new(), vec![1.0, 1.0])
+            .max_iters(1)
+            .run()
+            .unwrap();
+        // the plain Newton step would move to the saddle point
+        let param = res.state.get_param();
+        assert!(param[0] > 1.0);
+        assert!(-0.5 * param[0].powi(2) + 2.0 * param[1].powi(2) < 1.5);
+    }
+
+    #[cfg(feature = "sprsl")]
+    #[test]
+    fn test_sparse_hessian() {
+        use sprs::CsMat;
+
+        // same problem as in `test_vec_backend`
+        let op = FnOp::new(|x: &Vec<f64>| {
+            Ok(
+                0.5 * (4.0 * x[0].powi(2) + 2.0 * x[0] * x[1] + 3.0 * x[1].powi(2))
+                    - x[0]
+                    - 2.0 * x[1],
+            )
+        })
+        .gradient(|x: &Vec<f64>| Ok(vec![4.0 * x[0] + x[1] - 1.0, x[0] + 3.0 * x[1] - 2.0]))
+        .hessian(|_: &Vec<f64>| {
+            Ok(CsMat::new(
+                (2, 2),
+                vec![0, 2, 4],
+                vec![0, 1, 0, 1],
+                vec![4.0, 1.0, 1.0, 3.0],
+            ))
+        });
+        let res = Executor::new(op, Newton::new(), vec![5.0, -3.0])
+            .max_iters(1)
+            .run()
+            .unwrap();
+        let param = res.state.get_param();
+        assert!((param[0] - 1.0 / 11.0).abs() < 1e-10);
+        assert!((param[1] - 7.0 / 11.0).abs() < 1e-10);
+    }
 }
@@ -0,0 +1,258 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+//! Springer. ISBN 0-387-30303-0.
+//!
+//! [1] C. G. Broyden (1965). A Class of Methods for Solving Nonlinear Simultaneous Equations.
+//! Mathematics of Computation 19(92), 577–593.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Update of the approximate inverse Jacobian used by `Broyden`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BroydenMethod {
+    /// "Good" Broyden update: Rank one update of the Jacobian, applied to the inverse via the
+    /// Sherman-Morrison formula
+    #[default]
+    Good,
+    /// "Bad" Broyden update: Rank one update of the inverse Jacobian
+    Bad,
+}
+
+/// Broyden's method solves the system of nonlinear equations `F(x) = 0` without evaluating the
+/// Jacobian of `F`. `F(x)` is computed by `apply`.
+///
+/// An approximation `H` of the inverse Jacobian is updated in every iteration with the secant
+/// condition `H * (F(x_{k+1}) - F(x_k)) = x_{k+1} - x_k` and the step `-H * F(x)` is taken. The
+/// initial approximation has to be provided, for instance the identity or the inverse of a finite
+/// difference Jacobian.
+///
+/// The solver terminates with `ResidualToleranceReached` once `||F(x)|| <= tol` and with
+/// `StepToleranceReached` once the norm of the step is below `step_tol`.
+///
+/// # References:
+///
+/// [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+///
+/// [1] C. G. Broyden (1965). A Class of Methods for Solving Nonlinear Simultaneous Equations.
+/// Mathematics of Computation 19(92), 577–593.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Broyden<P, H, F> {
+    /// approximation of the inverse Jacobian
+    inv_jacobian: H,
+    /// update method
+    method: BroydenMethod,
+    /// Tolerance for the norm of the residual
+    tol: F,
+    /// Tolerance for the norm of the step
+    step_tol: F,
+    /// F(x) at the current parameter vector
+    fx: P,
+    /// Norm of the last step
+    step_norm: F,
+}
+
+impl<P: Default, H, F: ArgminFloat> Broyden<P, H, F> {
+    /// Constructor
+    ///
+    /// Parameters:
+    ///
+    /// `init_inv_jacobian`: initial approximation of the inverse Jacobian
+    pub fn new(init_inv_jacobian: H) -> Self {
+        Broyden {
+            inv_jacobian: init_inv_jacobian,
+            method: BroydenMethod::Good,
+            tol: F::epsilon().sqrt(),
+            step_tol: F::epsilon(),
+            fx: P::default(),
+            step_norm: F::infinity(),
+        }
+    }
+
+    /// Set update method (default: `BroydenMethod::Good`)
+    pub fn with_method(mut self, method: BroydenMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Set tolerance for the norm of the residual `||F(x)||`
+    pub fn with_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "Broyden: tol must be positive.".to_string(),
+            }
+            .into());
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set tolerance for the norm of the step
+    pub fn with_step_tol(mut self, step_tol: F) -> Result<Self, Error> {
+        if step_tol < F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "Broyden: step_tol must be non-negative.".to_string(),
+            }
+            .into());
+        }
+        self.step_tol = step_tol;
+        Ok(self)
+    }
+}
+
+impl<O, P, H, F> Solver<O> for Broyden<P, H, F>
+where
+    O: CostFunction<Param = P, Output = P, Float = F>,
+    P: Clone
+        + Serialize
+        + DeserializeOwned
+        + ArgminSub<P, P>
+        + ArgminAdd<P, P>
+        + ArgminMul<F, P>
+        + ArgminDot<P, F>
+        + ArgminDot<P, H>
+        + ArgminNorm<F>,
+    H: Clone
+        + Serialize
+        + DeserializeOwned
+        + ArgminDot<P, P>
+        + ArgminAdd<H, H>
+        + ArgminMul<F, H>
+        + ArgminTranspose<H>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Broyden";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        self.fx = op.apply(&param)?;
+        self.step_norm = F::infinity();
+        Ok(Some(
+            ArgminIterData::new().param(param).cost(self.fx.norm()),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let param = state.get_param();
+        let s = self
+            .inv_jacobian
+            .dot(&self.fx)
+            .mul(&F::from_f64(-1.0).unwrap());
+        let new_param = param.add(&s);
+        let fx_new = op.apply(&new_param)?;
+        let y = fx_new.sub(&self.fx);
+
+        // u = s - H y
+        let u = s.sub(&self.inv_jacobian.dot(&y));
+        let (v, denom): (P, F) = match self.method {
+            // H + u (H^T s)^T / (s^T H y)
+            BroydenMethod::Good => {
+                let hts: P = self.inv_jacobian.clone().t().dot(&s);
+                let denom = hts.dot(&y);
+                (hts, denom)
+            }
+            // H + u y^T / (y^T y)
+            BroydenMethod::Bad => {
+                let denom = y.dot(&y);
+                (y, denom)
+            }
+        };
+        // skip the update if it is not well defined
+        if denom != F::from_f64(0.0).unwrap() && denom.is_finite() {
+            let uv: H = u.dot(&v);
+            self.inv_jacobian = self
+                .inv_jacobian
+                .add(&uv.mul(&(F::from_f64(1.0).unwrap() / denom)));
+        }
+
+        self.step_norm = s.norm();
+        self.fx = fx_new;
+
+        Ok(ArgminIterData::new().param(new_param).cost(self.fx.norm()))
+    }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
+        if state.get_cost() <= self.tol {
+            return TerminationReason::ResidualToleranceReached;
+        }
+        if self.step_norm <= self.step_tol {
+            return TerminationReason::StepToleranceReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(broyden, Broyden<Vec<f64>, Vec<Vec<f64>>, f64>);
+
+    /// F(x) = (x_0^2 + x_1^2 - 4, x_0 - x_1) with root (sqrt(2), sqrt(2)) for positive x
+    fn circle() -> FnOp<Vec<f64>, Vec<f64>, f64> {
+        FnOp::from_operator(|x: &Vec<f64>| Ok(vec![x[0].powi(2) + x[1].powi(2) - 4.0, x[0] - x[1]]))
+    }
+
+    /// Inverse of the Jacobian of `circle` at (x_0, x_1)
+    fn circle_inv_jacobian(x: &[f64]) -> Vec<Vec<f64>> {
+        let det = -2.0 * x[0] - 2.0 * x[1];
+        vec![
+            vec![-1.0 / det, -2.0 * x[1] / det],
+            vec![-1.0 / det, 2.0 * x[0] / det],
+        ]
+    }
+
+    #[test]
+    fn test_parameters() {
+        let broyden: Broyden<Vec<f64>, Vec<Vec<f64>>, f64> = Broyden::new(vec![vec![1.0]]);
+        assert!(broyden.clone().with_tol(0.0).is_err());
+        assert!(broyden.clone().with_step_tol(-1.0).is_err());
+        let Broyden { tol, method, .. } = broyden
+            .with_tol(1e-4)
+            .unwrap()
+            .with_method(BroydenMethod::Bad);
+        assert!((tol - 1e-4).abs() < std::f64::EPSILON);
+        assert_eq!(method, BroydenMethod::Bad);
+    }
+
+    #[test]
+    fn test_circle() {
+        for method in [BroydenMethod::Good, BroydenMethod::Bad].iter() {
+            let init = vec![2.0, 1.0];
+            let solver = Broyden::new(circle_inv_jacobian(&init)).with_method(*method);
+            let res = Executor::new(circle(), solver, init)
+                .max_iters(100)
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                TerminationReason::ResidualToleranceReached
+            );
+            let x = res.state.get_param();
+            assert!((x[0] - 2.0f64.sqrt()).abs() < 1e-6);
+            assert!((x[1] - 2.0f64.sqrt()).abs() < 1e-6);
+            // no Jacobian evaluations
+            assert_eq!(res.state.get_jacobian_func_count(), 0);
+        }
+    }
+}
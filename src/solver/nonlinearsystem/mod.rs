@@ -0,0 +1,27 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Solvers for systems of nonlinear equations `F(x) = 0`
+//!
+//! * [Newton-Raphson](newton_raphson/struct.NewtonRaphson.html)
+//! * [Broyden](broyden/struct.Broyden.html)
+//!
+//! `apply` computes `F(x)`, therefore `Param` and `Output` are both vectors of the same length.
+//! The cost is the norm `||F(x)||`.
+//!
+//! # References:
+//!
+//! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+//! Springer. ISBN 0-387-30303-0.
+
+/// Broyden's method
+pub mod broyden;
+/// Newton-Raphson method
+pub mod newton_raphson;
+
+pub use self::broyden::*;
+pub use self::newton_raphson::*;
@@ -0,0 +1,275 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+//! Springer. ISBN 0-387-30303-0.
+
+use crate::core::capabilities::{CostFunction, Jacobian};
+use crate::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// The Newton-Raphson method solves the system of nonlinear equations `F(x) = 0`. `F(x)` is
+/// computed by `apply` and its Jacobian by `jacobian`.
+///
+/// In each iteration the Newton step `d = -J(x)^{-1} F(x)` is computed. The step length is found
+/// by backtracking until the merit function `1/2 ||F(x)||^2` is sufficiently decreased (section
+/// 11.2 in [0]). The step length is reported in the `step_length` key of the observers.
+///
+/// The solver terminates with `ResidualToleranceReached` once `||F(x)|| <= tol` and with
+/// `StepToleranceReached` once the norm of the step is below `step_tol`.
+///
+/// # References:
+///
+/// [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NewtonRaphson<P, F> {
+    /// Tolerance for the norm of the residual
+    tol: F,
+    /// Tolerance for the norm of the step
+    step_tol: F,
+    /// Parameter of the sufficient decrease condition
+    c: F,
+    /// Factor by which the step length is reduced during backtracking
+    rho: F,
+    /// Maximum number of backtracking steps
+    max_backtracks: u64,
+    /// F(x) at the current parameter vector
+    fx: P,
+    /// Norm of the last step
+    step_norm: F,
+}
+
+impl<P: Default, F: ArgminFloat> NewtonRaphson<P, F> {
+    /// Constructor
+    pub fn new() -> Self {
+        NewtonRaphson {
+            tol: F::epsilon().sqrt(),
+            step_tol: F::epsilon(),
+            c: F::from_f64(1e-4).unwrap(),
+            rho: F::from_f64(0.5).unwrap(),
+            max_backtracks: 50,
+            fx: P::default(),
+            step_norm: F::infinity(),
+        }
+    }
+
+    /// Set tolerance for the norm of the residual `||F(x)||`
+    pub fn with_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "Newton-Raphson: tol must be positive.".to_string(),
+            }
+            .into());
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set tolerance for the norm of the step
+    pub fn with_step_tol(mut self, step_tol: F) -> Result<Self, Error> {
+        if step_tol < F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "Newton-Raphson: step_tol must be non-negative.".to_string(),
+            }
+            .into());
+        }
+        self.step_tol = step_tol;
+        Ok(self)
+    }
+
+    /// Set parameter `c` of the sufficient decrease condition
+    /// `||F(x + alpha * d)||^2 <= (1 - 2 * c * alpha) * ||F(x)||^2` and the factor `rho` by
+    /// which `alpha` is reduced during backtracking. Defaults are `c = 1e-4` and `rho = 0.5`.
+    pub fn with_backtracking(mut self, c: F, rho: F) -> Result<Self, Error> {
+        if c <= F::from_f64(0.0).unwrap() || c >= F::from_f64(0.5).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "Newton-Raphson: c must be in (0, 0.5).".to_string(),
+            }
+            .into());
+        }
+        if rho <= F::from_f64(0.0).unwrap() || rho >= F::from_f64(1.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "Newton-Raphson: rho must be in (0, 1).".to_string(),
+            }
+            .into());
+        }
+        self.c = c;
+        self.rho = rho;
+        Ok(self)
+    }
+}
+
+impl<P: Default, F: ArgminFloat> Default for NewtonRaphson<P, F> {
+    fn default() -> NewtonRaphson<P, F> {
+        NewtonRaphson::new()
+    }
+}
+
+impl<O, P, F> Solver<O> for NewtonRaphson<P, F>
+where
+    O: CostFunction<Param = P, Output = P, Float = F> + Jacobian,
+    P: Clone
+        + Serialize
+        + DeserializeOwned
+        + ArgminScaledAdd<P, F, P>
+        + ArgminMul<F, P>
+        + ArgminNorm<F>,
+    O::Jacobian: ArgminLinSolve<P>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Newton-Raphson";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        self.fx = op.apply(&param)?;
+        self.step_norm = F::infinity();
+        Ok(Some(
+            ArgminIterData::new().param(param).cost(self.fx.norm()),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let param = state.get_param();
+        let jacobian = op.jacobian(&param)?;
+        let d = jacobian
+            .lin_solve(&self.fx)?
+            .mul(&F::from_f64(-1.0).unwrap());
+
+        // backtracking on 1/2 ||F(x)||^2, whose directional derivative along d is -||F(x)||^2
+        let fx_norm2 = self.fx.norm().powi(2);
+        let mut alpha = F::from_f64(1.0).unwrap();
+        let mut backtracks = 0;
+        let (new_param, fx_new) = loop {
+            let new_param = param.scaled_add(&alpha, &d);
+            let fx_new = op.apply(&new_param)?;
+            if fx_new.norm().powi(2)
+                <= (F::from_f64(1.0).unwrap() - F::from_f64(2.0).unwrap() * self.c * alpha)
+                    * fx_norm2
+            {
+                break (new_param, fx_new);
+            }
+            if backtracks >= self.max_backtracks {
+                return Err(ArgminError::ConditionViolated {
+                    text: "Newton-Raphson: backtracking failed to decrease ||F(x)||.".to_string(),
+                }
+                .into());
+            }
+            alpha = alpha * self.rho;
+            backtracks += 1;
+        };
+
+        self.step_norm = alpha * d.norm();
+        self.fx = fx_new;
+
+        Ok(ArgminIterData::new()
+            .param(new_param)
+            .cost(self.fx.norm())
+            .kv(make_kv!("step_length" => alpha;)))
+    }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
+        if state.get_cost() <= self.tol {
+            return TerminationReason::ResidualToleranceReached;
+        }
+        if self.step_norm <= self.step_tol {
+            return TerminationReason::StepToleranceReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(newton_raphson, NewtonRaphson<Vec<f64>, f64>);
+
+    /// F(x) = (x_0^2 + x_1^2 - 4, x_0 - x_1) with root (sqrt(2), sqrt(2)) for positive x
+    fn circle() -> FnOp<Vec<f64>, Vec<f64>, f64, (), (), FnOpFunc<Vec<f64>, Vec<Vec<f64>>>> {
+        FnOp::<Vec<f64>, Vec<f64>, f64>::from_operator(|x: &Vec<f64>| {
+            Ok(vec![x[0].powi(2) + x[1].powi(2) - 4.0, x[0] - x[1]])
+        })
+        .jacobian(|x: &Vec<f64>| Ok(vec![vec![2.0 * x[0], 2.0 * x[1]], vec![1.0, -1.0]]))
+    }
+
+    #[test]
+    fn test_parameters() {
+        let nr: NewtonRaphson<Vec<f64>, f64> = NewtonRaphson::new();
+        assert!(nr.clone().with_tol(0.0).is_err());
+        assert!(nr.clone().with_step_tol(-1.0).is_err());
+        assert!(nr.clone().with_backtracking(0.5, 0.5).is_err());
+        assert!(nr.clone().with_backtracking(1e-4, 1.0).is_err());
+        let NewtonRaphson { tol, c, rho, .. } = nr
+            .with_tol(1e-4)
+            .unwrap()
+            .with_backtracking(0.1, 0.3)
+            .unwrap();
+        assert!((tol - 1e-4).abs() < std::f64::EPSILON);
+        assert!((c - 0.1).abs() < std::f64::EPSILON);
+        assert!((rho - 0.3).abs() < std::f64::EPSILON);
+    }
+
+    #[test]
+    fn test_circle() {
+        let res = Executor::new(circle(), NewtonRaphson::new(), vec![3.0, 1.0])
+            .max_iters(50)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::ResidualToleranceReached
+        );
+        let x = res.state.get_param();
+        assert!((x[0] - 2.0f64.sqrt()).abs() < 1e-8);
+        assert!((x[1] - 2.0f64.sqrt()).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_backtracking() {
+        // Without backtracking, the full Newton step from (-1.2, 1) overshoots
+        let op = FnOp::<Vec<f64>, Vec<f64>, f64>::from_operator(|x: &Vec<f64>| {
+            Ok(vec![10.0 * (x[1] - x[0].powi(2)), 1.0 - x[0]])
+        })
+        .jacobian(|x: &Vec<f64>| Ok(vec![vec![-20.0 * x[0], 10.0], vec![-1.0, 0.0]]));
+        let res = Executor::new(op, NewtonRaphson::new(), vec![-1.2, 1.0])
+            .max_iters(50)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::ResidualToleranceReached
+        );
+        let x = res.state.get_param();
+        assert!((x[0] - 1.0).abs() < 1e-8);
+        assert!((x[1] - 1.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_singular_jacobian() {
+        let op = FnOp::<Vec<f64>, Vec<f64>, f64>::from_operator(|x: &Vec<f64>| {
+            Ok(vec![x[0] + x[1] - 1.0, 2.0 * x[0] + 2.0 * x[1] - 1.0])
+        })
+        .jacobian(|_: &Vec<f64>| Ok(vec![vec![1.0, 1.0], vec![2.0, 2.0]]));
+        assert!(Executor::new(op, NewtonRaphson::new(), vec![0.0, 0.0])
+            .max_iters(10)
+            .run()
+            .is_err());
+    }
+}
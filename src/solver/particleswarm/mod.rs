@@ -7,21 +7,67 @@
 
 //! # References:
 //!
-//! TODO
+//! [0] James Kennedy and Russell Eberhart (1995). Particle swarm optimization. Proceedings of
+//! ICNN'95 - International Conference on Neural Networks, 1942-1948.
+//!
+//! [1] Yuhui Shi and Russell Eberhart (1998). A modified particle swarm optimizer. 1998 IEEE
+//! International Conference on Evolutionary Computation Proceedings, 69-73.
+//!
+//! [2] Maurice Clerc and James Kennedy (2002). The particle swarm - explosion, stability, and
+//! convergence in a multidimensional complex space. IEEE Transactions on Evolutionary
+//! Computation 6(1), 58-73.
+//!
+//! [3] James Kennedy and Rui Mendes (2002). Population structure and particle swarm performance.
+//! Proceedings of the 2002 Congress on Evolutionary Computation, 1671-1676.
 
+use crate::core::capabilities::CostFunction;
 use crate::prelude::*;
+pub use crate::solver::bounds::BoundHandling;
+use crate::solver::bounds::{apply_bounds, check_bounds};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std;
-use std::default::Default;
 
 /// Particle Swarm Optimization (PSO)
 ///
+/// Each particle moves through the search region with a velocity which is updated in every
+/// iteration as
+///
+/// `v = weight_momentum * v + weight_particle * r_1 * (p - x) + weight_swarm * r_2 * (g - x)`,
+///
+/// where `x` is the position of the particle, `p` the best position found by the particle, `g`
+/// the best position found in its neighborhood and `r_1` and `r_2` are uniformly distributed
+/// random numbers in `[0, 1]` (drawn per component).
+///
+/// The neighborhood is defined by the `Topology`: either the entire swarm (default), a ring or a
+/// von Neumann grid [3]. Local topologies converge slower but are less prone to premature
+/// convergence.
+///
+/// The inertia `weight_momentum` can be decreased linearly over the iterations [1]
+/// (`with_inertia_schedule`). Alternatively, the weights can be derived from Clerc's constriction
+/// coefficient [2] (`with_constriction`), which guarantees convergence of the swarm.
+///
+/// Velocities can be clamped to a fraction of the width of the search region
+/// (`with_velocity_clamping`). Particles leaving the search region are treated according to the
+/// `BoundHandling` (`with_bound_handling`). With `with_stall_iters`, the solver terminates if the best cost function
+/// value did not improve for a given number of iterations.
+///
 /// [Example](https://github.com/argmin-rs/argmin/blob/master/examples/particleswarm.rs)
 ///
 /// # References:
 ///
-/// TODO
-#[derive(Serialize, Deserialize)]
+/// [0] James Kennedy and Russell Eberhart (1995). Particle swarm optimization. Proceedings of
+/// ICNN'95 - International Conference on Neural Networks, 1942-1948.
+///
+/// [1] Yuhui Shi and Russell Eberhart (1998). A modified particle swarm optimizer. 1998 IEEE
+/// International Conference on Evolutionary Computation Proceedings, 69-73.
+///
+/// [2] Maurice Clerc and James Kennedy (2002). The particle swarm - explosion, stability, and
+/// convergence in a multidimensional complex space. IEEE Transactions on Evolutionary
+/// Computation 6(1), 58-73.
+///
+/// [3] James Kennedy and Rui Mendes (2002). Population structure and particle swarm performance.
+/// Proceedings of the 2002 Congress on Evolutionary Computation, 1671-1676.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ParticleSwarm<P, F> {
     particles: Vec<Particle<P, F>>,
     best_position: P,
@@ -34,6 +80,37 @@ pub struct ParticleSwarm<P, F> {
 
     search_region: (P, P),
     num_particles: usize,
+
+    /// Neighborhood structure
+    topology: Topology,
+    /// Initial inertia, final inertia and number of iterations for a linear decrease
+    inertia_schedule: Option<(F, F, u64)>,
+    /// Maximum velocity as fraction of the width of the search region
+    velocity_clamping: Option<F>,
+    /// Treatment of particles leaving the search region
+    bound_handling: BoundHandling,
+    /// Maximum number of iterations without improvement of the best cost
+    stall_iter_best_max: u64,
+    /// Minimum improvement of the best cost
+    stall_tol: F,
+    /// Number of iterations without improvement of the best cost
+    stall_iter_best: u64,
+    /// Best cost at the last improvement
+    stall_cost: F,
+}
+
+/// Neighborhood structure of the swarm
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Topology {
+    /// Every particle is attracted by the best position of the entire swarm
+    Global,
+    /// Every particle is attracted by the best position of itself and its two neighbors in a ring
+    Ring,
+    /// The particles are arranged on a two-dimensional grid which wraps around at its boundaries.
+    /// Every particle is attracted by the best position of itself and its four neighbors on the
+    /// grid. The grid has the largest number of rows less or equal to the square root of the
+    /// number of particles for which all rows are of equal length.
+    VonNeumann,
 }
 
 impl<P, F> ParticleSwarm<P, F>
@@ -45,8 +122,12 @@ where
     ///
     /// Parameters:
     ///
-    /// * `cost_function`: cost function
-    /// * `init_temp`: initial temperature
+    /// * `search_region`: lower and upper bounds of the search region. The lower bounds must not
+    ///   exceed the upper bounds.
+    /// * `num_particles`: number of particles
+    /// * `weight_momentum`: inertia weight
+    /// * `weight_particle`: attraction to the best position of the particle
+    /// * `weight_swarm`: attraction to the best position of the neighborhood
     pub fn new(
         search_region: (P, P),
         num_particles: usize,
@@ -54,81 +135,220 @@ where
         weight_particle: F,
         weight_swarm: F,
     ) -> Result<Self, Error> {
+        if num_particles == 0 {
+            return Err(ArgminError::InvalidParameter {
+                text: "ParticleSwarm: number of particles must be > 0.".to_string(),
+            }
+            .into());
+        }
+        check_bounds("ParticleSwarm", &search_region.0, &search_region.1)?;
         let particle_swarm = ParticleSwarm {
             particles: vec![],
-            best_position: P::rand_from_range(
-                // FIXME: random smart?
-                &search_region.0,
-                &search_region.1,
-            ),
+            best_position: search_region.0.zero_like(),
             best_cost: F::infinity(),
             weight_momentum,
             weight_particle,
             weight_swarm,
             search_region,
             num_particles,
+            topology: Topology::Global,
+            inertia_schedule: None,
+            velocity_clamping: None,
+            bound_handling: BoundHandling::Clip,
+            stall_iter_best_max: u64::MAX,
+            stall_tol: F::from_f64(0.0).unwrap(),
+            stall_iter_best: 0,
+            stall_cost: F::infinity(),
         };
 
         Ok(particle_swarm)
     }
 
-    fn initialize_particles<O: ArgminOp<Param = P, Output = F, Float = F>>(
+    /// Set topology
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Derive the weights from Clerc's constriction coefficient
+    /// `chi = 2 / |2 - phi - sqrt(phi^2 - 4 phi)|` with `phi = c1 + c2 > 4`: `weight_momentum = chi`,
+    /// `weight_particle = chi * c1` and `weight_swarm = chi * c2`. A common choice is
+    /// `c1 = c2 = 2.05`. Removes an inertia schedule.
+    pub fn with_constriction(mut self, c1: F, c2: F) -> Result<Self, Error> {
+        let phi = c1 + c2;
+        let four = F::from_f64(4.0).unwrap();
+        if c1 < F::from_f64(0.0).unwrap() || c2 < F::from_f64(0.0).unwrap() || phi <= four {
+            return Err(ArgminError::InvalidParameter {
+                text: "ParticleSwarm: constriction requires c1, c2 >= 0 and c1 + c2 > 4."
+                    .to_string(),
+            }
+            .into());
+        }
+        let two = F::from_f64(2.0).unwrap();
+        let chi = two / (two - phi - (phi * phi - four * phi).sqrt()).abs();
+        self.weight_momentum = chi;
+        self.weight_particle = chi * c1;
+        self.weight_swarm = chi * c2;
+        self.inertia_schedule = None;
+        Ok(self)
+    }
+
+    /// Decrease the inertia weight linearly from `start` to `end` during the first `iters`
+    /// iterations. Overrides `weight_momentum`.
+    pub fn with_inertia_schedule(mut self, start: F, end: F, iters: u64) -> Result<Self, Error> {
+        if iters == 0 {
+            return Err(ArgminError::InvalidParameter {
+                text: "ParticleSwarm: number of iterations of inertia schedule must be > 0."
+                    .to_string(),
+            }
+            .into());
+        }
+        self.inertia_schedule = Some((start, end, iters));
+        Ok(self)
+    }
+
+    /// Clamp the velocity of each component to `factor` times the width of the search region
+    pub fn with_velocity_clamping(mut self, factor: F) -> Result<Self, Error> {
+        if factor <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "ParticleSwarm: velocity clamping factor must be > 0.".to_string(),
+            }
+            .into());
+        }
+        self.velocity_clamping = Some(factor);
+        Ok(self)
+    }
+
+    /// Set treatment of particles leaving the search region (default: `BoundHandling::Clip`). With
+    /// `BoundHandling::Reflect`, the velocity is set to the effective displacement of the particle.
+    pub fn with_bound_handling(mut self, bound_handling: BoundHandling) -> Self {
+        self.bound_handling = bound_handling;
+        self
+    }
+
+    /// Terminate if the best cost function value did not improve by more than `tol` for `iters`
+    /// iterations
+    pub fn with_stall_iters(mut self, iters: u64, tol: F) -> Result<Self, Error> {
+        if tol < F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "ParticleSwarm: stall tolerance must be >= 0.".to_string(),
+            }
+            .into());
+        }
+        self.stall_iter_best_max = iters;
+        self.stall_tol = tol;
+        Ok(self)
+    }
+
+    fn initialize_particles<O: CostFunction<Param = P, Output = F, Float = F>>(
         &mut self,
         op: &mut OpWrapper<O>,
-    ) {
+    ) -> Result<(), Error> {
         self.particles = (0..self.num_particles)
             .map(|_| self.initialize_particle(op))
-            .collect();
-
-        self.best_position = self.get_best_position();
-        self.best_cost = op.apply(&self.best_position).unwrap();
-        // TODO unwrap evil
+            .collect::<Result<_, Error>>()?;
+
+        let best = &self.particles[self.get_best_index()];
+        self.best_position = best.position.clone();
+        self.best_cost = best.cost;
+        self.stall_cost = self.best_cost;
+        self.stall_iter_best = 0;
+        Ok(())
     }
 
-    fn initialize_particle<O: ArgminOp<Param = P, Output = F, Float = F>>(
-        &mut self,
+    fn initialize_particle<O: CostFunction<Param = P, Output = F, Float = F>>(
+        &self,
         op: &mut OpWrapper<O>,
-    ) -> Particle<P, F> {
+    ) -> Result<Particle<P, F>, Error> {
         let (min, max) = &self.search_region;
         let delta = max.sub(min);
         let delta_neg = delta.mul(&F::from_f64(-1.0).unwrap());
 
-        let initial_position = O::Param::rand_from_range(min, max);
-        let initial_cost = op.apply(&initial_position).unwrap(); // FIXME do not unwrap
+        let initial_position = P::rand_from_range(min, max);
+        let initial_cost = op.apply(&initial_position)?;
 
-        Particle {
+        Ok(Particle {
             position: initial_position.clone(),
-            velocity: O::Param::rand_from_range(&delta_neg, &delta),
+            velocity: P::rand_from_range(&delta_neg, &delta),
             cost: initial_cost,
             best_position: initial_position,
             best_cost: initial_cost,
-        }
+        })
     }
 
-    fn get_best_position(&self) -> P {
-        let mut best: Option<(&P, F)> = None;
+    /// Index of the particle with the lowest best cost
+    fn get_best_index(&self) -> usize {
+        self.best_index(0..self.particles.len())
+    }
 
-        for p in &self.particles {
-            match best {
-                Some(best_sofar) => {
-                    if p.cost < best_sofar.1 {
-                        best = Some((&p.position, p.cost))
-                    }
-                }
-                None => best = Some((&p.position, p.cost)),
+    /// Index of the particle with the lowest best cost among `indices`
+    fn best_index<I: IntoIterator<Item = usize>>(&self, indices: I) -> usize {
+        indices
+            .into_iter()
+            .fold(None, |best: Option<usize>, i| match best {
+                Some(b) if self.particles[b].best_cost <= self.particles[i].best_cost => Some(b),
+                _ => Some(i),
+            })
+            .unwrap()
+    }
+
+    /// Neighbors of particle `i` (including `i`) for local topologies
+    fn neighbors(&self, i: usize) -> Vec<usize> {
+        let n = self.particles.len();
+        match self.topology {
+            Topology::Global => (0..n).collect(),
+            Topology::Ring => vec![(i + n - 1) % n, i, (i + 1) % n],
+            Topology::VonNeumann => {
+                let rows = (1..=n)
+                    .take_while(|r| r * r <= n)
+                    .filter(|r| (n / r) * r == n)
+                    .last()
+                    .unwrap();
+                let cols = n / rows;
+                let (row, col) = (i / cols, i % cols);
+                vec![
+                    i,
+                    ((row + rows - 1) % rows) * cols + col,
+                    ((row + 1) % rows) * cols + col,
+                    row * cols + (col + cols - 1) % cols,
+                    row * cols + (col + 1) % cols,
+                ]
             }
         }
+    }
+
+    /// Best positions of the neighborhoods of all particles
+    fn neighborhood_bests(&self) -> Vec<P> {
+        match self.topology {
+            Topology::Global => {
+                let best = &self.particles[self.get_best_index()].best_position;
+                vec![best.clone(); self.particles.len()]
+            }
+            _ => (0..self.particles.len())
+                .map(|i| {
+                    self.particles[self.best_index(self.neighbors(i))]
+                        .best_position
+                        .clone()
+                })
+                .collect(),
+        }
+    }
 
-        match best {
-            Some(best_sofar) => best_sofar.0.clone(),
-            None => panic!("Particles not initialized"),
+    /// Inertia weight in iteration `iter`
+    fn inertia(&self, iter: u64) -> F {
+        match self.inertia_schedule {
+            None => self.weight_momentum,
+            Some((start, end, iters)) => {
+                let t = F::from_u64(iter.min(iters)).unwrap() / F::from_u64(iters).unwrap();
+                start + (end - start) * t
+            }
         }
     }
 }
 
 impl<O, P, F> Solver<O> for ParticleSwarm<P, F>
 where
-    O: ArgminOp<Output = F, Param = P, Float = F>,
+    O: CostFunction<Output = F, Param = P, Float = F>,
     P: Position<F> + DeserializeOwned + Serialize,
     O::Hessian: Clone + Default,
     F: ArgminFloat,
@@ -137,51 +357,68 @@ where
 
     fn init(
         &mut self,
-        _op: &mut OpWrapper<O>,
+        op: &mut OpWrapper<O>,
         _state: &IterState<O>,
     ) -> Result<Option<ArgminIterData<O>>, Error> {
-        self.initialize_particles(_op);
+        self.initialize_particles(op)?;
 
-        Ok(None)
+        Ok(Some(
+            ArgminIterData::new()
+                .param(self.best_position.clone())
+                .cost(self.best_cost),
+        ))
     }
 
     /// Perform one iteration of algorithm
     fn next_iter(
         &mut self,
-        _op: &mut OpWrapper<O>,
-        _state: &IterState<O>,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
     ) -> Result<ArgminIterData<O>, Error> {
-        let zero = O::Param::zero_like(&self.best_position);
+        let zero = self.best_position.zero_like();
+        let inertia = self.inertia(state.get_iter());
+        let neighborhood_bests = self.neighborhood_bests();
+        let (min, max) = &self.search_region;
+        let max_velocity = self
+            .velocity_clamping
+            .map(|factor| max.sub(min).mul(&factor));
 
-        for p in self.particles.iter_mut() {
+        for (p, neighborhood_best) in self.particles.iter_mut().zip(neighborhood_bests.iter()) {
             // New velocity is composed of
             // 1) previous velocity (momentum),
             // 2) motion toward particle optimum and
-            // 3) motion toward global optimum.
+            // 3) motion toward neighborhood optimum.
 
             // ad 1)
-            let momentum = p.velocity.mul(&self.weight_momentum);
+            p.velocity.mul_assign(&inertia);
 
             // ad 2)
             let to_optimum = p.best_position.sub(&p.position);
-            let pull_to_optimum = O::Param::rand_from_range(&zero, &to_optimum);
-            let pull_to_optimum = pull_to_optimum.mul(&self.weight_particle);
+            let pull_to_optimum = P::rand_from_range(&zero, &to_optimum);
+            p.velocity
+                .scaled_add_assign(&self.weight_particle, &pull_to_optimum);
 
             // ad 3)
-            let to_global_optimum = self.best_position.sub(&p.position);
-            let pull_to_global_optimum =
-                O::Param::rand_from_range(&zero, &to_global_optimum).mul(&self.weight_swarm);
+            let to_neighborhood_optimum = neighborhood_best.sub(&p.position);
+            let pull_to_neighborhood_optimum = P::rand_from_range(&zero, &to_neighborhood_optimum);
+            p.velocity
+                .scaled_add_assign(&self.weight_swarm, &pull_to_neighborhood_optimum);
+
+            if let Some(vmax) = &max_velocity {
+                let vmin = vmax.mul(&F::from_f64(-1.0).unwrap());
+                p.velocity = P::min(&P::max(&p.velocity, &vmin), vmax);
+            }
 
-            p.velocity = momentum.add(&pull_to_optimum).add(&pull_to_global_optimum);
-            let new_position = p.position.add(&p.velocity);
+            let old_position = p.position.clone();
+            p.position.add_assign(&p.velocity);
 
-            // Limit to search window:
-            p.position = O::Param::min(
-                &O::Param::max(&new_position, &self.search_region.0),
-                &self.search_region.1,
-            );
+            // Handle particles leaving the search window:
+            p.position = apply_bounds(p.position.clone(), min, max, self.bound_handling);
+            if self.bound_handling == BoundHandling::Reflect {
+                p.velocity = p.position.sub(&old_position);
+            }
 
-            p.cost = _op.apply(&p.position)?;
+            p.cost = op.apply(&p.position)?;
             if p.cost < p.best_cost {
                 p.best_position = p.position.clone();
                 p.best_cost = p.cost;
@@ -193,6 +430,13 @@ where
             }
         }
 
+        if self.best_cost < self.stall_cost - self.stall_tol {
+            self.stall_cost = self.best_cost;
+            self.stall_iter_best = 0;
+        } else {
+            self.stall_iter_best += 1;
+        }
+
         // Store particles as population
         let population = self
             .particles
@@ -205,36 +449,51 @@ where
             .cost(self.best_cost)
             .population(population)
             .kv(make_kv!(
+                "inertia" => inertia;
+                "stall_iter_best" => self.stall_iter_best;
                 "particles" => &self.particles;
             ));
 
         Ok(out)
     }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason {
+        if self.stall_iter_best >= self.stall_iter_best_max {
+            return TerminationReason::BestStallIterExceeded;
+        }
+        TerminationReason::NotTerminated
+    }
 }
 
 /// Position
 pub trait Position<F: ArgminFloat>:
     Clone
-    + Default
     + ArgminAdd<Self, Self>
     + ArgminSub<Self, Self>
     + ArgminMul<F, Self>
+    + ArgminAddAssign<Self>
+    + ArgminMulAssign<F>
+    + ArgminScaledAddAssign<Self, F>
     + ArgminZeroLike
     + ArgminRandom
     + ArgminMinMax
+    + ArgminComponents<F>
     + std::fmt::Debug
 {
 }
 impl<T, F: ArgminFloat> Position<F> for T
 where
     T: Clone
-        + Default
         + ArgminAdd<Self, Self>
         + ArgminSub<Self, Self>
         + ArgminMul<F, Self>
+        + ArgminAddAssign<Self>
+        + ArgminMulAssign<F>
+        + ArgminScaledAddAssign<Self, F>
         + ArgminZeroLike
         + ArgminRandom
         + ArgminMinMax
+        + ArgminComponents<F>
         + std::fmt::Debug,
     F: ArgminFloat,
 {
@@ -242,7 +501,6 @@ where
 
 // trait_bound!(Position<F>
 // ; Clone
-// , Default
 // , ArgminAdd<Self, Self>
 // , ArgminSub<Self, Self>
 // , ArgminMul<F, Self>
@@ -266,3 +524,175 @@ pub struct Particle<T, F> {
     /// Best cost of particle so far
     best_cost: F,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(particleswarm, ParticleSwarm<Vec<f64>, f64>);
+
+    fn sphere(p: &[f64]) -> f64 {
+        p.iter().map(|x| (x - 1.0).powi(2)).sum()
+    }
+
+    fn swarm(num_particles: usize) -> ParticleSwarm<Vec<f64>, f64> {
+        ParticleSwarm::new(
+            (vec![-4.0, -4.0], vec![4.0, 4.0]),
+            num_particles,
+            0.5,
+            0.5,
+            0.5,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let mut solver = swarm(12).with_topology(Topology::Ring);
+        solver.particles = vec![
+            Particle {
+                position: vec![0.0],
+                velocity: vec![0.0],
+                cost: 0.0,
+                best_position: vec![0.0],
+                best_cost: 0.0,
+            };
+            12
+        ];
+        assert_eq!(solver.neighbors(0), vec![11, 0, 1]);
+        assert_eq!(solver.neighbors(11), vec![10, 11, 0]);
+        // 3 x 4 grid
+        let solver = solver.with_topology(Topology::VonNeumann);
+        assert_eq!(solver.neighbors(0), vec![0, 8, 4, 3, 1]);
+        assert_eq!(solver.neighbors(6), vec![6, 2, 10, 5, 7]);
+    }
+
+    #[test]
+    fn test_topologies() {
+        for topology in [Topology::Global, Topology::Ring, Topology::VonNeumann] {
+            let solver = swarm(20)
+                .with_topology(topology)
+                .with_constriction(2.05, 2.05)
+                .unwrap();
+            let res = Executor::new(
+                FnOp::new(|p: &Vec<f64>| Ok(sphere(p))),
+                solver,
+                vec![0.0, 0.0],
+            )
+            .max_iters(200)
+            .run()
+            .unwrap();
+            assert!(res.state.get_best_cost() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_bound_handling() {
+        for handling in [
+            BoundHandling::Clip,
+            BoundHandling::Reflect,
+            BoundHandling::RandomReinit,
+        ] {
+            // minimum outside of the search region
+            let op = FnOp::new(|p: &Vec<f64>| Ok((p[0] - 6.0).powi(2) + p[1].powi(2)));
+            let solver = swarm(20)
+                .with_bound_handling(handling)
+                .with_inertia_schedule(0.9, 0.4, 100)
+                .unwrap()
+                .with_velocity_clamping(0.5)
+                .unwrap();
+            let res = Executor::new(op, solver, vec![0.0, 0.0])
+                .max_iters(200)
+                .run()
+                .unwrap();
+            let p = res.state.get_best_param();
+            assert!(p[0] <= 4.0 && p[0] >= -4.0);
+            assert!(res.state.get_best_cost() < 4.5);
+            if handling != BoundHandling::RandomReinit {
+                assert!(p[0] > 3.99);
+                assert!(p[1].abs() < 0.01);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inertia() {
+        let solver = swarm(1).with_inertia_schedule(0.9, 0.4, 10).unwrap();
+        assert!((solver.inertia(0) - 0.9).abs() < std::f64::EPSILON);
+        assert!((solver.inertia(5) - 0.65).abs() < std::f64::EPSILON);
+        assert!((solver.inertia(20) - 0.4).abs() < std::f64::EPSILON);
+        let solver = solver.with_constriction(2.05, 2.05).unwrap();
+        assert!((solver.inertia(20) - 0.729_843_788_128_357_3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_stall() {
+        let solver = swarm(10).with_stall_iters(10, 1e-3).unwrap();
+        let res = Executor::new(
+            FnOp::new(|p: &Vec<f64>| Ok(sphere(p))),
+            solver,
+            vec![0.0, 0.0],
+        )
+        .max_iters(10000)
+        .run()
+        .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::BestStallIterExceeded
+        );
+        assert!(res.state.get_iter() < 10000);
+    }
+
+    #[cfg(feature = "ndarrayl")]
+    #[test]
+    fn test_ndarray() {
+        use ndarray::{array, Array1};
+
+        let op = FnOp::new(|p: &Array1<f64>| Ok(p.mapv(|x| (x - 1.0).powi(2)).sum()));
+        let solver = ParticleSwarm::new((array![-4.0, -4.0], array![4.0, 4.0]), 20, 0.5, 0.5, 0.5)
+            .unwrap()
+            .with_bound_handling(BoundHandling::Reflect);
+        let res = Executor::new(op, solver, array![0.0, 0.0])
+            .max_iters(200)
+            .run()
+            .unwrap();
+        assert!(res.state.get_best_cost() < 1e-6);
+    }
+
+    #[cfg(feature = "nalgebral")]
+    #[test]
+    fn test_nalgebra() {
+        use nalgebra::DVector;
+
+        let op = FnOp::new(|p: &DVector<f64>| Ok(p.map(|x| (x - 1.0).powi(2)).sum()));
+        let solver = ParticleSwarm::new(
+            (
+                DVector::from_element(2, -4.0),
+                DVector::from_element(2, 4.0),
+            ),
+            20,
+            0.5,
+            0.5,
+            0.5,
+        )
+        .unwrap()
+        .with_bound_handling(BoundHandling::Reflect);
+        let res = Executor::new(op, solver, DVector::zeros(2))
+            .max_iters(200)
+            .run()
+            .unwrap();
+        assert!(res.state.get_best_cost() < 1e-6);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(ParticleSwarm::new((vec![0.0], vec![1.0]), 0, 0.5, 0.5, 0.5).is_err());
+        assert!(ParticleSwarm::new((vec![1.0], vec![0.0]), 1, 0.5, 0.5, 0.5).is_err());
+        assert!(ParticleSwarm::new((vec![0.0], vec![1.0, 1.0]), 1, 0.5, 0.5, 0.5).is_err());
+        assert!(swarm(1).with_constriction(2.0, 2.0).is_err());
+        assert!(swarm(1).with_inertia_schedule(0.9, 0.4, 0).is_err());
+        assert!(swarm(1).with_velocity_clamping(0.0).is_err());
+        assert!(swarm(1).with_stall_iters(10, -1.0).is_err());
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Amir Beck and Marc Teboulle (2009). A Fast Iterative Shrinkage-Thresholding Algorithm for
+//! Linear Inverse Problems. SIAM Journal on Imaging Sciences 2(1), 183-202.
+//!
+//! [1] Brendan O'Donoghue and Emmanuel Candès (2015). Adaptive Restart for Accelerated Gradient
+//! Schemes. Foundations of Computational Mathematics 15, 715-732.
+
+use crate::core::capabilities::{CostFunction, Gradient};
+use crate::prelude::*;
+use crate::solver::gradientdescent::RestartScheme;
+use crate::solver::proximal::{check_params, prox_grad_step, ProximalOperator};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::default::Default;
+
+/// Fast iterative shrinkage-thresholding algorithm (FISTA)
+///
+/// Accelerated variant of [`ISTA`](../ista/struct.ISTA.html), which takes the proximal gradient
+/// step from an extrapolated point `y_k` [0]:
+///
+/// `x_{k+1} = prox(y_k - 1/L * grad f(y_k), 1/L)`
+///
+/// `t_{k+1} = (1 + sqrt(1 + 4 * t_k^2)) / 2`
+///
+/// `y_{k+1} = x_{k+1} + (t_k - 1) / t_{k+1} * (x_{k+1} - x_k)`
+///
+/// The Lipschitz constant `L` is estimated by backtracking as in `ISTA`. The momentum can be
+/// reset adaptively [1] with `with_restart` (default: `RestartScheme::Never`); the gradient
+/// scheme restarts if `(y_k - x_{k+1})^T (x_{k+1} - x_k) > 0`. The solver terminates if the norm
+/// of the gradient mapping `L * (y_k - x_{k+1})` falls below `tol` (default: `sqrt(EPSILON)`).
+///
+/// # References:
+///
+/// [0] Amir Beck and Marc Teboulle (2009). A Fast Iterative Shrinkage-Thresholding Algorithm for
+/// Linear Inverse Problems. SIAM Journal on Imaging Sciences 2(1), 183-202.
+///
+/// [1] Brendan O'Donoghue and Emmanuel Candès (2015). Adaptive Restart for Accelerated Gradient
+/// Schemes. Foundations of Computational Mathematics 15, 715-732.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FISTA<G, P, F> {
+    /// proximal operator of the nonsmooth part
+    prox: G,
+    /// estimate of the Lipschitz constant
+    lipschitz: F,
+    /// factor by which the Lipschitz estimate is increased
+    eta: F,
+    /// tolerance for the norm of the gradient mapping
+    tol: F,
+    /// restart scheme
+    restart: RestartScheme,
+    /// norm of the last gradient mapping
+    grad_map_norm: F,
+    /// extrapolated point
+    y: P,
+    /// momentum parameter
+    t: F,
+}
+
+impl<G, P: Default, F: ArgminFloat> FISTA<G, P, F> {
+    /// Constructor
+    pub fn new(prox: G) -> Self {
+        FISTA {
+            prox,
+            lipschitz: F::from_f64(1.0).unwrap(),
+            eta: F::from_f64(2.0).unwrap(),
+            tol: F::epsilon().sqrt(),
+            restart: RestartScheme::Never,
+            grad_map_norm: F::infinity(),
+            y: P::default(),
+            t: F::from_f64(1.0).unwrap(),
+        }
+    }
+
+    /// Set the initial estimate of the Lipschitz constant
+    pub fn with_lipschitz(mut self, lipschitz: F) -> Result<Self, Error> {
+        check_params(lipschitz, self.eta, self.tol)?;
+        self.lipschitz = lipschitz;
+        Ok(self)
+    }
+
+    /// Set the factor by which the Lipschitz estimate is increased during backtracking
+    pub fn with_backtracking_factor(mut self, eta: F) -> Result<Self, Error> {
+        check_params(self.lipschitz, eta, self.tol)?;
+        self.eta = eta;
+        Ok(self)
+    }
+
+    /// Set the tolerance for the norm of the gradient mapping
+    pub fn with_tol(mut self, tol: F) -> Result<Self, Error> {
+        check_params(self.lipschitz, self.eta, tol)?;
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set the adaptive restart scheme
+    pub fn with_restart(mut self, restart: RestartScheme) -> Self {
+        self.restart = restart;
+        self
+    }
+}
+
+impl<O, P, F, G> Solver<O> for FISTA<G, P, F>
+where
+    O: CostFunction<Param = P, Output = F, Float = F> + Gradient,
+    P: Clone
+        + Default
+        + Serialize
+        + DeserializeOwned
+        + ArgminSub<P, P>
+        + ArgminDot<P, F>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledSub<P, F, P>
+        + ArgminNorm<F>,
+    F: ArgminFloat,
+    G: ProximalOperator<P, F> + Clone + Serialize + DeserializeOwned,
+{
+    const NAME: &'static str = "FISTA";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        let cost = op.apply(&param)? + self.prox.value(&param);
+        self.y = param.clone();
+        self.t = F::from_f64(1.0).unwrap();
+        self.grad_map_norm = F::infinity();
+        Ok(Some(ArgminIterData::new().param(param).cost(cost)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let one = F::from_f64(1.0).unwrap();
+        let x_prev = state.get_param();
+        let (x, cost) = prox_grad_step(op, &self.prox, &self.y, &mut self.lipschitz, self.eta)?;
+        let grad_map = self.y.sub(&x);
+        self.grad_map_norm = grad_map.norm() * self.lipschitz;
+        let cost = cost + self.prox.value(&x);
+
+        let step = x.sub(&x_prev);
+        let restart = match self.restart {
+            RestartScheme::Never => false,
+            RestartScheme::Function => cost > state.get_cost(),
+            RestartScheme::Gradient => grad_map.dot(&step) > F::from_f64(0.0).unwrap(),
+        };
+        if restart {
+            self.t = one;
+            self.y = x.clone();
+        } else {
+            let t_next = F::from_f64(0.5).unwrap()
+                * (one + (one + F::from_f64(4.0).unwrap() * self.t * self.t).sqrt());
+            self.y = x.scaled_add(&((self.t - one) / t_next), &step);
+            self.t = t_next;
+        }
+
+        Ok(ArgminIterData::new().param(x).cost(cost).kv(make_kv!(
+            "lipschitz" => self.lipschitz;
+            "grad_map_norm" => self.grad_map_norm;
+            "restart" => restart;
+        )))
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason {
+        if self.grad_map_norm < self.tol {
+            return TerminationReason::TargetPrecisionReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::proximal::tests::{diagonal_lasso_solution, diagonal_least_squares};
+    use crate::solver::proximal::{BoxIndicator, GroupLasso, L1Norm, ISTA};
+    use crate::test_trait_impl;
+
+    test_trait_impl!(fista, FISTA<L1Norm<f64>, Vec<f64>, f64>);
+
+    #[test]
+    fn test_lasso() {
+        // ill-conditioned diagonal lasso problem
+        let d: Vec<f64> = (0..10).map(|i| 0.05 + 0.3 * i as f64).collect();
+        let b: Vec<f64> = (0..10).map(|i| (i as f64).sin() * 2.0).collect();
+        let lambda = 0.1;
+        let expected = diagonal_lasso_solution(&d, &b, lambda);
+        let op = || diagonal_least_squares(d.clone(), b.clone());
+
+        let mut iters = vec![];
+        for &restart in [
+            RestartScheme::Never,
+            RestartScheme::Function,
+            RestartScheme::Gradient,
+        ]
+        .iter()
+        {
+            let solver = FISTA::new(L1Norm::new(lambda).unwrap())
+                .with_restart(restart)
+                .with_tol(1e-9)
+                .unwrap();
+            let res = Executor::new(op(), solver, vec![0.0; 10])
+                .max_iters(100000)
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                TerminationReason::TargetPrecisionReached
+            );
+            for (x, e) in res.state.get_best_param().iter().zip(expected.iter()) {
+                assert!((x - e).abs() < 1e-7);
+            }
+            iters.push(res.state.get_iter());
+        }
+
+        let ista = ISTA::new(L1Norm::new(lambda).unwrap())
+            .with_tol(1e-9)
+            .unwrap();
+        let ista_iters = Executor::new(op(), ista, vec![0.0; 10])
+            .max_iters(100000)
+            .run()
+            .unwrap()
+            .state
+            .get_iter();
+        assert!(iters.iter().all(|&i| i < ista_iters));
+    }
+
+    #[test]
+    fn test_box() {
+        let solver = FISTA::new(BoxIndicator::new(vec![-1.0, -1.0], vec![1.0, 0.5]).unwrap());
+        let op = diagonal_least_squares(vec![1.0, 2.0], vec![3.0, -4.0]);
+        let res = Executor::new(op, solver, vec![0.0, 0.0])
+            .max_iters(1000)
+            .run()
+            .unwrap();
+        let p = res.state.get_best_param();
+        assert!((p[0] - 1.0).abs() < 1e-6);
+        assert!((p[1] + 1.0).abs() < 1e-6);
+        // the gradient is only known at the extrapolated point, not at the parameter vector
+        assert!(res.state.get_grad().is_none());
+    }
+
+    #[test]
+    fn test_group_lasso() {
+        // the second group is too small to survive the penalty
+        let solver = FISTA::new(GroupLasso::new(1.0, vec![vec![0, 1], vec![2, 3]]).unwrap())
+            .with_tol(1e-10)
+            .unwrap();
+        let op = diagonal_least_squares(vec![1.0; 4], vec![3.0, 4.0, 0.3, -0.4]);
+        let res = Executor::new(op, solver, vec![0.0; 4])
+            .max_iters(1000)
+            .run()
+            .unwrap();
+        let p = res.state.get_best_param();
+        for (x, e) in p.iter().zip([2.4, 3.2, 0.0, 0.0].iter()) {
+            assert!((x - e).abs() < 1e-8);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Amir Beck and Marc Teboulle (2009). A Fast Iterative Shrinkage-Thresholding Algorithm for
+//! Linear Inverse Problems. SIAM Journal on Imaging Sciences 2(1), 183-202.
+
+use crate::core::capabilities::{CostFunction, Gradient};
+use crate::prelude::*;
+use crate::solver::proximal::{check_params, prox_grad_step, ProximalOperator};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Iterative shrinkage-thresholding algorithm (ISTA)
+///
+/// Minimizes `f(x) + g(x)` with proximal gradient steps [0]
+///
+/// `x_{k+1} = prox(x_k - 1/L * grad f(x_k), 1/L)`.
+///
+/// The Lipschitz constant `L` of the gradient of `f` is estimated by backtracking: Starting
+/// from the value given by `with_lipschitz` (default: 1), it is multiplied by `eta` (default:
+/// 2) until the quadratic upper bound of `f` holds at `x_{k+1}`. The solver terminates if the
+/// norm of the gradient mapping `L * (x_k - x_{k+1})` falls below `tol` (default:
+/// `sqrt(EPSILON)`).
+///
+/// # References:
+///
+/// [0] Amir Beck and Marc Teboulle (2009). A Fast Iterative Shrinkage-Thresholding Algorithm for
+/// Linear Inverse Problems. SIAM Journal on Imaging Sciences 2(1), 183-202.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ISTA<G, F> {
+    /// proximal operator of the nonsmooth part
+    prox: G,
+    /// estimate of the Lipschitz constant
+    lipschitz: F,
+    /// factor by which the Lipschitz estimate is increased
+    eta: F,
+    /// tolerance for the norm of the gradient mapping
+    tol: F,
+    /// norm of the last gradient mapping
+    grad_map_norm: F,
+}
+
+impl<G, F: ArgminFloat> ISTA<G, F> {
+    /// Constructor
+    pub fn new(prox: G) -> Self {
+        ISTA {
+            prox,
+            lipschitz: F::from_f64(1.0).unwrap(),
+            eta: F::from_f64(2.0).unwrap(),
+            tol: F::epsilon().sqrt(),
+            grad_map_norm: F::infinity(),
+        }
+    }
+
+    /// Set the initial estimate of the Lipschitz constant
+    pub fn with_lipschitz(mut self, lipschitz: F) -> Result<Self, Error> {
+        check_params(lipschitz, self.eta, self.tol)?;
+        self.lipschitz = lipschitz;
+        Ok(self)
+    }
+
+    /// Set the factor by which the Lipschitz estimate is increased during backtracking
+    pub fn with_backtracking_factor(mut self, eta: F) -> Result<Self, Error> {
+        check_params(self.lipschitz, eta, self.tol)?;
+        self.eta = eta;
+        Ok(self)
+    }
+
+    /// Set the tolerance for the norm of the gradient mapping
+    pub fn with_tol(mut self, tol: F) -> Result<Self, Error> {
+        check_params(self.lipschitz, self.eta, tol)?;
+        self.tol = tol;
+        Ok(self)
+    }
+}
+
+impl<O, P, F, G> Solver<O> for ISTA<G, F>
+where
+    O: CostFunction<Param = P, Output = F, Float = F> + Gradient,
+    P: Clone
+        + Serialize
+        + DeserializeOwned
+        + ArgminSub<P, P>
+        + ArgminDot<P, F>
+        + ArgminScaledSub<P, F, P>
+        + ArgminNorm<F>,
+    F: ArgminFloat,
+    G: ProximalOperator<P, F> + Clone + Serialize + DeserializeOwned,
+{
+    const NAME: &'static str = "ISTA";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        let cost = op.apply(&param)? + self.prox.value(&param);
+        self.grad_map_norm = F::infinity();
+        Ok(Some(ArgminIterData::new().param(param).cost(cost)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let param = state.get_param();
+        let (new_param, cost) =
+            prox_grad_step(op, &self.prox, &param, &mut self.lipschitz, self.eta)?;
+        self.grad_map_norm = new_param.sub(&param).norm() * self.lipschitz;
+        let cost = cost + self.prox.value(&new_param);
+
+        Ok(ArgminIterData::new()
+            .param(new_param)
+            .cost(cost)
+            .kv(make_kv!("lipschitz" => self.lipschitz; "grad_map_norm" => self.grad_map_norm;)))
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason {
+        if self.grad_map_norm < self.tol {
+            return TerminationReason::TargetPrecisionReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::proximal::tests::{diagonal_lasso_solution, diagonal_least_squares};
+    use crate::solver::proximal::{L1Norm, NonNegative};
+    use crate::test_trait_impl;
+
+    test_trait_impl!(ista, ISTA<L1Norm<f64>, f64>);
+
+    #[test]
+    fn test_lasso() {
+        let d = vec![1.0, 2.0, 0.5, 3.0];
+        let b = vec![2.0, -0.2, 1.0, -4.0];
+        let lambda = 0.5;
+        let solver = ISTA::new(L1Norm::new(lambda).unwrap())
+            .with_tol(1e-10)
+            .unwrap();
+        let res = Executor::new(
+            diagonal_least_squares(d.clone(), b.clone()),
+            solver,
+            vec![0.0; 4],
+        )
+        .max_iters(10000)
+        .run()
+        .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        let expected = diagonal_lasso_solution(&d, &b, lambda);
+        for (x, e) in res.state.get_best_param().iter().zip(expected.iter()) {
+            assert!((x - e).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_nonnegative_least_squares() {
+        let solver = ISTA::new(NonNegative::new()).with_tol(1e-10).unwrap();
+        let op = diagonal_least_squares(vec![1.0, 1.0], vec![2.0, -1.0]);
+        let res = Executor::new(op, solver, vec![1.0, 1.0])
+            .max_iters(1000)
+            .run()
+            .unwrap();
+        let p = res.state.get_best_param();
+        assert!((p[0] - 2.0).abs() < 1e-8);
+        assert!(p[1].abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let ista: ISTA<NonNegative, f64> = ISTA::new(NonNegative::new());
+        assert!(ista.clone().with_lipschitz(0.0).is_err());
+        assert!(ista.clone().with_backtracking_factor(0.5).is_err());
+        assert!(ista.with_tol(-1.0).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Proximal gradient methods
+//!
+//! * [ISTA](ista/struct.ISTA.html)
+//! * [FISTA](fista/struct.FISTA.html)
+//!
+//! These solvers minimize composite objectives `f(x) + g(x)`, where `f` is a smooth operator
+//! providing `apply` and `gradient` and `g` is a possibly nonsmooth function which is given by
+//! its [`ProximalOperator`](prox/trait.ProximalOperator.html). Proximal operators for the
+//! [L1 norm](prox/struct.L1Norm.html), the [group lasso](prox/struct.GroupLasso.html), a
+//! [box](prox/struct.BoxIndicator.html) and the
+//! [nonnegative orthant](prox/struct.NonNegative.html) are provided.
+//!
+//! # References:
+//!
+//! [0] Amir Beck and Marc Teboulle (2009). A Fast Iterative Shrinkage-Thresholding Algorithm for
+//! Linear Inverse Problems. SIAM Journal on Imaging Sciences 2(1), 183-202.
+//!
+//! [1] Neal Parikh and Stephen Boyd (2014). Proximal Algorithms. Foundations and Trends in
+//! Optimization 1(3), 127-239.
+
+/// FISTA
+pub mod fista;
+/// ISTA
+pub mod ista;
+/// Proximal operators
+pub mod prox;
+
+pub use self::fista::*;
+pub use self::ista::*;
+pub use self::prox::*;
+
+use crate::core::capabilities::{CostFunction, Gradient};
+use crate::prelude::*;
+
+/// Takes a proximal gradient step `x = prox(y - 1/L * g(y), 1/L)` from `y`.
+///
+/// The estimate `lipschitz` of the Lipschitz constant `L` of the gradient is multiplied by
+/// `eta` until the quadratic upper bound `f(x) <= f(y) + g(y)^T (x - y) + L/2 * ||x - y||^2`
+/// holds up to rounding errors. Returns `x` and `f(x)`.
+fn prox_grad_step<O, P, F, G>(
+    op: &mut OpWrapper<O>,
+    prox: &G,
+    y: &P,
+    lipschitz: &mut F,
+    eta: F,
+) -> Result<(P, F), Error>
+where
+    O: CostFunction<Param = P, Output = F, Float = F> + Gradient,
+    P: Clone + ArgminSub<P, P> + ArgminDot<P, F> + ArgminScaledSub<P, F, P>,
+    F: ArgminFloat,
+    G: ProximalOperator<P, F>,
+{
+    let half = F::from_f64(0.5).unwrap();
+    let cost_y = op.apply(y)?;
+    let grad_y = op.gradient(y)?;
+    // rounding errors in the cost function values would otherwise increase the Lipschitz
+    // estimate indefinitely close to the minimum
+    let slack =
+        F::from_f64(10.0).unwrap() * F::epsilon() * (F::from_f64(1.0).unwrap() + cost_y.abs());
+    loop {
+        let t = F::from_f64(1.0).unwrap() / *lipschitz;
+        let x = prox.prox(&y.scaled_sub(&t, &grad_y), t);
+        let cost_x = op.apply(&x)?;
+        let d = x.sub(y);
+        if cost_x <= cost_y + grad_y.dot(&d) + half * *lipschitz * d.dot(&d) + slack {
+            return Ok((x, cost_x));
+        }
+        *lipschitz = *lipschitz * eta;
+        if !lipschitz.is_finite() {
+            return Err(ArgminError::ConditionViolated {
+                text: "Proximal gradient: backtracking failed.".to_string(),
+            }
+            .into());
+        }
+    }
+}
+
+/// Checks the common parameters of the proximal gradient methods
+fn check_params<F: ArgminFloat>(lipschitz: F, eta: F, tol: F) -> Result<(), Error> {
+    if lipschitz <= F::from_f64(0.0).unwrap() {
+        return Err(ArgminError::InvalidParameter {
+            text: "Proximal gradient: Lipschitz constant must be > 0.".to_string(),
+        }
+        .into());
+    }
+    if eta <= F::from_f64(1.0).unwrap() {
+        return Err(ArgminError::InvalidParameter {
+            text: "Proximal gradient: backtracking factor must be > 1.".to_string(),
+        }
+        .into());
+    }
+    if tol < F::from_f64(0.0).unwrap() {
+        return Err(ArgminError::InvalidParameter {
+            text: "Proximal gradient: tolerance must be >= 0.".to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Least squares problem with a gradient
+    pub(super) type LeastSquares = FnOp<Vec<f64>, f64, f64, FnOpFunc<Vec<f64>, Vec<f64>>>;
+
+    /// Least squares problem `0.5 * sum_i (d_i * x_i - b_i)^2` with a diagonal matrix
+    pub(super) fn diagonal_least_squares(d: Vec<f64>, b: Vec<f64>) -> LeastSquares {
+        let (d1, b1) = (d.clone(), b.clone());
+        FnOp::new(move |x: &Vec<f64>| {
+            Ok(0.5
+                * x.iter()
+                    .zip(d1.iter().zip(b1.iter()))
+                    .map(|(x, (d, b))| (d * x - b).powi(2))
+                    .sum::<f64>())
+        })
+        .gradient(move |x: &Vec<f64>| {
+            Ok(x.iter()
+                .zip(d.iter().zip(b.iter()))
+                .map(|(x, (d, b))| d * (d * x - b))
+                .collect())
+        })
+    }
+
+    /// Solution of the lasso problem with a diagonal matrix
+    pub(super) fn diagonal_lasso_solution(d: &[f64], b: &[f64], lambda: f64) -> Vec<f64> {
+        d.iter()
+            .zip(b.iter())
+            .map(|(d, b)| {
+                let db: f64 = d * b;
+                (db.abs() - lambda).max(0.0) * db.signum() / (d * d)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_prox_grad_step() {
+        let mut op = OpWrapper::new(diagonal_least_squares(vec![1.0, 3.0], vec![1.0, 1.0]));
+        let mut lipschitz = 1.0;
+        let (x, cost) = prox_grad_step(
+            &mut op,
+            &NonNegative::new(),
+            &vec![0.0, 0.0],
+            &mut lipschitz,
+            2.0,
+        )
+        .unwrap();
+        // the Lipschitz constant of the gradient is 9
+        assert!((9.0..18.0).contains(&lipschitz));
+        // step along the negative gradient [1, 3] at the origin
+        assert!((x[1] - 3.0 * x[0]).abs() < 1e-12);
+        assert!(cost < 1.0);
+        assert!(x.iter().all(|x| *x >= 0.0));
+        assert!(check_params(1.0, 1.0, 0.0).is_err());
+        assert!(check_params(0.0, 2.0, 0.0).is_err());
+    }
+}
@@ -0,0 +1,291 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Nonsmooth part `g` of a composite objective `f(x) + g(x)`, which is accessed via its
+/// proximal operator
+///
+/// `prox(x, t) = argmin_z g(z) + 1/(2t) * ||z - x||^2`.
+pub trait ProximalOperator<P, F> {
+    /// Computes `g(x)`. Indicator functions return infinity outside of their set.
+    fn value(&self, x: &P) -> F;
+
+    /// Computes the proximal operator of `t * g` at `x`
+    fn prox(&self, x: &P, t: F) -> P;
+}
+
+/// L1 norm `g(x) = lambda * ||x||_1`
+///
+/// The proximal operator is the soft-thresholding operator
+/// `prox(x, t)_i = sign(x_i) * max(|x_i| - t * lambda, 0)`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct L1Norm<F> {
+    /// regularization parameter
+    lambda: F,
+}
+
+impl<F: ArgminFloat> L1Norm<F> {
+    /// Constructor
+    pub fn new(lambda: F) -> Result<Self, Error> {
+        if lambda < F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "L1Norm: lambda must be >= 0.".to_string(),
+            }
+            .into());
+        }
+        Ok(L1Norm { lambda })
+    }
+}
+
+impl<P, F> ProximalOperator<P, F> for L1Norm<F>
+where
+    P: Clone + ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    fn value(&self, x: &P) -> F {
+        (0..x.num_components()).fold(F::from_f64(0.0).unwrap(), |acc, i| {
+            acc + x.component(i).abs()
+        }) * self.lambda
+    }
+
+    fn prox(&self, x: &P, t: F) -> P {
+        let threshold = t * self.lambda;
+        let mut out = x.clone();
+        for i in 0..x.num_components() {
+            let xi = x.component(i);
+            out.set_component(
+                i,
+                (xi.abs() - threshold).max(F::from_f64(0.0).unwrap()) * xi.signum(),
+            );
+        }
+        out
+    }
+}
+
+/// Group lasso penalty `g(x) = lambda * sum_G ||x_G||_2`
+///
+/// The groups `G` are given as lists of component indices and must not overlap. Components
+/// which belong to no group are not penalized. The proximal operator shrinks every group towards
+/// zero: `prox(x, t)_G = max(1 - t * lambda / ||x_G||_2, 0) * x_G`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupLasso<F> {
+    /// regularization parameter
+    lambda: F,
+    /// indices of the components of every group
+    groups: Vec<Vec<usize>>,
+}
+
+impl<F: ArgminFloat> GroupLasso<F> {
+    /// Constructor
+    pub fn new(lambda: F, groups: Vec<Vec<usize>>) -> Result<Self, Error> {
+        if lambda < F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "GroupLasso: lambda must be >= 0.".to_string(),
+            }
+            .into());
+        }
+        let mut indices: Vec<usize> = groups.iter().flatten().cloned().collect();
+        let num_indices = indices.len();
+        indices.sort_unstable();
+        indices.dedup();
+        if indices.len() != num_indices {
+            return Err(ArgminError::InvalidParameter {
+                text: "GroupLasso: groups must not overlap.".to_string(),
+            }
+            .into());
+        }
+        Ok(GroupLasso { lambda, groups })
+    }
+
+    /// Euclidean norm of the components of `x` in `group`
+    fn group_norm<P: ArgminComponents<F>>(x: &P, group: &[usize]) -> F {
+        group
+            .iter()
+            .fold(F::from_f64(0.0).unwrap(), |acc, &i| {
+                acc + x.component(i).powi(2)
+            })
+            .sqrt()
+    }
+}
+
+impl<P, F> ProximalOperator<P, F> for GroupLasso<F>
+where
+    P: Clone + ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    fn value(&self, x: &P) -> F {
+        self.groups
+            .iter()
+            .fold(F::from_f64(0.0).unwrap(), |acc, group| {
+                acc + Self::group_norm(x, group)
+            })
+            * self.lambda
+    }
+
+    fn prox(&self, x: &P, t: F) -> P {
+        let zero = F::from_f64(0.0).unwrap();
+        let mut out = x.clone();
+        for group in self.groups.iter() {
+            let norm = Self::group_norm(x, group);
+            let factor = if norm > zero {
+                (F::from_f64(1.0).unwrap() - t * self.lambda / norm).max(zero)
+            } else {
+                zero
+            };
+            for &i in group.iter() {
+                out.set_component(i, factor * x.component(i));
+            }
+        }
+        out
+    }
+}
+
+/// Indicator function of the box `lower <= x <= upper`
+///
+/// The proximal operator is the projection onto the box.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BoxIndicator<P> {
+    /// lower bounds
+    lower: P,
+    /// upper bounds
+    upper: P,
+}
+
+impl<P> BoxIndicator<P> {
+    /// Constructor
+    pub fn new<F>(lower: P, upper: P) -> Result<Self, Error>
+    where
+        P: ArgminComponents<F>,
+        F: ArgminFloat,
+    {
+        if lower.num_components() != upper.num_components()
+            || (0..lower.num_components()).any(|i| lower.component(i) > upper.component(i))
+        {
+            return Err(ArgminError::InvalidParameter {
+                text: "BoxIndicator: lower bounds must not exceed upper bounds.".to_string(),
+            }
+            .into());
+        }
+        Ok(BoxIndicator { lower, upper })
+    }
+}
+
+impl<P, F> ProximalOperator<P, F> for BoxIndicator<P>
+where
+    P: Clone + ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    fn value(&self, x: &P) -> F {
+        let feasible = (0..x.num_components()).all(|i| {
+            x.component(i) >= self.lower.component(i) && x.component(i) <= self.upper.component(i)
+        });
+        if feasible {
+            F::from_f64(0.0).unwrap()
+        } else {
+            F::infinity()
+        }
+    }
+
+    fn prox(&self, x: &P, _t: F) -> P {
+        let mut out = x.clone();
+        for i in 0..x.num_components() {
+            out.set_component(
+                i,
+                x.component(i)
+                    .max(self.lower.component(i))
+                    .min(self.upper.component(i)),
+            );
+        }
+        out
+    }
+}
+
+/// Indicator function of the nonnegative orthant `x >= 0`
+///
+/// The proximal operator sets all negative components to zero.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct NonNegative {}
+
+impl NonNegative {
+    /// Constructor
+    pub fn new() -> Self {
+        NonNegative {}
+    }
+}
+
+impl<P, F> ProximalOperator<P, F> for NonNegative
+where
+    P: Clone + ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    fn value(&self, x: &P) -> F {
+        if (0..x.num_components()).all(|i| x.component(i) >= F::from_f64(0.0).unwrap()) {
+            F::from_f64(0.0).unwrap()
+        } else {
+            F::infinity()
+        }
+    }
+
+    fn prox(&self, x: &P, _t: F) -> P {
+        let mut out = x.clone();
+        for i in 0..x.num_components() {
+            out.set_component(i, x.component(i).max(F::from_f64(0.0).unwrap()));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (a, b) in a.iter().zip(b.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_l1_norm() {
+        let g = L1Norm::new(2.0).unwrap();
+        let x = vec![3.0, -0.5, -4.0, 1.0];
+        assert!((ProximalOperator::<_, f64>::value(&g, &x) - 17.0).abs() < 1e-12);
+        assert_close(&g.prox(&x, 0.5), &[2.0, 0.0, -3.0, 0.0]);
+        assert!(L1Norm::new(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_group_lasso() {
+        let g = GroupLasso::new(1.0, vec![vec![0, 1], vec![2]]).unwrap();
+        let x = vec![3.0, 4.0, 0.5, 7.0];
+        assert!((ProximalOperator::<_, f64>::value(&g, &x) - 5.5).abs() < 1e-12);
+        // group [0, 1] has norm 5 and is shrunk by 1 - 1 / 5, group [2] vanishes, component 3
+        // is not penalized
+        assert_close(&g.prox(&x, 1.0), &[2.4, 3.2, 0.0, 7.0]);
+        assert!(GroupLasso::new(1.0, vec![vec![0, 1], vec![1]]).is_err());
+        assert!(GroupLasso::new(-1.0, vec![vec![0]]).is_err());
+    }
+
+    #[test]
+    fn test_box_indicator() {
+        let g = BoxIndicator::new(vec![0.0, -1.0], vec![1.0, 1.0]).unwrap();
+        assert_close(&g.prox(&vec![2.0, -3.0], 1.0), &[1.0, -1.0]);
+        assert!(ProximalOperator::<_, f64>::value(&g, &vec![0.5, 0.0]).abs() < 1e-12);
+        assert!(ProximalOperator::<_, f64>::value(&g, &vec![2.0, 0.0]).is_infinite());
+        assert!(BoxIndicator::new(vec![1.0], vec![0.0]).is_err());
+    }
+
+    #[test]
+    fn test_nonnegative() {
+        let g = NonNegative::new();
+        assert_close(&g.prox(&vec![2.0, -3.0], 1.0), &[2.0, 0.0]);
+        assert!(ProximalOperator::<_, f64>::value(&g, &vec![-1.0]).is_infinite());
+    }
+}
@@ -10,6 +10,7 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::{CostFunction, Gradient};
 use crate::prelude::*;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -51,7 +52,7 @@ impl<L, H, F: ArgminFloat> DFP<L, H, F> {
 
 impl<O, L, H, F> Solver<O> for DFP<L, H, F>
 where
-    O: ArgminOp<Output = F, Hessian = H, Float = F>,
+    O: CostFunction<Output = F, Hessian = H, Float = F> + Gradient,
     O::Param: Clone
         + Default
         + Serialize
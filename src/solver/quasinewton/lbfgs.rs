@@ -10,6 +10,7 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::{CostFunction, Gradient};
 use crate::prelude::*;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -70,18 +71,19 @@ impl<L, P, F: ArgminFloat> LBFGS<L, P, F> {
 
 impl<O, L, P, F> Solver<O> for LBFGS<L, P, F>
 where
-    O: ArgminOp<Param = P, Output = F, Float = F>,
+    O: CostFunction<Param = P, Output = F, Float = F> + Gradient,
     O::Param: Clone
         + Serialize
         + DeserializeOwned
         + Debug
         + Default
         + ArgminSub<O::Param, O::Param>
-        + ArgminAdd<O::Param, O::Param>
         + ArgminDot<O::Param, O::Float>
         + ArgminScaledAdd<O::Param, O::Float, O::Param>
         + ArgminNorm<O::Float>
-        + ArgminMul<O::Float, O::Param>,
+        + ArgminMulAssign<O::Float>
+        + ArgminScaledAddAssign<O::Param, O::Float>
+        + ArgminScaledSubAssign<O::Param, O::Float>,
     O::Hessian: Clone + Default + Serialize + DeserializeOwned,
     L: Clone + ArgminLineSearch<O::Param, O::Float> + Solver<OpWrapper<O>>,
     F: ArgminFloat,
@@ -126,18 +128,19 @@ where
             let rho_t = F::from_f64(1.0).unwrap() / yksk;
             let skq: F = sk.dot(&q);
             let alpha_t = skq.mul(rho_t);
-            q = q.sub(&yk.mul(&alpha_t));
+            q.scaled_sub_assign(&alpha_t, yk);
             rho[cur_m - i - 1] = rho_t;
             alpha[cur_m - i - 1] = alpha_t;
         }
-        let mut r = q.mul(&gamma);
+        let mut r = q;
+        r.mul_assign(&gamma);
         for (i, (sk, yk)) in self.s.iter().zip(self.y.iter()).enumerate() {
             let beta = yk.dot(&r).mul(rho[i]);
-            r = r.add(&sk.mul(&(alpha[i] - beta)));
+            r.scaled_add_assign(&(alpha[i] - beta), sk);
         }
+        r.mul_assign(&F::from_f64(-1.0).unwrap());
 
-        self.linesearch
-            .set_search_direction(r.mul(&F::from_f64(-1.0).unwrap()));
+        self.linesearch.set_search_direction(r);
 
         // Run solver
         let ArgminResult {
@@ -10,6 +10,7 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::{CostFunction, Gradient};
 use crate::prelude::*;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -77,7 +78,7 @@ impl<L, H, F: ArgminFloat> SR1<L, H, F> {
 
 impl<O, L, H, F> Solver<O> for SR1<L, H, F>
 where
-    O: ArgminOp<Output = F, Hessian = H, Float = F>,
+    O: CostFunction<Output = F, Hessian = H, Float = F> + Gradient,
     O::Param: Debug
         + Clone
         + Default
@@ -10,6 +10,7 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::{CostFunction, Gradient, Hessian};
 use crate::prelude::*;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -99,7 +100,7 @@ impl<B, R, F: ArgminFloat> SR1TrustRegion<B, R, F> {
 
 impl<O, B, R, F> Solver<O> for SR1TrustRegion<B, R, F>
 where
-    O: ArgminOp<Output = F, Hessian = B, Float = F>,
+    O: CostFunction<Output = F, Hessian = B, Float = F> + Gradient + Hessian,
     O::Param: Debug
         + Clone
         + Default
@@ -121,7 +122,7 @@ where
         + ArgminDot<O::Hessian, O::Hessian>
         + ArgminAdd<O::Hessian, O::Hessian>
         + ArgminMul<F, O::Hessian>,
-    R: ArgminTrustRegion<F> + Solver<OpWrapper<O>>,
+    R: ArgminTrustRegion<O::Param, F> + Solver<OpWrapper<SR1Subproblem<O>>>,
     F: ArgminFloat + ArgminNorm<O::Float>,
 {
     const NAME: &'static str = "SR1 Trust Region";
@@ -161,10 +162,10 @@ where
         self.subproblem.set_radius(self.radius);
 
         let ArgminResult {
-            operator: sub_op,
+            operator: mut sub_op,
             state: IterState { param: sk, .. },
         } = Executor::new(
-            OpWrapper::new_from_wrapper(op),
+            OpWrapper::new(SR1Subproblem::new(op.take_op().unwrap(), hessian.clone())),
             self.subproblem.clone(),
             // xk.clone(),
             xk.zero_like(),
@@ -175,7 +176,9 @@ where
         .ctrlc(false)
         .run()?;
 
-        op.consume_op(sub_op);
+        // The operator must be handed back, otherwise it is lost.
+        op.op = Some(sub_op.take_op().unwrap().op);
+        op.consume_func_counts(sub_op);
 
         let xksk = xk.add(&sk);
         let dfk1 = op.gradient(&xksk)?;
@@ -195,8 +198,9 @@ where
             (xk, cost, prev_grad)
         };
 
+        let sk_norm = self.subproblem.step_norm(&sk)?;
         self.radius = if ap > F::from_f64(0.75).unwrap() {
-            if sk.norm() <= F::from_f64(0.8).unwrap() * self.radius {
+            if sk_norm <= F::from_f64(0.8).unwrap() * self.radius {
                 self.radius
             } else {
                 F::from_f64(2.0).unwrap() * self.radius
@@ -243,6 +247,68 @@ where
     }
 }
 
+/// Operator passed to the subproblem of `SR1TrustRegion`. It forwards cost function and gradient
+/// evaluations to the wrapped operator, but Hessians and Hessian-vector products are computed from
+/// the current SR1 approximation of the Hessian.
+pub struct SR1Subproblem<O: CostFunction> {
+    /// Wrapped operator
+    op: O,
+    /// Approximation of the Hessian
+    hessian: O::Hessian,
+}
+
+impl<O: CostFunction> SR1Subproblem<O> {
+    /// Constructor
+    pub fn new(op: O, hessian: O::Hessian) -> Self {
+        SR1Subproblem { op, hessian }
+    }
+}
+
+impl<O: CostFunction> CostFunction for SR1Subproblem<O> {
+    type Param = O::Param;
+    type Output = O::Output;
+    type Hessian = O::Hessian;
+    type Jacobian = O::Jacobian;
+    type Float = O::Float;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.op.apply(param)
+    }
+}
+
+impl<O> Gradient for SR1Subproblem<O>
+where
+    O: Gradient,
+    O::Hessian: ArgminDot<O::Param, O::Param>,
+{
+    const HESSIAN_VEC_GRAD_EVALS: u64 = 0;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+        self.op.gradient(param)
+    }
+
+    fn hessian_vec(
+        &self,
+        _param: &Self::Param,
+        _grad: &Self::Param,
+        vec: &Self::Param,
+    ) -> Result<Self::Param, Error>
+    where
+        Self::Param: ArgminScaledAdd<Self::Param, Self::Float, Self::Param>
+            + ArgminSub<Self::Param, Self::Param>
+            + ArgminMul<Self::Float, Self::Param>
+            + ArgminNorm<Self::Float>,
+    {
+        Ok(self.hessian.dot(vec))
+    }
+}
+
+impl<O: CostFunction> Hessian for SR1Subproblem<O> {
+    fn hessian(&self, _param: &Self::Param) -> Result<Self::Hessian, Error> {
+        Ok(self.hessian.clone())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
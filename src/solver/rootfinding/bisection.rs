@@ -0,0 +1,138 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+//! Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+//! ISBN 978-0-521-88068-8.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::rootfinding::{same_sign, RootFindingError};
+use serde::{Deserialize, Serialize};
+
+/// The bisection method halves the bracketing interval in every iteration. It converges linearly,
+/// but only requires `f` to be continuous.
+///
+/// The solver terminates once the interval is shorter than `tol`.
+///
+/// # References:
+///
+/// [0] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+/// Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+/// ISBN 978-0-521-88068-8.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Bisection<F> {
+    /// absolute tolerance for the width of the interval
+    tol: F,
+    /// boundary of the interval
+    a: F,
+    /// boundary of the interval
+    b: F,
+    /// function value at `a`
+    fa: F,
+    /// function value at `b`
+    fb: F,
+}
+
+impl<F: ArgminFloat> Bisection<F> {
+    /// Constructor
+    /// The values `min` and `max` must bracket the root of the function.
+    /// The parameter `tol` specifies the absolute error to be targeted.
+    pub fn new(min: F, max: F, tol: F) -> Bisection<F> {
+        Bisection {
+            tol,
+            a: min,
+            b: max,
+            fa: F::nan(),
+            fb: F::nan(),
+        }
+    }
+}
+
+impl<O, F> Solver<O> for Bisection<F>
+where
+    O: CostFunction<Param = F, Output = F, Float = F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Bisection";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        // Bisection maintains its own state
+        _state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        self.fa = op.apply(&self.a)?;
+        self.fb = op.apply(&self.b)?;
+        if same_sign(self.fa, self.fb) {
+            return Err(RootFindingError::WrongSign.into());
+        }
+        Ok(Some(if self.fa.abs() < self.fb.abs() {
+            ArgminIterData::new().param(self.a).cost(self.fa.abs())
+        } else {
+            ArgminIterData::new().param(self.b).cost(self.fb.abs())
+        }))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        // Bisection maintains its own state
+        _state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let m = self.a + F::from_f64(0.5).unwrap() * (self.b - self.a);
+        let fm = op.apply(&m)?;
+        if same_sign(fm, self.fa) {
+            self.a = m;
+            self.fa = fm;
+        } else {
+            self.b = m;
+            self.fb = fm;
+        }
+        Ok(ArgminIterData::new().param(m).cost(fm.abs()))
+    }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
+        if (self.b - self.a).abs() <= self.tol || state.get_cost() == F::from_f64(0.0).unwrap() {
+            TerminationReason::TargetPrecisionReached
+        } else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(bisection, Bisection<f64>);
+
+    #[test]
+    fn test_root() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x.powi(3) - x - 2.0));
+        let res = Executor::new(op, Bisection::new(1.0, 2.0, 1e-10), 1.5)
+            .max_iters(100)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        assert!((res.state.get_best_param() - 1.521_379_706_804_567_6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_wrong_sign() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x.powi(2) + 1.0));
+        assert!(Executor::new(op, Bisection::new(-1.0, 1.0, 1e-10), 0.0)
+            .run()
+            .is_err());
+    }
+}
@@ -0,0 +1,101 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+//! Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+//! ISBN 978-0-521-88068-8.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::rootfinding::{same_sign, RootFindingError};
+
+/// Find an interval `(min, max)` for which `f(min)` and `f(max)` have different signs, starting
+/// from the interval `[x0, x0 + step]`. In each of at most `max_iters` iterations, the end with
+/// the smaller `|f|` is moved outwards by 1.6 times the current width (`zbrac` in [0]).
+///
+/// The returned interval can be passed to any of the bracketing root finders. Returns
+/// `RootFindingError::BracketNotFound` if no sign change was found.
+///
+/// # References:
+///
+/// [0] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+/// Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+/// ISBN 978-0-521-88068-8.
+pub fn find_bracket<O, F>(op: &O, x0: F, step: F, max_iters: u64) -> Result<(F, F), Error>
+where
+    O: CostFunction<Param = F, Output = F, Float = F>,
+    F: ArgminFloat,
+{
+    if step == F::from_f64(0.0).unwrap() || !step.is_finite() {
+        return Err(ArgminError::InvalidParameter {
+            text: "find_bracket: step must be finite and nonzero.".to_string(),
+        }
+        .into());
+    }
+    let factor = F::from_f64(1.6).unwrap();
+    let mut a = x0;
+    let mut b = x0 + step;
+    let mut fa = op.apply(&a)?;
+    let mut fb = op.apply(&b)?;
+    for _ in 0..max_iters {
+        if !same_sign(fa, fb) {
+            return Ok((a.min(b), a.max(b)));
+        }
+        if fa.abs() < fb.abs() {
+            a = a + factor * (a - b);
+            fa = op.apply(&a)?;
+        } else {
+            b = b + factor * (b - a);
+            fb = op.apply(&b)?;
+        }
+    }
+    if !same_sign(fa, fb) {
+        return Ok((a.min(b), a.max(b)));
+    }
+    Err(RootFindingError::BracketNotFound.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::rootfinding::Bisection;
+
+    #[test]
+    fn test_find_bracket() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x - 10.0));
+        let (a, b) = find_bracket(&op, 0.0, 0.1, 50).unwrap();
+        assert!(a < 10.0 && b > 10.0);
+
+        // expands to the left as well
+        let (a, b) = find_bracket(&op, 20.0, 0.1, 50).unwrap();
+        assert!(a < 10.0 && b > 10.0);
+
+        // negative step
+        let (a, b) = find_bracket(&op, 0.0, -0.1, 50).unwrap();
+        assert!(a < 10.0 && b > 10.0);
+    }
+
+    #[test]
+    fn test_find_bracket_and_solve() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x.powi(3) - x - 2.0));
+        let (a, b) = find_bracket(&op, -5.0, 0.5, 50).unwrap();
+        let res = Executor::new(op, Bisection::new(a, b, 1e-10), a)
+            .max_iters(100)
+            .run()
+            .unwrap();
+        assert!((res.state.get_best_param() - 1.521_379_706_804_567_6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bracket_not_found() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x.powi(2) + 1.0));
+        assert!(find_bracket(&op, 0.0, 0.1, 20).is_err());
+        assert!(find_bracket(&op, 0.0, 0.0, 20).is_err());
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] M. Dowell and P. Jarratt (1971). A modified regula falsi method for computing the root of
+//! an equation. BIT Numerical Mathematics 11, 168–174.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::rootfinding::{same_sign, RootFindingError};
+use serde::{Deserialize, Serialize};
+
+/// Regula falsi with the Illinois modification
+///
+/// The new point is the root of the secant through both ends of the bracketing interval. If the
+/// same end is retained twice in a row, its function value is halved, which avoids the one-sided
+/// linear convergence of plain regula falsi.
+///
+/// The solver terminates once the interval is shorter than `tol`.
+///
+/// # References:
+///
+/// [0] M. Dowell and P. Jarratt (1971). A modified regula falsi method for computing the root of
+/// an equation. BIT Numerical Mathematics 11, 168–174.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Illinois<F> {
+    /// absolute tolerance for the width of the interval
+    tol: F,
+    /// retained end of the interval
+    a: F,
+    /// most recent end of the interval
+    b: F,
+    /// (possibly scaled) function value at `a`
+    fa: F,
+    /// function value at `b`
+    fb: F,
+}
+
+impl<F: ArgminFloat> Illinois<F> {
+    /// Constructor
+    /// The values `min` and `max` must bracket the root of the function.
+    /// The parameter `tol` specifies the absolute error to be targeted.
+    pub fn new(min: F, max: F, tol: F) -> Illinois<F> {
+        Illinois {
+            tol,
+            a: min,
+            b: max,
+            fa: F::nan(),
+            fb: F::nan(),
+        }
+    }
+}
+
+impl<O, F> Solver<O> for Illinois<F>
+where
+    O: CostFunction<Param = F, Output = F, Float = F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Illinois";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        // Illinois maintains its own state
+        _state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        self.fa = op.apply(&self.a)?;
+        self.fb = op.apply(&self.b)?;
+        if same_sign(self.fa, self.fb) {
+            return Err(RootFindingError::WrongSign.into());
+        }
+        Ok(Some(if self.fa.abs() < self.fb.abs() {
+            ArgminIterData::new().param(self.a).cost(self.fa.abs())
+        } else {
+            ArgminIterData::new().param(self.b).cost(self.fb.abs())
+        }))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        // Illinois maintains its own state
+        _state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let x = (self.a * self.fb - self.b * self.fa) / (self.fb - self.fa);
+        let fx = op.apply(&x)?;
+        if same_sign(fx, self.fb) {
+            // `a` is retained again
+            self.fa = self.fa * F::from_f64(0.5).unwrap();
+        } else {
+            self.a = self.b;
+            self.fa = self.fb;
+        }
+        self.b = x;
+        self.fb = fx;
+        Ok(ArgminIterData::new().param(x).cost(fx.abs()))
+    }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
+        if (self.b - self.a).abs() <= self.tol || state.get_cost() == F::from_f64(0.0).unwrap() {
+            TerminationReason::TargetPrecisionReached
+        } else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(illinois, Illinois<f64>);
+
+    #[test]
+    fn test_root() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x.powi(3) - x - 2.0));
+        let res = Executor::new(op, Illinois::new(1.0, 2.0, 1e-10), 1.5)
+            .max_iters(100)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        assert!((res.state.get_best_param() - 1.521_379_706_804_567_6).abs() < 1e-9);
+        // superlinear convergence
+        assert!(res.state.get_iter() < 20);
+    }
+
+    #[test]
+    fn test_wrong_sign() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x.powi(2) + 1.0));
+        assert!(Executor::new(op, Illinois::new(-1.0, 1.0, 1e-10), 0.0)
+            .run()
+            .is_err());
+    }
+}
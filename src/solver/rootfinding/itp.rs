@@ -0,0 +1,242 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] I. F. D. Oliveira and R. H. C. Takahashi (2020). An Enhancement of the Bisection Method
+//! Average Performance Preserving Minmax Optimality. ACM Transactions on Mathematical Software
+//! 47(1), 1–24.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::rootfinding::{same_sign, RootFindingError};
+use serde::{Deserialize, Serialize};
+
+/// The ITP (Interpolate, Truncate, Project) method
+///
+/// Combines regula falsi with a truncation towards the midpoint and a projection onto an interval
+/// around the midpoint. It never needs more iterations than bisection plus `n0`, but converges
+/// superlinearly for well behaved functions.
+///
+/// The solver terminates once the interval is shorter than `tol`.
+///
+/// # References:
+///
+/// [0] I. F. D. Oliveira and R. H. C. Takahashi (2020). An Enhancement of the Bisection Method
+/// Average Performance Preserving Minmax Optimality. ACM Transactions on Mathematical Software
+/// 47(1), 1–24.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ITP<F> {
+    /// absolute tolerance for the width of the interval
+    tol: F,
+    /// truncation parameter k1; `NaN` means `0.2 / (max - min)`
+    k1: F,
+    /// truncation parameter k2
+    k2: F,
+    /// slack of the projection compared to bisection
+    n0: u64,
+    /// maximum number of iterations
+    n_max: u64,
+    /// lower boundary of the interval
+    a: F,
+    /// upper boundary of the interval
+    b: F,
+    /// function value at `a`
+    fa: F,
+    /// function value at `b`
+    fb: F,
+}
+
+impl<F: ArgminFloat> ITP<F> {
+    /// Constructor
+    /// The values `min` and `max` must bracket the root of the function.
+    /// The parameter `tol` specifies the absolute error to be targeted.
+    pub fn new(min: F, max: F, tol: F) -> ITP<F> {
+        ITP {
+            tol,
+            k1: F::nan(),
+            k2: F::from_f64(2.0).unwrap(),
+            n0: 1,
+            n_max: 0,
+            a: min.min(max),
+            b: min.max(max),
+            fa: F::nan(),
+            fb: F::nan(),
+        }
+    }
+
+    /// Set the parameters `k1 > 0`, `k2` in `[1, 1 + golden ratio)` and `n0 >= 0`. By default,
+    /// `k1 = 0.2 / (max - min)`, `k2 = 2` and `n0 = 1`.
+    pub fn with_params(mut self, k1: F, k2: F, n0: u64) -> Result<Self, Error> {
+        if k1 <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "ITP: k1 must be positive.".to_string(),
+            }
+            .into());
+        }
+        if k2 < F::from_f64(1.0).unwrap()
+            || k2 >= F::from_f64(1.0 + 0.5 * (1.0 + 5.0f64.sqrt())).unwrap()
+        {
+            return Err(ArgminError::InvalidParameter {
+                text: "ITP: k2 must be in [1, 1 + golden ratio).".to_string(),
+            }
+            .into());
+        }
+        self.k1 = k1;
+        self.k2 = k2;
+        self.n0 = n0;
+        Ok(self)
+    }
+}
+
+impl<O, F> Solver<O> for ITP<F>
+where
+    O: CostFunction<Param = F, Output = F, Float = F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "ITP";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        // ITP maintains its own state
+        _state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        self.fa = op.apply(&self.a)?;
+        self.fb = op.apply(&self.b)?;
+        if same_sign(self.fa, self.fb) {
+            return Err(RootFindingError::WrongSign.into());
+        }
+        if self.k1.is_nan() {
+            self.k1 = F::from_f64(0.2).unwrap() / (self.b - self.a);
+        }
+        let n_half = ((self.b - self.a) / self.tol)
+            .log2()
+            .ceil()
+            .max(F::from_f64(0.0).unwrap());
+        self.n_max = n_half.to_u64().unwrap_or(0) + self.n0;
+        Ok(Some(if self.fa.abs() < self.fb.abs() {
+            ArgminIterData::new().param(self.a).cost(self.fa.abs())
+        } else {
+            ArgminIterData::new().param(self.b).cost(self.fb.abs())
+        }))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let half = F::from_f64(0.5).unwrap();
+        let width = self.b - self.a;
+        let x_half = half * (self.a + self.b);
+        // radius of the projection interval around the midpoint
+        let j = state.get_iter().min(self.n_max);
+        let r = (half * self.tol * F::from_f64(2.0).unwrap().powi((self.n_max - j) as i32)
+            - half * width)
+            .max(F::from_f64(0.0).unwrap());
+        let delta = self.k1 * width.powf(self.k2);
+
+        // interpolation
+        let x_f = (self.fb * self.a - self.fa * self.b) / (self.fb - self.fa);
+        // truncation
+        let diff = x_half - x_f;
+        let sigma = if diff >= F::from_f64(0.0).unwrap() {
+            F::from_f64(1.0).unwrap()
+        } else {
+            F::from_f64(-1.0).unwrap()
+        };
+        let x_t = if delta <= diff.abs() {
+            x_f + sigma * delta
+        } else {
+            x_half
+        };
+        // projection
+        let x = if (x_t - x_half).abs() <= r {
+            x_t
+        } else {
+            x_half - sigma * r
+        };
+
+        let fx = op.apply(&x)?;
+        if same_sign(fx, self.fa) {
+            self.a = x;
+            self.fa = fx;
+        } else if same_sign(fx, self.fb) {
+            self.b = x;
+            self.fb = fx;
+        } else {
+            self.a = x;
+            self.b = x;
+        }
+        Ok(ArgminIterData::new().param(x).cost(fx.abs()))
+    }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
+        if self.b - self.a <= self.tol || state.get_cost() == F::from_f64(0.0).unwrap() {
+            TerminationReason::TargetPrecisionReached
+        } else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(itp, ITP<f64>);
+
+    #[test]
+    fn test_root() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x.powi(3) - x - 2.0));
+        let res = Executor::new(op, ITP::new(1.0, 2.0, 1e-10), 1.5)
+            .max_iters(100)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        assert!((res.state.get_best_param() - 1.521_379_706_804_567_6).abs() < 1e-9);
+        assert!(res.state.get_iter() < 20);
+    }
+
+    #[test]
+    fn test_worst_case() {
+        // Interpolation is useless for this function, but ITP must not be slower than bisection
+        // plus n0 iterations: ceil(log2(2 / 1e-8)) + 1 = 29
+        let op = FnOp::from_operator(|x: &f64| Ok(if *x < 0.1 { -1.0 } else { 1e-3 * x }));
+        let res = Executor::new(op, ITP::new(-1.0, 1.0, 1e-8), 0.0)
+            .max_iters(100)
+            .run()
+            .unwrap();
+        assert!((res.state.get_best_param() - 0.1).abs() < 1e-7);
+        assert!(res.state.get_iter() <= 29);
+    }
+
+    #[test]
+    fn test_params() {
+        let itp: ITP<f64> = ITP::new(0.0, 1.0, 1e-8);
+        assert!(itp.clone().with_params(0.0, 2.0, 1).is_err());
+        assert!(itp.clone().with_params(0.1, 0.5, 1).is_err());
+        assert!(itp.clone().with_params(0.1, 2.7, 1).is_err());
+        let ITP { k1, k2, n0, .. } = itp.with_params(0.1, 1.5, 2).unwrap();
+        assert!((k1 - 0.1).abs() < std::f64::EPSILON);
+        assert!((k2 - 1.5).abs() < std::f64::EPSILON);
+        assert_eq!(n0, 2);
+    }
+
+    #[test]
+    fn test_wrong_sign() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x.powi(2) + 1.0));
+        assert!(Executor::new(op, ITP::new(-1.0, 1.0, 1e-10), 0.0)
+            .run()
+            .is_err());
+    }
+}
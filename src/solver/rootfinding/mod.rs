@@ -0,0 +1,74 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! One dimensional root finding
+//!
+//! Bracketing methods, which require `f(min)` and `f(max)` to have different signs:
+//!
+//! * [Bisection](bisection/struct.Bisection.html)
+//! * [Regula falsi with the Illinois modification](illinois/struct.Illinois.html)
+//! * [Ridders' method](ridders/struct.Ridders.html)
+//! * [ITP method](itp/struct.ITP.html)
+//! * [Newton's method safeguarded by bisection](newton_safeguarded/struct.SafeguardedNewton.html)
+//! * [Brent's method](../brent/struct.Brent.html)
+//!
+//! Open methods:
+//!
+//! * [Secant method](secant/struct.Secant.html)
+//!
+//! All of them share the operator contract of `Brent`: `Param`, `Output` and `Float` are the same
+//! floating point type and `apply` computes `f(x)`. The cost is `|f(x)|`.
+//!
+//! If only a starting point is known, [`find_bracket`](bracket/fn.find_bracket.html) expands an
+//! interval until it brackets a root.
+//!
+//! # References:
+//!
+//! [0] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+//! Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+//! ISBN 978-0-521-88068-8.
+
+/// Bisection method
+pub mod bisection;
+/// Bracket expansion
+pub mod bracket;
+/// Regula falsi with the Illinois modification
+pub mod illinois;
+/// ITP method
+pub mod itp;
+/// Newton's method safeguarded by bisection
+pub mod newton_safeguarded;
+/// Ridders' method
+pub mod ridders;
+/// Secant method
+pub mod secant;
+
+pub use self::bisection::*;
+pub use self::bracket::*;
+pub use self::illinois::*;
+pub use self::itp::*;
+pub use self::newton_safeguarded::*;
+pub use self::ridders::*;
+pub use self::secant::*;
+
+use thiserror::Error;
+
+/// Error to be thrown if a root finder is initialized with improper parameters.
+#[derive(Debug, Error)]
+pub enum RootFindingError {
+    /// f(min) and f(max) must have different signs
+    #[error("Root finding error: f(min) and f(max) must have different signs.")]
+    WrongSign,
+    /// No bracket found
+    #[error("Root finding error: no interval with a sign change of f found.")]
+    BracketNotFound,
+}
+
+/// Returns `true` if `x` and `y` have the same sign and are both nonzero
+fn same_sign<F: crate::core::ArgminFloat>(x: F, y: F) -> bool {
+    x * y > F::from_f64(0.0).unwrap()
+}
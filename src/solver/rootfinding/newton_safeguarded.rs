@@ -0,0 +1,195 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+//! Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+//! ISBN 978-0-521-88068-8.
+
+use crate::core::capabilities::{CostFunction, Gradient};
+use crate::prelude::*;
+use crate::solver::rootfinding::{same_sign, RootFindingError};
+use serde::{Deserialize, Serialize};
+
+/// Newton's method safeguarded by bisection
+///
+/// Takes Newton steps `x - f(x) / f'(x)` with the derivative `f'(x)` computed by `gradient`, while
+/// maintaining a bracketing interval. Whenever the Newton step would leave the interval or does
+/// not reduce the step length fast enough, a bisection step is taken instead (`rtsafe` in [0]).
+///
+/// The initial parameter vector is used as starting point if it lies within the interval,
+/// otherwise the midpoint is used. The solver terminates once the step is shorter than `tol`.
+///
+/// # References:
+///
+/// [0] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+/// Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+/// ISBN 978-0-521-88068-8.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SafeguardedNewton<F> {
+    /// absolute tolerance for the step
+    tol: F,
+    /// boundary of the interval with f(a) < 0
+    a: F,
+    /// boundary of the interval with f(b) > 0
+    b: F,
+    /// current point
+    x: F,
+    /// function value at `x`
+    fx: F,
+    /// derivative at `x`
+    dfx: F,
+    /// last step
+    dx: F,
+    /// step before the last step
+    dx_old: F,
+}
+
+impl<F: ArgminFloat> SafeguardedNewton<F> {
+    /// Constructor
+    /// The values `min` and `max` must bracket the root of the function.
+    /// The parameter `tol` specifies the absolute error to be targeted.
+    pub fn new(min: F, max: F, tol: F) -> SafeguardedNewton<F> {
+        SafeguardedNewton {
+            tol,
+            a: min,
+            b: max,
+            x: F::nan(),
+            fx: F::nan(),
+            dfx: F::nan(),
+            dx: F::nan(),
+            dx_old: F::nan(),
+        }
+    }
+}
+
+impl<O, F> Solver<O> for SafeguardedNewton<F>
+where
+    O: CostFunction<Param = F, Output = F, Float = F> + Gradient,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Safeguarded Newton";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let fa = op.apply(&self.a)?;
+        let fb = op.apply(&self.b)?;
+        if same_sign(fa, fb) {
+            return Err(RootFindingError::WrongSign.into());
+        }
+        // orient the interval such that f(a) <= 0 <= f(b)
+        if fa > F::from_f64(0.0).unwrap() {
+            std::mem::swap(&mut self.a, &mut self.b);
+        }
+        let x = state.get_param();
+        self.x = if (x - self.a) * (x - self.b) < F::from_f64(0.0).unwrap() {
+            x
+        } else {
+            F::from_f64(0.5).unwrap() * (self.a + self.b)
+        };
+        self.fx = op.apply(&self.x)?;
+        self.dfx = op.gradient(&self.x)?;
+        self.dx = (self.b - self.a).abs();
+        self.dx_old = self.dx;
+        Ok(Some(
+            ArgminIterData::new().param(self.x).cost(self.fx.abs()),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        // SafeguardedNewton maintains its own state
+        _state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let two = F::from_f64(2.0).unwrap();
+        let out_of_range = ((self.x - self.b) * self.dfx - self.fx)
+            * ((self.x - self.a) * self.dfx - self.fx)
+            > F::from_f64(0.0).unwrap();
+        let too_slow = (two * self.fx).abs() > (self.dx_old * self.dfx).abs();
+        self.dx_old = self.dx;
+        let newton = !(out_of_range || too_slow || self.dfx == F::from_f64(0.0).unwrap());
+        if newton {
+            self.dx = self.fx / self.dfx;
+            self.x = self.x - self.dx;
+        } else {
+            self.dx = F::from_f64(0.5).unwrap() * (self.b - self.a);
+            self.x = self.a + self.dx;
+        }
+
+        self.fx = op.apply(&self.x)?;
+        self.dfx = op.gradient(&self.x)?;
+        if self.fx < F::from_f64(0.0).unwrap() {
+            self.a = self.x;
+        } else {
+            self.b = self.x;
+        }
+
+        Ok(ArgminIterData::new()
+            .param(self.x)
+            .cost(self.fx.abs())
+            .kv(make_kv!("newton_step" => newton;)))
+    }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
+        if self.dx.abs() <= self.tol || state.get_cost() == F::from_f64(0.0).unwrap() {
+            TerminationReason::TargetPrecisionReached
+        } else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(safeguarded_newton, SafeguardedNewton<f64>);
+
+    #[test]
+    fn test_root() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x.powi(3) - x - 2.0))
+            .gradient(|x: &f64| Ok(3.0 * x.powi(2) - 1.0));
+        let res = Executor::new(op, SafeguardedNewton::new(2.0, 1.0, 1e-12), 1.9)
+            .max_iters(100)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        assert!((res.state.get_best_param() - 1.521_379_706_804_567_6).abs() < 1e-9);
+        assert!(res.state.get_iter() < 10);
+    }
+
+    #[test]
+    fn test_safeguard() {
+        // Plain Newton diverges for atan starting at 1.5
+        let op =
+            FnOp::from_operator(|x: &f64| Ok(x.atan())).gradient(|x: &f64| Ok(1.0 / (1.0 + x * x)));
+        let res = Executor::new(op, SafeguardedNewton::new(-2.0, 3.0, 1e-12), 1.5)
+            .max_iters(100)
+            .run()
+            .unwrap();
+        assert!(res.state.get_best_param().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_wrong_sign() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x.powi(2) + 1.0)).gradient(|x: &f64| Ok(2.0 * x));
+        assert!(
+            Executor::new(op, SafeguardedNewton::new(-1.0, 1.0, 1e-10), 0.0)
+                .run()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] C. Ridders (1979). A new algorithm for computing a single root of a real continuous
+//! function. IEEE Transactions on Circuits and Systems 26(11), 979–980.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::rootfinding::{same_sign, RootFindingError};
+use serde::{Deserialize, Serialize};
+
+/// Ridders' method
+///
+/// Evaluates `f` at the midpoint of the bracketing interval and applies regula falsi to the
+/// function multiplied by an exponential that makes the three points collinear. It requires two
+/// function evaluations per iteration and converges quadratically.
+///
+/// The solver terminates once the interval is shorter than `tol`.
+///
+/// # References:
+///
+/// [0] C. Ridders (1979). A new algorithm for computing a single root of a real continuous
+/// function. IEEE Transactions on Circuits and Systems 26(11), 979–980.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Ridders<F> {
+    /// absolute tolerance for the width of the interval
+    tol: F,
+    /// boundary of the interval
+    a: F,
+    /// boundary of the interval
+    b: F,
+    /// function value at `a`
+    fa: F,
+    /// function value at `b`
+    fb: F,
+}
+
+impl<F: ArgminFloat> Ridders<F> {
+    /// Constructor
+    /// The values `min` and `max` must bracket the root of the function.
+    /// The parameter `tol` specifies the absolute error to be targeted.
+    pub fn new(min: F, max: F, tol: F) -> Ridders<F> {
+        Ridders {
+            tol,
+            a: min,
+            b: max,
+            fa: F::nan(),
+            fb: F::nan(),
+        }
+    }
+}
+
+impl<O, F> Solver<O> for Ridders<F>
+where
+    O: CostFunction<Param = F, Output = F, Float = F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Ridders";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        // Ridders maintains its own state
+        _state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        self.fa = op.apply(&self.a)?;
+        self.fb = op.apply(&self.b)?;
+        if same_sign(self.fa, self.fb) {
+            return Err(RootFindingError::WrongSign.into());
+        }
+        Ok(Some(if self.fa.abs() < self.fb.abs() {
+            ArgminIterData::new().param(self.a).cost(self.fa.abs())
+        } else {
+            ArgminIterData::new().param(self.b).cost(self.fb.abs())
+        }))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        // Ridders maintains its own state
+        _state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let zero = F::from_f64(0.0).unwrap();
+        let m = F::from_f64(0.5).unwrap() * (self.a + self.b);
+        let fm = op.apply(&m)?;
+        let s = (fm * fm - self.fa * self.fb).sqrt();
+        if s == zero {
+            // fm == 0 and one of fa, fb is zero as well
+            return Ok(ArgminIterData::new().param(m).cost(fm.abs()));
+        }
+        let sign = if self.fa >= self.fb {
+            F::from_f64(1.0).unwrap()
+        } else {
+            F::from_f64(-1.0).unwrap()
+        };
+        let x = m + (m - self.a) * sign * fm / s;
+        let fx = op.apply(&x)?;
+
+        if !same_sign(fm, fx) {
+            self.a = m;
+            self.fa = fm;
+            self.b = x;
+            self.fb = fx;
+        } else if !same_sign(self.fa, fx) {
+            self.b = x;
+            self.fb = fx;
+        } else {
+            self.a = x;
+            self.fa = fx;
+        }
+
+        Ok(if fm.abs() < fx.abs() {
+            ArgminIterData::new().param(m).cost(fm.abs())
+        } else {
+            ArgminIterData::new().param(x).cost(fx.abs())
+        })
+    }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
+        if (self.b - self.a).abs() <= self.tol || state.get_cost() == F::from_f64(0.0).unwrap() {
+            TerminationReason::TargetPrecisionReached
+        } else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(ridders, Ridders<f64>);
+
+    #[test]
+    fn test_root() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x.powi(3) - x - 2.0));
+        let res = Executor::new(op, Ridders::new(1.0, 2.0, 1e-10), 1.5)
+            .max_iters(100)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        assert!((res.state.get_best_param() - 1.521_379_706_804_567_6).abs() < 1e-9);
+        assert!(res.state.get_iter() < 20);
+    }
+
+    #[test]
+    fn test_wrong_sign() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x.powi(2) + 1.0));
+        assert!(Executor::new(op, Ridders::new(-1.0, 1.0, 1e-10), 0.0)
+            .run()
+            .is_err());
+    }
+}
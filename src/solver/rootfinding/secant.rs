@@ -0,0 +1,140 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+//! Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+//! ISBN 978-0-521-88068-8.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The secant method
+///
+/// An open method which replaces the derivative in Newton's method by the slope of the secant
+/// through the last two points. It does not require a bracket, but may diverge if the starting
+/// points are not close enough to a root.
+///
+/// The solver terminates once the step is shorter than `tol`.
+///
+/// # References:
+///
+/// [0] William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+/// Numerical Recipes: The Art of Scientific Computing. Cambridge University Press.
+/// ISBN 978-0-521-88068-8.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Secant<F> {
+    /// absolute tolerance for the step
+    tol: F,
+    /// previous point
+    x0: F,
+    /// current point
+    x1: F,
+    /// function value at `x0`
+    f0: F,
+    /// function value at `x1`
+    f1: F,
+}
+
+impl<F: ArgminFloat> Secant<F> {
+    /// Constructor
+    /// The values `x0` and `x1` are the two starting points, which should be close to the root.
+    /// The parameter `tol` specifies the absolute error to be targeted.
+    pub fn new(x0: F, x1: F, tol: F) -> Secant<F> {
+        Secant {
+            tol,
+            x0,
+            x1,
+            f0: F::nan(),
+            f1: F::nan(),
+        }
+    }
+}
+
+impl<O, F> Solver<O> for Secant<F>
+where
+    O: CostFunction<Param = F, Output = F, Float = F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Secant";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        // Secant maintains its own state
+        _state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        self.f0 = op.apply(&self.x0)?;
+        self.f1 = op.apply(&self.x1)?;
+        Ok(Some(
+            ArgminIterData::new().param(self.x1).cost(self.f1.abs()),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        // Secant maintains its own state
+        _state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        if self.f1 == self.f0 {
+            return Err(ArgminError::ConditionViolated {
+                text: "Secant: f(x0) == f(x1), secant is horizontal.".to_string(),
+            }
+            .into());
+        }
+        let x = self.x1 - self.f1 * (self.x1 - self.x0) / (self.f1 - self.f0);
+        let fx = op.apply(&x)?;
+        self.x0 = self.x1;
+        self.f0 = self.f1;
+        self.x1 = x;
+        self.f1 = fx;
+        Ok(ArgminIterData::new().param(x).cost(fx.abs()))
+    }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
+        if (self.x1 - self.x0).abs() <= self.tol || state.get_cost() == F::from_f64(0.0).unwrap() {
+            TerminationReason::TargetPrecisionReached
+        } else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(secant, Secant<f64>);
+
+    #[test]
+    fn test_root() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x.powi(3) - x - 2.0));
+        let res = Executor::new(op, Secant::new(1.0, 2.0, 1e-12), 1.5)
+            .max_iters(100)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        assert!((res.state.get_best_param() - 1.521_379_706_804_567_6).abs() < 1e-9);
+        assert!(res.state.get_iter() < 20);
+    }
+
+    #[test]
+    fn test_horizontal_secant() {
+        let op = FnOp::from_operator(|x: &f64| Ok(x.powi(2) - 1.0));
+        assert!(Executor::new(op, Secant::new(-2.0, 2.0, 1e-12), 0.0)
+            .max_iters(10)
+            .run()
+            .is_err());
+    }
+}
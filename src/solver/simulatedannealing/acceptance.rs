@@ -0,0 +1,120 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Acceptance functions for Simulated Annealing
+//!
+//! # References
+//!
+//! [0] Constantino Tsallis and Daniel A. Stariolo (1996). Generalized simulated annealing.
+//! Physica A 233, 395-406.
+
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Acceptance function for Simulated Annealing
+///
+/// Moves which decrease the cost function value are always accepted. Moves which increase the cost
+/// function value by `delta > 0` are accepted with the probability returned by `probability`.
+pub trait AcceptanceFunction<F> {
+    /// Probability of accepting a move which increases the cost function value by `delta` at
+    /// temperature `temp`
+    fn probability(&self, delta: F, temp: F) -> F;
+}
+
+/// Barker acceptance function: `1 / (1 + exp(delta / temp))`
+///
+/// The probability is always below 0.5.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct Barker {}
+
+impl<F: ArgminFloat> AcceptanceFunction<F> for Barker {
+    fn probability(&self, delta: F, temp: F) -> F {
+        let one = F::from_f64(1.0).unwrap();
+        one / (one + (delta / temp).exp())
+    }
+}
+
+/// Metropolis acceptance function: `exp(-delta / temp)`
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct Metropolis {}
+
+impl<F: ArgminFloat> AcceptanceFunction<F> for Metropolis {
+    fn probability(&self, delta: F, temp: F) -> F {
+        (-delta / temp).exp()
+    }
+}
+
+/// Tsallis acceptance function of generalized simulated annealing [0]:
+/// `[1 - (1 - q) * delta / temp]^(1 / (1 - q))` if the term in brackets is positive and `0`
+/// otherwise.
+///
+/// For `q -> 1`, this approaches the Metropolis acceptance function. Values of `q < 1` (typically
+/// negative, for instance `-5`) reject large increases of the cost function value entirely.
+///
+/// # References
+///
+/// [0] Constantino Tsallis and Daniel A. Stariolo (1996). Generalized simulated annealing.
+/// Physica A 233, 395-406.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub struct Tsallis<F> {
+    /// acceptance parameter
+    q: F,
+}
+
+impl<F: ArgminFloat> Tsallis<F> {
+    /// Constructor
+    pub fn new(q: F) -> Result<Self, Error> {
+        if q == F::from_f64(1.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "Tsallis: q must not be 1 (use Metropolis instead).".to_string(),
+            }
+            .into());
+        }
+        Ok(Tsallis { q })
+    }
+}
+
+impl<F: ArgminFloat> AcceptanceFunction<F> for Tsallis<F> {
+    fn probability(&self, delta: F, temp: F) -> F {
+        let one = F::from_f64(1.0).unwrap();
+        let base = one - (one - self.q) * delta / temp;
+        if base <= F::from_f64(0.0).unwrap() {
+            F::from_f64(0.0).unwrap()
+        } else {
+            base.powf(one / (one - self.q))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_barker() {
+        assert!((Barker {}.probability(0.0, 1.0) - 0.5f64).abs() < std::f64::EPSILON);
+        assert!(Barker {}.probability(1.0, 1.0) < 0.5f64);
+    }
+
+    #[test]
+    fn test_metropolis() {
+        let p: f64 = Metropolis {}.probability(2.0, 4.0);
+        assert!((p - (-0.5f64).exp()).abs() < std::f64::EPSILON);
+    }
+
+    #[test]
+    fn test_tsallis() {
+        let t = Tsallis::new(-1.0).unwrap();
+        // [1 - 2 * 0.25]^(1/2)
+        assert!((t.probability(1.0, 4.0) - 0.5f64.sqrt()).abs() < std::f64::EPSILON);
+        assert!(t.probability(2.0, 1.0).abs() < std::f64::EPSILON);
+        // close to Metropolis for q -> 1
+        let t = Tsallis::new(1.0 - 1e-8).unwrap();
+        assert!((t.probability(2.0, 4.0) - (-0.5f64).exp()).abs() < 1e-6);
+        assert!(Tsallis::new(1.0).is_err());
+    }
+}
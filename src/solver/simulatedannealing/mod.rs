@@ -15,40 +15,33 @@
 //! Science 13 May 1983, Vol. 220, Issue 4598, pp. 671-680
 //! DOI: 10.1126/science.220.4598.671
 
+use crate::core::capabilities::{Anneal, CostFunction};
 use crate::prelude::*;
 use rand::prelude::*;
 use rand_xorshift::XorShiftRng;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-/// Temperature functions for Simulated Annealing.
-///
-/// Given the initial temperature `t_init` and the iteration number `i`, the current temperature
-/// `t_i` is given as follows:
-///
-/// * `SATempFunc::TemperatureFast`: `t_i = t_init / i`
-/// * `SATempFunc::Boltzmann`: `t_i = t_init / ln(i)`
-/// * `SATempFunc::Exponential`: `t_i = t_init * 0.95^i`
-#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
-pub enum SATempFunc<F> {
-    /// `t_i = t_init / i`
-    TemperatureFast,
-    /// `t_i = t_init / ln(i)`
-    Boltzmann,
-    /// `t_i = t_init * x^i`
-    Exponential(F),
-    // /// User-provided temperature function. The first parameter must be the current temperature and
-    // /// the second parameter must be the iteration number.
-    // Custom(Box<Fn(f64, u64) -> f64>),
-}
+/// Acceptance functions
+pub mod acceptance;
+/// Temperature schedules
+pub mod schedule;
 
-impl<F> std::default::Default for SATempFunc<F> {
-    fn default() -> Self {
-        SATempFunc::Boltzmann
-    }
-}
+pub use self::acceptance::*;
+pub use self::schedule::*;
 
 /// Simulated Annealing
 ///
+/// In each iteration, a new parameter vector is generated by `modify` of the operator (see
+/// `Anneal`) with the current temperature as extent of the modification. Better parameter
+/// vectors are always accepted, worse ones with a probability given by the acceptance function
+/// (`Barker` by default, see also `Metropolis` and `Tsallis`). The temperature is updated by a
+/// temperature schedule (`SATempFunc::TemperatureFast` by default), which can be any type
+/// implementing `TemperatureSchedule`.
+///
+/// With `auto_init_temp`, the initial temperature is derived from the increases of the cost
+/// function value of random moves from the initial parameter vector such that the average
+/// increase is accepted with a given probability by the acceptance function.
+///
 /// [Example](https://github.com/argmin-rs/argmin/blob/master/examples/simulatedannealing.rs)
 ///
 /// # References
@@ -59,11 +52,15 @@ impl<F> std::default::Default for SATempFunc<F> {
 /// Science 13 May 1983, Vol. 220, Issue 4598, pp. 671-680
 /// DOI: 10.1126/science.220.4598.671  
 #[derive(Clone, Serialize, Deserialize)]
-pub struct SimulatedAnnealing<F> {
+pub struct SimulatedAnnealing<F, T = SATempFunc<F>, A = Barker> {
     /// Initial temperature
     init_temp: F,
+    /// Number of samples and acceptance probability for the automatic initial temperature
+    auto_init_temp: Option<(u64, F)>,
     /// which temperature function?
-    temp_func: SATempFunc<F>,
+    temp_func: T,
+    /// which acceptance function?
+    acceptance: A,
     /// Number of iterations used for the caluclation of temperature. This is needed for
     /// reannealing!
     temp_iter: u64,
@@ -111,7 +108,9 @@ where
         } else {
             Ok(SimulatedAnnealing {
                 init_temp,
+                auto_init_temp: None,
                 temp_func: SATempFunc::TemperatureFast,
+                acceptance: Barker {},
                 temp_iter: 0,
                 stall_iter_accepted: 0,
                 stall_iter_accepted_limit: std::u64::MAX,
@@ -128,11 +127,139 @@ where
             })
         }
     }
+}
 
-    /// Set temperature function to one of the options in `SATempFunc`.
-    pub fn temp_func(mut self, temperature_func: SATempFunc<F>) -> Self {
-        self.temp_func = temperature_func;
-        self
+impl<F, T, A> SimulatedAnnealing<F, T, A>
+where
+    F: ArgminFloat,
+    T: TemperatureSchedule<F>,
+    A: AcceptanceFunction<F>,
+{
+    /// Set temperature function to one of the options in `SATempFunc` or any other type
+    /// implementing `TemperatureSchedule`.
+    pub fn temp_func<T2: TemperatureSchedule<F>>(
+        self,
+        temperature_func: T2,
+    ) -> SimulatedAnnealing<F, T2, A> {
+        self.rebuild(|_, acceptance| (temperature_func, acceptance))
+    }
+
+    /// Set acceptance function (`Barker`, `Metropolis`, `Tsallis` or any other type implementing
+    /// `AcceptanceFunction`).
+    pub fn acceptance<A2: AcceptanceFunction<F>>(
+        self,
+        acceptance: A2,
+    ) -> SimulatedAnnealing<F, T, A2> {
+        self.rebuild(|temp_func, _| (temp_func, acceptance))
+    }
+
+    /// Replace temperature schedule and acceptance function by the ones returned by `f` and keep
+    /// everything else
+    fn rebuild<T2, A2>(self, f: impl FnOnce(T, A) -> (T2, A2)) -> SimulatedAnnealing<F, T2, A2> {
+        let (temp_func, acceptance) = f(self.temp_func, self.acceptance);
+        SimulatedAnnealing {
+            init_temp: self.init_temp,
+            auto_init_temp: self.auto_init_temp,
+            temp_func,
+            acceptance,
+            temp_iter: self.temp_iter,
+            stall_iter_accepted: self.stall_iter_accepted,
+            stall_iter_accepted_limit: self.stall_iter_accepted_limit,
+            stall_iter_best: self.stall_iter_best,
+            stall_iter_best_limit: self.stall_iter_best_limit,
+            reanneal_fixed: self.reanneal_fixed,
+            reanneal_iter_fixed: self.reanneal_iter_fixed,
+            reanneal_accepted: self.reanneal_accepted,
+            reanneal_iter_accepted: self.reanneal_iter_accepted,
+            reanneal_best: self.reanneal_best,
+            reanneal_iter_best: self.reanneal_iter_best,
+            cur_temp: self.cur_temp,
+            rng: self.rng,
+        }
+    }
+
+    /// Derive the initial temperature from `samples` random moves from the initial parameter
+    /// vector, such that the average increase of the cost function value is accepted with
+    /// probability `acceptance_prob` by the configured acceptance function (for `Metropolis`:
+    /// `t_init = -mean(delta) / ln(acceptance_prob)`). The initial temperature passed to `new` is
+    /// used as extent of the moves and is kept if none of the moves increases the cost function
+    /// value.
+    ///
+    /// `acceptance_prob` must be below the probability the acceptance function approaches for
+    /// infinite temperatures (0.5 for `Barker`).
+    pub fn auto_init_temp(mut self, samples: u64, acceptance_prob: F) -> Result<Self, Error> {
+        if samples == 0 {
+            return Err(ArgminError::InvalidParameter {
+                text: "SimulatedAnnealing: number of samples must be > 0.".to_string(),
+            }
+            .into());
+        }
+        if acceptance_prob <= F::from_f64(0.0).unwrap()
+            || acceptance_prob >= F::from_f64(1.0).unwrap()
+        {
+            return Err(ArgminError::InvalidParameter {
+                text: "SimulatedAnnealing: acceptance probability must be in (0, 1).".to_string(),
+            }
+            .into());
+        }
+        if acceptance_prob
+            >= self
+                .acceptance
+                .probability(F::from_f64(1.0).unwrap(), F::max_value())
+        {
+            return Err(ArgminError::InvalidParameter {
+                text: "SimulatedAnnealing: acceptance probability is not reachable with the \
+                       acceptance function."
+                    .to_string(),
+            }
+            .into());
+        }
+        self.auto_init_temp = Some((samples, acceptance_prob));
+        Ok(self)
+    }
+
+    /// Temperature at which an increase of the cost function value by `delta` is accepted with
+    /// probability `acceptance_prob`. The acceptance probability increases with the temperature,
+    /// hence the temperature is bracketed by doubling or halving and then found by bisection.
+    fn calibrate_temperature(&self, delta: F, acceptance_prob: F) -> Result<F, Error> {
+        let two = F::from_f64(2.0).unwrap();
+        let reached = |temp: F| self.acceptance.probability(delta, temp) >= acceptance_prob;
+        let not_reachable = || {
+            Err(ArgminError::ConditionViolated {
+                text: "SimulatedAnnealing: acceptance probability is not reachable with the \
+                       acceptance function."
+                    .to_string(),
+            }
+            .into())
+        };
+        let (mut lo, mut hi) = if reached(delta) {
+            let mut hi = delta;
+            while reached(hi / two) {
+                hi = hi / two;
+                if hi <= F::from_f64(0.0).unwrap() {
+                    return not_reachable();
+                }
+            }
+            (hi / two, hi)
+        } else {
+            let mut lo = delta;
+            while !reached(lo * two) {
+                lo = lo * two;
+                if !lo.is_finite() {
+                    return not_reachable();
+                }
+            }
+            (lo, lo * two)
+        };
+        for _ in 0..64 {
+            let mid = (lo + hi) / two;
+            if reached(mid) {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        Ok(hi)
     }
 
     /// The optimization stops after there has been no accepted solution after `iter` iterations
@@ -167,17 +294,11 @@ where
 
     /// Update the temperature based on the current iteration number.
     ///
-    /// Updates are performed based on specific update functions. See `SATempFunc` for details.
-    fn update_temperature(&mut self) {
-        self.cur_temp = match self.temp_func {
-            SATempFunc::TemperatureFast => {
-                self.init_temp / F::from_u64(self.temp_iter + 1).unwrap()
-            }
-            SATempFunc::Boltzmann => self.init_temp / F::from_u64(self.temp_iter + 1).unwrap().ln(),
-            SATempFunc::Exponential(x) => {
-                self.init_temp * x.powf(F::from_u64(self.temp_iter + 1).unwrap())
-            }
-        };
+    /// Updates are performed by the temperature schedule. See `TemperatureSchedule` for details.
+    fn update_temperature(&mut self, accepted: bool) {
+        self.cur_temp =
+            self.temp_func
+                .temperature(self.init_temp, self.cur_temp, self.temp_iter, accepted);
     }
 
     /// Perform reannealing
@@ -193,6 +314,7 @@ where
             self.reanneal_iter_best = 0;
             self.cur_temp = self.init_temp;
             self.temp_iter = 0;
+            self.temp_func.reset();
         }
         out
     }
@@ -225,18 +347,41 @@ where
     }
 }
 
-impl<O, F> Solver<O> for SimulatedAnnealing<F>
+impl<O, F, T, A> Solver<O> for SimulatedAnnealing<F, T, A>
 where
-    O: ArgminOp<Output = F, Float = F>,
+    O: CostFunction<Output = F, Float = F> + Anneal,
     F: ArgminFloat,
+    T: TemperatureSchedule<F> + Clone + Serialize + DeserializeOwned,
+    A: AcceptanceFunction<F> + Clone + Serialize + DeserializeOwned,
 {
     const NAME: &'static str = "Simulated Annealing";
     fn init(
         &mut self,
-        _op: &mut OpWrapper<O>,
-        _state: &IterState<O>,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
     ) -> Result<Option<ArgminIterData<O>>, Error> {
-        Ok(Some(ArgminIterData::new().kv(make_kv!(
+        let mut data = ArgminIterData::new();
+        if let Some((samples, acceptance_prob)) = self.auto_init_temp {
+            let param = state.get_param();
+            let cost = op.apply(&param)?;
+            let mut sum = F::from_f64(0.0).unwrap();
+            let mut count = 0;
+            for _ in 0..samples {
+                let new_param = op.modify(&param, self.init_temp)?;
+                let new_cost = op.apply(&new_param)?;
+                if new_cost > cost {
+                    sum = sum + new_cost - cost;
+                    count += 1;
+                }
+            }
+            if count > 0 {
+                self.init_temp =
+                    self.calibrate_temperature(sum / F::from_u64(count).unwrap(), acceptance_prob)?;
+                self.cur_temp = self.init_temp;
+            }
+            data = data.param(param).cost(cost);
+        }
+        Ok(Some(data.kv(make_kv!(
             "initial_temperature" => self.init_temp;
             "stall_iter_accepted_limit" => self.stall_iter_accepted_limit;
             "stall_iter_best_limit" => self.stall_iter_best_limit;
@@ -272,17 +417,13 @@ where
         // If no, move on with old parameter vector.
         //
         // Any solution which satisfies `next_cost < prev_cost` will be accepted. Solutions worse
-        // than the previous one are accepted with a probability given as:
-        //
-        // `1 / (1 + exp((next_cost - prev_cost) / current_temperature))`,
-        //
-        // which will always be between 0 and 0.5.
+        // than the previous one are accepted with a probability given by the acceptance function.
         let prob: f64 = self.rng.gen();
         let prob = F::from_f64(prob).unwrap();
         let accepted = (new_cost < state.get_prev_cost())
-            || (F::from_f64(1.0).unwrap()
-                / (F::from_f64(1.0).unwrap()
-                    + ((new_cost - state.get_prev_cost()) / self.cur_temp).exp())
+            || (self
+                .acceptance
+                .probability(new_cost - state.get_prev_cost(), self.cur_temp)
                 > prob);
 
         // Update stall iter variables
@@ -295,7 +436,7 @@ where
         // Todo: this variable may not be necessary (temp_iter does the same?)
         self.reanneal_iter_fixed += 1;
 
-        self.update_temperature();
+        self.update_temperature(accepted);
 
         Ok(if accepted {
             ArgminIterData::new().param(new_param).cost(new_cost)
@@ -334,4 +475,86 @@ mod tests {
     use crate::test_trait_impl;
 
     test_trait_impl!(sa, SimulatedAnnealing<f64>);
+    test_trait_impl!(
+        sa_adaptive_tsallis,
+        SimulatedAnnealing<f64, AdaptiveSchedule<f64>, Tsallis<f64>>
+    );
+
+    fn run<T, A>(solver: SimulatedAnnealing<f64, T, A>) -> f64
+    where
+        T: TemperatureSchedule<f64> + Clone + Serialize + DeserializeOwned,
+        A: AcceptanceFunction<f64> + Clone + Serialize + DeserializeOwned,
+    {
+        let op = FnOp::new(|x: &f64| Ok(x.powi(2)))
+            .modify(|x: &f64, _t: f64| Ok(x + rand::thread_rng().gen_range(-0.5..0.5)));
+        Executor::new(op, solver, 5.0)
+            .max_iters(2000)
+            .run()
+            .unwrap()
+            .state
+            .get_best_cost()
+    }
+
+    #[test]
+    fn test_schedules() {
+        let sa = SimulatedAnnealing::new(10.0).unwrap();
+        assert!(run(sa.clone()) < 1.0);
+        assert!(
+            run(sa
+                .clone()
+                .temp_func(LinearSchedule::new(0.01, 1000).unwrap()))
+                < 1.0
+        );
+        assert!(run(sa.clone().temp_func(LogarithmicSchedule::new(1.0).unwrap())) < 1.0);
+        assert!(run(sa.clone().temp_func(LundyMeesSchedule::new(0.1).unwrap())) < 1.0);
+        assert!(run(sa.temp_func(AdaptiveSchedule::new(0.3, 50).unwrap())) < 1.0);
+    }
+
+    #[test]
+    fn test_acceptance() {
+        let sa = SimulatedAnnealing::new(10.0)
+            .unwrap()
+            .temp_func(SATempFunc::Exponential(0.99));
+        assert!(run(sa.clone().acceptance(Metropolis {})) < 1.0);
+        assert!(run(sa.acceptance(Tsallis::new(-5.0).unwrap())) < 1.0);
+    }
+
+    #[test]
+    fn test_auto_init_temp() {
+        // every move increases the cost function value by 1
+        let op = FnOp::new(|x: &f64| Ok(*x)).modify(|x: &f64, _t: f64| Ok(x + 1.0));
+        let mut op = OpWrapper::new(op);
+        let mut sa = SimulatedAnnealing::new(1.0)
+            .unwrap()
+            .acceptance(Metropolis {})
+            .auto_init_temp(10, 0.5)
+            .unwrap();
+        sa.init(&mut op, &IterState::new(0.0)).unwrap();
+        assert!((sa.init_temp + 1.0 / 0.5f64.ln()).abs() < 1e-12);
+        assert!((sa.cur_temp - sa.init_temp).abs() < std::f64::EPSILON);
+
+        // Barker: 1 / (1 + exp(1 / t)) = 0.25
+        let mut sa = SimulatedAnnealing::new(1.0)
+            .unwrap()
+            .auto_init_temp(10, 0.25)
+            .unwrap();
+        sa.init(&mut op, &IterState::new(0.0)).unwrap();
+        assert!((sa.init_temp - 1.0 / 3.0f64.ln()).abs() < 1e-12);
+        assert!((sa.cur_temp - sa.init_temp).abs() < std::f64::EPSILON);
+
+        let sa = SimulatedAnnealing::<f64>::new(1.0).unwrap();
+        assert!(sa.clone().auto_init_temp(0, 0.5).is_err());
+        assert!(sa.clone().auto_init_temp(10, 1.0).is_err());
+        // Barker never accepts with probability 0.5 or more
+        assert!(sa.clone().auto_init_temp(10, 0.5).is_err());
+        assert!(sa.acceptance(Metropolis {}).auto_init_temp(10, 0.5).is_ok());
+        // the acceptance function is changed after setting the acceptance probability
+        let mut sa = SimulatedAnnealing::new(1.0)
+            .unwrap()
+            .acceptance(Metropolis {})
+            .auto_init_temp(10, 0.75)
+            .unwrap()
+            .acceptance(Barker {});
+        assert!(sa.init(&mut op, &IterState::new(0.0)).is_err());
+    }
 }
@@ -0,0 +1,324 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Temperature schedules for Simulated Annealing
+//!
+//! # References
+//!
+//! [0] M. Lundy and A. Mees (1986). Convergence of an annealing algorithm. Mathematical
+//! Programming 34, 111-124.
+//!
+//! [1] Yaghout Nourani and Bjarne Andresen (1998). A comparison of simulated annealing cooling
+//! strategies. Journal of Physics A: Mathematical and General 31, 8373-8385.
+
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Temperature schedule for Simulated Annealing
+///
+/// The schedule is called once per iteration and returns the temperature for the next iteration.
+/// Schedules may keep state (for instance acceptance statistics), which is reset when
+/// reannealing is performed.
+pub trait TemperatureSchedule<F> {
+    /// Compute the temperature for the next iteration.
+    ///
+    /// * `init_temp`: initial temperature
+    /// * `cur_temp`: temperature of the current iteration
+    /// * `iter`: number of iterations since the start or the last reannealing (starting at 1)
+    /// * `accepted`: whether the move of the current iteration was accepted
+    fn temperature(&mut self, init_temp: F, cur_temp: F, iter: u64, accepted: bool) -> F;
+
+    /// Reset the internal state of the schedule (called when reannealing is performed)
+    fn reset(&mut self) {}
+}
+
+/// Temperature functions for Simulated Annealing.
+///
+/// Given the initial temperature `t_init` and the iteration number `i`, the current temperature
+/// `t_i` is given as follows:
+///
+/// * `SATempFunc::TemperatureFast`: `t_i = t_init / i`
+/// * `SATempFunc::Boltzmann`: `t_i = t_init / ln(i)`
+/// * `SATempFunc::Exponential`: `t_i = t_init * x^i`
+///
+/// Schedules with parameters or internal state are implemented as separate types, see
+/// `LinearSchedule`, `LogarithmicSchedule`, `LundyMeesSchedule` and `AdaptiveSchedule`.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+pub enum SATempFunc<F> {
+    /// `t_i = t_init / i`
+    TemperatureFast,
+    /// `t_i = t_init / ln(i)`
+    Boltzmann,
+    /// `t_i = t_init * x^i`
+    Exponential(F),
+}
+
+impl<F> std::default::Default for SATempFunc<F> {
+    fn default() -> Self {
+        SATempFunc::Boltzmann
+    }
+}
+
+impl<F: ArgminFloat> TemperatureSchedule<F> for SATempFunc<F> {
+    fn temperature(&mut self, init_temp: F, _cur_temp: F, iter: u64, _accepted: bool) -> F {
+        let i = F::from_u64(iter + 1).unwrap();
+        match *self {
+            SATempFunc::TemperatureFast => init_temp / i,
+            SATempFunc::Boltzmann => init_temp / i.ln(),
+            SATempFunc::Exponential(x) => init_temp * x.powf(i),
+        }
+    }
+}
+
+/// Linear schedule
+///
+/// The temperature decreases linearly from `t_init` to `final_temp` within `iters` iterations and
+/// stays at `final_temp` afterwards: `t_i = t_init + (final_temp - t_init) * min(i / iters, 1)`.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+pub struct LinearSchedule<F> {
+    /// final temperature
+    final_temp: F,
+    /// number of iterations until the final temperature is reached
+    iters: u64,
+}
+
+impl<F: ArgminFloat> LinearSchedule<F> {
+    /// Constructor
+    pub fn new(final_temp: F, iters: u64) -> Result<Self, Error> {
+        if final_temp <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "LinearSchedule: final temperature must be > 0.".to_string(),
+            }
+            .into());
+        }
+        if iters == 0 {
+            return Err(ArgminError::InvalidParameter {
+                text: "LinearSchedule: number of iterations must be > 0.".to_string(),
+            }
+            .into());
+        }
+        Ok(LinearSchedule { final_temp, iters })
+    }
+}
+
+impl<F: ArgminFloat> TemperatureSchedule<F> for LinearSchedule<F> {
+    fn temperature(&mut self, init_temp: F, _cur_temp: F, iter: u64, _accepted: bool) -> F {
+        let t = F::from_u64(iter.min(self.iters)).unwrap() / F::from_u64(self.iters).unwrap();
+        init_temp + (self.final_temp - init_temp) * t
+    }
+}
+
+/// Logarithmic schedule
+///
+/// `t_i = t_init / (1 + c * ln(1 + i))` with `c > 0` [1]. Unlike `SATempFunc::Boltzmann`, the
+/// temperature starts at `t_init`.
+///
+/// # References
+///
+/// [1] Yaghout Nourani and Bjarne Andresen (1998). A comparison of simulated annealing cooling
+/// strategies. Journal of Physics A: Mathematical and General 31, 8373-8385.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+pub struct LogarithmicSchedule<F> {
+    /// scaling factor
+    c: F,
+}
+
+impl<F: ArgminFloat> LogarithmicSchedule<F> {
+    /// Constructor
+    pub fn new(c: F) -> Result<Self, Error> {
+        if c <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "LogarithmicSchedule: c must be > 0.".to_string(),
+            }
+            .into());
+        }
+        Ok(LogarithmicSchedule { c })
+    }
+}
+
+impl<F: ArgminFloat> TemperatureSchedule<F> for LogarithmicSchedule<F> {
+    fn temperature(&mut self, init_temp: F, _cur_temp: F, iter: u64, _accepted: bool) -> F {
+        let one = F::from_f64(1.0).unwrap();
+        init_temp / (one + self.c * (one + F::from_u64(iter).unwrap()).ln())
+    }
+}
+
+/// Lundy-Mees schedule
+///
+/// `t_{i+1} = t_i / (1 + beta * t_i)` with `beta > 0` [0].
+///
+/// # References
+///
+/// [0] M. Lundy and A. Mees (1986). Convergence of an annealing algorithm. Mathematical
+/// Programming 34, 111-124.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+pub struct LundyMeesSchedule<F> {
+    /// cooling rate
+    beta: F,
+}
+
+impl<F: ArgminFloat> LundyMeesSchedule<F> {
+    /// Constructor
+    pub fn new(beta: F) -> Result<Self, Error> {
+        if beta <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "LundyMeesSchedule: beta must be > 0.".to_string(),
+            }
+            .into());
+        }
+        Ok(LundyMeesSchedule { beta })
+    }
+}
+
+impl<F: ArgminFloat> TemperatureSchedule<F> for LundyMeesSchedule<F> {
+    fn temperature(&mut self, _init_temp: F, cur_temp: F, _iter: u64, _accepted: bool) -> F {
+        cur_temp / (F::from_f64(1.0).unwrap() + self.beta * cur_temp)
+    }
+}
+
+/// Adaptive schedule
+///
+/// Every `window` iterations, the rate of accepted moves within the window is compared to
+/// `target_rate`. If it is above the target, the temperature is multiplied by `factor` (default
+/// `0.9`), if it is below, the temperature is divided by `factor`. This keeps the acceptance rate
+/// close to the target without tuning the temperature by hand.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+pub struct AdaptiveSchedule<F> {
+    /// target acceptance rate
+    target_rate: F,
+    /// number of iterations between updates
+    window: u64,
+    /// cooling factor
+    factor: F,
+    /// accepted moves in the current window
+    accepted: u64,
+    /// iterations in the current window
+    count: u64,
+}
+
+impl<F: ArgminFloat> AdaptiveSchedule<F> {
+    /// Constructor
+    pub fn new(target_rate: F, window: u64) -> Result<Self, Error> {
+        if target_rate <= F::from_f64(0.0).unwrap() || target_rate >= F::from_f64(1.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "AdaptiveSchedule: target rate must be in (0, 1).".to_string(),
+            }
+            .into());
+        }
+        if window == 0 {
+            return Err(ArgminError::InvalidParameter {
+                text: "AdaptiveSchedule: window must be > 0.".to_string(),
+            }
+            .into());
+        }
+        Ok(AdaptiveSchedule {
+            target_rate,
+            window,
+            factor: F::from_f64(0.9).unwrap(),
+            accepted: 0,
+            count: 0,
+        })
+    }
+
+    /// Set cooling factor
+    pub fn factor(mut self, factor: F) -> Result<Self, Error> {
+        if factor <= F::from_f64(0.0).unwrap() || factor >= F::from_f64(1.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "AdaptiveSchedule: factor must be in (0, 1).".to_string(),
+            }
+            .into());
+        }
+        self.factor = factor;
+        Ok(self)
+    }
+}
+
+impl<F: ArgminFloat> TemperatureSchedule<F> for AdaptiveSchedule<F> {
+    fn temperature(&mut self, _init_temp: F, cur_temp: F, _iter: u64, accepted: bool) -> F {
+        self.count += 1;
+        if accepted {
+            self.accepted += 1;
+        }
+        if self.count < self.window {
+            return cur_temp;
+        }
+        let rate = F::from_u64(self.accepted).unwrap() / F::from_u64(self.count).unwrap();
+        self.reset();
+        if rate > self.target_rate {
+            cur_temp * self.factor
+        } else if rate < self.target_rate {
+            cur_temp / self.factor
+        } else {
+            cur_temp
+        }
+    }
+
+    fn reset(&mut self) {
+        self.accepted = 0;
+        self.count = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sa_temp_func() {
+        let mut f = SATempFunc::TemperatureFast;
+        assert!((f.temperature(10.0, 10.0, 4, true) - 2.0).abs() < std::f64::EPSILON);
+        let mut f = SATempFunc::Exponential(0.5);
+        assert!((f.temperature(10.0, 10.0, 1, true) - 2.5).abs() < std::f64::EPSILON);
+    }
+
+    #[test]
+    fn test_linear() {
+        let mut s = LinearSchedule::new(1.0, 10).unwrap();
+        assert!((s.temperature(11.0, 11.0, 5, true) - 6.0).abs() < std::f64::EPSILON);
+        assert!((s.temperature(11.0, 11.0, 20, true) - 1.0).abs() < std::f64::EPSILON);
+        assert!(LinearSchedule::new(0.0, 10).is_err());
+        assert!(LinearSchedule::new(1.0, 0).is_err());
+    }
+
+    #[test]
+    fn test_logarithmic() {
+        let mut s = LogarithmicSchedule::new(2.0).unwrap();
+        let t = s.temperature(10.0, 10.0, 3, true);
+        assert!((t - 10.0 / (1.0 + 2.0 * 4.0f64.ln())).abs() < std::f64::EPSILON);
+        assert!(LogarithmicSchedule::new(0.0).is_err());
+    }
+
+    #[test]
+    fn test_lundy_mees() {
+        let mut s = LundyMeesSchedule::new(0.5).unwrap();
+        assert!((s.temperature(10.0, 2.0, 1, true) - 1.0).abs() < std::f64::EPSILON);
+        assert!(LundyMeesSchedule::new(-0.5).is_err());
+    }
+
+    #[test]
+    fn test_adaptive() {
+        let mut s = AdaptiveSchedule::new(0.5, 4).unwrap().factor(0.5).unwrap();
+        let mut t = 8.0;
+        for _ in 0..4 {
+            t = s.temperature(8.0, t, 1, true);
+        }
+        // acceptance rate too high
+        assert!((t - 4.0).abs() < std::f64::EPSILON);
+        for _ in 0..4 {
+            t = s.temperature(8.0, t, 1, false);
+        }
+        // acceptance rate too low
+        assert!((t - 8.0).abs() < std::f64::EPSILON);
+        for accepted in &[true, false, true, false] {
+            t = s.temperature(8.0, t, 1, *accepted);
+        }
+        assert!((t - 8.0).abs() < std::f64::EPSILON);
+        assert!(AdaptiveSchedule::new(1.0, 4).is_err());
+        assert!(AdaptiveSchedule::new(0.5, 0).is_err());
+        assert!(AdaptiveSchedule::new(0.5, 4).unwrap().factor(1.0).is_err());
+    }
+}
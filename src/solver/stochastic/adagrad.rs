@@ -0,0 +1,185 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] John Duchi, Elad Hazan and Yoram Singer (2011). Adaptive Subgradient Methods for Online
+//! Learning and Stochastic Optimization. Journal of Machine Learning Research 12, 2121-2159.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::stochastic::{
+    report_epoch, sqrt_plus_eps, BatchSampler, FullGradient, GradientSource, LearningRateSchedule,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::default::Default;
+
+/// AdaGrad
+///
+/// Scales the learning rate of every component by the root of the sum of all squared gradients
+/// of this component [0]:
+///
+/// `G_{k+1} = G_k + g_k^2`, `x_{k+1} = x_k - lr_k * g_k / (sqrt(G_{k+1}) + epsilon)`.
+///
+/// The default for `epsilon` is `1e-10`.
+///
+/// With `with_sampler`, `g_k` is the mean gradient of a mini-batch of terms of a `StochasticOp`
+/// instead of the gradient computed by `gradient`.
+///
+/// # References:
+///
+/// [0] John Duchi, Elad Hazan and Yoram Singer (2011). Adaptive Subgradient Methods for Online
+/// Learning and Stochastic Optimization. Journal of Machine Learning Research 12, 2121-2159.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AdaGrad<P, F, L = F, G = FullGradient> {
+    /// learning rate
+    learning_rate: L,
+    /// source of the gradients
+    gradient_source: G,
+    /// epsilon
+    epsilon: F,
+    /// sum of squared gradients
+    sum_sq: P,
+}
+
+impl<P, F, L> AdaGrad<P, F, L>
+where
+    P: Default,
+    F: ArgminFloat,
+    L: LearningRateSchedule<F>,
+{
+    /// Constructor
+    pub fn new(learning_rate: L) -> Self {
+        AdaGrad {
+            learning_rate,
+            gradient_source: FullGradient,
+            epsilon: F::from_f64(1e-10).unwrap(),
+            sum_sq: P::default(),
+        }
+    }
+
+    /// Compute the gradients on mini-batches drawn from `sampler`
+    pub fn with_sampler(self, sampler: BatchSampler) -> AdaGrad<P, F, L, BatchSampler> {
+        AdaGrad {
+            learning_rate: self.learning_rate,
+            gradient_source: sampler,
+            epsilon: self.epsilon,
+            sum_sq: self.sum_sq,
+        }
+    }
+}
+
+impl<P, F, L, G> AdaGrad<P, F, L, G>
+where
+    F: ArgminFloat,
+{
+    /// Set epsilon
+    pub fn with_epsilon(mut self, epsilon: F) -> Result<Self, Error> {
+        if epsilon <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "AdaGrad: epsilon must be > 0.".to_string(),
+            }
+            .into());
+        }
+        self.epsilon = epsilon;
+        Ok(self)
+    }
+}
+
+impl<O, P, F, L, G> Solver<O> for AdaGrad<P, F, L, G>
+where
+    O: CostFunction<Param = P, Float = F>,
+    P: Clone
+        + Default
+        + Serialize
+        + DeserializeOwned
+        + ArgminZeroLike
+        + ArgminAdd<P, P>
+        + ArgminMul<P, P>
+        + ArgminDiv<P, P>
+        + ArgminScaledSub<P, F, P>
+        + ArgminComponents<F>,
+    F: ArgminFloat,
+    L: LearningRateSchedule<F> + Clone + Serialize + DeserializeOwned,
+    G: GradientSource<O> + Clone + Serialize + DeserializeOwned,
+{
+    const NAME: &'static str = "AdaGrad";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        self.gradient_source.prepare(op)?;
+        self.sum_sq = state.get_param().zero_like();
+        Ok(None)
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let param = state.get_param();
+        let grad = self.gradient_source.gradient(op, &param)?;
+        let lr = self.learning_rate.learning_rate(state.get_iter());
+
+        self.sum_sq = self.sum_sq.add(&grad.mul(&grad));
+        let step = grad.div(&sqrt_plus_eps(&self.sum_sq, self.epsilon));
+        let new_param = param.scaled_sub(&lr, &step);
+
+        let data = ArgminIterData::new()
+            .param(new_param)
+            .grad(grad)
+            .kv(make_kv!("learning_rate" => lr;));
+        Ok(report_epoch(data, &self.gradient_source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::stochastic::tests::LeastSquares;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(adagrad, AdaGrad<Vec<f64>, f64>);
+    test_trait_impl!(adagrad_sampler, AdaGrad<Vec<f64>, f64, f64, BatchSampler>);
+
+    #[test]
+    fn test_adagrad() {
+        let op = FnOp::new(|x: &Vec<f64>| Ok((x[0] - 1.0).powi(2) + 10.0 * (x[1] + 1.0).powi(2)))
+            .gradient(|x: &Vec<f64>| Ok(vec![2.0 * (x[0] - 1.0), 20.0 * (x[1] + 1.0)]));
+        let res = Executor::new(op, AdaGrad::new(1.0), vec![5.0, 5.0])
+            .max_iters(2000)
+            .run()
+            .unwrap();
+        let p = res.state.get_best_param();
+        assert!((p[0] - 1.0).abs() < 1e-6);
+        assert!((p[1] + 1.0).abs() < 1e-6);
+        assert!(AdaGrad::<Vec<f64>, f64>::new(1.0)
+            .with_epsilon(0.0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_sampler() {
+        let sampler = BatchSampler::new(5).unwrap().with_seed(0);
+        let res = Executor::new(
+            LeastSquares::new(50),
+            AdaGrad::new(0.5).with_sampler(sampler),
+            vec![0.0, 0.0],
+        )
+        .max_epochs(300)
+        .run()
+        .unwrap();
+        assert_eq!(res.state.get_epoch(), 300);
+        assert_eq!(res.state.get_grad_func_count(), 3000);
+        let p = res.state.get_best_param();
+        assert!((p[0] - 1.0).abs() < 1e-3);
+        assert!((p[1] + 2.0).abs() < 1e-3);
+    }
+}
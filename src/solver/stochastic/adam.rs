@@ -0,0 +1,314 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Diederik P. Kingma and Jimmy Ba (2015). Adam: A Method for Stochastic Optimization.
+//! International Conference on Learning Representations.
+//!
+//! [1] Ilya Loshchilov and Frank Hutter (2019). Decoupled Weight Decay Regularization.
+//! International Conference on Learning Representations.
+//!
+//! [2] Sashank J. Reddi, Satyen Kale and Sanjiv Kumar (2018). On the Convergence of Adam and
+//! Beyond. International Conference on Learning Representations.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::stochastic::{
+    report_epoch, sqrt_plus_eps, BatchSampler, FullGradient, GradientSource, LearningRateSchedule,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::default::Default;
+
+/// Adam
+///
+/// Maintains exponential moving averages of the gradient `g_k` and of its componentwise square
+/// [0]:
+///
+/// `m_{k+1} = beta1 * m_k + (1 - beta1) * g_k`
+///
+/// `v_{k+1} = beta2 * v_k + (1 - beta2) * g_k^2`
+///
+/// `x_{k+1} = x_k - lr_k * m_hat / (sqrt(v_hat) + epsilon)`,
+///
+/// where `m_hat` and `v_hat` are the bias-corrected averages `m_{k+1} / (1 - beta1^(k+1))` and
+/// `v_{k+1} / (1 - beta2^(k+1))`. The defaults are `beta1 = 0.9`, `beta2 = 0.999` and
+/// `epsilon = 1e-8`.
+///
+/// With `with_weight_decay`, decoupled weight decay `x_k - lr_k * lambda * x_k` is applied
+/// (AdamW [1]). With `with_amsgrad`, the maximum of all `v_k` is used instead of `v_k`
+/// (AMSGrad [2]).
+///
+/// The gradients are computed with `gradient` unless a `BatchSampler` is set with `with_sampler`,
+/// in which case `g_k` is the mean gradient of a mini-batch of terms of a `StochasticOp`.
+///
+/// # References:
+///
+/// [0] Diederik P. Kingma and Jimmy Ba (2015). Adam: A Method for Stochastic Optimization.
+/// International Conference on Learning Representations.
+///
+/// [1] Ilya Loshchilov and Frank Hutter (2019). Decoupled Weight Decay Regularization.
+/// International Conference on Learning Representations.
+///
+/// [2] Sashank J. Reddi, Satyen Kale and Sanjiv Kumar (2018). On the Convergence of Adam and
+/// Beyond. International Conference on Learning Representations.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Adam<P, F, L = F, G = FullGradient> {
+    /// learning rate
+    learning_rate: L,
+    /// source of the gradients
+    gradient_source: G,
+    /// decay rate of the first moment
+    beta1: F,
+    /// decay rate of the second moment
+    beta2: F,
+    /// epsilon
+    epsilon: F,
+    /// decoupled weight decay
+    weight_decay: F,
+    /// use AMSGrad
+    amsgrad: bool,
+    /// first moment
+    m: P,
+    /// second moment
+    v: P,
+    /// maximum of second moments (AMSGrad)
+    v_max: P,
+}
+
+impl<P, F, L> Adam<P, F, L>
+where
+    P: Default,
+    F: ArgminFloat,
+    L: LearningRateSchedule<F>,
+{
+    /// Constructor
+    pub fn new(learning_rate: L) -> Self {
+        Adam {
+            learning_rate,
+            gradient_source: FullGradient,
+            beta1: F::from_f64(0.9).unwrap(),
+            beta2: F::from_f64(0.999).unwrap(),
+            epsilon: F::from_f64(1e-8).unwrap(),
+            weight_decay: F::from_f64(0.0).unwrap(),
+            amsgrad: false,
+            m: P::default(),
+            v: P::default(),
+            v_max: P::default(),
+        }
+    }
+
+    /// Compute the gradients on mini-batches drawn from `sampler`
+    pub fn with_sampler(self, sampler: BatchSampler) -> Adam<P, F, L, BatchSampler> {
+        Adam {
+            learning_rate: self.learning_rate,
+            gradient_source: sampler,
+            beta1: self.beta1,
+            beta2: self.beta2,
+            epsilon: self.epsilon,
+            weight_decay: self.weight_decay,
+            amsgrad: self.amsgrad,
+            m: self.m,
+            v: self.v,
+            v_max: self.v_max,
+        }
+    }
+}
+
+impl<P, F, L, G> Adam<P, F, L, G>
+where
+    F: ArgminFloat,
+{
+    /// Set decay rates of the moments (must be in `[0, 1)`)
+    pub fn with_betas(mut self, beta1: F, beta2: F) -> Result<Self, Error> {
+        let zero = F::from_f64(0.0).unwrap();
+        let one = F::from_f64(1.0).unwrap();
+        if beta1 < zero || beta1 >= one || beta2 < zero || beta2 >= one {
+            return Err(ArgminError::InvalidParameter {
+                text: "Adam: beta1 and beta2 must be in [0, 1).".to_string(),
+            }
+            .into());
+        }
+        self.beta1 = beta1;
+        self.beta2 = beta2;
+        Ok(self)
+    }
+
+    /// Set epsilon
+    pub fn with_epsilon(mut self, epsilon: F) -> Result<Self, Error> {
+        if epsilon <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "Adam: epsilon must be > 0.".to_string(),
+            }
+            .into());
+        }
+        self.epsilon = epsilon;
+        Ok(self)
+    }
+
+    /// Set decoupled weight decay (AdamW)
+    pub fn with_weight_decay(mut self, weight_decay: F) -> Result<Self, Error> {
+        if weight_decay < F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "Adam: weight decay must be >= 0.".to_string(),
+            }
+            .into());
+        }
+        self.weight_decay = weight_decay;
+        Ok(self)
+    }
+
+    /// Use AMSGrad
+    pub fn with_amsgrad(mut self) -> Self {
+        self.amsgrad = true;
+        self
+    }
+}
+
+impl<O, P, F, L, G> Solver<O> for Adam<P, F, L, G>
+where
+    O: CostFunction<Param = P, Float = F>,
+    P: Clone
+        + Default
+        + Serialize
+        + DeserializeOwned
+        + ArgminZeroLike
+        + ArgminMul<F, P>
+        + ArgminMul<P, P>
+        + ArgminDiv<P, P>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledSub<P, F, P>
+        + ArgminMinMax
+        + ArgminComponents<F>,
+    F: ArgminFloat,
+    L: LearningRateSchedule<F> + Clone + Serialize + DeserializeOwned,
+    G: GradientSource<O> + Clone + Serialize + DeserializeOwned,
+{
+    const NAME: &'static str = "Adam";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        self.gradient_source.prepare(op)?;
+        let zero = state.get_param().zero_like();
+        self.m = zero.clone();
+        self.v = zero.clone();
+        self.v_max = zero;
+        Ok(None)
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let one = F::from_f64(1.0).unwrap();
+        let param = state.get_param();
+        let grad = self.gradient_source.gradient(op, &param)?;
+        let lr = self.learning_rate.learning_rate(state.get_iter());
+        let t = F::from_u64(state.get_iter() + 1).unwrap();
+
+        self.m = self
+            .m
+            .mul(&self.beta1)
+            .scaled_add(&(one - self.beta1), &grad);
+        self.v = self
+            .v
+            .mul(&self.beta2)
+            .scaled_add(&(one - self.beta2), &grad.mul(&grad));
+        let v = if self.amsgrad {
+            self.v_max = P::max(&self.v_max, &self.v);
+            &self.v_max
+        } else {
+            &self.v
+        };
+
+        let m_hat = self.m.mul(&(one / (one - self.beta1.powf(t))));
+        let v_hat = v.mul(&(one / (one - self.beta2.powf(t))));
+        let step = m_hat.div(&sqrt_plus_eps(&v_hat, self.epsilon));
+
+        let param = if self.weight_decay > F::from_f64(0.0).unwrap() {
+            param.scaled_sub(&(lr * self.weight_decay), &param)
+        } else {
+            param
+        };
+        let new_param = param.scaled_sub(&lr, &step);
+
+        let data = ArgminIterData::new()
+            .param(new_param)
+            .grad(grad)
+            .kv(make_kv!("learning_rate" => lr;));
+        Ok(report_epoch(data, &self.gradient_source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::stochastic::tests::LeastSquares;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(adam, Adam<Vec<f64>, f64>);
+    test_trait_impl!(adam_sampler, Adam<Vec<f64>, f64, f64, BatchSampler>);
+
+    fn run(solver: Adam<Vec<f64>, f64>) -> Vec<f64> {
+        let op = FnOp::new(|x: &Vec<f64>| Ok((x[0] - 1.0).powi(2) + 10.0 * (x[1] + 1.0).powi(2)))
+            .gradient(|x: &Vec<f64>| Ok(vec![2.0 * (x[0] - 1.0), 20.0 * (x[1] + 1.0)]));
+        Executor::new(op, solver, vec![5.0, 5.0])
+            .max_iters(3000)
+            .run()
+            .unwrap()
+            .state
+            .get_best_param()
+    }
+
+    #[test]
+    fn test_adam() {
+        for solver in &[Adam::new(0.05), Adam::new(0.05).with_amsgrad()] {
+            let p = run(solver.clone());
+            assert!((p[0] - 1.0).abs() < 1e-4);
+            assert!((p[1] + 1.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_adamw() {
+        // weight decay shrinks the minimizer towards zero
+        let p = run(Adam::new(0.05).with_weight_decay(0.1).unwrap());
+        assert!(p[0] > 0.0 && p[0] < 1.0);
+        assert!(p[1] > -1.0 && p[1] < 0.0);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let adam: Adam<Vec<f64>, f64> = Adam::new(0.1);
+        assert!(adam.clone().with_betas(1.0, 0.9).is_err());
+        assert!(adam.clone().with_betas(0.9, -0.1).is_err());
+        assert!(adam.clone().with_epsilon(0.0).is_err());
+        assert!(adam.with_weight_decay(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_sampler() {
+        let sampler = BatchSampler::new(5).unwrap().with_seed(0);
+        let res = Executor::new(
+            LeastSquares::new(50),
+            Adam::new(0.05).with_sampler(sampler),
+            vec![0.0, 0.0],
+        )
+        .max_epochs(300)
+        .run()
+        .unwrap();
+        assert_eq!(res.state.get_epoch(), 300);
+        assert_eq!(res.state.get_grad_func_count(), 3000);
+        let p = res.state.get_best_param();
+        assert!((p[0] - 1.0).abs() < 1e-3);
+        assert!((p[1] + 2.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Stochastic first-order methods
+//!
+//! * [Stochastic gradient descent](sgd/struct.SGD.html) (with momentum and Nesterov momentum)
+//! * [Adam](adam/struct.Adam.html) (with AdamW and AMSGrad variants)
+//! * [AdaGrad](adagrad/struct.AdaGrad.html)
+//! * [RMSProp](rmsprop/struct.RMSProp.html)
+//! * [SVRG](svrg/struct.SVRG.html) (stochastic variance reduced gradient)
+//! * [SAGA](saga/struct.SAGA.html)
+//!
+//! These solvers only use gradients, which may be noisy. The cost function is not evaluated,
+//! therefore the last parameter vector is reported as best parameter vector. The learning rate is
+//! either a constant or any [`LearningRateSchedule`](schedule/trait.LearningRateSchedule.html).
+//!
+//! Operators implementing `StochasticOp` have a cost function which is the mean of `num_terms`
+//! terms. Solvers for such operators draw mini-batches of terms from a
+//! [`BatchSampler`](sampler/struct.BatchSampler.html) and report the number of completed passes
+//! over all terms (epochs), which can be limited with `Executor::max_epochs`. `SVRG` and `SAGA`
+//! always work this way. `SGD`, `Adam`, `AdaGrad` and `RMSProp` use the gradient computed by
+//! `gradient` by default and switch to mini-batches with `with_sampler`.
+//!
+//! # References:
+//!
+//! [0] Sebastian Ruder (2016). An overview of gradient descent optimization algorithms.
+//! arXiv:1609.04747
+
+/// AdaGrad
+pub mod adagrad;
+/// Adam, AdamW and AMSGrad
+pub mod adam;
+/// RMSProp
+pub mod rmsprop;
+/// SAGA
+pub mod saga;
+/// Mini-batch sampling
+pub mod sampler;
+/// Learning rate schedules
+pub mod schedule;
+/// Stochastic gradient descent
+pub mod sgd;
+/// Stochastic variance reduced gradient
+pub mod svrg;
+
+pub use self::adagrad::*;
+pub use self::adam::*;
+pub use self::rmsprop::*;
+pub use self::saga::*;
+pub use self::sampler::*;
+pub use self::schedule::*;
+pub use self::sgd::*;
+pub use self::svrg::*;
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+
+/// Computes `sqrt(x) + epsilon` for every component of `x`
+fn sqrt_plus_eps<P, F>(x: &P, epsilon: F) -> P
+where
+    P: Clone + ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    let mut out = x.clone();
+    for i in 0..x.num_components() {
+        out.set_component(i, x.component(i).sqrt() + epsilon);
+    }
+    out
+}
+
+/// Adds the number of completed epochs of `source` to `data` if it draws mini-batches
+fn report_epoch<O, G>(data: ArgminIterData<O>, source: &G) -> ArgminIterData<O>
+where
+    O: CostFunction,
+    G: GradientSource<O>,
+{
+    match source.completed_epochs() {
+        Some(epoch) => data.epoch(epoch),
+        None => data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::capabilities::StochasticOp;
+
+    /// Linear least squares problem with terms `f_i(x) = 0.5 * (a_i^T x - b_i)^2` and solution
+    /// `x = [1, -2]`
+    pub(super) struct LeastSquares {
+        pub(super) a: Vec<Vec<f64>>,
+        pub(super) b: Vec<f64>,
+    }
+
+    impl LeastSquares {
+        pub(super) fn new(num_terms: usize) -> Self {
+            let a: Vec<Vec<f64>> = (0..num_terms)
+                .map(|i| {
+                    let t = i as f64;
+                    vec![t.cos(), (0.7 * t).sin() + 1.0]
+                })
+                .collect();
+            let b = a.iter().map(|a| a[0] - 2.0 * a[1]).collect();
+            LeastSquares { a, b }
+        }
+
+        fn residual(&self, x: &[f64], i: usize) -> f64 {
+            self.a[i].iter().zip(x).map(|(a, x)| a * x).sum::<f64>() - self.b[i]
+        }
+    }
+
+    impl CostFunction for LeastSquares {
+        type Param = Vec<f64>;
+        type Output = f64;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, x: &Vec<f64>) -> Result<f64, Error> {
+            let indices: Vec<usize> = (0..self.b.len()).collect();
+            self.apply_batch(x, &indices)
+        }
+    }
+
+    impl StochasticOp for LeastSquares {
+        fn num_terms(&self) -> usize {
+            self.b.len()
+        }
+
+        fn apply_batch(&self, x: &Vec<f64>, indices: &[usize]) -> Result<f64, Error> {
+            let sum: f64 = indices
+                .iter()
+                .map(|&i| 0.5 * self.residual(x, i).powi(2))
+                .sum();
+            Ok(sum / indices.len() as f64)
+        }
+
+        fn gradient_batch(&self, x: &Vec<f64>, indices: &[usize]) -> Result<Vec<f64>, Error> {
+            let mut grad = vec![0.0; x.len()];
+            for &i in indices {
+                let r = self.residual(x, i);
+                grad = grad.scaled_add(&r, &self.a[i]);
+            }
+            Ok(grad.mul(&(1.0 / indices.len() as f64)))
+        }
+    }
+
+    #[test]
+    fn test_sqrt_plus_eps() {
+        let x = sqrt_plus_eps(&vec![4.0f64, 9.0], 0.5);
+        assert!((x[0] - 2.5).abs() < std::f64::EPSILON);
+        assert!((x[1] - 3.5).abs() < std::f64::EPSILON);
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Tijmen Tieleman and Geoffrey Hinton (2012). Lecture 6.5 - RMSProp: Divide the gradient by a
+//! running average of its recent magnitude. COURSERA: Neural Networks for Machine Learning.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::stochastic::{
+    report_epoch, sqrt_plus_eps, BatchSampler, FullGradient, GradientSource, LearningRateSchedule,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::default::Default;
+
+/// RMSProp
+///
+/// Scales the learning rate of every component by the root of an exponential moving average of
+/// the squared gradients of this component [0]:
+///
+/// `E_{k+1} = rho * E_k + (1 - rho) * g_k^2`, `x_{k+1} = x_k - lr_k * g_k / (sqrt(E_{k+1}) + epsilon)`.
+///
+/// The defaults are `rho = 0.9` and `epsilon = 1e-8`.
+///
+/// With `with_sampler`, `g_k` is the mean gradient of a mini-batch of terms of a `StochasticOp`
+/// instead of the gradient computed by `gradient`.
+///
+/// # References:
+///
+/// [0] Tijmen Tieleman and Geoffrey Hinton (2012). Lecture 6.5 - RMSProp: Divide the gradient by a
+/// running average of its recent magnitude. COURSERA: Neural Networks for Machine Learning.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RMSProp<P, F, L = F, G = FullGradient> {
+    /// learning rate
+    learning_rate: L,
+    /// source of the gradients
+    gradient_source: G,
+    /// decay rate
+    rho: F,
+    /// epsilon
+    epsilon: F,
+    /// moving average of squared gradients
+    mean_sq: P,
+}
+
+impl<P, F, L> RMSProp<P, F, L>
+where
+    P: Default,
+    F: ArgminFloat,
+    L: LearningRateSchedule<F>,
+{
+    /// Constructor
+    pub fn new(learning_rate: L) -> Self {
+        RMSProp {
+            learning_rate,
+            gradient_source: FullGradient,
+            rho: F::from_f64(0.9).unwrap(),
+            epsilon: F::from_f64(1e-8).unwrap(),
+            mean_sq: P::default(),
+        }
+    }
+
+    /// Compute the gradients on mini-batches drawn from `sampler`
+    pub fn with_sampler(self, sampler: BatchSampler) -> RMSProp<P, F, L, BatchSampler> {
+        RMSProp {
+            learning_rate: self.learning_rate,
+            gradient_source: sampler,
+            rho: self.rho,
+            epsilon: self.epsilon,
+            mean_sq: self.mean_sq,
+        }
+    }
+}
+
+impl<P, F, L, G> RMSProp<P, F, L, G>
+where
+    F: ArgminFloat,
+{
+    /// Set decay rate (must be in `[0, 1)`)
+    pub fn with_rho(mut self, rho: F) -> Result<Self, Error> {
+        if rho < F::from_f64(0.0).unwrap() || rho >= F::from_f64(1.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "RMSProp: rho must be in [0, 1).".to_string(),
+            }
+            .into());
+        }
+        self.rho = rho;
+        Ok(self)
+    }
+
+    /// Set epsilon
+    pub fn with_epsilon(mut self, epsilon: F) -> Result<Self, Error> {
+        if epsilon <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "RMSProp: epsilon must be > 0.".to_string(),
+            }
+            .into());
+        }
+        self.epsilon = epsilon;
+        Ok(self)
+    }
+}
+
+impl<O, P, F, L, G> Solver<O> for RMSProp<P, F, L, G>
+where
+    O: CostFunction<Param = P, Float = F>,
+    P: Clone
+        + Default
+        + Serialize
+        + DeserializeOwned
+        + ArgminZeroLike
+        + ArgminMul<F, P>
+        + ArgminMul<P, P>
+        + ArgminDiv<P, P>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledSub<P, F, P>
+        + ArgminComponents<F>,
+    F: ArgminFloat,
+    L: LearningRateSchedule<F> + Clone + Serialize + DeserializeOwned,
+    G: GradientSource<O> + Clone + Serialize + DeserializeOwned,
+{
+    const NAME: &'static str = "RMSProp";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        self.gradient_source.prepare(op)?;
+        self.mean_sq = state.get_param().zero_like();
+        Ok(None)
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let param = state.get_param();
+        let grad = self.gradient_source.gradient(op, &param)?;
+        let lr = self.learning_rate.learning_rate(state.get_iter());
+
+        self.mean_sq = self
+            .mean_sq
+            .mul(&self.rho)
+            .scaled_add(&(F::from_f64(1.0).unwrap() - self.rho), &grad.mul(&grad));
+        let step = grad.div(&sqrt_plus_eps(&self.mean_sq, self.epsilon));
+        let new_param = param.scaled_sub(&lr, &step);
+
+        let data = ArgminIterData::new()
+            .param(new_param)
+            .grad(grad)
+            .kv(make_kv!("learning_rate" => lr;));
+        Ok(report_epoch(data, &self.gradient_source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::stochastic::tests::LeastSquares;
+    use crate::solver::stochastic::ExponentialDecay;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(rmsprop, RMSProp<Vec<f64>, f64>);
+    test_trait_impl!(rmsprop_sampler, RMSProp<Vec<f64>, f64, f64, BatchSampler>);
+
+    #[test]
+    fn test_rmsprop() {
+        let op = FnOp::new(|x: &Vec<f64>| Ok((x[0] - 1.0).powi(2) + 10.0 * (x[1] + 1.0).powi(2)))
+            .gradient(|x: &Vec<f64>| Ok(vec![2.0 * (x[0] - 1.0), 20.0 * (x[1] + 1.0)]));
+        let solver = RMSProp::new(ExponentialDecay::new(0.1, 0.995).unwrap());
+        let res = Executor::new(op, solver, vec![5.0, 5.0])
+            .max_iters(2000)
+            .run()
+            .unwrap();
+        let p = res.state.get_best_param();
+        assert!((p[0] - 1.0).abs() < 1e-4);
+        assert!((p[1] + 1.0).abs() < 1e-4);
+        let rmsprop: RMSProp<Vec<f64>, f64> = RMSProp::new(0.1);
+        assert!(rmsprop.clone().with_rho(1.0).is_err());
+        assert!(rmsprop.with_epsilon(0.0).is_err());
+    }
+
+    #[test]
+    fn test_sampler() {
+        let sampler = BatchSampler::new(5).unwrap().with_seed(0);
+        let res = Executor::new(
+            LeastSquares::new(50),
+            RMSProp::new(ExponentialDecay::new(0.05, 0.998).unwrap()).with_sampler(sampler),
+            vec![0.0, 0.0],
+        )
+        .max_epochs(300)
+        .run()
+        .unwrap();
+        assert_eq!(res.state.get_epoch(), 300);
+        assert_eq!(res.state.get_grad_func_count(), 3000);
+        let p = res.state.get_best_param();
+        assert!((p[0] - 1.0).abs() < 1e-3);
+        assert!((p[1] + 2.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Aaron Defazio, Francis Bach and Simon Lacoste-Julien (2014). SAGA: A Fast Incremental
+//! Gradient Method With Support for Non-Strongly Convex Composite Objectives. Advances in Neural
+//! Information Processing Systems 27, 1646-1654.
+
+use crate::core::capabilities::{CostFunction, StochasticOp};
+use crate::prelude::*;
+use crate::solver::stochastic::{BatchSampler, LearningRateSchedule};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::default::Default;
+
+/// SAGA
+///
+/// Stores the most recent gradient `g_i` of every term and their mean `g_mean`. Every iteration
+/// draws a mini-batch `B` of terms, recomputes their gradients `g_i'` at the current parameter
+/// vector and updates it with the variance reduced gradient estimate [0]
+///
+/// `x_{k+1} = x_k - lr_k * (1/|B| * sum_{i in B} (g_i' - g_i) + g_mean)`.
+///
+/// Afterwards, `g_i` is replaced with `g_i'` for all `i` in `B`. Initializing the table
+/// requires one gradient evaluation per term. The number of completed epochs is reported to the
+/// executor.
+///
+/// # References:
+///
+/// [0] Aaron Defazio, Francis Bach and Simon Lacoste-Julien (2014). SAGA: A Fast Incremental
+/// Gradient Method With Support for Non-Strongly Convex Composite Objectives. Advances in Neural
+/// Information Processing Systems 27, 1646-1654.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SAGA<P, F, L = F> {
+    /// learning rate
+    learning_rate: L,
+    /// mini-batch sampler
+    sampler: BatchSampler,
+    /// most recent gradient of every term
+    grads: Vec<P>,
+    /// mean of `grads`
+    mean_grad: P,
+    /// float type
+    float: std::marker::PhantomData<F>,
+}
+
+impl<P, F, L> SAGA<P, F, L>
+where
+    P: Default,
+    F: ArgminFloat,
+    L: LearningRateSchedule<F>,
+{
+    /// Constructor
+    pub fn new(learning_rate: L, sampler: BatchSampler) -> Self {
+        SAGA {
+            learning_rate,
+            sampler,
+            grads: vec![],
+            mean_grad: P::default(),
+            float: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<O, P, F, L> Solver<O> for SAGA<P, F, L>
+where
+    O: CostFunction<Param = P, Float = F> + StochasticOp,
+    P: Clone
+        + Default
+        + Serialize
+        + DeserializeOwned
+        + ArgminZeroLike
+        + ArgminAdd<P, P>
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminScaledSub<P, F, P>,
+    F: ArgminFloat,
+    L: LearningRateSchedule<F> + Clone + Serialize + DeserializeOwned,
+{
+    const NAME: &'static str = "SAGA";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let num_terms = op.num_terms();
+        self.sampler.init(num_terms)?;
+        let param = state.get_param();
+        self.grads = (0..num_terms)
+            .map(|i| op.gradient_batch(&param, &[i]))
+            .collect::<Result<Vec<P>, Error>>()?;
+        self.mean_grad = self
+            .grads
+            .iter()
+            .fold(param.zero_like(), |acc, g| acc.add(g))
+            .mul(&(F::from_f64(1.0).unwrap() / F::from_usize(num_terms).unwrap()));
+        Ok(None)
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let one = F::from_f64(1.0).unwrap();
+        let param = state.get_param();
+        let batch = self.sampler.next_batch();
+        let lr = self.learning_rate.learning_rate(state.get_iter());
+
+        // sum of the changes of the gradients of all terms in the batch
+        let mut correction = param.zero_like();
+        for &i in batch.iter() {
+            let grad_i = op.gradient_batch(&param, &[i])?;
+            correction = correction.add(&grad_i.sub(&self.grads[i]));
+            self.grads[i] = grad_i;
+        }
+        let grad = correction
+            .mul(&(one / F::from_usize(batch.len()).unwrap()))
+            .add(&self.mean_grad);
+        self.mean_grad = self
+            .mean_grad
+            .add(&correction.mul(&(one / F::from_usize(self.grads.len()).unwrap())));
+        let new_param = param.scaled_sub(&lr, &grad);
+
+        Ok(ArgminIterData::new()
+            .param(new_param)
+            .grad(grad)
+            .epoch(self.sampler.epoch())
+            .kv(make_kv!("learning_rate" => lr;)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::stochastic::tests::LeastSquares;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(saga, SAGA<Vec<f64>, f64>);
+
+    #[test]
+    fn test_saga() {
+        for &batch_size in [1, 5].iter() {
+            let sampler = BatchSampler::new(batch_size).unwrap().with_seed(1);
+            let res = Executor::new(
+                LeastSquares::new(50),
+                SAGA::new(0.2, sampler),
+                vec![0.0, 0.0],
+            )
+            .max_epochs(40)
+            .run()
+            .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                TerminationReason::MaxEpochsReached
+            );
+            // the table is initialized with one gradient evaluation per term
+            let iters = res.state.get_iter();
+            assert_eq!(
+                res.state.get_grad_func_count(),
+                50 + iters * batch_size as u64
+            );
+            let p = res.state.get_best_param();
+            assert!((p[0] - 1.0).abs() < 1e-5);
+            assert!((p[1] + 2.0).abs() < 1e-5);
+        }
+    }
+}
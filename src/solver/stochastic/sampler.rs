@@ -0,0 +1,216 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::capabilities::{CostFunction, Gradient, StochasticOp};
+use crate::prelude::*;
+use rand::prelude::*;
+use rand_xorshift::XorShiftRng;
+use serde::{Deserialize, Serialize};
+
+/// Splits the terms of a `StochasticOp` into mini-batches
+///
+/// At the beginning of every epoch, the indices `0..num_terms` are shuffled (unless
+/// `without_shuffle` is used) and then handed out in batches of `batch_size` indices. The last
+/// batch of an epoch is smaller if `batch_size` does not divide `num_terms`. The random number
+/// generator can be seeded with `with_seed` to obtain reproducible runs.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BatchSampler {
+    /// number of indices per batch
+    batch_size: usize,
+    /// shuffle indices at the beginning of every epoch
+    shuffle: bool,
+    /// random number generator
+    rng: XorShiftRng,
+    /// order of the indices in the current epoch
+    order: Vec<usize>,
+    /// position of the next batch in `order`
+    pos: usize,
+    /// number of completed epochs
+    epoch: u64,
+}
+
+impl BatchSampler {
+    /// Constructor
+    pub fn new(batch_size: usize) -> Result<Self, Error> {
+        if batch_size == 0 {
+            return Err(ArgminError::InvalidParameter {
+                text: "BatchSampler: batch_size must be > 0.".to_string(),
+            }
+            .into());
+        }
+        Ok(BatchSampler {
+            batch_size,
+            shuffle: true,
+            rng: XorShiftRng::from_entropy(),
+            order: vec![],
+            pos: 0,
+            epoch: 0,
+        })
+    }
+
+    /// Seed the random number generator
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = XorShiftRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Hand out the indices in their natural order
+    pub fn without_shuffle(mut self) -> Self {
+        self.shuffle = false;
+        self
+    }
+
+    /// Prepares the sampler for an operator with `num_terms` terms and resets the epoch counter
+    pub fn init(&mut self, num_terms: usize) -> Result<(), Error> {
+        if num_terms == 0 {
+            return Err(ArgminError::InvalidParameter {
+                text: "BatchSampler: operator has no terms.".to_string(),
+            }
+            .into());
+        }
+        self.order = (0..num_terms).collect();
+        if self.shuffle {
+            self.order.shuffle(&mut self.rng);
+        }
+        self.pos = 0;
+        self.epoch = 0;
+        Ok(())
+    }
+
+    /// Returns the indices of the next batch
+    pub fn next_batch(&mut self) -> Vec<usize> {
+        if self.pos >= self.order.len() {
+            if self.shuffle {
+                self.order.shuffle(&mut self.rng);
+            }
+            self.pos = 0;
+        }
+        let end = (self.pos + self.batch_size).min(self.order.len());
+        let batch = self.order[self.pos..end].to_vec();
+        self.pos = end;
+        if self.pos >= self.order.len() {
+            self.epoch += 1;
+        }
+        batch
+    }
+
+    /// Returns the batch size
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Returns the number of completed epochs
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Returns `true` if the last batch completed an epoch
+    pub fn epoch_completed(&self) -> bool {
+        self.pos >= self.order.len()
+    }
+}
+
+/// Source of the gradients used by `SGD`, `Adam`, `AdaGrad` and `RMSProp`
+///
+/// `FullGradient` computes the gradient with `Gradient::gradient`. A `BatchSampler` computes the
+/// mean gradient of the terms of a `StochasticOp` in the next mini-batch with `gradient_batch` and
+/// reports the number of completed epochs.
+pub trait GradientSource<O: CostFunction> {
+    /// Prepares the source for the operator `op`
+    fn prepare(&mut self, op: &mut OpWrapper<O>) -> Result<(), Error>;
+
+    /// Computes the (estimated) gradient at `param`
+    fn gradient(&mut self, op: &mut OpWrapper<O>, param: &O::Param) -> Result<O::Param, Error>;
+
+    /// Returns the number of completed epochs if the source draws mini-batches
+    fn completed_epochs(&self) -> Option<u64>;
+}
+
+/// Computes gradients with `Gradient::gradient`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FullGradient;
+
+impl<O: Gradient> GradientSource<O> for FullGradient {
+    fn prepare(&mut self, _op: &mut OpWrapper<O>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn gradient(&mut self, op: &mut OpWrapper<O>, param: &O::Param) -> Result<O::Param, Error> {
+        op.gradient(param)
+    }
+
+    fn completed_epochs(&self) -> Option<u64> {
+        None
+    }
+}
+
+impl<O: StochasticOp> GradientSource<O> for BatchSampler {
+    fn prepare(&mut self, op: &mut OpWrapper<O>) -> Result<(), Error> {
+        self.init(op.num_terms())
+    }
+
+    fn gradient(&mut self, op: &mut OpWrapper<O>, param: &O::Param) -> Result<O::Param, Error> {
+        let batch = self.next_batch();
+        op.gradient_batch(param, &batch)
+    }
+
+    fn completed_epochs(&self) -> Option<u64> {
+        Some(self.epoch())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    send_sync_test!(batch_sampler, BatchSampler);
+
+    #[test]
+    fn test_epochs() {
+        let mut sampler = BatchSampler::new(3).unwrap().with_seed(42);
+        sampler.init(7).unwrap();
+        for epoch in 0..3 {
+            let mut indices = vec![];
+            for size in [3, 3, 1].iter() {
+                assert_eq!(sampler.epoch(), epoch);
+                let batch = sampler.next_batch();
+                assert_eq!(batch.len(), *size);
+                indices.extend(batch);
+            }
+            assert!(sampler.epoch_completed());
+            indices.sort_unstable();
+            assert_eq!(indices, (0..7).collect::<Vec<usize>>());
+        }
+        assert_eq!(sampler.epoch(), 3);
+    }
+
+    #[test]
+    fn test_seed() {
+        let batches = |seed| {
+            let mut sampler = BatchSampler::new(2).unwrap().with_seed(seed);
+            sampler.init(100).unwrap();
+            (0..10).map(|_| sampler.next_batch()).collect::<Vec<_>>()
+        };
+        assert_eq!(batches(1), batches(1));
+        assert_ne!(batches(1), batches(2));
+    }
+
+    #[test]
+    fn test_without_shuffle() {
+        let mut sampler = BatchSampler::new(2).unwrap().without_shuffle();
+        sampler.init(3).unwrap();
+        assert_eq!(sampler.next_batch(), vec![0, 1]);
+        assert_eq!(sampler.next_batch(), vec![2]);
+        assert_eq!(sampler.next_batch(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(BatchSampler::new(0).is_err());
+        assert!(BatchSampler::new(1).unwrap().init(0).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Learning rate schedules for stochastic first-order methods
+//!
+//! # References:
+//!
+//! [0] Ilya Loshchilov and Frank Hutter (2017). SGDR: Stochastic Gradient Descent with Warm
+//! Restarts. International Conference on Learning Representations.
+
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Learning rate depending on the iteration number
+///
+/// Implemented for floats (constant learning rate) and the schedules in this module.
+pub trait LearningRateSchedule<F> {
+    /// Learning rate in iteration `iter` (starting at 0)
+    fn learning_rate(&self, iter: u64) -> F;
+}
+
+impl<F: ArgminFloat> LearningRateSchedule<F> for F {
+    fn learning_rate(&self, _iter: u64) -> F {
+        *self
+    }
+}
+
+/// Step decay: `lr_i = lr * factor^floor(i / step)`
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+pub struct StepDecay<F> {
+    /// initial learning rate
+    lr: F,
+    /// decay factor
+    factor: F,
+    /// number of iterations between decays
+    step: u64,
+}
+
+impl<F: ArgminFloat> StepDecay<F> {
+    /// Constructor
+    pub fn new(lr: F, factor: F, step: u64) -> Result<Self, Error> {
+        if factor <= F::from_f64(0.0).unwrap() || factor > F::from_f64(1.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "StepDecay: factor must be in (0, 1].".to_string(),
+            }
+            .into());
+        }
+        if step == 0 {
+            return Err(ArgminError::InvalidParameter {
+                text: "StepDecay: step must be > 0.".to_string(),
+            }
+            .into());
+        }
+        Ok(StepDecay { lr, factor, step })
+    }
+}
+
+impl<F: ArgminFloat> LearningRateSchedule<F> for StepDecay<F> {
+    fn learning_rate(&self, iter: u64) -> F {
+        self.lr * self.factor.powi((iter / self.step) as i32)
+    }
+}
+
+/// Exponential decay: `lr_i = lr * rate^i`
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+pub struct ExponentialDecay<F> {
+    /// initial learning rate
+    lr: F,
+    /// decay rate
+    rate: F,
+}
+
+impl<F: ArgminFloat> ExponentialDecay<F> {
+    /// Constructor
+    pub fn new(lr: F, rate: F) -> Result<Self, Error> {
+        if rate <= F::from_f64(0.0).unwrap() || rate > F::from_f64(1.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "ExponentialDecay: rate must be in (0, 1].".to_string(),
+            }
+            .into());
+        }
+        Ok(ExponentialDecay { lr, rate })
+    }
+}
+
+impl<F: ArgminFloat> LearningRateSchedule<F> for ExponentialDecay<F> {
+    fn learning_rate(&self, iter: u64) -> F {
+        self.lr * self.rate.powf(F::from_u64(iter).unwrap())
+    }
+}
+
+/// Inverse time decay: `lr_i = lr / (1 + decay * i)`
+///
+/// Satisfies the Robbins-Monro conditions, which guarantee convergence of SGD for convex
+/// problems.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+pub struct InverseTimeDecay<F> {
+    /// initial learning rate
+    lr: F,
+    /// decay
+    decay: F,
+}
+
+impl<F: ArgminFloat> InverseTimeDecay<F> {
+    /// Constructor
+    pub fn new(lr: F, decay: F) -> Result<Self, Error> {
+        if decay < F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "InverseTimeDecay: decay must be >= 0.".to_string(),
+            }
+            .into());
+        }
+        Ok(InverseTimeDecay { lr, decay })
+    }
+}
+
+impl<F: ArgminFloat> LearningRateSchedule<F> for InverseTimeDecay<F> {
+    fn learning_rate(&self, iter: u64) -> F {
+        self.lr / (F::from_f64(1.0).unwrap() + self.decay * F::from_u64(iter).unwrap())
+    }
+}
+
+/// Cosine annealing [0]: `lr_i = lr_min + (lr_max - lr_min) * (1 + cos(pi * t / period)) / 2`
+///
+/// Without restarts, `t = min(i, period)`, i.e. the learning rate stays at `lr_min` after
+/// `period` iterations. With restarts, `t = i mod period`.
+///
+/// # References:
+///
+/// [0] Ilya Loshchilov and Frank Hutter (2017). SGDR: Stochastic Gradient Descent with Warm
+/// Restarts. International Conference on Learning Representations.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+pub struct CosineAnnealing<F> {
+    /// maximum learning rate
+    lr_max: F,
+    /// minimum learning rate
+    lr_min: F,
+    /// number of iterations from maximum to minimum learning rate
+    period: u64,
+    /// restart after each period
+    restarts: bool,
+}
+
+impl<F: ArgminFloat> CosineAnnealing<F> {
+    /// Constructor
+    pub fn new(lr_max: F, lr_min: F, period: u64) -> Result<Self, Error> {
+        if lr_min > lr_max {
+            return Err(ArgminError::InvalidParameter {
+                text: "CosineAnnealing: lr_min must be <= lr_max.".to_string(),
+            }
+            .into());
+        }
+        if period == 0 {
+            return Err(ArgminError::InvalidParameter {
+                text: "CosineAnnealing: period must be > 0.".to_string(),
+            }
+            .into());
+        }
+        Ok(CosineAnnealing {
+            lr_max,
+            lr_min,
+            period,
+            restarts: false,
+        })
+    }
+
+    /// Restart at the maximum learning rate after each period
+    pub fn with_restarts(mut self) -> Self {
+        self.restarts = true;
+        self
+    }
+}
+
+impl<F: ArgminFloat> LearningRateSchedule<F> for CosineAnnealing<F> {
+    fn learning_rate(&self, iter: u64) -> F {
+        let t = if self.restarts {
+            iter % self.period
+        } else {
+            iter.min(self.period)
+        };
+        let t = F::from_u64(t).unwrap() / F::from_u64(self.period).unwrap();
+        let half = F::from_f64(0.5).unwrap();
+        self.lr_min
+            + (self.lr_max - self.lr_min) * half * (F::from_f64(1.0).unwrap() + (F::PI() * t).cos())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant() {
+        assert!((0.1f64.learning_rate(100) - 0.1).abs() < std::f64::EPSILON);
+    }
+
+    #[test]
+    fn test_step_decay() {
+        let s = StepDecay::new(1.0f64, 0.5, 10).unwrap();
+        assert!((s.learning_rate(9) - 1.0).abs() < std::f64::EPSILON);
+        assert!((s.learning_rate(25) - 0.25).abs() < std::f64::EPSILON);
+        assert!(StepDecay::new(1.0f64, 1.5, 10).is_err());
+        assert!(StepDecay::new(1.0f64, 0.5, 0).is_err());
+    }
+
+    #[test]
+    fn test_exponential_decay() {
+        let s = ExponentialDecay::new(2.0f64, 0.5).unwrap();
+        assert!((s.learning_rate(3) - 0.25).abs() < std::f64::EPSILON);
+        assert!(ExponentialDecay::new(2.0f64, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_inverse_time_decay() {
+        let s = InverseTimeDecay::new(1.0f64, 0.5).unwrap();
+        assert!((s.learning_rate(2) - 0.5).abs() < std::f64::EPSILON);
+        assert!(InverseTimeDecay::new(1.0f64, -0.5).is_err());
+    }
+
+    #[test]
+    fn test_cosine_annealing() {
+        let s = CosineAnnealing::new(1.0f64, 0.0, 10).unwrap();
+        assert!((s.learning_rate(0) - 1.0).abs() < std::f64::EPSILON);
+        assert!((s.learning_rate(5) - 0.5).abs() < 1e-12);
+        assert!(s.learning_rate(15).abs() < 1e-12);
+        let s = s.with_restarts();
+        assert!((s.learning_rate(15) - 0.5).abs() < 1e-12);
+        assert!(CosineAnnealing::new(0.0f64, 1.0, 10).is_err());
+        assert!(CosineAnnealing::new(1.0f64, 0.0, 0).is_err());
+    }
+}
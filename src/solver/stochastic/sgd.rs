@@ -0,0 +1,262 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Ilya Sutskever, James Martens, George Dahl and Geoffrey Hinton (2013). On the importance of
+//! initialization and momentum in deep learning. Proceedings of the 30th International Conference
+//! on Machine Learning, 1139-1147.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::stochastic::{
+    report_epoch, BatchSampler, FullGradient, GradientSource, LearningRateSchedule,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::default::Default;
+
+/// Stochastic gradient descent
+///
+/// In iteration `k`, the parameter vector is updated with the gradient `g_k` and the learning
+/// rate `lr_k` according to
+///
+/// `x_{k+1} = x_k - lr_k * g_k`.
+///
+/// With momentum `mu` (`with_momentum`), a velocity is accumulated:
+///
+/// `v_{k+1} = mu * v_k + g_k`, `x_{k+1} = x_k - lr_k * v_{k+1}`.
+///
+/// With Nesterov momentum (`with_nesterov`), the update uses the velocity extrapolated by one
+/// step [0]:
+///
+/// `v_{k+1} = mu * v_k + g_k`, `x_{k+1} = x_k - lr_k * (g_k + mu * v_{k+1})`.
+///
+/// By default, `g_k` is computed with `gradient`. With `with_sampler`, `g_k` is the mean gradient
+/// of a mini-batch of terms of a `StochasticOp` and the number of completed epochs is reported.
+///
+/// # References:
+///
+/// [0] Ilya Sutskever, James Martens, George Dahl and Geoffrey Hinton (2013). On the importance of
+/// initialization and momentum in deep learning. Proceedings of the 30th International Conference
+/// on Machine Learning, 1139-1147.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SGD<P, F, L = F, G = FullGradient> {
+    /// learning rate
+    learning_rate: L,
+    /// source of the gradients
+    gradient_source: G,
+    /// momentum
+    momentum: F,
+    /// use Nesterov momentum
+    nesterov: bool,
+    /// velocity
+    velocity: P,
+}
+
+impl<P, F, L> SGD<P, F, L>
+where
+    P: Default,
+    F: ArgminFloat,
+    L: LearningRateSchedule<F>,
+{
+    /// Constructor
+    pub fn new(learning_rate: L) -> Self {
+        SGD {
+            learning_rate,
+            gradient_source: FullGradient,
+            momentum: F::from_f64(0.0).unwrap(),
+            nesterov: false,
+            velocity: P::default(),
+        }
+    }
+
+    /// Compute the gradients on mini-batches drawn from `sampler`
+    pub fn with_sampler(self, sampler: BatchSampler) -> SGD<P, F, L, BatchSampler> {
+        SGD {
+            learning_rate: self.learning_rate,
+            gradient_source: sampler,
+            momentum: self.momentum,
+            nesterov: self.nesterov,
+            velocity: self.velocity,
+        }
+    }
+}
+
+impl<P, F, L, G> SGD<P, F, L, G>
+where
+    F: ArgminFloat,
+{
+    /// Set momentum (must be in `[0, 1)`)
+    pub fn with_momentum(mut self, momentum: F) -> Result<Self, Error> {
+        if momentum < F::from_f64(0.0).unwrap() || momentum >= F::from_f64(1.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "SGD: momentum must be in [0, 1).".to_string(),
+            }
+            .into());
+        }
+        self.momentum = momentum;
+        self.nesterov = false;
+        Ok(self)
+    }
+
+    /// Set Nesterov momentum (must be in `[0, 1)`)
+    pub fn with_nesterov(self, momentum: F) -> Result<Self, Error> {
+        let mut sgd = self.with_momentum(momentum)?;
+        sgd.nesterov = true;
+        Ok(sgd)
+    }
+}
+
+impl<O, P, F, L, G> Solver<O> for SGD<P, F, L, G>
+where
+    O: CostFunction<Param = P, Float = F>,
+    P: Clone
+        + Default
+        + Serialize
+        + DeserializeOwned
+        + ArgminZeroLike
+        + ArgminAdd<P, P>
+        + ArgminMul<F, P>
+        + ArgminScaledSub<P, F, P>,
+    F: ArgminFloat,
+    L: LearningRateSchedule<F> + Clone + Serialize + DeserializeOwned,
+    G: GradientSource<O> + Clone + Serialize + DeserializeOwned,
+{
+    const NAME: &'static str = "Stochastic gradient descent";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        self.gradient_source.prepare(op)?;
+        self.velocity = state.get_param().zero_like();
+        Ok(None)
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let param = state.get_param();
+        let grad = self.gradient_source.gradient(op, &param)?;
+        let lr = self.learning_rate.learning_rate(state.get_iter());
+
+        let step = if self.momentum > F::from_f64(0.0).unwrap() {
+            self.velocity = self.velocity.mul(&self.momentum).add(&grad);
+            if self.nesterov {
+                grad.add(&self.velocity.mul(&self.momentum))
+            } else {
+                self.velocity.clone()
+            }
+        } else {
+            grad.clone()
+        };
+        let new_param = param.scaled_sub(&lr, &step);
+
+        let data = ArgminIterData::new()
+            .param(new_param)
+            .grad(grad)
+            .kv(make_kv!("learning_rate" => lr;));
+        Ok(report_epoch(data, &self.gradient_source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::stochastic::tests::LeastSquares;
+    use crate::solver::stochastic::InverseTimeDecay;
+    use crate::test_trait_impl;
+    use rand::Rng;
+
+    test_trait_impl!(sgd, SGD<Vec<f64>, f64>);
+    test_trait_impl!(sgd_sampler, SGD<Vec<f64>, f64, f64, BatchSampler>);
+
+    type Quadratic = FnOp<Vec<f64>, f64, f64, FnOpFunc<Vec<f64>, Vec<f64>>>;
+
+    fn quadratic_cost(x: &Vec<f64>) -> Result<f64, Error> {
+        Ok((x[0] - 1.0).powi(2) + 10.0 * (x[1] + 1.0).powi(2))
+    }
+
+    fn quadratic() -> Quadratic {
+        FnOp::new(quadratic_cost)
+            .gradient(|x: &Vec<f64>| Ok(vec![2.0 * (x[0] - 1.0), 20.0 * (x[1] + 1.0)]))
+    }
+
+    fn check<S>(solver: S, iters: u64, tol: f64)
+    where
+        S: Solver<Quadratic>,
+    {
+        let res = Executor::new(quadratic(), solver, vec![5.0, 5.0])
+            .max_iters(iters)
+            .run()
+            .unwrap();
+        let p = res.state.get_best_param();
+        assert!((p[0] - 1.0).abs() < tol);
+        assert!((p[1] + 1.0).abs() < tol);
+    }
+
+    #[test]
+    fn test_sgd() {
+        check(SGD::new(0.05), 500, 1e-6);
+        check(SGD::new(0.01).with_momentum(0.9).unwrap(), 500, 1e-6);
+        check(SGD::new(0.01).with_nesterov(0.9).unwrap(), 500, 1e-6);
+    }
+
+    #[test]
+    fn test_noisy_gradient() {
+        let op = FnOp::new(quadratic_cost).gradient(|x: &Vec<f64>| {
+            let mut rng = rand::thread_rng();
+            Ok(vec![
+                2.0 * (x[0] - 1.0) + rng.gen_range(-1.0..1.0),
+                20.0 * (x[1] + 1.0) + rng.gen_range(-1.0..1.0),
+            ])
+        });
+        let solver = SGD::new(InverseTimeDecay::new(0.05, 0.01).unwrap())
+            .with_momentum(0.5)
+            .unwrap();
+        let res = Executor::new(op, solver, vec![5.0, 5.0])
+            .max_iters(5000)
+            .run()
+            .unwrap();
+        let p = res.state.get_best_param();
+        assert!((p[0] - 1.0).abs() < 0.1);
+        assert!((p[1] + 1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_sampler() {
+        let sampler = BatchSampler::new(5).unwrap().with_seed(0);
+        let solver = SGD::new(0.1)
+            .with_momentum(0.5)
+            .unwrap()
+            .with_sampler(sampler);
+        let res = Executor::new(LeastSquares::new(50), solver, vec![0.0, 0.0])
+            .max_epochs(200)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::MaxEpochsReached
+        );
+        // 10 batches per epoch, one gradient evaluation per batch
+        assert_eq!(res.state.get_iter(), 2000);
+        assert_eq!(res.state.get_grad_func_count(), 2000);
+        let p = res.state.get_best_param();
+        assert!((p[0] - 1.0).abs() < 1e-4);
+        assert!((p[1] + 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_invalid_momentum() {
+        let sgd: SGD<Vec<f64>, f64> = SGD::new(0.1);
+        assert!(sgd.clone().with_momentum(1.0).is_err());
+        assert!(sgd.with_nesterov(-0.1).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] Rie Johnson and Tong Zhang (2013). Accelerating Stochastic Gradient Descent using
+//! Predictive Variance Reduction. Advances in Neural Information Processing Systems 26, 315-323.
+
+use crate::core::capabilities::{CostFunction, StochasticOp};
+use crate::prelude::*;
+use crate::solver::stochastic::{BatchSampler, LearningRateSchedule};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::default::Default;
+
+/// Stochastic variance reduced gradient (SVRG)
+///
+/// At the beginning of every epoch, the full gradient `mu` is computed at the current parameter
+/// vector, which is stored as snapshot `x_s`. Within the epoch, every iteration draws a
+/// mini-batch `B` of terms and updates the parameter vector with the variance reduced gradient
+/// estimate [0]
+///
+/// `x_{k+1} = x_k - lr_k * (g_B(x_k) - g_B(x_s) + mu)`,
+///
+/// where `g_B` is the mean gradient of the terms in `B`. The number of completed epochs is
+/// reported to the executor.
+///
+/// # References:
+///
+/// [0] Rie Johnson and Tong Zhang (2013). Accelerating Stochastic Gradient Descent using
+/// Predictive Variance Reduction. Advances in Neural Information Processing Systems 26, 315-323.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SVRG<P, F, L = F> {
+    /// learning rate
+    learning_rate: L,
+    /// mini-batch sampler
+    sampler: BatchSampler,
+    /// snapshot of the parameter vector
+    snapshot: P,
+    /// full gradient at the snapshot
+    full_grad: P,
+    /// float type
+    float: std::marker::PhantomData<F>,
+}
+
+impl<P, F, L> SVRG<P, F, L>
+where
+    P: Default,
+    F: ArgminFloat,
+    L: LearningRateSchedule<F>,
+{
+    /// Constructor
+    pub fn new(learning_rate: L, sampler: BatchSampler) -> Self {
+        SVRG {
+            learning_rate,
+            sampler,
+            snapshot: P::default(),
+            full_grad: P::default(),
+            float: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<P, F, L> SVRG<P, F, L> {
+    /// Stores `param` as snapshot and computes the full gradient there
+    fn update_snapshot<O>(&mut self, op: &mut OpWrapper<O>, param: P) -> Result<(), Error>
+    where
+        O: StochasticOp<Param = P, Float = F>,
+    {
+        let indices: Vec<usize> = (0..op.num_terms()).collect();
+        self.full_grad = op.gradient_batch(&param, &indices)?;
+        self.snapshot = param;
+        Ok(())
+    }
+}
+
+impl<O, P, F, L> Solver<O> for SVRG<P, F, L>
+where
+    O: CostFunction<Param = P, Float = F> + StochasticOp,
+    P: Clone
+        + Default
+        + Serialize
+        + DeserializeOwned
+        + ArgminAdd<P, P>
+        + ArgminSub<P, P>
+        + ArgminScaledSub<P, F, P>,
+    F: ArgminFloat,
+    L: LearningRateSchedule<F> + Clone + Serialize + DeserializeOwned,
+{
+    const NAME: &'static str = "SVRG";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        self.sampler.init(op.num_terms())?;
+        self.update_snapshot(op, state.get_param())?;
+        Ok(None)
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let param = state.get_param();
+        if self.sampler.epoch_completed() {
+            self.update_snapshot(op, param.clone())?;
+        }
+        let batch = self.sampler.next_batch();
+        let lr = self.learning_rate.learning_rate(state.get_iter());
+
+        let grad = op
+            .gradient_batch(&param, &batch)?
+            .sub(&op.gradient_batch(&self.snapshot, &batch)?)
+            .add(&self.full_grad);
+        let new_param = param.scaled_sub(&lr, &grad);
+
+        Ok(ArgminIterData::new()
+            .param(new_param)
+            .grad(grad)
+            .epoch(self.sampler.epoch())
+            .kv(make_kv!("learning_rate" => lr;)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::stochastic::tests::LeastSquares;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(svrg, SVRG<Vec<f64>, f64>);
+
+    fn run(batch_size: usize, seed: u64) -> ArgminResult<LeastSquares> {
+        let sampler = BatchSampler::new(batch_size).unwrap().with_seed(seed);
+        Executor::new(
+            LeastSquares::new(50),
+            SVRG::new(0.2, sampler),
+            vec![0.0, 0.0],
+        )
+        .max_epochs(30)
+        .run()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_svrg() {
+        for &batch_size in [1, 5].iter() {
+            let res = run(batch_size, 0);
+            assert_eq!(
+                res.state.get_termination_reason(),
+                TerminationReason::MaxEpochsReached
+            );
+            assert_eq!(res.state.get_epoch(), 30);
+            let p = res.state.get_best_param();
+            assert!((p[0] - 1.0).abs() < 1e-6);
+            assert!((p[1] + 2.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_seed() {
+        let p1 = run(3, 7).state.get_best_param();
+        let p2 = run(3, 7).state.get_best_param();
+        assert!(p1
+            .iter()
+            .zip(p2.iter())
+            .all(|(a, b)| (a - b).abs() < std::f64::EPSILON));
+    }
+}
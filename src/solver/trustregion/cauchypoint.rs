@@ -10,6 +10,7 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::{CostFunction, Gradient, Hessian};
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
@@ -36,12 +37,13 @@ impl<F: ArgminFloat> CauchyPoint<F> {
 
 impl<O, F> Solver<O> for CauchyPoint<F>
 where
-    O: ArgminOp<Output = F, Float = F>,
+    O: CostFunction<Output = F, Float = F> + Gradient + Hessian,
     O::Param: Debug
         + Clone
         + Serialize
         + ArgminMul<O::Float, O::Param>
         + ArgminWeightedDot<O::Param, F, O::Hessian>
+        + ArgminDot<O::Param, F>
         + ArgminNorm<O::Float>,
     O::Hessian: Clone + Serialize,
     F: ArgminFloat,
@@ -54,13 +56,15 @@ where
         state: &IterState<O>,
     ) -> Result<ArgminIterData<O>, Error> {
         let param = state.get_param();
-        let grad = state
-            .get_grad()
-            .unwrap_or_else(|| op.gradient(&param).unwrap());
+        let grad = match state.get_grad() {
+            Some(grad) => grad,
+            None => op.gradient(&param)?,
+        };
         let grad_norm = grad.norm();
-        let hessian = state
-            .get_hessian()
-            .unwrap_or_else(|| op.hessian(&param).unwrap());
+        let hessian = match state.get_hessian() {
+            Some(hessian) => hessian,
+            None => op.hessian(&param)?,
+        };
 
         let wdp = grad.weighted_dot(&hessian, &grad);
         let tau: F = if wdp <= F::from_f64(0.0).unwrap() {
@@ -72,7 +76,14 @@ where
         };
 
         let new_param = grad.mul(&(-tau * self.radius / grad_norm));
-        Ok(ArgminIterData::new().param(new_param))
+        // change of the quadratic model
+        let model_change = new_param.dot(&grad)
+            + F::from_f64(0.5).unwrap() * new_param.weighted_dot(&hessian, &new_param);
+        // the Hessian is reported so that it can be reused if the step is rejected
+        Ok(ArgminIterData::new()
+            .param(new_param)
+            .cost(model_change)
+            .hessian(hessian))
     }
 
     fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
@@ -84,7 +95,7 @@ where
     }
 }
 
-impl<F: ArgminFloat> ArgminTrustRegion<F> for CauchyPoint<F> {
+impl<P, F: ArgminFloat> ArgminTrustRegion<P, F> for CauchyPoint<F> {
     fn set_radius(&mut self, radius: F) {
         self.radius = radius;
     }
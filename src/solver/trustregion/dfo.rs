@@ -0,0 +1,961 @@
+// Copyright 2018-2020 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # References:
+//!
+//! [0] M. J. D. Powell (2006). The NEWUOA software for unconstrained optimization without
+//! derivatives. In: Large-Scale Nonlinear Optimization, Springer, 255-297.
+//!
+//! [1] M. J. D. Powell (2009). The BOBYQA algorithm for bound constrained optimization without
+//! derivatives. Technical Report DAMTP 2009/NA06, University of Cambridge.
+//!
+//! [2] Andrew R. Conn, Katya Scheinberg and Luis N. Vicente (2009). Introduction to
+//! Derivative-Free Optimization. SIAM. ISBN 978-0-898716-68-9.
+
+use crate::core::capabilities::CostFunction;
+use crate::prelude::*;
+use crate::solver::bounds::{apply_bounds, check_bounds, check_bounds_dim, BoundHandling};
+use crate::solver::trustregion::{reduction_ratio, update_radius};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Quadratic model `c + g^T (x - center) + 1/2 (x - center)^T H (x - center)`
+#[derive(Clone, Serialize, Deserialize)]
+struct QuadraticModel<F> {
+    /// point around which the model is expanded
+    center: Vec<F>,
+    /// constant term
+    c: F,
+    /// gradient at `center`
+    g: Vec<F>,
+    /// Hessian
+    h: Vec<Vec<F>>,
+}
+
+impl<F: ArgminFloat> QuadraticModel<F> {
+    /// Model which vanishes everywhere
+    fn zero(center: Vec<F>) -> Self {
+        let n = center.len();
+        let zero = F::from_f64(0.0).unwrap();
+        QuadraticModel {
+            center,
+            c: zero,
+            g: vec![zero; n],
+            h: vec![vec![zero; n]; n],
+        }
+    }
+}
+
+impl<F> QuadraticModel<F>
+where
+    F: ArgminFloat,
+    Vec<F>: ArgminDot<Vec<F>, F> + ArgminSub<Vec<F>, Vec<F>> + ArgminNorm<F>,
+    Vec<Vec<F>>: ArgminDot<Vec<F>, Vec<F>>,
+{
+    /// Value of the model for the step `s` from `center`
+    fn eval_step(&self, s: &Vec<F>) -> F {
+        let half = F::from_f64(0.5).unwrap();
+        self.c + self.g.dot(s) + half * s.dot(&self.h.dot(s))
+    }
+
+    /// Value of the model at `x`
+    fn eval(&self, x: &Vec<F>) -> F {
+        self.eval_step(&x.sub(&self.center))
+    }
+
+    /// Same model expanded around `center`
+    fn shift(&self, center: &Vec<F>) -> Self {
+        let s = center.sub(&self.center);
+        let hs = self.h.dot(&s);
+        QuadraticModel {
+            center: center.clone(),
+            c: self.eval_step(&s),
+            g: self
+                .g
+                .iter()
+                .zip(hs.iter())
+                .map(|(g, hs)| *g + *hs)
+                .collect(),
+            h: self.h.clone(),
+        }
+    }
+}
+
+/// Interpolation system of the current set of points, centered at the best point and scaled by
+/// the largest distance of a point to the center
+struct InterpolationSystem<F> {
+    /// LU decomposition of the KKT matrix of the minimum Frobenius norm interpolation problem
+    lu: LU<Vec<Vec<F>>>,
+    /// scaled differences of the interpolation points to the center
+    scaled: Vec<Vec<F>>,
+    /// center
+    center: Vec<F>,
+    /// scaling factor
+    scale: F,
+}
+
+impl<F> InterpolationSystem<F>
+where
+    F: ArgminFloat,
+    Vec<F>: ArgminDot<Vec<F>, F> + ArgminSub<Vec<F>, Vec<F>> + ArgminNorm<F>,
+    Vec<Vec<F>>: ArgminDot<Vec<F>, Vec<F>> + ArgminLU<LU<Vec<Vec<F>>>>,
+    LU<Vec<Vec<F>>>: ArgminDecompositionSolve<Vec<F>>,
+{
+    /// Sets up and decomposes the KKT matrix
+    ///
+    /// `[A Y^T; Y 0]` with `A_ij = 1/2 (s_i^T s_j)^2` and `Y = [1 ... 1; s_1 ... s_m]` [0]
+    fn new(points: &[Vec<F>], center: &Vec<F>) -> Result<Self, Error> {
+        let zero = F::from_f64(0.0).unwrap();
+        let half = F::from_f64(0.5).unwrap();
+        let m = points.len();
+        let n = center.len();
+        let diffs: Vec<Vec<F>> = points.iter().map(|p| p.sub(center)).collect();
+        let scale = diffs.iter().fold(zero, |acc, d| acc.max(d.norm()));
+        let scaled: Vec<Vec<F>> = diffs
+            .iter()
+            .map(|d| d.iter().map(|di| *di / scale).collect())
+            .collect();
+        let mut kkt = vec![vec![zero; m + n + 1]; m + n + 1];
+        for i in 0..m {
+            for j in 0..m {
+                kkt[i][j] = half * scaled[i].dot(&scaled[j]).powi(2);
+            }
+            kkt[i][m] = F::from_f64(1.0).unwrap();
+            kkt[m][i] = F::from_f64(1.0).unwrap();
+            for l in 0..n {
+                kkt[i][m + 1 + l] = scaled[i][l];
+                kkt[m + 1 + l][i] = scaled[i][l];
+            }
+        }
+        Ok(InterpolationSystem {
+            lu: kkt.lu()?,
+            scaled,
+            center: center.clone(),
+            scale,
+        })
+    }
+
+    /// Quadratic with minimum Frobenius norm Hessian which takes the values `r` at the
+    /// interpolation points
+    fn interpolate(&self, r: &[F]) -> Result<QuadraticModel<F>, Error> {
+        let zero = F::from_f64(0.0).unwrap();
+        let m = self.scaled.len();
+        let n = self.center.len();
+        let mut rhs = r.to_vec();
+        rhs.extend(vec![zero; n + 1]);
+        let sol = self.lu.solve(&rhs)?;
+        let scale2 = self.scale * self.scale;
+        let mut h = vec![vec![zero; n]; n];
+        for (lambda, s) in sol.iter().take(m).zip(self.scaled.iter()) {
+            for i in 0..n {
+                for j in 0..n {
+                    h[i][j] = h[i][j] + *lambda * s[i] * s[j] / scale2;
+                }
+            }
+        }
+        Ok(QuadraticModel {
+            center: self.center.clone(),
+            c: sol[m],
+            g: sol[m + 1..].iter().map(|g| *g / self.scale).collect(),
+            h,
+        })
+    }
+
+    /// Values of all Lagrange functions at `x`
+    fn lagrange_values(&self, x: &Vec<F>) -> Result<Vec<F>, Error> {
+        let half = F::from_f64(0.5).unwrap();
+        let s: Vec<F> = x
+            .sub(&self.center)
+            .iter()
+            .map(|si| *si / self.scale)
+            .collect();
+        // the KKT matrix is symmetric, therefore the values are K^{-1} w
+        let mut w: Vec<F> = self
+            .scaled
+            .iter()
+            .map(|sj| half * sj.dot(&s).powi(2))
+            .collect();
+        w.push(F::from_f64(1.0).unwrap());
+        w.extend(s);
+        let mut v = self.lu.solve(&w)?;
+        v.truncate(self.scaled.len());
+        Ok(v)
+    }
+
+    /// Lagrange function of point `t`
+    fn lagrange_function(&self, t: usize) -> Result<QuadraticModel<F>, Error> {
+        let mut e = vec![F::from_f64(0.0).unwrap(); self.scaled.len()];
+        e[t] = F::from_f64(1.0).unwrap();
+        self.interpolate(&e)
+    }
+}
+
+/// Kind of step taken in an iteration
+#[derive(Clone, Copy)]
+enum StepKind {
+    /// trust region step
+    TrustRegion,
+    /// geometry step
+    Geometry,
+    /// reduction of the resolution
+    Resolution,
+}
+
+impl StepKind {
+    /// Name of the step, as reported in the KV
+    fn as_str(self) -> &'static str {
+        match self {
+            StepKind::TrustRegion => "trust region",
+            StepKind::Geometry => "geometry",
+            StepKind::Resolution => "resolution",
+        }
+    }
+}
+
+/// Derivative-free trust region method
+///
+/// Minimizes a function using only cost function values by building quadratic models from
+/// `2n + 1` interpolation points and minimizing them in a trust region, in the style of NEWUOA [0]
+/// and BOBYQA [1]. Each model is the quadratic which interpolates the cost function values and
+/// whose Hessian differs least (in the Frobenius norm) from the Hessian of the previous model.
+///
+/// The trust region subproblem is solved approximately by truncated conjugate gradients, which
+/// fixes variables once they reach a bound. The reduction ratio of a trial step is computed with
+/// `reduction_ratio` and the radius is updated with `update_radius`, as in `TrustRegion`. The
+/// trial point replaces the interpolation point whose Lagrange function has the largest value at
+/// the trial point, weighted by the distance to the best point, which keeps the interpolation set
+/// well-poised.
+///
+/// The radius never falls below a resolution `rho`, which starts at the initial radius (default:
+/// 1, set with `with_initial_radius`). If steps fail although all points are close to the best
+/// point, `rho` is reduced. Geometry steps replace points which are far from the best point by
+/// points which maximize the modulus of their Lagrange function [2]. The method terminates with
+/// `TerminationReason::StepToleranceReached` once `rho` reaches `tol` (default: `1e-6`).
+///
+/// Bounds can be given with `with_bounds`. All points at which the cost function is evaluated
+/// satisfy the bounds. The bounds must be at least twice the initial radius apart; starting
+/// points which are closer than the initial radius to a bound are moved onto the bound.
+///
+/// The observers receive the current `radius`, the resolution `rho`, the reduction ratio `ratio`
+/// and the difference of the cost function value and the model value at the trial point
+/// (`model_error`), which indicates the quality of the model, as well as the kind of the
+/// iteration (`step`).
+///
+/// # References:
+///
+/// [0] M. J. D. Powell (2006). The NEWUOA software for unconstrained optimization without
+/// derivatives. In: Large-Scale Nonlinear Optimization, Springer, 255-297.
+///
+/// [1] M. J. D. Powell (2009). The BOBYQA algorithm for bound constrained optimization without
+/// derivatives. Technical Report DAMTP 2009/NA06, University of Cambridge.
+///
+/// [2] Andrew R. Conn, Katya Scheinberg and Luis N. Vicente (2009). Introduction to
+/// Derivative-Free Optimization. SIAM. ISBN 978-0-898716-68-9.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DFOTrustRegion<P, F> {
+    /// initial radius
+    initial_radius: F,
+    /// maximum radius
+    max_radius: F,
+    /// final resolution
+    tol: F,
+    /// lower and upper bounds
+    bounds: Option<(P, P)>,
+    /// radius
+    radius: F,
+    /// resolution, lower bound of the radius
+    rho: F,
+    /// interpolation points
+    points: Vec<Vec<F>>,
+    /// cost function values at the interpolation points
+    fvals: Vec<F>,
+    /// index of the best interpolation point
+    kopt: usize,
+    /// model of the last iteration
+    model: QuadraticModel<F>,
+    /// the last trust region step failed
+    improve_geometry: bool,
+}
+
+impl<P, F> DFOTrustRegion<P, F>
+where
+    P: ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    /// Constructor
+    pub fn new() -> Self {
+        DFOTrustRegion {
+            initial_radius: F::from_f64(1.0).unwrap(),
+            max_radius: F::infinity(),
+            tol: F::from_f64(1e-6).unwrap(),
+            bounds: None,
+            radius: F::nan(),
+            rho: F::nan(),
+            points: vec![],
+            fvals: vec![],
+            kopt: 0,
+            model: QuadraticModel::zero(vec![]),
+            improve_geometry: false,
+        }
+    }
+
+    /// Set initial radius
+    pub fn with_initial_radius(mut self, radius: F) -> Result<Self, Error> {
+        if radius <= F::from_f64(0.0).unwrap() || !radius.is_finite() {
+            return Err(ArgminError::InvalidParameter {
+                text: "DFOTrustRegion: initial radius must be positive and finite.".to_string(),
+            }
+            .into());
+        }
+        self.initial_radius = radius;
+        Ok(self)
+    }
+
+    /// Set maximum radius
+    pub fn with_max_radius(mut self, max_radius: F) -> Result<Self, Error> {
+        if max_radius <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "DFOTrustRegion: maximum radius must be positive.".to_string(),
+            }
+            .into());
+        }
+        self.max_radius = max_radius;
+        Ok(self)
+    }
+
+    /// Set final resolution
+    pub fn with_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= F::from_f64(0.0).unwrap() {
+            return Err(ArgminError::InvalidParameter {
+                text: "DFOTrustRegion: tol must be positive.".to_string(),
+            }
+            .into());
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set lower and upper bounds
+    pub fn with_bounds(mut self, lower: P, upper: P) -> Result<Self, Error> {
+        check_bounds("DFOTrustRegion", &lower, &upper)?;
+        self.bounds = Some((lower, upper));
+        Ok(self)
+    }
+}
+
+impl<P, F> Default for DFOTrustRegion<P, F>
+where
+    P: ArgminComponents<F>,
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        DFOTrustRegion::new()
+    }
+}
+
+impl<P, F> DFOTrustRegion<P, F>
+where
+    P: Clone + ArgminComponents<F>,
+    F: ArgminFloat,
+    Vec<F>: ArgminDot<Vec<F>, F>
+        + ArgminSub<Vec<F>, Vec<F>>
+        + ArgminMul<F, Vec<F>>
+        + ArgminMinMax
+        + ArgminRandom
+        + ArgminNorm<F>,
+    Vec<Vec<F>>: ArgminDot<Vec<F>, Vec<F>> + ArgminLU<LU<Vec<Vec<F>>>>,
+    LU<Vec<Vec<F>>>: ArgminDecompositionSolve<Vec<F>>,
+{
+    /// Lower and upper bound of component `i`
+    fn bound(&self, i: usize) -> (F, F) {
+        match &self.bounds {
+            Some((lower, upper)) => (lower.component(i), upper.component(i)),
+            None => (F::neg_infinity(), F::infinity()),
+        }
+    }
+
+    /// Clips `x` to the bounds
+    fn project(&self, x: Vec<F>) -> Vec<F> {
+        match &self.bounds {
+            Some((lower, upper)) => {
+                let (lower, upper): (Vec<F>, Vec<F>) = (0..x.len())
+                    .map(|i| (lower.component(i), upper.component(i)))
+                    .unzip();
+                apply_bounds(x, &lower, &upper, BoundHandling::Clip)
+            }
+            None => x,
+        }
+    }
+
+    /// Cost function value at `x`
+    fn eval<O>(&self, op: &mut OpWrapper<O>, template: &P, x: &[F]) -> Result<F, Error>
+    where
+        O: CostFunction<Param = P, Output = F, Float = F>,
+    {
+        op.apply(&self.to_param(template, x))
+    }
+
+    /// Converts `x` to a parameter vector
+    fn to_param(&self, template: &P, x: &[F]) -> P {
+        let mut p = template.clone();
+        for (i, xi) in x.iter().enumerate() {
+            p.set_component(i, *xi);
+        }
+        p
+    }
+
+    /// Updates the model to interpolate the current points
+    fn update_model(&mut self) -> Result<InterpolationSystem<F>, Error> {
+        let center = self.points[self.kopt].clone();
+        let system = InterpolationSystem::new(&self.points, &center)?;
+        let shifted = self.model.shift(&center);
+        let residuals: Vec<F> = self
+            .points
+            .iter()
+            .zip(self.fvals.iter())
+            .map(|(p, f)| *f - shifted.eval(p))
+            .collect();
+        let correction = system.interpolate(&residuals)?;
+        self.model = QuadraticModel {
+            center,
+            c: shifted.c + correction.c,
+            g: shifted
+                .g
+                .iter()
+                .zip(correction.g.iter())
+                .map(|(a, b)| *a + *b)
+                .collect(),
+            h: shifted
+                .h
+                .iter()
+                .zip(correction.h.iter())
+                .map(|(ra, rb)| ra.iter().zip(rb.iter()).map(|(a, b)| *a + *b).collect())
+                .collect(),
+        };
+        Ok(system)
+    }
+
+    /// Approximately minimizes the model within the trust region and the bounds with truncated
+    /// conjugate gradients. Returns the step and whether it reached the trust region boundary.
+    fn trust_region_step(&self) -> (Vec<F>, bool) {
+        let zero = F::from_f64(0.0).unwrap();
+        let xk = &self.model.center;
+        let g = &self.model.g;
+        let h = &self.model.h;
+        let n = xk.len();
+        let delta2 = self.radius * self.radius;
+        let tiny = F::epsilon() * (F::from_f64(1.0).unwrap() + g.norm());
+
+        // variables at a bound whose gradient points out of the feasible region stay fixed
+        let mut free: Vec<bool> = (0..n)
+            .map(|i| {
+                let (l, u) = self.bound(i);
+                !((xk[i] <= l && g[i] > zero) || (xk[i] >= u && g[i] < zero))
+            })
+            .collect();
+        let mut s = vec![zero; n];
+
+        'restart: for _ in 0..=n {
+            let hs = h.dot(&s);
+            let mut r: Vec<F> = (0..n)
+                .map(|i| if free[i] { -(g[i] + hs[i]) } else { zero })
+                .collect();
+            let mut d = r.clone();
+            let mut rr = r.dot(&r);
+            for _ in 0..n {
+                if rr.sqrt() <= tiny {
+                    break 'restart;
+                }
+                let hd = h.dot(&d);
+                let dhd = d.dot(&hd);
+                let (ss, sd, dd) = (s.dot(&s), s.dot(&d), d.dot(&d));
+                let alpha_tr = ((sd * sd + dd * (delta2 - ss).max(zero)).sqrt() - sd) / dd;
+                let mut alpha_box = (F::infinity(), 0);
+                for i in (0..n).filter(|&i| free[i] && d[i] != zero) {
+                    let (l, u) = self.bound(i);
+                    let limit = if d[i] > zero { u } else { l };
+                    let alpha = ((limit - xk[i] - s[i]) / d[i]).max(zero);
+                    if alpha < alpha_box.0 {
+                        alpha_box = (alpha, i);
+                    }
+                }
+                let alpha_cg = if dhd > zero { rr / dhd } else { F::infinity() };
+
+                if alpha_tr <= alpha_cg && alpha_tr <= alpha_box.0 {
+                    for i in 0..n {
+                        s[i] = s[i] + alpha_tr * d[i];
+                    }
+                    return (s, true);
+                }
+                if alpha_box.0 < alpha_cg {
+                    // fix the variable at the bound and restart from the current step
+                    let (i, alpha) = (alpha_box.1, alpha_box.0);
+                    for j in 0..n {
+                        s[j] = s[j] + alpha * d[j];
+                    }
+                    let (l, u) = self.bound(i);
+                    s[i] = if d[i] > zero { u - xk[i] } else { l - xk[i] };
+                    free[i] = false;
+                    continue 'restart;
+                }
+                for i in (0..n).filter(|&i| free[i]) {
+                    s[i] = s[i] + alpha_cg * d[i];
+                    r[i] = r[i] - alpha_cg * hd[i];
+                }
+                let rr_new = r.dot(&r);
+                let beta = rr_new / rr;
+                for i in 0..n {
+                    d[i] = r[i] + beta * d[i];
+                }
+                rr = rr_new;
+            }
+            break;
+        }
+        (s, false)
+    }
+
+    /// Index of the point which is farthest from the best point if its distance exceeds twice
+    /// the radius
+    fn far_point(&self) -> Option<usize> {
+        let xk = &self.points[self.kopt];
+        let (t, dist) = self
+            .points
+            .iter()
+            .enumerate()
+            .map(|(t, p)| (t, p.sub(xk).norm()))
+            .fold((self.kopt, F::from_f64(0.0).unwrap()), |best, cur| {
+                if cur.1 > best.1 {
+                    cur
+                } else {
+                    best
+                }
+            });
+        if dist > F::from_f64(2.0).unwrap() * self.radius {
+            Some(t)
+        } else {
+            None
+        }
+    }
+
+    /// Index of the point which is replaced by `x`
+    fn replacement_index(
+        &self,
+        system: &InterpolationSystem<F>,
+        x: &Vec<F>,
+        best: &Vec<F>,
+    ) -> Result<usize, Error> {
+        let one = F::from_f64(1.0).unwrap();
+        let lagrange = system.lagrange_values(x)?;
+        let mut choice = (0, F::neg_infinity());
+        for (t, (p, l)) in self.points.iter().zip(lagrange.iter()).enumerate() {
+            if t == self.kopt {
+                continue;
+            }
+            let weight = (p.sub(best).norm() / self.radius).powi(2).max(one);
+            if l.abs() * weight > choice.1 {
+                choice = (t, l.abs() * weight);
+            }
+        }
+        Ok(choice.0)
+    }
+
+    /// Whether `x` coincides with one of the interpolation points
+    fn coincides(&self, x: &Vec<F>) -> bool {
+        let tol = F::epsilon().sqrt() * self.rho;
+        self.points.iter().any(|p| p.sub(x).norm() <= tol)
+    }
+
+    /// Replaces point `t` by a point close to the best point which maximizes the modulus of the
+    /// Lagrange function of `t`. Returns `false` if the Lagrange function vanishes at all
+    /// candidates, which can happen if they are projected onto the bounds.
+    fn geometry_step<O>(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        template: &P,
+        system: &InterpolationSystem<F>,
+        t: usize,
+    ) -> Result<bool, Error>
+    where
+        O: CostFunction<Param = P, Output = F, Float = F>,
+    {
+        let xk = self.points[self.kopt].clone();
+        let n = xk.len();
+        let dist = self.points[t].sub(&xk).norm();
+        let step = (F::from_f64(0.1).unwrap() * dist)
+            .min(self.radius)
+            .max(self.rho);
+        let lagrange = system.lagrange_function(t)?;
+
+        // candidates along the coordinate directions and the gradient of the Lagrange function,
+        // with half the step length in case the full step is cut off by the bounds
+        let mut directions: Vec<Vec<F>> = (0..n)
+            .map(|i| {
+                let mut e = vec![F::from_f64(0.0).unwrap(); n];
+                e[i] = F::from_f64(1.0).unwrap();
+                e
+            })
+            .collect();
+        let gnorm = lagrange.g.norm();
+        if gnorm > F::from_f64(0.0).unwrap() {
+            directions.push(lagrange.g.iter().map(|gi| *gi / gnorm).collect());
+        }
+        // points at which the Lagrange function (almost) vanishes would make the interpolation
+        // system singular
+        let mut best = (xk.clone(), F::epsilon().sqrt());
+        for d in directions.iter() {
+            for scale in [1.0, -1.0, 0.5, -0.5].iter() {
+                let length = F::from_f64(*scale).unwrap() * step;
+                let x = self.project(
+                    xk.iter()
+                        .zip(d.iter())
+                        .map(|(xi, di)| *xi + length * *di)
+                        .collect(),
+                );
+                let value = lagrange.eval(&x).abs();
+                if value > best.1 && !self.coincides(&x) {
+                    best = (x, value);
+                }
+            }
+        }
+
+        if best.0 == xk {
+            return Ok(false);
+        }
+        let f = self.eval(op, template, &best.0)?;
+        self.points[t] = best.0;
+        self.fvals[t] = f;
+        if f < self.fvals[self.kopt] {
+            self.kopt = t;
+        }
+        Ok(true)
+    }
+
+    /// Reduces the resolution. Returns `false` if the final resolution was already reached.
+    fn reduce_resolution(&mut self) -> bool {
+        if self.rho <= self.tol {
+            return false;
+        }
+        let ratio = self.rho / self.tol;
+        let rho = if ratio <= F::from_f64(16.0).unwrap() {
+            self.tol
+        } else if ratio <= F::from_f64(250.0).unwrap() {
+            (self.rho * self.tol).sqrt()
+        } else {
+            F::from_f64(0.1).unwrap() * self.rho
+        };
+        self.radius = (F::from_f64(0.5).unwrap() * self.rho).max(rho);
+        self.rho = rho;
+        true
+    }
+}
+
+impl<O, P, F> Solver<O> for DFOTrustRegion<P, F>
+where
+    O: CostFunction<Param = P, Output = F, Float = F>,
+    P: Clone + Serialize + DeserializeOwned + ArgminComponents<F>,
+    F: ArgminFloat,
+    Vec<F>: ArgminDot<Vec<F>, F>
+        + ArgminSub<Vec<F>, Vec<F>>
+        + ArgminMul<F, Vec<F>>
+        + ArgminMinMax
+        + ArgminRandom
+        + ArgminNorm<F>,
+    Vec<Vec<F>>: ArgminDot<Vec<F>, Vec<F>> + ArgminLU<LU<Vec<Vec<F>>>>,
+    LU<Vec<Vec<F>>>: ArgminDecompositionSolve<Vec<F>>,
+{
+    const NAME: &'static str = "Derivative-free trust region";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let template = state.get_param();
+        let n = template.num_components();
+        let delta = self.initial_radius;
+        let two = F::from_f64(2.0).unwrap();
+        if let Some((lower, upper)) = &self.bounds {
+            check_bounds_dim("DFOTrustRegion", lower, &template)?;
+            if (0..n).any(|i| upper.component(i) - lower.component(i) < two * delta) {
+                return Err(ArgminError::InvalidParameter {
+                    text: "DFOTrustRegion: bounds must be at least twice the initial radius apart."
+                        .to_string(),
+                }
+                .into());
+            }
+        }
+
+        // move the starting point onto bounds which are closer than the initial radius and place
+        // two points along every coordinate direction inside the bounds
+        let mut x0 = self.project((0..n).map(|i| template.component(i)).collect());
+        let mut offsets = vec![];
+        for (i, xi) in x0.iter_mut().enumerate() {
+            let (l, u) = self.bound(i);
+            offsets.push(if *xi - l < delta {
+                *xi = l;
+                (delta, two * delta)
+            } else if u - *xi < delta {
+                *xi = u;
+                (-delta, -two * delta)
+            } else {
+                (delta, -delta)
+            });
+        }
+        self.points = vec![x0.clone()];
+        for (i, (a, b)) in offsets.into_iter().enumerate() {
+            for offset in [a, b].iter() {
+                let mut x = x0.clone();
+                x[i] = x[i] + *offset;
+                self.points.push(x);
+            }
+        }
+        self.fvals = self
+            .points
+            .iter()
+            .map(|x| self.eval(op, &template, x))
+            .collect::<Result<Vec<F>, Error>>()?;
+        self.kopt = (0..self.points.len()).fold(0, |best, t| {
+            if self.fvals[t] < self.fvals[best] {
+                t
+            } else {
+                best
+            }
+        });
+        self.radius = delta;
+        self.rho = delta;
+        self.model = QuadraticModel::zero(x0);
+        self.improve_geometry = false;
+
+        let best = self.to_param(&template, &self.points[self.kopt]);
+        Ok(Some(
+            ArgminIterData::new()
+                .param(best)
+                .cost(self.fvals[self.kopt]),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let template = state.get_param();
+        let system = self.update_model()?;
+        let radius = self.radius;
+        let mut ratio = F::nan();
+        let mut model_error = F::nan();
+        let mut terminated = false;
+
+        let kind = if self.improve_geometry {
+            // the last trust region step failed
+            self.improve_geometry = false;
+            let moved = match self.far_point() {
+                Some(t) => self.geometry_step(op, &template, &system, t)?,
+                None => false,
+            };
+            if moved {
+                Some(StepKind::Geometry)
+            } else if self.radius <= self.rho {
+                terminated = !self.reduce_resolution();
+                Some(StepKind::Resolution)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        let kind = if let Some(kind) = kind {
+            kind
+        } else {
+            let xk = self.points[self.kopt].clone();
+            let fk = self.fvals[self.kopt];
+            let (s, on_boundary) = self.trust_region_step();
+            let s_norm = s.norm();
+            let predicted = self.model.c - self.model.eval_step(&s);
+            let x = self.project(xk.iter().zip(s.iter()).map(|(a, b)| *a + *b).collect());
+            if s_norm < F::from_f64(0.5).unwrap() * self.rho
+                || predicted.is_nan()
+                || predicted <= F::from_f64(0.0).unwrap()
+                || self.coincides(&x)
+            {
+                // the model cannot make progress at the current resolution
+                let moved = match self.far_point() {
+                    Some(t) => self.geometry_step(op, &template, &system, t)?,
+                    None => false,
+                };
+                if moved {
+                    StepKind::Geometry
+                } else {
+                    terminated = !self.reduce_resolution();
+                    StepKind::Resolution
+                }
+            } else {
+                let f = self.eval(op, &template, &x)?;
+                let mx = self.model.eval(&x);
+                ratio = reduction_ratio(fk, f, self.model.c, mx);
+                model_error = (f - mx).abs();
+                let step_norm = if on_boundary { self.radius } else { s_norm };
+                self.radius =
+                    update_radius(self.radius, self.max_radius, ratio, step_norm).max(self.rho);
+
+                let best = if f < fk { x.clone() } else { xk };
+                let t = self.replacement_index(&system, &x, &best)?;
+                self.points[t] = x;
+                self.fvals[t] = f;
+                if f < fk {
+                    self.kopt = t;
+                }
+                self.improve_geometry = ratio < F::from_f64(0.1).unwrap();
+                StepKind::TrustRegion
+            }
+        };
+
+        let best = self.to_param(&template, &self.points[self.kopt]);
+        let data = ArgminIterData::new()
+            .param(best)
+            .cost(self.fvals[self.kopt])
+            .kv(make_kv!(
+                "radius" => radius;
+                "rho" => self.rho;
+                "ratio" => ratio;
+                "model_error" => model_error;
+                "step" => kind.as_str();
+            ));
+        Ok(if terminated {
+            data.termination_reason(TerminationReason::StepToleranceReached)
+        } else {
+            data
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_trait_impl;
+    use argmin_testfunctions::{rosenbrock, rosenbrock_2d};
+
+    test_trait_impl!(dfo_trustregion, DFOTrustRegion<Vec<f64>, f64>);
+
+    #[test]
+    fn test_quadratic_model() {
+        let model = QuadraticModel {
+            center: vec![1.0, 2.0],
+            c: 3.0,
+            g: vec![1.0, -1.0],
+            h: vec![vec![2.0, 1.0], vec![1.0, 4.0]],
+        };
+        let shifted = model.shift(&vec![0.0, 1.0]);
+        for x in [vec![0.0, 0.0], vec![1.0, 2.0], vec![-3.0, 0.5]].iter() {
+            assert!((model.eval(x) - shifted.eval(x)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_interpolation() {
+        // a quadratic with diagonal Hessian is recovered from 2n + 1 points
+        let f = |x: &[f64]| 1.0 + x[0] - 2.0 * x[1] + 1.5 * x[0].powi(2) + 0.5 * x[1].powi(2);
+        let points = vec![
+            vec![0.5, 0.5],
+            vec![1.5, 0.5],
+            vec![-0.5, 0.5],
+            vec![0.5, 1.5],
+            vec![0.5, -0.5],
+        ];
+        let fvals: Vec<f64> = points.iter().map(|p| f(p)).collect();
+        let system = InterpolationSystem::new(&points, &points[0]).unwrap();
+        let model = system.interpolate(&fvals).unwrap();
+        for x in [vec![0.0, 0.0], vec![2.0, -1.0], vec![0.3, 0.7]].iter() {
+            assert!((model.eval(x) - f(x)).abs() < 1e-10);
+        }
+        // Lagrange functions are 1 at their own point and 0 at all others
+        for (t, p) in points.iter().enumerate() {
+            let values = system.lagrange_values(p).unwrap();
+            for (j, v) in values.iter().enumerate() {
+                let expected = if j == t { 1.0 } else { 0.0 };
+                assert!((v - expected).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        for x0 in [vec![-1.2, 1.0], vec![-1.0, 1.0, -1.0, 1.0]].iter() {
+            let op = FnOp::new(|x: &Vec<f64>| Ok(rosenbrock(x, 1.0, 100.0)));
+            let res = Executor::new(op, DFOTrustRegion::new(), x0.clone())
+                .max_iters(2000)
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                TerminationReason::StepToleranceReached
+            );
+            let x = res.state.get_best_param();
+            for xi in x.iter() {
+                assert!((xi - 1.0).abs() < 1e-4);
+            }
+            assert_eq!(res.state.get_grad_func_count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_fewer_evaluations_than_nelder_mead() {
+        use crate::solver::neldermead::NelderMead;
+        let op = FnOp::new(|x: &Vec<f64>| Ok(rosenbrock_2d(x, 1.0, 100.0)));
+        let res = Executor::new(op, DFOTrustRegion::new(), vec![-1.2, 1.0])
+            .max_iters(2000)
+            .run()
+            .unwrap();
+        let dfo_evals = res.state.get_cost_func_count();
+        let nm = NelderMead::new()
+            .with_initial_params(vec![vec![-1.2, 1.0], vec![-0.2, 1.0], vec![-1.2, 2.0]])
+            .sd_tolerance(1e-12);
+        let op = FnOp::new(|x: &Vec<f64>| Ok(rosenbrock_2d(x, 1.0, 100.0)));
+        let res_nm = Executor::new(op, nm, vec![-1.2, 1.0])
+            .max_iters(2000)
+            .run()
+            .unwrap();
+        assert!(dfo_evals < res_nm.state.get_cost_func_count());
+    }
+
+    #[test]
+    fn test_bounds() {
+        // the unconstrained minimum (1, 1) is cut off by x[0] <= 0.5
+        let solver = DFOTrustRegion::new()
+            .with_initial_radius(0.5)
+            .unwrap()
+            .with_bounds(vec![-2.0, -2.0], vec![0.5, 2.0])
+            .unwrap();
+        let op = FnOp::new(|x: &Vec<f64>| Ok(rosenbrock_2d(x, 1.0, 100.0)));
+        let res = Executor::new(op, solver, vec![-1.2, 1.0])
+            .max_iters(2000)
+            .run()
+            .unwrap();
+        let x = res.state.get_best_param();
+        assert!((x[0] - 0.5).abs() < 1e-6);
+        assert!((x[1] - 0.25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let solver: DFOTrustRegion<Vec<f64>, f64> = DFOTrustRegion::new();
+        assert!(solver.clone().with_initial_radius(0.0).is_err());
+        assert!(solver.clone().with_max_radius(-1.0).is_err());
+        assert!(solver.clone().with_tol(0.0).is_err());
+        assert!(solver
+            .clone()
+            .with_bounds(vec![1.0, 0.0], vec![0.0, 1.0])
+            .is_err());
+        // bounds too narrow for the initial radius
+        let solver = solver.with_bounds(vec![0.0, 0.0], vec![1.0, 1.0]).unwrap();
+        let op = FnOp::new(|x: &Vec<f64>| Ok(rosenbrock_2d(x, 1.0, 100.0)));
+        let res = Executor::new(op, solver, vec![0.5, 0.5])
+            .max_iters(10)
+            .run();
+        assert!(res.is_err());
+    }
+}
@@ -10,6 +10,7 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::{CostFunction, Gradient, Hessian};
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -36,7 +37,7 @@ impl<F: ArgminFloat> Dogleg<F> {
 
 impl<O, F> Solver<O> for Dogleg<F>
 where
-    O: ArgminOp<Output = F, Float = F>,
+    O: CostFunction<Output = F, Float = F> + Gradient + Hessian,
     O::Param: std::fmt::Debug
         + ArgminMul<F, O::Param>
         + ArgminWeightedDot<O::Param, O::Float, O::Hessian>
@@ -44,7 +45,7 @@ where
         + ArgminDot<O::Param, O::Float>
         + ArgminAdd<O::Param, O::Param>
         + ArgminSub<O::Param, O::Param>,
-    O::Hessian: ArgminInv<O::Hessian> + ArgminDot<O::Param, O::Param>,
+    O::Hessian: ArgminLinSolve<O::Param>,
     F: ArgminFloat,
 {
     const NAME: &'static str = "Dogleg";
@@ -55,16 +56,18 @@ where
         state: &IterState<O>,
     ) -> Result<ArgminIterData<O>, Error> {
         let param = state.get_param();
-        let g = state
-            .get_grad()
-            .unwrap_or_else(|| op.gradient(&param).unwrap());
-        let h = state
-            .get_hessian()
-            .unwrap_or_else(|| op.hessian(&param).unwrap());
+        let g = match state.get_grad() {
+            Some(g) => g,
+            None => op.gradient(&param)?,
+        };
+        let h = match state.get_hessian() {
+            Some(h) => h,
+            None => op.hessian(&param)?,
+        };
         let pstar;
 
         // pb = -H^-1g
-        let pb = (h.inv()?).dot(&g).mul(&F::from_f64(-1.0).unwrap());
+        let pb = h.lin_solve(&g)?.mul(&F::from_f64(-1.0).unwrap());
 
         if pb.norm() <= self.radius {
             pstar = pb;
@@ -107,7 +110,14 @@ where
                 .into());
             }
         }
-        let out = ArgminIterData::new().param(pstar);
+        // change of the quadratic model
+        let model_change =
+            pstar.dot(&g) + F::from_f64(0.5).unwrap() * pstar.weighted_dot(&h, &pstar);
+        // the Hessian is reported so that it can be reused if the step is rejected
+        let out = ArgminIterData::new()
+            .param(pstar)
+            .cost(model_change)
+            .hessian(h);
         Ok(out)
     }
 
@@ -120,7 +130,7 @@ where
     }
 }
 
-impl<F: ArgminFloat> ArgminTrustRegion<F> for Dogleg<F> {
+impl<P, F: ArgminFloat> ArgminTrustRegion<P, F> for Dogleg<F> {
     fn set_radius(&mut self, radius: F) {
         self.radius = radius;
     }
@@ -9,6 +9,8 @@
 
 /// Cauchy Point
 pub mod cauchypoint;
+/// Derivative-free trust region method
+pub mod dfo;
 /// Dogleg method
 pub mod dogleg;
 /// Steihaug method
@@ -17,6 +19,7 @@ pub mod steihaug;
 pub mod trustregion_method;
 
 pub use self::cauchypoint::*;
+pub use self::dfo::*;
 pub use self::dogleg::*;
 pub use self::steihaug::*;
 pub use self::trustregion_method::*;
@@ -27,3 +30,19 @@ use crate::core::ArgminFloat;
 pub fn reduction_ratio<F: ArgminFloat>(fxk: F, fxkpk: F, mk0: F, mkpk: F) -> F {
     (fxk - fxkpk) / (mk0 - mkpk)
 }
+
+/// Computes the trust region radius for the next iteration from the reduction ratio `rho` of a
+/// step of length `step_norm`: The radius is reduced to a quarter of the step length if
+/// `rho < 1/4` and doubled (up to `max_radius`) if `rho > 3/4` and the step reached the boundary
+/// of the trust region.
+pub fn update_radius<F: ArgminFloat>(radius: F, max_radius: F, rho: F, step_norm: F) -> F {
+    if rho < F::from_f64(0.25).unwrap() {
+        F::from_f64(0.25).unwrap() * step_norm
+    } else if rho > F::from_f64(0.75).unwrap()
+        && (step_norm - radius).abs() <= F::from_f64(10.0).unwrap() * F::epsilon()
+    {
+        max_radius.min(F::from_f64(2.0).unwrap() * radius)
+    } else {
+        radius
+    }
+}
@@ -10,40 +10,68 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::{CostFunction, ExactHessian, Gradient, HessianProduct};
 use crate::prelude::*;
+use crate::solver::conjugategradient::{ArgminPreconditioner, IdentityPreconditioner};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 /// The Steihaug method is a conjugate gradients based approach for finding an approximate solution
 /// to the second order approximation of the cost function within the trust region.
 ///
+/// Only products of the Hessian with vectors are required. By default, they are computed with the
+/// Hessian given by the state (as provided for instance by `SR1TrustRegion`) or by
+/// `Hessian::hessian`. With `with_hessian_product(HessianFree)`, `Gradient::hessian_vec` is used
+/// instead and the Hessian is never formed.
+///
+/// A preconditioner `M` can be set via `with_preconditioner`. The trust region is then measured in
+/// the norm `||p||_M = sqrt(p^T M p)` [1], which is also used by `TrustRegion` and
+/// `SR1TrustRegion` to update the radius (`ArgminTrustRegion::step_norm`).
+///
 /// # References:
 ///
 /// [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 /// Springer. ISBN 0-387-30303-0.
+///
+/// [1] Andrew R. Conn, Nicholas I. M. Gould and Philippe L. Toint (2000). Trust-Region Methods.
+/// SIAM. ISBN 0-89871-460-7.
 #[derive(Clone, Serialize, Deserialize, Debug, Copy, PartialEq, PartialOrd, Default)]
-pub struct Steihaug<P, F> {
+pub struct Steihaug<P, F, M = IdentityPreconditioner, H = ExactHessian> {
     /// Radius
     radius: F,
     /// epsilon
     epsilon: F,
+    /// Point at which the quadratic model is built
+    x: P,
     /// p
     p: P,
+    /// Hessian times p
+    hp: P,
     /// residual
     r: P,
-    /// r^Tr
-    rtr: F,
+    /// r^T M^{-1} r
+    rty: F,
+    /// p^T M p
+    ptmp: F,
+    /// p^T M d
+    ptmd: F,
+    /// d^T M d
+    dtmd: F,
     /// initial residual
     r_0_norm: F,
     /// direction
     d: P,
     /// max iters
     max_iters: u64,
+    /// preconditioner
+    preconditioner: M,
+    /// computation of the products with the Hessian
+    hessian_product: H,
 }
 
 impl<P, F> Steihaug<P, F>
 where
-    P: Default + Clone + ArgminMul<F, P> + ArgminDot<P, F> + ArgminAdd<P, P>,
+    P: Default,
     F: ArgminFloat,
 {
     /// Constructor
@@ -51,12 +79,67 @@ where
         Steihaug {
             radius: F::nan(),
             epsilon: F::from_f64(10e-10).unwrap(),
+            x: P::default(),
             p: P::default(),
+            hp: P::default(),
             r: P::default(),
-            rtr: F::nan(),
+            rty: F::nan(),
+            ptmp: F::nan(),
+            ptmd: F::nan(),
+            dtmd: F::nan(),
             r_0_norm: F::nan(),
             d: P::default(),
             max_iters: std::u64::MAX,
+            preconditioner: IdentityPreconditioner,
+            hessian_product: ExactHessian,
+        }
+    }
+}
+
+impl<P, F, M, H> Steihaug<P, F, M, H>
+where
+    P: Default + Clone + ArgminMul<F, P> + ArgminDot<P, F> + ArgminAdd<P, P>,
+    F: ArgminFloat,
+{
+    /// Set preconditioner
+    pub fn with_preconditioner<M2>(self, preconditioner: M2) -> Steihaug<P, F, M2, H> {
+        Steihaug {
+            radius: self.radius,
+            epsilon: self.epsilon,
+            x: self.x,
+            p: self.p,
+            hp: self.hp,
+            r: self.r,
+            rty: self.rty,
+            ptmp: self.ptmp,
+            ptmd: self.ptmd,
+            dtmd: self.dtmd,
+            r_0_norm: self.r_0_norm,
+            d: self.d,
+            max_iters: self.max_iters,
+            preconditioner,
+            hessian_product: self.hessian_product,
+        }
+    }
+
+    /// Set how the products with the Hessian are computed (default: `ExactHessian`)
+    pub fn with_hessian_product<H2>(self, hessian_product: H2) -> Steihaug<P, F, M, H2> {
+        Steihaug {
+            radius: self.radius,
+            epsilon: self.epsilon,
+            x: self.x,
+            p: self.p,
+            hp: self.hp,
+            r: self.r,
+            rty: self.rty,
+            ptmp: self.ptmp,
+            ptmd: self.ptmd,
+            dtmd: self.dtmd,
+            r_0_norm: self.r_0_norm,
+            d: self.d,
+            max_iters: self.max_iters,
+            preconditioner: self.preconditioner,
+            hessian_product,
         }
     }
 
@@ -79,24 +162,19 @@ where
     }
 
     /// evaluate m(p) (without considering f_init because it is not available)
-    fn eval_m<H>(&self, p: &P, g: &P, h: &H) -> F
-    where
-        P: ArgminWeightedDot<P, F, H>,
-    {
-        // self.cur_grad().dot(&p) + 0.5 * p.weighted_dot(&self.cur_hessian(), &p)
-        g.dot(p) + F::from_f64(0.5).unwrap() * p.weighted_dot(h, p)
+    fn eval_m(&self, p: &P, hp: &P, g: &P) -> F {
+        g.dot(p) + F::from_f64(0.5).unwrap() * p.dot(hp)
     }
 
     /// calculate all possible step lengths
     #[allow(clippy::many_single_char_names)]
-    fn tau<G, H>(&self, filter_func: G, eval: bool, g: &P, h: &H) -> F
+    fn tau<G>(&self, filter_func: G, eval: bool, g: &P, hd: &P) -> F
     where
         G: Fn(F) -> bool,
-        H: ArgminDot<P, P>,
     {
-        let a = self.p.dot(&self.p);
-        let b = self.d.dot(&self.d);
-        let c = self.p.dot(&self.d);
+        let a = self.ptmp;
+        let b = self.dtmd;
+        let c = self.ptmd;
         let delta = self.radius.powi(2);
         let t1 = (-a * b + b * delta + c.powi(2)).sqrt();
         let tau1 = -(t1 + c) / b;
@@ -116,8 +194,10 @@ where
                 .enumerate()
                 .filter(|(_, tau)| (!tau.is_nan() || !tau.is_infinite()) && filter_func(*tau))
                 .map(|(i, tau)| {
+                    // H * (p + tau * d) = H * p + tau * H * d
                     let p = self.p.add(&self.d.mul(&tau));
-                    (i, self.eval_m(&p, g, h))
+                    let hp = self.hp.add(&hd.mul(&tau));
+                    (i, self.eval_m(&p, &hp, g))
                 })
                 .filter(|(_, m)| !m.is_nan() || !m.is_infinite())
                 .collect::<Vec<(usize, F)>>();
@@ -136,99 +216,142 @@ where
 
         t[v[0].0]
     }
+
+    /// Step `p + tau * d` to the boundary of the trust region
+    fn boundary_step<O>(&self, tau: F, g: &P, hd: &P) -> ArgminIterData<O>
+    where
+        O: CostFunction<Param = P, Float = F>,
+        P: ArgminScaledAdd<P, F, P>,
+    {
+        let p = self.p.scaled_add(&tau, &self.d);
+        let hp = self.hp.scaled_add(&tau, hd);
+        ArgminIterData::new()
+            .cost(self.eval_m(&p, &hp, g))
+            .param(p)
+            .termination_reason(TerminationReason::TargetPrecisionReached)
+    }
 }
 
-impl<P, O, F> Solver<O> for Steihaug<P, F>
+impl<P, O, F, M, H> Solver<O> for Steihaug<P, F, M, H>
 where
-    O: ArgminOp<Param = P, Output = F, Float = F>,
+    O: CostFunction<Param = P, Output = F, Float = F> + Gradient,
     P: Clone
         + Serialize
         + DeserializeOwned
         + Default
         + ArgminMul<F, P>
-        + ArgminWeightedDot<P, F, O::Hessian>
         + ArgminNorm<F>
         + ArgminDot<P, F>
         + ArgminAdd<P, P>
         + ArgminSub<P, P>
+        + ArgminScaledAdd<P, F, P>
         + ArgminZeroLike
         + ArgminMul<F, P>,
-    O::Hessian: ArgminDot<P, P>,
     F: ArgminFloat,
+    M: ArgminPreconditioner<P> + Clone + Serialize + DeserializeOwned,
+    H: HessianProduct<O> + Clone + Serialize + DeserializeOwned,
 {
     const NAME: &'static str = "Steihaug";
 
     fn init(
         &mut self,
-        _op: &mut OpWrapper<O>,
+        op: &mut OpWrapper<O>,
         state: &IterState<O>,
     ) -> Result<Option<ArgminIterData<O>>, Error> {
+        self.x = state.get_param();
         self.r = state.get_grad().unwrap();
 
         self.r_0_norm = self.r.norm();
-        self.rtr = self.r.dot(&self.r);
-        self.d = self.r.mul(&F::from_f64(-1.0).unwrap());
+        let y = self.preconditioner.precondition(&self.r)?;
+        self.rty = self.r.dot(&y);
+        self.d = y.mul(&F::from_f64(-1.0).unwrap());
         self.p = self.r.zero_like();
+        self.ptmp = F::from_f64(0.0).unwrap();
+        self.ptmd = F::from_f64(0.0).unwrap();
+        self.dtmd = self.rty;
+        self.hp = self.r.zero_like();
 
-        Ok(if self.r_0_norm < self.epsilon {
-            Some(
+        if self.r_0_norm < self.epsilon {
+            return Ok(Some(
                 ArgminIterData::new()
                     .param(self.p.clone())
+                    .cost(F::from_f64(0.0).unwrap())
                     .termination_reason(TerminationReason::TargetPrecisionReached),
-            )
-        } else {
-            None
-        })
+            ));
+        }
+        let mut data = ArgminIterData::new().cost(F::from_f64(0.0).unwrap());
+        if state.hessian.is_none() {
+            if let Some(hessian) = self.hessian_product.hessian(op, &self.x)? {
+                data = data.hessian(hessian);
+            }
+        }
+        Ok(Some(data))
     }
 
     fn next_iter(
         &mut self,
-        _op: &mut OpWrapper<O>,
+        op: &mut OpWrapper<O>,
         state: &IterState<O>,
     ) -> Result<ArgminIterData<O>, Error> {
         let grad = state.get_grad().unwrap();
-        let h = state.get_hessian().unwrap();
-        let dhd = self.d.weighted_dot(&h, &self.d);
+        let hd = self.hessian_product.product(
+            op.op.as_ref().unwrap(),
+            &self.x,
+            &grad,
+            state.hessian.as_ref(),
+            &self.d,
+        )?;
+        if state.hessian.is_none() {
+            op.hessian_vec_func_count += 1;
+            op.grad_func_count += O::HESSIAN_VEC_GRAD_EVALS;
+        }
+        let dhd = self.d.dot(&hd);
 
         // Current search direction d is a direction of zero curvature or negative curvature
         if dhd <= F::from_f64(0.0).unwrap() {
-            let tau = self.tau(|_| true, true, &grad, &h);
-            return Ok(ArgminIterData::new()
-                .param(self.p.add(&self.d.mul(&tau)))
-                .termination_reason(TerminationReason::TargetPrecisionReached));
+            let tau = self.tau(|_| true, true, &grad, &hd);
+            return Ok(self.boundary_step(tau, &grad, &hd));
         }
 
-        let alpha = self.rtr / dhd;
+        let alpha = self.rty / dhd;
         let p_n = self.p.add(&self.d.mul(&alpha));
+        let ptmp_n =
+            self.ptmp + F::from_f64(2.0).unwrap() * alpha * self.ptmd + alpha.powi(2) * self.dtmd;
 
         // new p violates trust region bound
-        if p_n.norm() >= self.radius {
-            let tau = self.tau(|x| x >= F::from_f64(0.0).unwrap(), false, &grad, &h);
-            return Ok(ArgminIterData::new()
-                .param(self.p.add(&self.d.mul(&tau)))
-                .termination_reason(TerminationReason::TargetPrecisionReached));
+        if ptmp_n.sqrt() >= self.radius {
+            let tau = self.tau(|x| x >= F::from_f64(0.0).unwrap(), false, &grad, &hd);
+            return Ok(self.boundary_step(tau, &grad, &hd));
         }
 
-        let r_n = self.r.add(&h.dot(&self.d).mul(&alpha));
+        let r_n = self.r.add(&hd.mul(&alpha));
+        let hp_n = self.hp.scaled_add(&alpha, &hd);
 
         if r_n.norm() < self.epsilon * self.r_0_norm {
+            let m = self.eval_m(&p_n, &hp_n, &grad);
             return Ok(ArgminIterData::new()
                 .param(p_n)
+                .cost(m)
                 .termination_reason(TerminationReason::TargetPrecisionReached));
         }
 
-        let rjtrj = r_n.dot(&r_n);
-        let beta = rjtrj / self.rtr;
-        self.d = r_n.mul(&F::from_f64(-1.0).unwrap()).add(&self.d.mul(&beta));
+        let y_n = self.preconditioner.precondition(&r_n)?;
+        let rty_n = r_n.dot(&y_n);
+        let beta = rty_n / self.rty;
+        self.d = y_n.mul(&F::from_f64(-1.0).unwrap()).add(&self.d.mul(&beta));
+        // recurrences for the M-norms, see section 7.5.1 of [1]
+        self.ptmd = beta * (self.ptmd + alpha * self.dtmd);
+        self.dtmd = rty_n + beta.powi(2) * self.dtmd;
+        self.ptmp = ptmp_n;
         self.r = r_n;
         self.p = p_n;
-        self.rtr = rjtrj;
+        self.hp = hp_n;
+        self.rty = rty_n;
 
         Ok(ArgminIterData::new()
             .param(self.p.clone())
-            .cost(self.rtr)
-            .grad(grad)
-            .hessian(h))
+            .cost(self.eval_m(&self.p, &self.hp, &grad))
+            .grad(grad))
     }
 
     fn terminate(&mut self, state: &IterState<O>) -> TerminationReason {
@@ -240,10 +363,24 @@ where
     }
 }
 
-impl<P: Clone + Serialize, F: ArgminFloat> ArgminTrustRegion<F> for Steihaug<P, F> {
+impl<P, F, M, H> ArgminTrustRegion<P, F> for Steihaug<P, F, M, H>
+where
+    P: Clone + Serialize + ArgminDot<P, F>,
+    F: ArgminFloat,
+    M: ArgminPreconditioner<P> + Clone + Serialize,
+    H: Clone + Serialize,
+{
     fn set_radius(&mut self, radius: F) {
         self.radius = radius;
     }
+
+    /// The M-norm `sqrt(p^T M p)` of the step
+    fn step_norm(&self, step: &P) -> Result<F, Error>
+    where
+        P: ArgminNorm<F>,
+    {
+        Ok(step.dot(&self.preconditioner.mul_vec(step)?).sqrt())
+    }
 }
 
 #[cfg(test)]
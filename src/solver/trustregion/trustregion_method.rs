@@ -10,8 +10,9 @@
 //! [0] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+use crate::core::capabilities::{CostFunction, Gradient};
 use crate::prelude::*;
-use crate::solver::trustregion::reduction_ratio;
+use crate::solver::trustregion::{reduction_ratio, update_radius};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
@@ -29,6 +30,14 @@ use std::fmt::Debug;
 /// This subproblem can be set via `set_subproblem(...)`. If this is not provided, it will default
 /// to the Steihaug method.
 ///
+/// The reduction ratio compares the actual reduction of the cost function with the reduction
+/// predicted by the quadratic model which the subproblem minimizes. The trust region method itself
+/// therefore requires neither Hessians nor Hessian-vector products. The Cauchy point and Dogleg
+/// methods compute the Hessian via `Hessian::hessian`. The Steihaug method can also run on
+/// Hessian-vector products only (`Steihaug::with_hessian_product(HessianFree)`), in which case the
+/// Hessian is never formed. If a step is rejected, the Hessian computed by the subproblem is kept
+/// in the state and reused in the next iteration, which starts from the same point.
+///
 /// [Example](https://github.com/argmin-rs/argmin/blob/master/examples/trustregion_nd.rs)
 ///
 /// # References:
@@ -49,6 +58,8 @@ pub struct TrustRegion<R, F> {
     fxk: F,
     /// mk(0)
     mk0: F,
+    /// The Hessian in the state belongs to the current point, because the last step was rejected
+    reuse_hessian: bool,
 }
 
 impl<R, F: ArgminFloat> TrustRegion<R, F> {
@@ -61,6 +72,7 @@ impl<R, F: ArgminFloat> TrustRegion<R, F> {
             subproblem,
             fxk: F::nan(),
             mk0: F::nan(),
+            reuse_hessian: false,
         }
     }
 
@@ -91,21 +103,20 @@ impl<R, F: ArgminFloat> TrustRegion<R, F> {
 
 impl<O, R, F> Solver<O> for TrustRegion<R, F>
 where
-    O: ArgminOp<Output = F, Float = F>,
+    O: CostFunction<Output = F, Float = F> + Gradient,
     O::Param: Default
         + Clone
         + Debug
         + Serialize
         + ArgminMul<F, O::Param>
-        + ArgminWeightedDot<O::Param, F, O::Hessian>
         + ArgminNorm<F>
         + ArgminDot<O::Param, F>
         + ArgminAdd<O::Param, O::Param>
         + ArgminSub<O::Param, O::Param>
+        + ArgminScaledAdd<O::Param, F, O::Param>
         + ArgminZeroLike
         + ArgminMul<F, O::Param>,
-    O::Hessian: Default + Clone + Debug + Serialize + ArgminDot<O::Param, O::Param>,
-    R: ArgminTrustRegion<F> + Solver<OpWrapper<O>>,
+    R: ArgminTrustRegion<O::Param, F> + Solver<OpWrapper<O>>,
     F: ArgminFloat,
 {
     const NAME: &'static str = "Trust region";
@@ -117,15 +128,11 @@ where
     ) -> Result<Option<ArgminIterData<O>>, Error> {
         let param = state.get_param();
         let grad = op.gradient(&param)?;
-        let hessian = op.hessian(&param)?;
         self.fxk = op.apply(&param)?;
         self.mk0 = self.fxk;
+        self.reuse_hessian = false;
         Ok(Some(
-            ArgminIterData::new()
-                .param(param)
-                .cost(self.fxk)
-                .grad(grad)
-                .hessian(hessian),
+            ArgminIterData::new().param(param).cost(self.fxk).grad(grad),
         ))
     }
 
@@ -135,64 +142,78 @@ where
         state: &IterState<O>,
     ) -> Result<ArgminIterData<O>, Error> {
         let param = state.get_param();
-        let grad = state
-            .get_grad()
-            .unwrap_or_else(|| op.gradient(&param).unwrap());
-        let hessian = state
-            .get_hessian()
-            .unwrap_or_else(|| op.hessian(&param).unwrap());
+        let grad = match state.get_grad() {
+            Some(grad) => grad,
+            None => op.gradient(&param)?,
+        };
 
         self.subproblem.set_radius(self.radius);
 
-        let ArgminResult {
-            operator: sub_op,
-            state: IterState { param: pk, .. },
-        } = Executor::new(
+        let mut executor = Executor::new(
             OpWrapper::new_from_wrapper(op),
             self.subproblem.clone(),
             param.clone(),
         )
         .grad(grad.clone())
-        .hessian(hessian.clone())
-        .ctrlc(false)
-        .run()?;
+        .ctrlc(false);
+        if self.reuse_hessian {
+            if let Some(hessian) = state.get_hessian() {
+                executor = executor.hessian(hessian);
+            }
+        }
+        let ArgminResult {
+            operator: sub_op,
+            state:
+                IterState {
+                    param: pk,
+                    cost: model_change,
+                    hessian,
+                    cost_func_count,
+                    grad_func_count,
+                    hessian_func_count,
+                    hessian_vec_func_count,
+                    ..
+                },
+        } = executor.run()?;
 
         // Operator must be consumed again, otherwise the operator, which moved into the subproblem
-        // executor as well as the function evaluation counts are lost.
+        // executor as well as the function evaluation counts are lost. The evaluations of the
+        // subproblem are counted in its state.
         op.consume_op(sub_op);
+        op.cost_func_count += cost_func_count;
+        op.grad_func_count += grad_func_count;
+        op.hessian_func_count += hessian_func_count;
+        op.hessian_vec_func_count += hessian_vec_func_count;
 
         let new_param = pk.add(&param);
         let fxkpk = op.apply(&new_param)?;
-        let mkpk =
-            self.fxk + pk.dot(&grad) + F::from_f64(0.5).unwrap() * pk.weighted_dot(&hessian, &pk);
+        // The subproblem reports m(pk) - f(xk) as cost function value
+        let mkpk = self.fxk + model_change;
 
         let rho = reduction_ratio(self.fxk, fxkpk, self.mk0, mkpk);
 
-        let pk_norm = pk.norm();
+        // The step length is measured in the norm of the subproblem
+        let pk_norm = self.subproblem.step_norm(&pk)?;
 
         let cur_radius = self.radius;
-        self.radius = if rho < F::from_f64(0.25).unwrap() {
-            F::from_f64(0.25).unwrap() * pk_norm
-        } else if rho > F::from_f64(0.75).unwrap()
-            && (pk_norm - self.radius).abs() <= F::from_f64(10.0).unwrap() * F::epsilon()
-        {
-            self.max_radius.min(F::from_f64(2.0).unwrap() * self.radius)
-        } else {
-            self.radius
-        };
+        self.radius = update_radius(self.radius, self.max_radius, rho, pk_norm);
 
-        Ok(if rho > self.eta {
+        let accepted = rho > self.eta;
+        self.reuse_hessian = !accepted;
+        Ok(if accepted {
             self.fxk = fxkpk;
             self.mk0 = fxkpk;
             let grad = op.gradient(&new_param)?;
-            let hessian = op.hessian(&new_param)?;
             ArgminIterData::new()
                 .param(new_param)
                 .cost(fxkpk)
                 .grad(grad)
-                .hessian(hessian)
         } else {
-            ArgminIterData::new().param(param).cost(self.fxk)
+            let data = ArgminIterData::new().param(param).cost(self.fxk);
+            match hessian {
+                Some(hessian) => data.hessian(hessian),
+                None => data,
+            }
         }
         .kv(make_kv!("radius" => cur_radius;)))
     }
@@ -206,10 +227,239 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::solver::trustregion::steihaug::Steihaug;
+    use crate::core::capabilities::{Hessian, HessianFree};
+    use crate::solver::conjugategradient::JacobiPreconditioner;
+    use crate::solver::trustregion::{CauchyPoint, Dogleg, Steihaug};
     use crate::test_trait_impl;
+    use argmin_testfunctions::{rosenbrock_2d, rosenbrock_2d_derivative, rosenbrock_2d_hessian};
 
     type Operator = MinimalNoOperator;
 
     test_trait_impl!(trustregion, TrustRegion<Steihaug<Operator, f64>, f64>);
+
+    /// f(x) = 1/2 x^T A x - b^T x with A = diag(1, 2, ..., n), which only provides a gradient
+    #[derive(Clone, Default, Serialize, Deserialize)]
+    struct Quadratic {
+        b: Vec<f64>,
+    }
+
+    impl CostFunction for Quadratic {
+        type Param = Vec<f64>;
+        type Output = f64;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, p: &Vec<f64>) -> Result<f64, Error> {
+            Ok(p.iter()
+                .zip(self.b.iter())
+                .enumerate()
+                .map(|(i, (x, b))| 0.5 * (i + 1) as f64 * x.powi(2) - b * x)
+                .sum())
+        }
+    }
+
+    impl Gradient for Quadratic {
+        fn gradient(&self, p: &Vec<f64>) -> Result<Vec<f64>, Error> {
+            Ok(p.iter()
+                .zip(self.b.iter())
+                .enumerate()
+                .map(|(i, (x, b))| (i + 1) as f64 * x - b)
+                .collect())
+        }
+    }
+
+    /// Same as `Quadratic`, but with a Hessian, whose computation fails if `fail` is set
+    #[derive(Clone, Default, Serialize, Deserialize)]
+    struct QuadraticWithHessian {
+        quadratic: Quadratic,
+        fail: bool,
+    }
+
+    impl CostFunction for QuadraticWithHessian {
+        type Param = Vec<f64>;
+        type Output = f64;
+        type Hessian = Vec<Vec<f64>>;
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, p: &Vec<f64>) -> Result<f64, Error> {
+            self.quadratic.apply(p)
+        }
+    }
+
+    impl Gradient for QuadraticWithHessian {
+        fn gradient(&self, p: &Vec<f64>) -> Result<Vec<f64>, Error> {
+            self.quadratic.gradient(p)
+        }
+    }
+
+    impl Hessian for QuadraticWithHessian {
+        fn hessian(&self, p: &Vec<f64>) -> Result<Vec<Vec<f64>>, Error> {
+            if self.fail {
+                return Err(ArgminError::NotImplemented {
+                    text: "no Hessian".to_string(),
+                }
+                .into());
+            }
+            let n = p.len();
+            Ok((0..n)
+                .map(|i| {
+                    (0..n)
+                        .map(|j| if i == j { (i + 1) as f64 } else { 0.0 })
+                        .collect()
+                })
+                .collect())
+        }
+    }
+
+    /// Rosenbrock function with `a = 1` and `b = 100`
+    #[derive(Clone, Default, Serialize, Deserialize)]
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+        type Hessian = Vec<Vec<f64>>;
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, p: &Vec<f64>) -> Result<f64, Error> {
+            Ok(rosenbrock_2d(p, 1.0, 100.0))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        fn gradient(&self, p: &Vec<f64>) -> Result<Vec<f64>, Error> {
+            Ok(rosenbrock_2d_derivative(p, 1.0, 100.0))
+        }
+    }
+
+    impl Hessian for Rosenbrock {
+        fn hessian(&self, p: &Vec<f64>) -> Result<Vec<Vec<f64>>, Error> {
+            let h = rosenbrock_2d_hessian(p, 1.0, 100.0);
+            Ok(vec![vec![h[0], h[1]], vec![h[2], h[3]]])
+        }
+    }
+
+    fn run<R>(subproblem: R, fail: bool) -> Result<ArgminResult<QuadraticWithHessian>, Error>
+    where
+        R: ArgminTrustRegion<Vec<f64>, f64> + Solver<OpWrapper<QuadraticWithHessian>>,
+    {
+        let n = 10;
+        let op = QuadraticWithHessian {
+            quadratic: Quadratic { b: vec![1.0; n] },
+            fail,
+        };
+        Executor::new(op, TrustRegion::new(subproblem).radius(10.0), vec![0.0; n])
+            .max_iters(50)
+            .run()
+    }
+
+    #[test]
+    fn test_exact_hessian() {
+        let results = vec![
+            run(Steihaug::new(), false).unwrap(),
+            run(Dogleg::new(), false).unwrap(),
+        ];
+        for res in results {
+            for (i, x) in res.state.get_best_param().iter().enumerate() {
+                assert!((x - 1.0 / (i + 1) as f64).abs() < 1e-6);
+            }
+        }
+        // the Cauchy point converges slowly, therefore only the minimal cost is compared
+        let res = run(CauchyPoint::new(), false).unwrap();
+        let min_cost = -0.5 * (1..=10).map(|i| 1.0 / i as f64).sum::<f64>();
+        assert!((res.state.get_best_cost() - min_cost).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_hessian_reused_after_rejected_step() {
+        // The gradient is only computed at accepted points. The Hessian must not be computed more
+        // often, even if steps are rejected.
+        fn run_rosenbrock<R>(subproblem: R) -> IterState<Rosenbrock>
+        where
+            R: ArgminTrustRegion<Vec<f64>, f64> + Solver<OpWrapper<Rosenbrock>>,
+        {
+            Executor::new(Rosenbrock {}, TrustRegion::new(subproblem), vec![-1.2, 1.0])
+                .max_iters(50)
+                .run()
+                .unwrap()
+                .state
+        }
+        let states = vec![
+            run_rosenbrock(Steihaug::new()),
+            run_rosenbrock(Dogleg::new()),
+            run_rosenbrock(CauchyPoint::new()),
+        ];
+        // Steihaug rejects steps on the way to the minimum
+        assert!(states[0].get_grad_func_count() < states[0].get_iter() + 1);
+        for state in states {
+            assert!(state.get_hessian_func_count() <= state.get_grad_func_count());
+        }
+    }
+
+    #[test]
+    fn test_hessian_error() {
+        assert!(run(Steihaug::new(), true).is_err());
+        assert!(run(Dogleg::new(), true).is_err());
+        assert!(run(CauchyPoint::new(), true).is_err());
+    }
+
+    #[test]
+    fn test_hessian_free_steihaug() {
+        let n = 10;
+        let op = Quadratic { b: vec![1.0; n] };
+        let steihaug = Steihaug::new().with_hessian_product(HessianFree);
+        let solver = TrustRegion::new(steihaug).radius(10.0);
+        let res = Executor::new(op, solver, vec![0.0; n])
+            .max_iters(20)
+            .run()
+            .unwrap();
+
+        for (i, x) in res.state.get_best_param().iter().enumerate() {
+            assert!((x - 1.0 / (i + 1) as f64).abs() < 1e-6);
+        }
+        assert_eq!(res.state.get_hessian_func_count(), 0);
+    }
+
+    #[test]
+    fn test_preconditioned_steihaug() {
+        let n = 10;
+        let op = Quadratic { b: vec![1.0; n] };
+        let diag = (1..=n).map(|i| i as f64).collect();
+        let steihaug = Steihaug::new()
+            .with_preconditioner(JacobiPreconditioner::new(diag))
+            .with_hessian_product(HessianFree);
+        let solver = TrustRegion::new(steihaug).radius(10.0);
+        let res = Executor::new(op, solver, vec![0.0; n])
+            .max_iters(20)
+            .run()
+            .unwrap();
+
+        for (i, x) in res.state.get_best_param().iter().enumerate() {
+            assert!((x - 1.0 / (i + 1) as f64).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_preconditioned_steihaug_radius() {
+        // The steps are measured in the norm of the preconditioner. Starting from a small radius,
+        // the minimum can only be reached if the radius grows when the steps hit its boundary.
+        let n = 10;
+        let op = Quadratic { b: vec![1.0; n] };
+        let diag = (1..=n).map(|i| i as f64).collect();
+        let steihaug = Steihaug::new()
+            .with_preconditioner(JacobiPreconditioner::new(diag))
+            .with_hessian_product(HessianFree);
+        let solver = TrustRegion::new(steihaug).radius(0.01);
+        let res = Executor::new(op, solver, vec![0.0; n])
+            .max_iters(20)
+            .run()
+            .unwrap();
+
+        for (i, x) in res.state.get_best_param().iter().enumerate() {
+            assert!((x - 1.0 / (i + 1) as f64).abs() < 1e-6);
+        }
+    }
 }